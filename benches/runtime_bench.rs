@@ -85,6 +85,7 @@ fn bench_embed_latency(c: &mut Criterion) {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Object(serde_json::Map::new()),
         };
@@ -110,6 +111,7 @@ fn bench_embed_latency(c: &mut Criterion) {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Object(serde_json::Map::new()),
         };
@@ -137,5 +139,56 @@ fn bench_embed_latency(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_runtime_init, bench_embed_latency);
+/// First loads of many distinct models at once, which contend on the
+/// loader-lock map rather than on any one model's lock.
+fn bench_concurrent_first_loads(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let specs: Vec<ModelAliasSpec> = (0..64)
+        .map(|i| ModelAliasSpec {
+            alias: format!("bench/m{i}"),
+            task: ModelTask::Embed,
+            provider_id: "bench".to_string(),
+            model_id: format!("bench-{i}"),
+            revision: None,
+            warmup: WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options: serde_json::Value::Object(serde_json::Map::new()),
+        })
+        .collect();
+    let aliases: Vec<String> = specs.iter().map(|s| s.alias.clone()).collect();
+
+    c.bench_function("concurrent_first_load_64_distinct_models", |b| {
+        b.to_async(&rt).iter(|| async {
+            // A fresh runtime per iteration so every call is a first load.
+            let runtime = ModelRuntime::builder()
+                .register_provider(BenchProvider)
+                .catalog(specs.clone())
+                .build()
+                .await
+                .unwrap();
+            let tasks: Vec<_> = aliases
+                .iter()
+                .map(|alias| {
+                    let runtime = runtime.clone();
+                    let alias = alias.clone();
+                    tokio::spawn(async move { runtime.embedding(&alias).await.map(|_| ()) })
+                })
+                .collect();
+            for task in tasks {
+                task.await.unwrap().unwrap();
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_runtime_init,
+    bench_embed_latency,
+    bench_concurrent_first_loads
+);
 criterion_main!(benches);
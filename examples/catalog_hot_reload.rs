@@ -0,0 +1,64 @@
+//! Catalog hot-reload example: poll a catalog file and apply changes to a
+//! running runtime without restarting it.
+//!
+//! Run with:
+//! ```sh
+//! cargo run --example catalog_hot_reload --features provider-candle -- catalog.json
+//! ```
+//!
+//! Edit `catalog.json` while the example runs; added, changed, and removed
+//! aliases are printed on each reload. An invalid edit is reported and the
+//! previous catalog stays in effect.
+
+#[cfg(feature = "provider-candle")]
+use std::time::{Duration, SystemTime};
+#[cfg(feature = "provider-candle")]
+use uni_xervo::provider::candle::LocalCandleProvider;
+#[cfg(feature = "provider-candle")]
+use uni_xervo::runtime::ModelRuntime;
+
+#[cfg(feature = "provider-candle")]
+fn modified(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(feature = "provider-candle")]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "catalog.json".to_string());
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog_from_file(&path)?
+        .build()
+        .await?;
+    println!("Watching {path} for changes");
+
+    let mut last_modified = modified(&path);
+    loop {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let current = modified(&path);
+        if current == last_modified {
+            continue;
+        }
+        last_modified = current;
+
+        match runtime.reload_catalog_from_file(&path).await {
+            Ok(changes) => println!(
+                "Reloaded: added {:?}, updated {:?}, removed {:?}",
+                changes.added, changes.updated, changes.removed
+            ),
+            Err(e) => eprintln!("Rejected catalog change, keeping the previous one: {e}"),
+        }
+    }
+}
+
+#[cfg(not(feature = "provider-candle"))]
+fn main() {
+    eprintln!(
+        "This example requires the `provider-candle` feature.\n\
+         Run with: cargo run --example catalog_hot_reload --features provider-candle -- catalog.json"
+    );
+}
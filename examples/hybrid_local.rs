@@ -33,6 +33,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -48,6 +49,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: json!({
             "isq": "Q4K",
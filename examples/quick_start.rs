@@ -26,6 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
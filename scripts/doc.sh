@@ -10,7 +10,7 @@ set -euo pipefail
 echo "Building rustdoc..."
 # Use explicit feature list instead of --all-features because
 # provider-mistralrs and gpu-cuda require a CUDA toolkit at build time.
-DOC_FEATURES="provider-candle,provider-fastembed,provider-openai,provider-gemini,provider-vertexai,provider-mistral,provider-anthropic,provider-voyageai,provider-cohere,provider-azure-openai"
+DOC_FEATURES="provider-candle,provider-fastembed,provider-onnx,provider-openai,provider-gemini,provider-vertexai,provider-mistral,provider-anthropic,provider-voyageai,provider-cohere,provider-azure-openai,provider-jina,provider-nim"
 cargo doc --no-deps --features "$DOC_FEATURES"
 
 echo "Copying rustdoc into website/docs/api/..."
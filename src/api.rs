@@ -14,6 +14,8 @@ pub enum ModelTask {
     Rerank,
     /// Generate text (chat completions, summarization, etc.).
     Generate,
+    /// Assign a text to one of a set of candidate labels.
+    Classify,
 }
 
 /// Controls when a model or provider is initialized during runtime startup.
@@ -86,6 +88,10 @@ pub struct ModelAliasSpec {
     /// Model load timeout in seconds. Defaults to 600 s if unset.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub load_timeout: Option<u64>,
+    /// Timeout in seconds for the model's warmup step after loading. Falls back
+    /// to `load_timeout` if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warmup_timeout: Option<u64>,
     /// Retry configuration for transient inference failures.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryConfig>,
@@ -95,6 +101,41 @@ pub struct ModelAliasSpec {
     pub options: serde_json::Value,
 }
 
+/// A category of error that [`RetryConfig::retry_on`] can opt into retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryableKind {
+    /// [`RuntimeError::Timeout`].
+    Timeout,
+    /// [`RuntimeError::RateLimited`], or an [`RuntimeError::ApiError`] with
+    /// HTTP status 429.
+    RateLimited,
+    /// [`RuntimeError::Unavailable`] (e.g. an open circuit breaker), or an
+    /// [`RuntimeError::ApiError`] with a 5xx status.
+    Unavailable,
+    /// Only an [`RuntimeError::ApiError`] with a 5xx status, leaving out
+    /// open circuit breakers.
+    #[serde(rename = "api_5xx")]
+    Api5xx,
+}
+
+impl RetryableKind {
+    /// Whether `err` falls into this category.
+    pub fn matches(&self, err: &RuntimeError) -> bool {
+        match self {
+            Self::Timeout => matches!(err, RuntimeError::Timeout { .. }),
+            Self::RateLimited => {
+                matches!(err, RuntimeError::RateLimited) || err.status() == Some(429)
+            }
+            Self::Unavailable => {
+                matches!(err, RuntimeError::Unavailable)
+                    || err.status().is_some_and(|s| (500..600).contains(&s))
+            }
+            Self::Api5xx => err.status().is_some_and(|s| (500..600).contains(&s)),
+        }
+    }
+}
+
 /// Configuration for exponential-backoff retries on transient inference errors.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RetryConfig {
@@ -102,6 +143,17 @@ pub struct RetryConfig {
     pub max_attempts: u32,
     /// Base delay in milliseconds; doubled on each subsequent attempt.
     pub initial_backoff_ms: u64,
+    /// Error kinds to retry. When set, only errors matching one of these
+    /// kinds are retried, replacing [`RuntimeError::is_retryable`]; when
+    /// unset, every retryable error is retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_on: Option<Vec<RetryableKind>>,
+    /// Overall budget in milliseconds across all attempts and backoffs. No
+    /// retry is started if its backoff would end past this budget, so with a
+    /// per-call `timeout` a call finishes within roughly this budget plus one
+    /// `timeout`. Unbounded when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_duration_ms: Option<u64>,
 }
 
 impl RetryConfig {
@@ -113,6 +165,46 @@ impl RetryConfig {
             self.initial_backoff_ms * 2u64.pow(attempt.saturating_sub(1)),
         )
     }
+
+    /// Whether a failed attempt with `err` should be retried, per
+    /// [`retry_on`](Self::retry_on) or, when unset,
+    /// [`RuntimeError::is_retryable`].
+    pub fn should_retry(&self, err: &RuntimeError) -> bool {
+        match &self.retry_on {
+            Some(kinds) => kinds.iter().any(|kind| kind.matches(err)),
+            None => err.is_retryable(),
+        }
+    }
+
+    /// The overall retry budget set by
+    /// [`max_total_duration_ms`](Self::max_total_duration_ms).
+    pub fn max_total_duration(&self) -> Option<std::time::Duration> {
+        self.max_total_duration_ms
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// The backoff to wait before retrying after attempt number `attempt`
+    /// (1-based) failed with `err`, `elapsed` after the first attempt
+    /// started; `None` when `err` should be returned instead.
+    ///
+    /// Stops when attempts are exhausted, `err` is not retried per
+    /// [`should_retry`](Self::should_retry), or the backoff would run past
+    /// [`max_total_duration`](Self::max_total_duration).
+    pub fn next_backoff(
+        &self,
+        attempt: u32,
+        elapsed: std::time::Duration,
+        err: &RuntimeError,
+    ) -> Option<std::time::Duration> {
+        if attempt >= self.max_attempts || !self.should_retry(err) {
+            return None;
+        }
+        let backoff = self.get_backoff(attempt);
+        match self.max_total_duration() {
+            Some(budget) if elapsed + backoff > budget => None,
+            _ => Some(backoff),
+        }
+    }
 }
 
 impl Default for RetryConfig {
@@ -120,16 +212,59 @@ impl Default for RetryConfig {
         Self {
             max_attempts: 3,
             initial_backoff_ms: 100,
+            retry_on: None,
+            max_total_duration_ms: None,
         }
     }
 }
 
+/// A logical alias that spreads calls across several catalog aliases in
+/// proportion to their weights.
+///
+/// Each backend refers to an ordinary [`ModelAliasSpec`] in the catalog, which
+/// keeps its own provider, model, options, timeout, and retry settings.
+///
+/// ```json
+/// {
+///   "alias": "embed/balanced",
+///   "task": "embed",
+///   "backends": [
+///     { "alias": "embed/openai", "weight": 70 },
+///     { "alias": "embed/azure", "weight": 30 }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiBackendSpec {
+    /// Name used to request the balanced model. Must contain a `/` separator
+    /// and must not clash with a catalog alias.
+    pub alias: String,
+    /// The inference task; every backend alias must have the same task.
+    pub task: ModelTask,
+    /// Member aliases and their relative weights.
+    pub backends: Vec<WeightedBackend>,
+    /// Settings of the circuit breaker guarding each member; the
+    /// [`CircuitBreakerConfig`](crate::reliability::CircuitBreakerConfig)
+    /// defaults when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<crate::reliability::CircuitBreakerConfig>,
+}
+
+/// One member of a [`MultiBackendSpec`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightedBackend {
+    /// Catalog alias that serves this share of the calls.
+    pub alias: String,
+    /// Relative share of calls; must be greater than 0.
+    pub weight: u32,
+}
+
 /// Deduplication key used by the runtime to share a single loaded model instance
 /// across multiple aliases that point to the same provider, model, revision, and
 /// options.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ModelRuntimeKey {
-    /// The task type (embed, rerank, generate).
+    /// The task type (embed, rerank, generate, classify).
     pub task: ModelTask,
     /// Provider that owns this model instance.
     pub provider_id: String,
@@ -139,7 +274,9 @@ pub struct ModelRuntimeKey {
     pub revision: Option<String>,
     /// Hash of the provider-specific options JSON. Two specs with semantically
     /// equivalent options (same keys/values, any object-key order) produce the
-    /// same hash.
+    /// same hash. Keys built with
+    /// [`with_load_options`](Self::with_load_options) hash only the listed
+    /// options.
     pub variant_hash: u64,
 }
 
@@ -163,6 +300,26 @@ impl ModelRuntimeKey {
             variant_hash: hasher.finish(),
         }
     }
+
+    /// Derive a runtime key that hashes only the option keys in
+    /// `load_options`, so specs differing only in other options share an
+    /// instance. See [`ModelProvider::load_option_keys`](crate::traits::ModelProvider::load_option_keys).
+    ///
+    /// Options that are not a JSON object are hashed whole.
+    pub fn with_load_options(spec: &ModelAliasSpec, load_options: &[&str]) -> Self {
+        let serde_json::Value::Object(options) = &spec.options else {
+            return Self::new(spec);
+        };
+        let relevant: serde_json::Map<String, serde_json::Value> = options
+            .iter()
+            .filter(|(key, _)| load_options.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        Self::new(&ModelAliasSpec {
+            options: serde_json::Value::Object(relevant),
+            ..spec.clone()
+        })
+    }
 }
 
 /// Recursively hash a JSON value in a deterministic, key-order-independent way.
@@ -234,6 +391,11 @@ impl ModelAliasSpec {
                 "Load timeout must be greater than 0".to_string(),
             ));
         }
+        if self.warmup_timeout == Some(0) {
+            return Err(RuntimeError::Config(
+                "Warmup timeout must be greater than 0".to_string(),
+            ));
+        }
         Ok(())
     }
 
@@ -254,10 +416,88 @@ impl ModelAliasSpec {
     }
 }
 
-/// Parse a catalog (array) of `ModelAliasSpec` from a JSON string.
+/// A catalog with a `defaults` template, the object form accepted by
+/// [`catalog_from_str`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayeredCatalog {
+    #[serde(default)]
+    defaults: serde_json::Map<String, serde_json::Value>,
+    models: Vec<serde_json::Value>,
+}
+
+/// Fill each entry's unset fields from `defaults`. `options` objects are
+/// merged key by key; for every other field an entry's value, even `null`,
+/// wins over the default.
+fn apply_catalog_defaults(
+    defaults: &serde_json::Map<String, serde_json::Value>,
+    models: Vec<serde_json::Value>,
+) -> Result<Vec<serde_json::Value>> {
+    if defaults.contains_key("alias") {
+        return Err(RuntimeError::Config(
+            "Catalog defaults cannot set 'alias'".to_string(),
+        ));
+    }
+    models
+        .into_iter()
+        .map(|model| {
+            let serde_json::Value::Object(mut entry) = model else {
+                return Err(RuntimeError::Config(format!(
+                    "Invalid catalog JSON: model entries must be objects, got {}",
+                    model
+                )));
+            };
+            for (key, default) in defaults {
+                match (entry.get_mut(key), default) {
+                    (None, _) => {
+                        entry.insert(key.clone(), default.clone());
+                    }
+                    (
+                        Some(serde_json::Value::Object(options)),
+                        serde_json::Value::Object(default_options),
+                    ) if key == "options" => {
+                        for (k, v) in default_options {
+                            options.entry(k.clone()).or_insert_with(|| v.clone());
+                        }
+                    }
+                    (Some(_), _) => {}
+                }
+            }
+            Ok(serde_json::Value::Object(entry))
+        })
+        .collect()
+}
+
+/// Parse a catalog of `ModelAliasSpec` from a JSON string.
+///
+/// The catalog is either a JSON array of specs or an object with a `models`
+/// array and an optional `defaults` partial spec:
+///
+/// ```json
+/// {
+///   "defaults": { "provider_id": "remote/openai", "warmup": "lazy" },
+///   "models": [
+///     { "alias": "embed/small", "task": "embed", "model_id": "text-embedding-3-small" },
+///     { "alias": "chat/fast", "task": "generate", "model_id": "gpt-4o-mini", "warmup": "eager" }
+///   ]
+/// }
+/// ```
+///
+/// Each entry takes every field it does not set from `defaults`; `options`
+/// objects are merged key by key with the entry's keys winning. An explicit
+/// `null` in an entry keeps the default out, e.g. `"retry": null`.
 pub fn catalog_from_str(s: &str) -> Result<Vec<ModelAliasSpec>> {
-    let specs: Vec<ModelAliasSpec> = serde_json::from_str(s)
-        .map_err(|e| RuntimeError::Config(format!("Invalid catalog JSON: {}", e)))?;
+    let invalid =
+        |e: serde_json::Error| RuntimeError::Config(format!("Invalid catalog JSON: {}", e));
+    let value: serde_json::Value = serde_json::from_str(s).map_err(invalid)?;
+    let entries = match value {
+        serde_json::Value::Object(_) => {
+            let layered: LayeredCatalog = serde_json::from_value(value).map_err(invalid)?;
+            serde_json::Value::Array(apply_catalog_defaults(&layered.defaults, layered.models)?)
+        }
+        other => other,
+    };
+    let specs: Vec<ModelAliasSpec> = serde_json::from_value(entries).map_err(invalid)?;
     for spec in &specs {
         spec.validate()?;
     }
@@ -266,7 +506,8 @@ pub fn catalog_from_str(s: &str) -> Result<Vec<ModelAliasSpec>> {
 
 /// Read and parse a catalog from a JSON file.
 ///
-/// The file must contain a JSON array of model alias specs.
+/// The file must contain a JSON array of model alias specs or a layered
+/// catalog object; see [`catalog_from_str`].
 pub fn catalog_from_file(path: impl AsRef<Path>) -> Result<Vec<ModelAliasSpec>> {
     let path = path.as_ref();
     let contents = std::fs::read_to_string(path).map_err(|e| {
@@ -361,6 +602,74 @@ mod tests {
         assert!(catalog_from_str(json).is_err()); // alias has no '/'
     }
 
+    #[test]
+    fn catalog_defaults_fill_unset_fields() {
+        let json = r#"{
+            "defaults": {
+                "provider_id": "remote/openai",
+                "warmup": "eager",
+                "timeout": 30,
+                "options": { "api_key_env": "TEAM_KEY", "dimensions": 256 }
+            },
+            "models": [
+                { "alias": "embed/small", "task": "embed", "model_id": "text-embedding-3-small" },
+                {
+                    "alias": "embed/local",
+                    "task": "embed",
+                    "provider_id": "local/candle",
+                    "model_id": "all-MiniLM-L6-v2",
+                    "timeout": null,
+                    "options": { "dimensions": 384 }
+                }
+            ]
+        }"#;
+        let specs = catalog_from_str(json).unwrap();
+        assert_eq!(specs.len(), 2);
+
+        assert_eq!(specs[0].provider_id, "remote/openai");
+        assert_eq!(specs[0].warmup, WarmupPolicy::Eager);
+        assert_eq!(specs[0].timeout, Some(30));
+        assert_eq!(
+            specs[0].options,
+            json!({ "api_key_env": "TEAM_KEY", "dimensions": 256 })
+        );
+
+        assert_eq!(specs[1].provider_id, "local/candle");
+        assert_eq!(specs[1].warmup, WarmupPolicy::Eager);
+        assert_eq!(specs[1].timeout, None);
+        assert_eq!(
+            specs[1].options,
+            json!({ "api_key_env": "TEAM_KEY", "dimensions": 384 })
+        );
+    }
+
+    #[test]
+    fn catalog_defaults_reject_alias() {
+        let json = r#"{
+            "defaults": { "alias": "embed/shared" },
+            "models": [{ "alias": "embed/a", "task": "embed", "provider_id": "x", "model_id": "y" }]
+        }"#;
+        let err = catalog_from_str(json).unwrap_err();
+        assert!(err.to_string().contains("alias"));
+    }
+
+    #[test]
+    fn catalog_object_requires_models_and_rejects_unknown_keys() {
+        assert!(catalog_from_str(r#"{ "defaults": {} }"#).is_err());
+        assert!(catalog_from_str(r#"{ "models": [], "extra": 1 }"#).is_err());
+        assert!(catalog_from_str(r#"{ "models": [] }"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn catalog_defaults_still_require_complete_specs() {
+        let json = r#"{
+            "defaults": { "provider_id": "remote/openai" },
+            "models": [{ "alias": "embed/a", "task": "embed" }]
+        }"#;
+        let err = catalog_from_str(json).unwrap_err();
+        assert!(err.to_string().contains("model_id"));
+    }
+
     #[test]
     fn catalog_from_file_reads_and_parses() {
         let dir = std::env::temp_dir();
@@ -0,0 +1,495 @@
+//! Weighted round-robin across several catalog aliases.
+//!
+//! A [`MultiBackendSpec`](crate::api::MultiBackendSpec) names a logical alias
+//! served by several ordinary catalog aliases, e.g. 70% OpenAI and 30% Azure
+//! OpenAI. The runtime resolves each member through its usual handle (with its
+//! own timeout, retry, and usage tracking) and wraps them in a balanced model
+//! that picks one member per call.
+//!
+//! # Selection
+//!
+//! Members are chosen with smooth weighted round-robin, so a 70/30 split sends
+//! exactly 7 of every 10 calls to the first member, interleaved rather than in
+//! runs. Each member is guarded by its own circuit breaker; members whose
+//! breaker is open are skipped and their share goes to the others. When every
+//! member is open the call fails with [`RuntimeError::Unavailable`].
+//!
+//! The selection state lives in the runtime, so separate handles for the same
+//! balanced alias share one rotation and one set of breakers.
+
+use crate::api::MultiBackendSpec;
+use crate::error::{Result, RuntimeError};
+use crate::reliability::CircuitBreakerWrapper;
+use crate::traits::{
+    ClassifierModel, EmbeddingModel, EmbeddingStream, GenerationOptions, GenerationResult,
+    GenerationStream, GeneratorModel, Message, QuantizedEmbedding, RerankOptions, RerankerModel,
+    ScoredDoc, ScoredLabel, TokenUsage,
+};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+struct Member {
+    alias: String,
+    weight: i64,
+    breaker: CircuitBreakerWrapper,
+}
+
+/// Shared rotation state and per-member circuit breakers for one balanced
+/// alias.
+pub(crate) struct WeightedSelector {
+    alias: String,
+    members: Vec<Member>,
+    /// Smooth weighted round-robin counters, one per member.
+    current: Mutex<Vec<i64>>,
+}
+
+impl WeightedSelector {
+    /// `provider_of` maps a member alias to the provider serving it, which
+    /// labels that member's breaker.
+    pub(crate) fn new<'a>(
+        spec: &MultiBackendSpec,
+        provider_of: impl Fn(&str) -> Option<&'a str>,
+    ) -> Self {
+        let config = spec.circuit_breaker.unwrap_or_default();
+        Self {
+            alias: spec.alias.clone(),
+            members: spec
+                .backends
+                .iter()
+                .map(|b| Member {
+                    alias: b.alias.clone(),
+                    weight: i64::from(b.weight),
+                    breaker: CircuitBreakerWrapper::new(config)
+                        .with_labels(provider_of(&b.alias).unwrap_or("unknown"), &b.alias),
+                })
+                .collect(),
+            current: Mutex::new(vec![0; spec.backends.len()]),
+        }
+    }
+
+    /// Index of the member that should serve the next call, skipping members
+    /// whose breaker is open.
+    fn pick(&self) -> Result<usize> {
+        let available: Vec<bool> = self.members.iter().map(|m| !m.breaker.is_open()).collect();
+        let total: i64 = self
+            .members
+            .iter()
+            .zip(&available)
+            .filter(|(_, ok)| **ok)
+            .map(|(m, _)| m.weight)
+            .sum();
+        if total == 0 {
+            return Err(RuntimeError::Unavailable);
+        }
+
+        let mut current = self.current.lock().unwrap();
+        let mut best: Option<usize> = None;
+        for (i, member) in self.members.iter().enumerate() {
+            if !available[i] {
+                continue;
+            }
+            current[i] += member.weight;
+            if best.is_none_or(|b| current[i] > current[b]) {
+                best = Some(i);
+            }
+        }
+        let chosen = best.expect("at least one member is available");
+        current[chosen] -= total;
+        Ok(chosen)
+    }
+
+    /// Run `f` against the next member's model through that member's breaker.
+    async fn call<M, F, Fut, T>(&self, models: &[Arc<M>], f: F) -> Result<T>
+    where
+        M: ?Sized,
+        F: FnOnce(Arc<M>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let index = self.pick()?;
+        let member = &self.members[index];
+        crate::telemetry::counter!(
+            "model_backend.selected",
+            "alias" => self.alias.clone(),
+            "backend" => member.alias.clone()
+        )
+        .increment(1);
+        tracing::debug!(alias = %self.alias, backend = %member.alias, "Selected backend");
+        let model = models[index].clone();
+        member.breaker.call(move || f(model)).await
+    }
+}
+
+/// An [`EmbeddingModel`] that spreads calls across several member models.
+///
+/// Members must produce vectors of the same dimensions; see
+/// [`new`](Self::new).
+pub(crate) struct WeightedEmbeddingModel {
+    alias: String,
+    selector: Arc<WeightedSelector>,
+    models: Vec<Arc<dyn EmbeddingModel>>,
+}
+
+impl WeightedEmbeddingModel {
+    /// `models` must be in the same order as the selector's members.
+    ///
+    /// Fails with [`RuntimeError::Config`] if the members report different
+    /// dimensions, since callers could not rely on the vector length.
+    pub(crate) fn new(
+        selector: Arc<WeightedSelector>,
+        models: Vec<Arc<dyn EmbeddingModel>>,
+    ) -> Result<Self> {
+        if let Some(first) = models.first()
+            && let Some((i, other)) = models
+                .iter()
+                .enumerate()
+                .find(|(_, m)| m.dimensions() != first.dimensions())
+        {
+            return Err(RuntimeError::Config(format!(
+                "Backends of '{}' have different dimensions: '{}' has {}, '{}' has {}",
+                selector.alias,
+                selector.members[0].alias,
+                first.dimensions(),
+                selector.members[i].alias,
+                other.dimensions()
+            )));
+        }
+        Ok(Self {
+            alias: selector.alias.clone(),
+            selector,
+            models,
+        })
+    }
+}
+
+#[async_trait]
+impl EmbeddingModel for WeightedEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.selector
+            .call(&self.models, |m| async move { m.embed(texts).await })
+            .await
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        self.selector
+            .call(
+                &self.models,
+                |m| async move { m.embed_with_usage(texts).await },
+            )
+            .await
+    }
+
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        self.selector
+            .call(&self.models, |m| async move {
+                m.embed_with(texts, overrides).await
+            })
+            .await
+    }
+
+    async fn embed_quantized(&self, texts: Vec<&str>) -> Result<Vec<QuantizedEmbedding>> {
+        self.selector
+            .call(
+                &self.models,
+                |m| async move { m.embed_quantized(texts).await },
+            )
+            .await
+    }
+
+    async fn embed_int8(&self, texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+        self.selector
+            .call(&self.models, |m| async move { m.embed_int8(texts).await })
+            .await
+    }
+
+    async fn embed_binary(&self, texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+        self.selector
+            .call(&self.models, |m| async move { m.embed_binary(texts).await })
+            .await
+    }
+
+    async fn embed_tokens(&self, batches: Vec<Vec<u32>>) -> Result<Vec<Vec<f32>>> {
+        self.selector
+            .call(
+                &self.models,
+                |m| async move { m.embed_tokens(batches).await },
+            )
+            .await
+    }
+
+    /// The whole stream is served by one member.
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        self.selector
+            .call(&self.models, |m| async move { m.embed_stream(texts).await })
+            .await
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.models.first().map_or(0, |m| m.dimensions())
+    }
+
+    /// The balanced alias, since calls may be served by different models.
+    fn model_id(&self) -> &str {
+        &self.alias
+    }
+}
+
+/// A [`GeneratorModel`] that spreads calls across several member models.
+pub(crate) struct WeightedGeneratorModel {
+    selector: Arc<WeightedSelector>,
+    models: Vec<Arc<dyn GeneratorModel>>,
+}
+
+impl WeightedGeneratorModel {
+    /// `models` must be in the same order as the selector's members.
+    pub(crate) fn new(
+        selector: Arc<WeightedSelector>,
+        models: Vec<Arc<dyn GeneratorModel>>,
+    ) -> Self {
+        Self { selector, models }
+    }
+}
+
+#[async_trait]
+impl GeneratorModel for WeightedGeneratorModel {
+    async fn generate(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationResult> {
+        self.selector
+            .call(&self.models, |m| async move {
+                m.generate(messages, options).await
+            })
+            .await
+    }
+
+    /// The member's breaker sees only whether the stream could be opened.
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        self.selector
+            .call(&self.models, |m| async move {
+                m.generate_stream(messages, options).await
+            })
+            .await
+    }
+}
+
+/// A [`RerankerModel`] that spreads calls across several member models.
+pub(crate) struct WeightedRerankerModel {
+    selector: Arc<WeightedSelector>,
+    models: Vec<Arc<dyn RerankerModel>>,
+}
+
+impl WeightedRerankerModel {
+    /// `models` must be in the same order as the selector's members.
+    pub(crate) fn new(
+        selector: Arc<WeightedSelector>,
+        models: Vec<Arc<dyn RerankerModel>>,
+    ) -> Self {
+        Self { selector, models }
+    }
+}
+
+#[async_trait]
+impl RerankerModel for WeightedRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        self.selector
+            .call(&self.models, |m| async move { m.rerank(query, docs).await })
+            .await
+    }
+
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        self.selector
+            .call(&self.models, |m| async move {
+                m.rerank_with_options(query, docs, options).await
+            })
+            .await
+    }
+}
+
+/// A [`ClassifierModel`] that spreads calls across several member models.
+pub(crate) struct WeightedClassifierModel {
+    selector: Arc<WeightedSelector>,
+    models: Vec<Arc<dyn ClassifierModel>>,
+}
+
+impl WeightedClassifierModel {
+    /// `models` must be in the same order as the selector's members.
+    pub(crate) fn new(
+        selector: Arc<WeightedSelector>,
+        models: Vec<Arc<dyn ClassifierModel>>,
+    ) -> Self {
+        Self { selector, models }
+    }
+}
+
+#[async_trait]
+impl ClassifierModel for WeightedClassifierModel {
+    async fn classify(&self, text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        self.selector
+            .call(
+                &self.models,
+                |m| async move { m.classify(text, labels).await },
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ModelTask, WeightedBackend};
+    use crate::reliability::CircuitBreakerConfig;
+
+    fn spec(weights: &[u32]) -> MultiBackendSpec {
+        MultiBackendSpec {
+            alias: "embed/balanced".to_string(),
+            task: ModelTask::Embed,
+            backends: weights
+                .iter()
+                .enumerate()
+                .map(|(i, w)| WeightedBackend {
+                    alias: format!("embed/member-{i}"),
+                    weight: *w,
+                })
+                .collect(),
+            circuit_breaker: None,
+        }
+    }
+
+    fn selector(weights: &[u32]) -> WeightedSelector {
+        WeightedSelector::new(&spec(weights), |_| Some("mock/embed"))
+    }
+
+    async fn fail(selector: &WeightedSelector, member: usize) {
+        let _ = selector.members[member]
+            .breaker
+            .call(|| async { Err::<(), _>(RuntimeError::Unavailable) })
+            .await;
+    }
+
+    #[test]
+    fn picks_members_in_proportion_to_weight() {
+        let selector = selector(&[7, 3]);
+        let picks: Vec<usize> = (0..10).map(|_| selector.pick().unwrap()).collect();
+        assert_eq!(picks.iter().filter(|i| **i == 0).count(), 7);
+        assert_eq!(picks.iter().filter(|i| **i == 1).count(), 3);
+        // Smooth round-robin interleaves rather than sending runs.
+        assert_ne!(&picks[..3], &[0, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn skips_members_with_open_breaker() {
+        let selector = selector(&[1, 1]);
+        for _ in 0..CircuitBreakerConfig::default().failure_threshold {
+            fail(&selector, 0).await;
+        }
+        assert!(selector.members[0].breaker.is_open());
+
+        for _ in 0..4 {
+            assert_eq!(selector.pick().unwrap(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn all_members_open_is_unavailable() {
+        let selector = selector(&[1]);
+        for _ in 0..CircuitBreakerConfig::default().failure_threshold {
+            fail(&selector, 0).await;
+        }
+        assert!(matches!(selector.pick(), Err(RuntimeError::Unavailable)));
+    }
+
+    #[tokio::test]
+    async fn member_breakers_use_the_spec_config() {
+        let spec = MultiBackendSpec {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 1,
+                open_wait_seconds: 60,
+            }),
+            ..spec(&[1, 1])
+        };
+        let selector = WeightedSelector::new(&spec, |_| Some("mock/embed"));
+        fail(&selector, 0).await;
+        assert!(selector.members[0].breaker.is_open());
+        assert!(!selector.members[1].breaker.is_open());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn member_breakers_are_labelled_with_the_member_provider() {
+        use metrics_util::debugging::DebuggingRecorder;
+
+        let spec = MultiBackendSpec {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 1,
+                open_wait_seconds: 60,
+            }),
+            ..spec(&[1, 1])
+        };
+        let selector = WeightedSelector::new(&spec, |member| {
+            Some(if member == "embed/member-0" {
+                "remote/openai"
+            } else {
+                "remote/azure-openai"
+            })
+        });
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                fail(&selector, 0).await;
+                fail(&selector, 1).await;
+            })
+        });
+
+        let mut labels: Vec<(String, String)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == "circuit_breaker.transitions")
+            .map(|(key, _, _, _)| {
+                let label = |name: &str| {
+                    key.key()
+                        .labels()
+                        .find(|l| l.key() == name)
+                        .map(|l| l.value().to_string())
+                        .unwrap()
+                };
+                (label("provider"), label("model"))
+            })
+            .collect();
+        labels.sort();
+        assert_eq!(
+            labels,
+            vec![
+                (
+                    "remote/azure-openai".to_string(),
+                    "embed/member-1".to_string()
+                ),
+                ("remote/openai".to_string(), "embed/member-0".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,599 @@
+//! Micro-batching of concurrent embedding requests.
+//!
+//! [`MicroBatchingEmbeddingModel`] coalesces `embed` calls that arrive within a
+//! short window into a single upstream batch, then fans the resulting vectors
+//! back out to each caller by position. This trades at most `max_delay` of
+//! added latency for far fewer upstream requests when many callers embed one
+//! or two texts at a time.
+//!
+//! # Error isolation
+//!
+//! All participants of a batch share one upstream call, so per-caller error
+//! isolation is not possible: if the upstream call fails (including because of
+//! a single input the provider rejects), **every** caller in that batch
+//! receives the same error.
+//!
+//! # Usage
+//!
+//! The provider reports token usage for the whole upstream batch. Callers of
+//! [`embed_with_usage`](EmbeddingModel::embed_with_usage) each receive a share
+//! of it proportional to the byte length of their texts, with the rounding
+//! remainder going to the last caller so the shares add up to the batch total.
+//!
+//! # Cancellation
+//!
+//! The upstream call runs detached, so cancelling one caller never aborts a
+//! batch others are waiting on. Texts of callers cancelled before the flush
+//! are left out of the batch, and a batch whose callers have all gone is not
+//! sent at all.
+//!
+//! # Splitting
+//!
+//! [`embed_in_sub_batches`] goes the other way: providers that split one large
+//! call into sub-batches run several at once through it, and it reassembles
+//! the vectors in input order no matter which sub-batch finishes first.
+
+use crate::error::{Result, RuntimeError};
+use crate::traits::{
+    EmbeddingModel, EmbeddingStream, RateLimitSnapshot, TokenUsage, has_overrides,
+};
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Tunable parameters for micro-batching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicroBatchConfig {
+    /// Flush the pending batch as soon as it holds at least this many texts.
+    pub max_batch: usize,
+    /// Flush the pending batch this long after its first text arrived, even
+    /// if `max_batch` has not been reached.
+    pub max_delay: Duration,
+}
+
+impl Default for MicroBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch: 64,
+            max_delay: Duration::from_millis(5),
+        }
+    }
+}
+
+/// Embeddings for one caller's texts, with that caller's share of the
+/// batch's token usage.
+type WaiterResult = Result<(Vec<Vec<f32>>, Option<TokenUsage>)>;
+
+/// A caller waiting on a slice of the next upstream batch.
+struct Waiter {
+    len: usize,
+    /// Total byte length of the caller's texts; its weight when the batch's
+    /// usage is split.
+    bytes: usize,
+    tx: oneshot::Sender<WaiterResult>,
+}
+
+/// Texts and waiters accumulated since the last flush. Waiters are stored in
+/// arrival order, so each waiter's vectors are a contiguous run of the batch
+/// output.
+#[derive(Default)]
+struct PendingBatch {
+    /// Incremented on every flush so a stale delay timer can tell that the
+    /// batch it was scheduled for has already been sent.
+    generation: u64,
+    texts: Vec<String>,
+    waiters: Vec<Waiter>,
+}
+
+impl PendingBatch {
+    fn take(&mut self) -> (Vec<String>, Vec<Waiter>) {
+        self.generation = self.generation.wrapping_add(1);
+        (
+            std::mem::take(&mut self.texts),
+            std::mem::take(&mut self.waiters),
+        )
+    }
+}
+
+/// Wrapper around an [`EmbeddingModel`] that coalesces concurrent `embed`
+/// calls into a single upstream batch.
+///
+/// A batch is flushed when it reaches [`MicroBatchConfig::max_batch`] texts or
+/// when [`MicroBatchConfig::max_delay`] has elapsed since its first text
+/// arrived, whichever comes first. A single call larger than `max_batch` is
+/// sent as one batch rather than split.
+///
+/// See the [module docs](self) for the error-isolation trade-off.
+pub struct MicroBatchingEmbeddingModel {
+    inner: Arc<dyn EmbeddingModel>,
+    config: MicroBatchConfig,
+    pending: Arc<Mutex<PendingBatch>>,
+}
+
+impl MicroBatchingEmbeddingModel {
+    /// Wrap `inner` with the given batching configuration.
+    pub fn new(inner: Arc<dyn EmbeddingModel>, config: MicroBatchConfig) -> Self {
+        Self {
+            inner,
+            config,
+            pending: Arc::new(Mutex::new(PendingBatch::default())),
+        }
+    }
+
+    /// Add `texts` to the pending batch and wait for their slice of the
+    /// result.
+    async fn enqueue(&self, texts: Vec<&str>) -> WaiterResult {
+        if texts.is_empty() {
+            return Ok((vec![], None));
+        }
+
+        let (tx, rx) = oneshot::channel();
+
+        let ready = {
+            let mut pending = self.pending.lock().unwrap();
+            let first = pending.waiters.is_empty();
+            pending.texts.extend(texts.iter().map(|s| s.to_string()));
+            pending.waiters.push(Waiter {
+                len: texts.len(),
+                bytes: texts.iter().map(|t| t.len()).sum(),
+                tx,
+            });
+
+            if pending.texts.len() >= self.config.max_batch {
+                Some(pending.take())
+            } else {
+                if first {
+                    // First caller in a fresh batch arms the delay timer.
+                    let generation = pending.generation;
+                    let pending_ref = self.pending.clone();
+                    let inner = self.inner.clone();
+                    let delay = self.config.max_delay;
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        let (texts, waiters) = {
+                            let mut pending = pending_ref.lock().unwrap();
+                            if pending.generation != generation || pending.waiters.is_empty() {
+                                return;
+                            }
+                            pending.take()
+                        };
+                        run_batch(inner, texts, waiters).await;
+                    });
+                }
+                None
+            }
+        };
+
+        // Run the upstream call detached so that a cancelled caller does not
+        // abort the batch for everyone else.
+        if let Some((texts, waiters)) = ready {
+            tokio::spawn(run_batch(self.inner.clone(), texts, waiters));
+        }
+
+        rx.await.map_err(|_| {
+            RuntimeError::InferenceError("Micro-batch worker dropped the request".to_string())
+        })?
+    }
+}
+
+/// Run one upstream batch and distribute the results to its waiters.
+async fn run_batch(inner: Arc<dyn EmbeddingModel>, texts: Vec<String>, waiters: Vec<Waiter>) {
+    let mut live_texts = Vec::with_capacity(texts.len());
+    let mut live_waiters = Vec::with_capacity(waiters.len());
+    let mut offset = 0;
+    for waiter in waiters {
+        let end = offset + waiter.len;
+        if !waiter.tx.is_closed() {
+            live_texts.extend_from_slice(&texts[offset..end]);
+            live_waiters.push(waiter);
+        }
+        offset = end;
+    }
+    if live_waiters.is_empty() {
+        return;
+    }
+    let (texts, waiters) = (live_texts, live_waiters);
+
+    let expected = texts.len();
+    let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+    let result = inner
+        .embed_with_usage(refs)
+        .await
+        .and_then(|(vectors, usage)| {
+            if vectors.len() == expected {
+                Ok((vectors, usage))
+            } else {
+                Err(RuntimeError::InferenceError(format!(
+                    "Micro-batch expected {} embeddings, provider returned {}",
+                    expected,
+                    vectors.len()
+                )))
+            }
+        });
+
+    match result {
+        Ok((vectors, usage)) => {
+            let weights: Vec<usize> = waiters.iter().map(|w| w.bytes).collect();
+            let mut shares = match usage {
+                Some(usage) => split_usage(&usage, &weights)
+                    .into_iter()
+                    .map(Some)
+                    .collect(),
+                None => vec![None; waiters.len()],
+            }
+            .into_iter();
+            let mut vectors = vectors.into_iter();
+            for waiter in waiters {
+                let chunk: Vec<Vec<f32>> = vectors.by_ref().take(waiter.len).collect();
+                let _ = waiter.tx.send(Ok((chunk, shares.next().flatten())));
+            }
+        }
+        Err(e) => {
+            for waiter in waiters {
+                let _ = waiter.tx.send(Err(e.clone()));
+            }
+        }
+    }
+}
+
+/// Split `usage` between callers in proportion to `weights`. The last caller
+/// takes the rounding remainder, so the shares always sum to `usage`.
+fn split_usage(usage: &TokenUsage, weights: &[usize]) -> Vec<TokenUsage> {
+    let total_weight: usize = weights.iter().sum();
+    let mut remaining = usage.clone();
+    let mut shares = Vec::with_capacity(weights.len());
+    for (i, &weight) in weights.iter().enumerate() {
+        if i + 1 == weights.len() {
+            shares.push(remaining.clone());
+            break;
+        }
+        let part = |n: usize| n * weight / total_weight.max(1);
+        let share = TokenUsage {
+            prompt_tokens: part(usage.prompt_tokens),
+            completion_tokens: part(usage.completion_tokens),
+            total_tokens: part(usage.total_tokens),
+            cache_read_tokens: part(usage.cache_read_tokens),
+            cache_creation_tokens: part(usage.cache_creation_tokens),
+        };
+        remaining.prompt_tokens -= share.prompt_tokens;
+        remaining.completion_tokens -= share.completion_tokens;
+        remaining.total_tokens -= share.total_tokens;
+        remaining.cache_read_tokens -= share.cache_read_tokens;
+        remaining.cache_creation_tokens -= share.cache_creation_tokens;
+        shares.push(share);
+    }
+    shares
+}
+
+/// Most sub-batches of one split `embed` call that a provider sends at once.
+pub const SUB_BATCH_CONCURRENCY: usize = 4;
+
+/// Run `embed` on each of `batches`, with up to `max_concurrency` sub-batches
+/// in flight at once, and concatenate the vectors.
+///
+/// `embed` must return one vector per input of its sub-batch. The result is
+/// always in input order, however the sub-batches' completion interleaves,
+/// and the usage the sub-batches report is summed. The first error (including
+/// a sub-batch returning the wrong number of vectors) fails the whole call and
+/// drops the sub-batches still in flight.
+///
+/// A `max_concurrency` of 0 is treated as 1.
+pub async fn embed_in_sub_batches<I, V, F, Fut>(
+    batches: Vec<Vec<I>>,
+    max_concurrency: usize,
+    embed: F,
+) -> Result<(Vec<V>, Option<TokenUsage>)>
+where
+    F: Fn(Vec<I>) -> Fut,
+    Fut: Future<Output = Result<(Vec<V>, Option<TokenUsage>)>>,
+{
+    let results = stream::iter(batches.into_iter().enumerate())
+        .map(|(index, batch)| {
+            let expected = batch.len();
+            let fut = embed(batch);
+            async move {
+                let (vectors, usage) = fut.await?;
+                if vectors.len() != expected {
+                    return Err(RuntimeError::InferenceError(format!(
+                        "Sub-batch {} expected {} embeddings, provider returned {}",
+                        index,
+                        expected,
+                        vectors.len()
+                    )));
+                }
+                Ok((vectors, usage))
+            }
+        })
+        .buffered(max_concurrency.max(1));
+    let mut results = std::pin::pin!(results);
+
+    let mut vectors = Vec::new();
+    let mut usage: Option<TokenUsage> = None;
+    while let Some(result) = results.next().await {
+        let (batch_vectors, batch_usage) = result?;
+        vectors.extend(batch_vectors);
+        if let Some(batch_usage) = batch_usage {
+            *usage.get_or_insert_with(Default::default) += &batch_usage;
+        }
+    }
+    Ok((vectors, usage))
+}
+
+#[async_trait]
+impl EmbeddingModel for MicroBatchingEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        Ok(self.enqueue(texts).await?.0)
+    }
+
+    /// Joins the same batch as [`embed`](EmbeddingModel::embed); see the
+    /// [module docs](self) for how the batch's usage is shared out.
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        self.enqueue(texts).await
+    }
+
+    /// Calls with overrides cannot share a batch with other callers, so they
+    /// go straight to the wrapped model.
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        if !has_overrides(&overrides) {
+            return self.embed(texts).await;
+        }
+        self.inner.embed_with(texts, overrides).await
+    }
+
+    /// Compact representations are not batched; the call goes straight to
+    /// the wrapped model.
+    async fn embed_int8(&self, texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+        self.inner.embed_int8(texts).await
+    }
+
+    /// See [`embed_int8`](EmbeddingModel::embed_int8).
+    async fn embed_binary(&self, texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+        self.inner.embed_binary(texts).await
+    }
+
+    /// Pre-tokenized calls are not batched either.
+    async fn embed_tokens(&self, batches: Vec<Vec<u32>>) -> Result<Vec<Vec<f32>>> {
+        self.inner.embed_tokens(batches).await
+    }
+
+    /// Streamed calls are already bulk, so they bypass the batcher.
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        self.inner.embed_stream(texts).await
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.inner.rate_limits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Returns `[len(text)]` for every text so callers can check fan-out order.
+    struct LengthModel {
+        calls: AtomicU32,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for LengthModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(RuntimeError::Unavailable);
+            }
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        /// Reports one prompt token per byte of input.
+        async fn embed_with_usage(
+            &self,
+            texts: Vec<&str>,
+        ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+            let tokens = texts.iter().map(|t| t.len()).sum();
+            let vectors = self.embed(texts).await?;
+            let usage = TokenUsage {
+                prompt_tokens: tokens,
+                total_tokens: tokens,
+                ..Default::default()
+            };
+            Ok((vectors, Some(usage)))
+        }
+
+        fn dimensions(&self) -> u32 {
+            1
+        }
+
+        fn model_id(&self) -> &str {
+            "length"
+        }
+    }
+
+    fn batcher(
+        fail: bool,
+        config: MicroBatchConfig,
+    ) -> (Arc<LengthModel>, Arc<MicroBatchingEmbeddingModel>) {
+        let inner = Arc::new(LengthModel {
+            calls: AtomicU32::new(0),
+            fail,
+        });
+        let model = Arc::new(MicroBatchingEmbeddingModel::new(inner.clone(), config));
+        (inner, model)
+    }
+
+    fn short_window() -> MicroBatchConfig {
+        MicroBatchConfig {
+            max_batch: 64,
+            max_delay: Duration::from_millis(20),
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_calls_coalesce_into_one_batch() {
+        let (inner, model) = batcher(false, short_window());
+
+        let a = model.clone();
+        let b = model.clone();
+        let (ra, rb) = tokio::join!(async move { a.embed(vec!["a", "bbb"]).await }, async move {
+            b.embed(vec!["cc"]).await
+        });
+
+        assert_eq!(ra.unwrap(), vec![vec![1.0], vec![3.0]]);
+        assert_eq!(rb.unwrap(), vec![vec![2.0]]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn coalesced_callers_each_get_their_share_of_usage() {
+        let (inner, model) = batcher(false, short_window());
+
+        let a = model.clone();
+        let b = model.clone();
+        let (ra, rb) = tokio::join!(
+            async move { a.embed_with_usage(vec!["a", "bbb"]).await },
+            async move { b.embed_with_usage(vec!["cc"]).await }
+        );
+
+        let (vectors, usage) = ra.unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![3.0]]);
+        assert_eq!(usage.unwrap().prompt_tokens, 4);
+        let (vectors, usage) = rb.unwrap();
+        assert_eq!(vectors, vec![vec![2.0]]);
+        assert_eq!(usage.unwrap().prompt_tokens, 2);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn split_usage_gives_rounding_remainder_to_last_share() {
+        let usage = TokenUsage {
+            prompt_tokens: 10,
+            total_tokens: 10,
+            ..Default::default()
+        };
+        let shares = split_usage(&usage, &[1, 1, 1]);
+        let prompt: Vec<usize> = shares.iter().map(|u| u.prompt_tokens).collect();
+        assert_eq!(prompt, vec![3, 3, 4]);
+        assert_eq!(shares.iter().map(|u| u.total_tokens).sum::<usize>(), 10);
+    }
+
+    #[tokio::test]
+    async fn full_batch_flushes_without_waiting_for_delay() {
+        let (inner, model) = batcher(
+            false,
+            MicroBatchConfig {
+                max_batch: 2,
+                max_delay: Duration::from_secs(60),
+            },
+        );
+
+        let res = tokio::time::timeout(Duration::from_secs(1), model.embed(vec!["x", "yy"])).await;
+        assert_eq!(res.unwrap().unwrap(), vec![vec![1.0], vec![2.0]]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_error_fails_every_participant() {
+        let (_inner, model) = batcher(true, short_window());
+
+        let a = model.clone();
+        let b = model.clone();
+        let (ra, rb) = tokio::join!(async move { a.embed(vec!["a"]).await }, async move {
+            b.embed(vec!["b"]).await
+        });
+
+        assert!(matches!(ra, Err(RuntimeError::Unavailable)));
+        assert!(matches!(rb, Err(RuntimeError::Unavailable)));
+    }
+
+    #[tokio::test]
+    async fn cancelled_callers_are_left_out_of_the_batch() {
+        let (inner, model) = batcher(false, short_window());
+
+        // Cancelled before the flush: nothing is sent upstream.
+        let cancelled =
+            tokio::time::timeout(Duration::from_millis(1), model.embed(vec!["a"])).await;
+        assert!(cancelled.is_err());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 0);
+
+        // A cancelled caller sharing a batch does not disturb the others.
+        let a = model.clone();
+        let b = model.clone();
+        let (ra, rb) = tokio::join!(
+            tokio::time::timeout(Duration::from_millis(1), a.embed(vec!["aaaa"])),
+            b.embed(vec!["bb"])
+        );
+        assert!(ra.is_err());
+        assert_eq!(rb.unwrap(), vec![vec![2.0]]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn empty_input_skips_upstream() {
+        let (inner, model) = batcher(false, short_window());
+        assert!(model.embed(vec![]).await.unwrap().is_empty());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn sub_batch_failure_fails_the_call() {
+        let batches = || vec![vec!["a", "b"], vec!["c", "d"]];
+
+        let err = embed_in_sub_batches(batches(), 2, |batch| async move {
+            if batch.contains(&"c") {
+                Err(RuntimeError::Unavailable)
+            } else {
+                Ok((batch.iter().map(|_| vec![0.0]).collect(), None))
+            }
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, RuntimeError::Unavailable));
+
+        let err = embed_in_sub_batches(batches(), 2, |_| async { Ok((vec![vec![0.0f32]], None)) })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("expected 2 embeddings"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn sub_batches_sum_usage() {
+        let (vectors, usage) = embed_in_sub_batches(vec![vec![1], vec![2, 3]], 2, |batch| {
+            let usage = TokenUsage {
+                prompt_tokens: batch.len(),
+                total_tokens: batch.len(),
+                ..Default::default()
+            };
+            async move { Ok((batch, Some(usage))) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(vectors, vec![1, 2, 3]);
+        assert_eq!(usage.map(|u| u.total_tokens), Some(3));
+    }
+}
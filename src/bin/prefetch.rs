@@ -8,8 +8,9 @@
 //!
 //! Models with remote providers (`remote/openai`, `remote/gemini`, `remote/vertexai`,
 //! `remote/mistral`, `remote/anthropic`, `remote/voyageai`, `remote/cohere`,
-//! `remote/azure-openai`, etc.) are skipped
-//! because they have no local weights to cache.
+//! `remote/azure-openai`, `remote/jina`, etc.) are skipped
+//! because they have no local weights to cache, as are `local/onnx` models,
+//! which load from a directory you supply.
 //!
 //! If a model is not pre-cached the runtime will still download it on first use —
 //! this tool is purely an optimisation for pre-warming / bundling.
@@ -98,13 +99,20 @@ async fn run() -> anyhow::Result<()> {
     );
 
     // --- Split local vs remote ----------------------------------------------
-    let (local_specs, remote_specs): (Vec<_>, Vec<_>) = all_specs
+    // `local/onnx` loads from a directory the user supplies, so like remote
+    // providers it has nothing to download.
+    let (local_specs, skipped_specs): (Vec<_>, Vec<_>) = all_specs
         .into_iter()
-        .partition(|s| s.provider_id.starts_with("local/"));
+        .partition(|s| s.provider_id.starts_with("local/") && s.provider_id != "local/onnx");
 
-    for spec in &remote_specs {
+    for spec in &skipped_specs {
+        let reason = if spec.provider_id.starts_with("local/") {
+            "loads from a local directory"
+        } else {
+            "remote provider"
+        };
         println!(
-            "  skip  {}  ({})  — remote provider, nothing to cache",
+            "  skip  {}  ({})  — {reason}, nothing to cache",
             spec.alias, spec.provider_id
         );
     }
@@ -215,8 +223,30 @@ async fn run() -> anyhow::Result<()> {
     }
     println!();
 
+    // Print one line per 10% of each file so long downloads don't look hung.
+    let printed = std::sync::Mutex::new(std::collections::HashMap::<String, u64>::new());
     builder
         .catalog(eager_specs)
+        .on_download_progress(move |p| {
+            let Some(total) = p.total.filter(|t| *t > 0) else {
+                return;
+            };
+            let decile = p.downloaded * 10 / total;
+            let key = format!("{}/{}", p.model_id, p.file);
+            let mut printed = printed.lock().unwrap();
+            if printed.get(&key).is_some_and(|d| *d >= decile) {
+                return;
+            }
+            printed.insert(key, decile);
+            println!(
+                "  ↓  {} {}  {:>3}%  ({:.1} / {:.1} MB)",
+                p.model_id,
+                p.file,
+                decile * 10,
+                p.downloaded as f64 / 1e6,
+                total as f64 / 1e6
+            );
+        })
         .build()
         .await
         .map_err(|e| anyhow::anyhow!("Prefetch failed: {e}"))?;
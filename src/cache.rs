@@ -6,6 +6,9 @@
 //! 1. A per-model `cache_dir` option in the spec's JSON options.
 //! 2. The `UNI_CACHE_DIR` environment variable (global root override).
 //! 3. A default `.uni_cache/` directory relative to the working directory.
+//!
+//! It also resolves the HuggingFace Hub endpoint (mirror) those downloads go
+//! through; see [`resolve_hf_endpoint`].
 
 use serde_json::Value;
 use std::path::PathBuf;
@@ -59,6 +62,28 @@ pub fn resolve_cache_dir(provider: &str, model_id: &str, options: &Value) -> Pat
         .join(sanitize_model_name(model_id))
 }
 
+/// The environment variable naming a HuggingFace Hub mirror (same name the
+/// `huggingface_hub` Python client uses).
+pub const HF_ENDPOINT_ENV: &str = "HF_ENDPOINT";
+
+/// Resolve the HuggingFace Hub endpoint local providers download from.
+///
+/// Priority (highest first):
+/// 1. `options["hf_endpoint"]` -- per-model override
+/// 2. `HF_ENDPOINT` env var
+/// 3. `None` -- use the hub client's default (`https://huggingface.co`)
+///
+/// A trailing `/` is removed so the value can be joined with repo paths.
+pub fn resolve_hf_endpoint(options: &Value) -> Option<String> {
+    options
+        .get("hf_endpoint")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| std::env::var(HF_ENDPOINT_ENV).ok())
+        .map(|e| e.trim_end_matches('/').to_string())
+        .filter(|e| !e.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +175,24 @@ mod tests {
             PathBuf::from(".uni_cache/candle/sentence-transformers--all-MiniLM-L6-v2")
         );
     }
+
+    #[test]
+    fn resolve_hf_endpoint_prefers_option_over_env() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        // SAFETY: protected by ENV_LOCK
+        unsafe { std::env::set_var(HF_ENDPOINT_ENV, "https://env-mirror.example") };
+        let from_option = resolve_hf_endpoint(&json!({ "hf_endpoint": "https://hf-mirror.com/" }));
+        let from_env = resolve_hf_endpoint(&json!({}));
+        unsafe { std::env::remove_var(HF_ENDPOINT_ENV) };
+        assert_eq!(from_option.as_deref(), Some("https://hf-mirror.com"));
+        assert_eq!(from_env.as_deref(), Some("https://env-mirror.example"));
+    }
+
+    #[test]
+    fn resolve_hf_endpoint_defaults_to_none() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        // SAFETY: protected by ENV_LOCK
+        unsafe { std::env::remove_var(HF_ENDPOINT_ENV) };
+        assert_eq!(resolve_hf_endpoint(&json!({})), None);
+    }
 }
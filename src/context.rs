@@ -0,0 +1,55 @@
+//! Per-call request context propagated through the instrumented model wrappers.
+//!
+//! A request ID set with [`with_request_id`] is attached to the `model_call`
+//! tracing span opened by every [`Instrumented*`](crate::reliability) wrapper
+//! (alongside `alias` and `provider`) and is sent to remote providers as an
+//! `x-request-id` header, so logs, traces, and provider-side request logs can
+//! be correlated.
+//!
+//! ```rust,no_run
+//! # async fn example(model: std::sync::Arc<dyn uni_xervo::traits::EmbeddingModel>) {
+//! use uni_xervo::context::with_request_id;
+//!
+//! let vectors = with_request_id("req-42", model.embed(vec!["hello"])).await;
+//! # }
+//! ```
+//!
+//! The ID is stored in a tokio task-local, so it follows the future it scopes
+//! but not work spawned onto other tasks. In particular, calls coalesced by
+//! micro-batching run on the batcher's own task and carry no request ID.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Run `fut` with `id` as the current request ID.
+///
+/// Scopes nest: an inner `with_request_id` shadows the outer ID until its
+/// future completes.
+pub async fn with_request_id<F: Future>(id: impl Into<String>, fut: F) -> F::Output {
+    REQUEST_ID.scope(id.into(), fut).await
+}
+
+/// The request ID of the enclosing [`with_request_id`] scope, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_id_is_scoped_to_the_future() {
+        assert_eq!(current_request_id(), None);
+        let seen = with_request_id("outer", async {
+            let inner = with_request_id("inner", async { current_request_id() }).await;
+            (current_request_id(), inner)
+        })
+        .await;
+        assert_eq!(seen, (Some("outer".into()), Some("inner".into())));
+        assert_eq!(current_request_id(), None);
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the Uni-Xervo runtime.
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Convenience alias used throughout the crate.
@@ -11,7 +12,7 @@ pub type Result<T> = std::result::Result<T, RuntimeError>;
 /// Variants are intentionally coarse-grained so that callers can match on error
 /// *category* (e.g. retryable vs permanent) rather than on provider-specific
 /// details.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum RuntimeError {
     /// Invalid or missing configuration (bad alias format, unknown option, etc.).
     #[error("Configuration error: {0}")]
@@ -29,15 +30,29 @@ pub enum RuntimeError {
     #[error("Load error: {0}")]
     Load(String),
 
-    /// An HTTP or transport-level error from a remote provider.
-    #[error("API error: {0}")]
-    ApiError(String),
+    /// An HTTP, transport, or response-format error from a remote provider.
+    ///
+    /// Retryable when `status` is 429 or 5xx. 401/403 map to
+    /// [`Unauthorized`](Self::Unauthorized) instead.
+    #[error(
+        "API error: {provider}{}: {message}",
+        .status.map(|s| format!(" (HTTP {s})")).unwrap_or_default()
+    )]
+    ApiError {
+        /// Provider ID of the failing backend (e.g. `"remote/openai"`).
+        provider: String,
+        /// HTTP status code, when the error came from a non-success response.
+        status: Option<u16>,
+        /// Human-readable details, such as a truncated response body.
+        message: String,
+    },
 
     /// An error during model inference (tokenization, forward pass, etc.).
     #[error("Inference error: {0}")]
     InferenceError(String),
 
-    /// The remote API returned HTTP 429 (too many requests).
+    /// Too many requests. Built-in remote providers report HTTP 429 as an
+    /// [`ApiError`](Self::ApiError) with the status and response body.
     #[error("Rate limited")]
     RateLimited,
 
@@ -46,19 +61,137 @@ pub enum RuntimeError {
     Unauthorized,
 
     /// The operation exceeded its configured timeout.
-    #[error("Timeout")]
-    Timeout,
+    #[error("{phase} timed out after {}", format_limit(*.limit))]
+    Timeout {
+        /// Which stage of work timed out.
+        phase: TimeoutPhase,
+        /// The timeout that was exceeded.
+        limit: Duration,
+    },
 
-    /// The service is currently unavailable (HTTP 5xx, circuit breaker open, etc.).
+    /// The service is currently unavailable (circuit breaker open, etc.).
+    /// Built-in remote providers report HTTP 5xx as an
+    /// [`ApiError`](Self::ApiError) with the status and response body.
     #[error("Unavailable")]
     Unavailable,
+
+    /// The runtime is shutting down (see
+    /// [`ModelRuntime::shutdown`](crate::runtime::ModelRuntime::shutdown))
+    /// and no longer hands out or loads models. Not retryable.
+    #[error("Runtime is shutting down")]
+    ShuttingDown,
+
+    /// The provider answered but withheld the output under its content
+    /// policy, e.g. a Gemini prompt or response blocked for `SAFETY`.
+    ///
+    /// Not retryable, and not counted as a failure by circuit breakers.
+    #[error("Content filtered by {provider}: {reason}")]
+    ContentFiltered {
+        /// Provider ID of the backend that filtered the content.
+        provider: String,
+        /// The provider's block or finish reason, e.g. `"SAFETY"`.
+        reason: String,
+    },
+}
+
+/// The stage of work that exceeded its limit in a
+/// [`RuntimeError::Timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPhase {
+    /// Loading the model, bounded by `load_timeout`.
+    Load,
+    /// The model's first warmup pass, bounded by `warmup_timeout`.
+    Warmup,
+    /// A single inference call, bounded by the alias `timeout`.
+    Inference,
+}
+
+impl std::fmt::Display for TimeoutPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Load => "load",
+            Self::Warmup => "warmup",
+            Self::Inference => "inference",
+        })
+    }
+}
+
+/// Whole seconds as `30s`, anything finer as `1500ms`.
+fn format_limit(limit: Duration) -> String {
+    if limit.subsec_nanos() == 0 {
+        format!("{}s", limit.as_secs())
+    } else {
+        format!("{}ms", limit.as_millis())
+    }
 }
 
 impl RuntimeError {
+    /// Build a [`Timeout`](Self::Timeout) for `phase` exceeding `limit`.
+    pub fn timeout(phase: TimeoutPhase, limit: Duration) -> Self {
+        Self::Timeout { phase, limit }
+    }
+
+    /// Build an [`ApiError`](Self::ApiError) with no HTTP status, e.g. for
+    /// transport failures or malformed responses.
+    pub fn api_error(provider: impl Into<String>, message: impl ToString) -> Self {
+        Self::ApiError {
+            provider: provider.into(),
+            status: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Build an [`ApiError`](Self::ApiError) for a non-success HTTP status.
+    pub fn api_status_error(
+        provider: impl Into<String>,
+        status: u16,
+        message: impl ToString,
+    ) -> Self {
+        Self::ApiError {
+            provider: provider.into(),
+            status: Some(status),
+            message: message.to_string(),
+        }
+    }
+
+    /// Build a [`ContentFiltered`](Self::ContentFiltered) error.
+    pub fn content_filtered(provider: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::ContentFiltered {
+            provider: provider.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// The provider ID attached to this error, if any.
+    pub fn provider(&self) -> Option<&str> {
+        match self {
+            Self::ApiError { provider, .. } | Self::ContentFiltered { provider, .. } => {
+                Some(provider)
+            }
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code attached to this error, if any.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::ApiError { status, .. } => *status,
+            _ => None,
+        }
+    }
+
     /// Returns `true` for transient errors that may succeed on retry:
-    /// [`RateLimited`](Self::RateLimited), [`Timeout`](Self::Timeout), and
-    /// [`Unavailable`](Self::Unavailable).
+    /// [`RateLimited`](Self::RateLimited), [`Timeout`](Self::Timeout),
+    /// [`Unavailable`](Self::Unavailable), and an
+    /// [`ApiError`](Self::ApiError) with status 429 or 5xx.
     pub fn is_retryable(&self) -> bool {
-        matches!(self, Self::RateLimited | Self::Timeout | Self::Unavailable)
+        match self {
+            Self::RateLimited | Self::Timeout { .. } | Self::Unavailable => true,
+            Self::ApiError {
+                status: Some(status),
+                ..
+            } => *status == 429 || (500..600).contains(status),
+            _ => false,
+        }
     }
 }
@@ -14,9 +14,10 @@
 //! - **Providers** — pluggable backends that implement [`ModelProvider`](traits::ModelProvider).
 //!   Each provider advertises the tasks it supports and knows how to load models.
 //! - **Traits** — [`EmbeddingModel`](traits::EmbeddingModel),
-//!   [`RerankerModel`](traits::RerankerModel), and
-//!   [`GeneratorModel`](traits::GeneratorModel) are the task-specific interfaces returned
-//!   by the runtime.
+//!   [`RerankerModel`](traits::RerankerModel),
+//!   [`GeneratorModel`](traits::GeneratorModel), and
+//!   [`ClassifierModel`](traits::ClassifierModel) are the task-specific interfaces
+//!   returned by the runtime.
 //!
 //! # Quick start
 //!
@@ -38,6 +39,7 @@
 //!     required: true,
 //!     timeout: None,
 //!     load_timeout: None,
+//!     warmup_timeout: None,
 //!     retry: None,
 //!     options: serde_json::Value::Null,
 //! };
@@ -55,13 +57,19 @@
 //! ```
 
 pub mod api;
+pub mod balancing;
+pub mod batching;
 pub mod cache;
+pub mod context;
 pub mod error;
 mod options_validation;
 pub mod provider;
 pub mod reliability;
 pub mod runtime;
+mod telemetry;
+pub mod text;
 pub mod traits;
+pub mod usage;
 
 #[cfg(test)]
 mod mock;
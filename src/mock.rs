@@ -9,9 +9,10 @@ use crate::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
 use crate::error::{Result, RuntimeError};
 use crate::runtime::ModelRuntime;
 use crate::traits::{
-    AudioOutput, ContentBlock, EmbeddingModel, GeneratedImage, GenerationOptions, GenerationResult,
-    GeneratorModel, LoadedModelHandle, Message, ModelProvider, ProviderCapabilities,
-    ProviderHealth, RerankerModel, ScoredDoc, TokenUsage,
+    AudioOutput, ClassifierModel, ContentBlock, EmbeddingModel, GeneratedImage, GenerationOptions,
+    GenerationResult, GeneratorModel, LoadedModelHandle, Message, ModelProvider,
+    ProviderCapabilities, ProviderHealth, RerankerModel, ScoredDoc, ScoredLabel, TokenUsage,
+    generate_each,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -182,6 +183,59 @@ impl RerankerModel for MockRerankerModel {
     }
 }
 
+/// Mock classifier: earlier labels score higher, and scores sum to 1.
+pub struct MockClassifierModel {
+    fail_on_classify: bool,
+    call_count: AtomicU32,
+}
+
+impl MockClassifierModel {
+    pub fn new() -> Self {
+        Self {
+            fail_on_classify: false,
+            call_count: AtomicU32::new(0),
+        }
+    }
+
+    pub fn with_failure(mut self, fail: bool) -> Self {
+        self.fail_on_classify = fail;
+        self
+    }
+
+    pub fn call_count(&self) -> u32 {
+        self.call_count.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for MockClassifierModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ClassifierModel for MockClassifierModel {
+    async fn classify(&self, _text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        self.call_count.fetch_add(1, Ordering::SeqCst);
+
+        if self.fail_on_classify {
+            return Err(RuntimeError::InferenceError(
+                "Mock classifier failure".to_string(),
+            ));
+        }
+
+        let total: usize = (1..=labels.len()).sum();
+        Ok(labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| ScoredLabel {
+                label: label.to_string(),
+                score: (labels.len() - i) as f32 / total as f32,
+            })
+            .collect())
+    }
+}
+
 /// Mock generator model with configurable behavior
 pub struct MockGeneratorModel {
     response_text: String,
@@ -233,8 +287,11 @@ impl GeneratorModel for MockGeneratorModel {
     async fn generate(
         &self,
         messages: &[Message],
-        _options: GenerationOptions,
+        options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         self.call_count.fetch_add(1, Ordering::SeqCst);
 
         if self.fail_on_generate {
@@ -261,9 +318,15 @@ impl GeneratorModel for MockGeneratorModel {
                 completion_tokens: self.response_text.split_whitespace().count(),
                 total_tokens: all_text.split_whitespace().count()
                     + self.response_text.split_whitespace().count(),
+                ..Default::default()
             }),
             images: self.response_images.clone(),
             audio: self.response_audio.clone(),
+            alternatives: vec![],
+            finish_reason: None,
+            raw: None,
+            logprobs: None,
+            reasoning: None,
         })
     }
 
@@ -330,6 +393,10 @@ impl MockProvider {
         Self::new("mock/rerank", vec![ModelTask::Rerank])
     }
 
+    pub fn classify_only() -> Self {
+        Self::new("mock/classify", vec![ModelTask::Classify])
+    }
+
     pub fn failing() -> Self {
         let mut provider = Self::new("mock/failing", vec![ModelTask::Embed]);
         provider.fail_on_load = true;
@@ -411,6 +478,10 @@ impl ModelProvider for MockProvider {
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
             }
+            ModelTask::Classify => {
+                let handle: Arc<dyn ClassifierModel> = Arc::new(MockClassifierModel::new());
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
         }
     }
 
@@ -441,6 +512,7 @@ pub fn make_spec(
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Object(serde_json::Map::new()),
     }
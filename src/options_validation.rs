@@ -4,9 +4,11 @@
 //! [`ModelRuntime::register`](crate::runtime::ModelRuntime::register) to reject
 //! unknown or malformed options before any model loading occurs.
 
-use crate::api::ModelTask;
+use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
+use crate::traits::ModelProvider;
 use serde_json::Value;
+use std::borrow::Cow;
 
 /// Validate provider-specific options for the given `provider_id` and `task`.
 ///
@@ -17,30 +19,360 @@ pub fn validate_provider_options(
     task: ModelTask,
     options: &Value,
 ) -> Result<()> {
+    let options = &*validate_runtime_options(provider_id, task, options)?;
     match provider_id {
-        "remote/openai" | "remote/gemini" | "remote/mistral" | "remote/voyageai" => {
-            validate_string_keys_only(provider_id, options, &["api_key_env"])
-        }
-        "remote/anthropic" => {
-            validate_string_keys_only(provider_id, options, &["api_key_env", "anthropic_version"])
-        }
-        "remote/cohere" => {
-            validate_string_keys_only(provider_id, options, &["api_key_env", "input_type"])
-        }
-        "remote/azure-openai" => validate_string_keys_only(
+        "remote/openai" => validate_openai_embedding_options(
+            provider_id,
+            task,
+            options,
+            &["api_key_env", "base_url", "encoding_format", "truncation"],
+        ),
+        "remote/mistral" => validate_mistral_options(provider_id, task, options),
+        "remote/voyageai" => validate_voyageai_options(provider_id, task, options),
+        "remote/gemini" => validate_gemini_options(provider_id, task, options),
+        "remote/anthropic" => validate_anthropic_options(provider_id, options),
+        "remote/cohere" => validate_cohere_options(provider_id, task, options),
+        "remote/azure-openai" => validate_openai_embedding_options(
             provider_id,
+            task,
             options,
-            &["api_key_env", "resource_name", "api_version"],
+            &[
+                "api_key_env",
+                "base_url",
+                "resource_name",
+                "api_version",
+                "encoding_format",
+                "truncation",
+            ],
         ),
+        "remote/jina" => validate_jina_options(provider_id, task, options),
+        "remote/nim" => validate_nim_options(provider_id, task, options),
+        "remote/replicate" => validate_replicate_options(provider_id, options),
         "remote/vertexai" => validate_vertexai_options(provider_id, task, options),
-        "local/candle" | "local/fastembed" => {
-            validate_string_keys_only(provider_id, options, &["cache_dir"])
-        }
+        "local/candle" => validate_candle_options(provider_id, task, options),
+        "local/fastembed" => validate_fastembed_options(provider_id, task, options),
         "local/mistralrs" => validate_mistralrs_options(provider_id, task, options),
+        "local/onnx" => validate_onnx_options(provider_id, task, options),
+        "test/null" => validate_null_options(provider_id, options),
         _ => Ok(()),
     }
 }
 
+/// Validate `spec.options` with the built-in rules for its provider and, if
+/// `provider` publishes one, against its
+/// [`options_schema`](ModelProvider::options_schema).
+pub(crate) fn validate_spec_options(
+    provider: &dyn ModelProvider,
+    spec: &ModelAliasSpec,
+) -> Result<()> {
+    validate_provider_options(&spec.provider_id, spec.task, &spec.options)?;
+    if let Some(schema) = provider.options_schema() {
+        let options = validate_runtime_options(&spec.provider_id, spec.task, &spec.options)?;
+        validate_options_schema(&spec.provider_id, &schema, &options)?;
+    }
+    Ok(())
+}
+
+/// Validate `options` against a provider's options JSON Schema.
+///
+/// Implements the keyword subset documented on
+/// [`ModelProvider::options_schema`]; `null` options are checked as an empty
+/// object.
+pub(crate) fn validate_options_schema(
+    provider_id: &str,
+    schema: &Value,
+    options: &Value,
+) -> Result<()> {
+    let empty = Value::Object(serde_json::Map::new());
+    let options = match options {
+        Value::Null => &empty,
+        Value::Object(_) => options,
+        _ => {
+            return Err(RuntimeError::Config(format!(
+                "Options for provider '{}' must be a JSON object or null",
+                provider_id
+            )));
+        }
+    };
+    check_schema(provider_id, "", schema, options)
+}
+
+/// Check `value`, found at option `path`, against `schema`.
+fn check_schema(provider_id: &str, path: &str, schema: &Value, value: &Value) -> Result<()> {
+    let invalid = |requirement: String| {
+        RuntimeError::Config(format!(
+            "Option '{}' for provider '{}' must be {}",
+            path, provider_id, requirement
+        ))
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_json_type(value, t)) {
+            let names: Vec<&str> = types.iter().map(|t| json_type_name(t)).collect();
+            return Err(invalid(names.join(" or ")));
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array())
+        && !allowed.contains(value)
+    {
+        let names: Vec<String> = allowed
+            .iter()
+            .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+            .collect();
+        return Err(invalid(format!("one of: {}", names.join(", "))));
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64())
+            && n < min
+        {
+            return Err(invalid(format!("at least {}", min)));
+        }
+        if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64())
+            && n > max
+        {
+            return Err(invalid(format!("at most {}", max)));
+        }
+    }
+
+    if let Value::Array(items) = value
+        && let Some(item_schema) = schema.get("items")
+    {
+        for (i, item) in items.iter().enumerate() {
+            check_schema(provider_id, &format!("{}[{}]", path, i), item_schema, item)?;
+        }
+    }
+
+    if let Value::Object(map) = value {
+        let child = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+
+        for key in schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|k| k.as_str())
+        {
+            if !map.contains_key(key) {
+                return Err(RuntimeError::Config(format!(
+                    "Missing required option '{}' for provider '{}'",
+                    child(key),
+                    provider_id
+                )));
+            }
+        }
+
+        if let Some(dependent) = schema.get("dependentRequired").and_then(|d| d.as_object()) {
+            for (key, needs) in dependent {
+                if !map.contains_key(key) {
+                    continue;
+                }
+                for need in needs
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|n| n.as_str())
+                {
+                    if !map.contains_key(need) {
+                        return Err(RuntimeError::Config(format!(
+                            "Option '{}' for provider '{}' requires '{}'",
+                            child(key),
+                            provider_id,
+                            child(need)
+                        )));
+                    }
+                }
+            }
+        }
+
+        for (key, item) in map {
+            match (
+                properties.and_then(|p| p.get(key)),
+                schema.get("additionalProperties"),
+            ) {
+                (Some(property), _) => check_schema(provider_id, &child(key), property, item)?,
+                (None, Some(Value::Bool(false))) => {
+                    return Err(RuntimeError::Config(format!(
+                        "Unknown option '{}' for provider '{}'",
+                        child(key),
+                        provider_id
+                    )));
+                }
+                (None, Some(extra @ Value::Object(_))) => {
+                    check_schema(provider_id, &child(key), extra, item)?
+                }
+                (None, _) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `value` is an instance of the JSON Schema type `name`. Unknown
+/// type names match anything.
+fn has_json_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+/// Phrase for a JSON Schema type in error messages.
+fn json_type_name(name: &str) -> &str {
+    match name {
+        "object" => "an object",
+        "array" => "an array",
+        "string" => "a string",
+        "boolean" => "a boolean",
+        "null" => "null",
+        "number" => "a number",
+        "integer" => "an integer",
+        other => other,
+    }
+}
+
+/// Embedding options each provider accepts as per-call overrides in
+/// [`EmbeddingModel::embed_with`](crate::traits::EmbeddingModel::embed_with).
+fn embed_override_keys(provider_id: &str) -> &'static [&'static str] {
+    match provider_id {
+        "remote/cohere" => &["input_type"],
+        "remote/gemini" => &["task_type", "title"],
+        _ => &[],
+    }
+}
+
+/// Merge per-call `overrides` onto an embedding alias's `options` and
+/// validate the result as that provider's options.
+///
+/// Only keys listed for the provider may be overridden. A `null` override
+/// removes the key for this call, e.g. to drop an alias-level `title` when
+/// switching Gemini's `task_type`.
+#[cfg_attr(
+    not(any(feature = "provider-cohere", feature = "provider-gemini")),
+    allow(dead_code)
+)]
+pub(crate) fn merge_embed_overrides(
+    provider_id: &str,
+    options: &Value,
+    overrides: &Value,
+) -> Result<Value> {
+    let Some(overrides) = as_object(provider_id, overrides)? else {
+        return Ok(options.clone());
+    };
+    let allowed = embed_override_keys(provider_id);
+    if let Some(key) = overrides.keys().find(|k| !allowed.contains(&k.as_str())) {
+        return Err(RuntimeError::Config(format!(
+            "Option '{}' for provider '{}' cannot be overridden per call",
+            key, provider_id
+        )));
+    }
+
+    let mut merged = options.as_object().cloned().unwrap_or_default();
+    for (key, value) in overrides {
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    let merged = Value::Object(merged);
+    validate_provider_options(provider_id, ModelTask::Embed, &merged)?;
+    Ok(merged)
+}
+
+/// Options interpreted by the runtime rather than the provider. They are
+/// accepted for every provider.
+pub(crate) const RUNTIME_OPTION_KEYS: &[&str] = &[
+    "expected_dimensions",
+    "max_input_chars",
+    "on_overlong",
+    "dedup_documents",
+];
+
+/// Validate runtime-level options and return `options` with them removed, so
+/// provider validation only sees provider-specific keys.
+fn validate_runtime_options<'a>(
+    provider_id: &str,
+    task: ModelTask,
+    options: &'a Value,
+) -> Result<Cow<'a, Value>> {
+    let Value::Object(map) = options else {
+        return Ok(Cow::Borrowed(options));
+    };
+    if !RUNTIME_OPTION_KEYS.iter().any(|k| map.contains_key(*k)) {
+        return Ok(Cow::Borrowed(options));
+    }
+
+    if map.contains_key("expected_dimensions") {
+        require_positive_u64(provider_id, map, "expected_dimensions")?;
+        if task != ModelTask::Embed {
+            return Err(RuntimeError::Config(
+                "Option 'expected_dimensions' is only valid for embed tasks".to_string(),
+            ));
+        }
+    }
+
+    for key in ["max_input_chars", "on_overlong"] {
+        if map.contains_key(key) && task == ModelTask::Generate {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' is only valid for embed, rerank, and classify tasks",
+                key
+            )));
+        }
+    }
+    if map.contains_key("max_input_chars") {
+        require_positive_u64(provider_id, map, "max_input_chars")?;
+    }
+    if let Some(value) = map.get("on_overlong") {
+        if !matches!(value.as_str(), Some("error" | "truncate")) {
+            return Err(RuntimeError::Config(
+                "Option 'on_overlong' must be one of: error, truncate".to_string(),
+            ));
+        }
+        if !map.contains_key("max_input_chars") {
+            return Err(RuntimeError::Config(
+                "Option 'on_overlong' requires 'max_input_chars'".to_string(),
+            ));
+        }
+    }
+
+    if let Some(value) = map.get("dedup_documents") {
+        if !value.is_boolean() {
+            return Err(RuntimeError::Config(
+                "Option 'dedup_documents' must be a boolean".to_string(),
+            ));
+        }
+        if task != ModelTask::Rerank {
+            return Err(RuntimeError::Config(
+                "Option 'dedup_documents' is only valid for rerank tasks".to_string(),
+            ));
+        }
+    }
+
+    let mut stripped = map.clone();
+    stripped.retain(|k, _| !RUNTIME_OPTION_KEYS.contains(&k.as_str()));
+    Ok(Cow::Owned(Value::Object(stripped)))
+}
+
 /// Parse `options` as a JSON object map, returning `None` for null and an
 /// error for non-object types.
 fn as_object<'a>(
@@ -74,81 +406,593 @@ fn reject_unknown_keys(
     Ok(())
 }
 
-/// Require that all specified keys, if present, are strings.
-fn require_string_keys(
-    provider_id: &str,
-    map: &serde_json::Map<String, Value>,
-    keys: &[&str],
-) -> Result<()> {
-    for key in keys {
-        if let Some(value) = map.get(*key)
-            && !value.is_string()
-        {
+/// Require that all specified keys, if present, are strings.
+fn require_string_keys(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+    keys: &[&str],
+) -> Result<()> {
+    for key in keys {
+        if let Some(value) = map.get(*key)
+            && !value.is_string()
+        {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' for provider '{}' must be a string",
+                key, provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Require that all specified keys, if present, are booleans.
+fn require_bool_keys(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+    keys: &[&str],
+) -> Result<()> {
+    for key in keys {
+        if let Some(value) = map.get(*key)
+            && !value.is_boolean()
+        {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' for provider '{}' must be a boolean",
+                key, provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Require that the named key, if present, is a positive (> 0) integer.
+fn require_positive_u64(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+    key: &str,
+) -> Result<()> {
+    if let Some(value) = map.get(key) {
+        let Some(v) = value.as_u64() else {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' for provider '{}' must be a positive integer",
+                key, provider_id
+            )));
+        };
+        if v == 0 {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' for provider '{}' must be greater than 0",
+                key, provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate that a string option, if present, is an `http://` or `https://` URL.
+fn require_http_url(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+    key: &str,
+) -> Result<()> {
+    if let Some(url) = map.get(key).and_then(|v| v.as_str())
+        && !(url.starts_with("http://") || url.starts_with("https://"))
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option '{}' for provider '{}' must start with http:// or https://",
+            key, provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that the embedding_dimensions option is a positive integer and only
+/// used for embed tasks.
+fn require_embedding_dimensions(
+    provider_id: &str,
+    task: ModelTask,
+    map: &serde_json::Map<String, Value>,
+) -> Result<()> {
+    if map.contains_key("embedding_dimensions") {
+        require_positive_u64(provider_id, map, "embedding_dimensions")?;
+        if task != ModelTask::Embed {
+            return Err(RuntimeError::Config(
+                "Option 'embedding_dimensions' is only valid for embed tasks".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the `truncation` option, if present: one of `allowed`, the
+/// strategies the provider can honour. Task checks are left to the caller.
+fn require_truncation(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+    allowed: &[&str],
+) -> Result<()> {
+    if let Some(value) = map.get("truncation")
+        && !value.as_str().is_some_and(|t| allowed.contains(&t))
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'truncation' for provider '{}' must be one of: {}",
+            provider_id,
+            allowed.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Validate providers whose options are all optional string keys, where
+/// `base_url`, if allowed and present, must be an HTTP(S) URL.
+fn validate_string_keys_only(
+    provider_id: &str,
+    options: &Value,
+    allowed_keys: &[&str],
+) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(provider_id, map, allowed_keys)?;
+    require_string_keys(provider_id, map, allowed_keys)?;
+    require_http_url(provider_id, map, "base_url")
+}
+
+/// Validate OpenAI and Azure OpenAI options: string `allowed_keys`, where
+/// the embed-only `encoding_format` must be `float` or `base64`, and the
+/// embed-only `truncation` can only be `none`, since these APIs reject
+/// over-long inputs rather than truncate them.
+fn validate_openai_embedding_options(
+    provider_id: &str,
+    task: ModelTask,
+    options: &Value,
+    allowed_keys: &[&str],
+) -> Result<()> {
+    validate_string_keys_only(provider_id, options, allowed_keys)?;
+    require_embed_only_truncation(provider_id, task, options, &["none"])?;
+    let Some(format) = options.get("encoding_format").and_then(|v| v.as_str()) else {
+        return Ok(());
+    };
+    if task != ModelTask::Embed {
+        return Err(RuntimeError::Config(
+            "Option 'encoding_format' is only valid for embed tasks".to_string(),
+        ));
+    }
+    if !["float", "base64"].contains(&format) {
+        return Err(RuntimeError::Config(format!(
+            "Option 'encoding_format' for provider '{}' must be one of: float, base64",
+            provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Validate `truncation` against `allowed` and reject it for tasks other
+/// than embed.
+fn require_embed_only_truncation(
+    provider_id: &str,
+    task: ModelTask,
+    options: &Value,
+    allowed: &[&str],
+) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    if map.contains_key("truncation") && task != ModelTask::Embed {
+        return Err(RuntimeError::Config(
+            "Option 'truncation' is only valid for embed tasks".to_string(),
+        ));
+    }
+    require_truncation(provider_id, map, allowed)
+}
+
+/// Validate Mistral options: `api_key_env` and `base_url`, plus the
+/// embed-only `truncation`, which can only be `none` since Mistral rejects
+/// over-long inputs rather than truncate them.
+fn validate_mistral_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    validate_string_keys_only(
+        provider_id,
+        options,
+        &["api_key_env", "base_url", "truncation"],
+    )?;
+    require_embed_only_truncation(provider_id, task, options, &["none"])
+}
+
+/// Embedding task types accepted by Gemini's `batchEmbedContents`.
+const GEMINI_TASK_TYPES: &[&str] = &[
+    "TASK_TYPE_UNSPECIFIED",
+    "RETRIEVAL_QUERY",
+    "RETRIEVAL_DOCUMENT",
+    "SEMANTIC_SIMILARITY",
+    "CLASSIFICATION",
+    "CLUSTERING",
+    "QUESTION_ANSWERING",
+    "FACT_VERIFICATION",
+    "CODE_RETRIEVAL_QUERY",
+];
+
+/// Validate Gemini options: `api_key_env` and `base_url`, the embed-only
+/// `task_type`, `title`, and `truncation`, and the generate-only `safety_settings` and
+/// `response_mime_type`.
+fn validate_gemini_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "api_key_env",
+            "base_url",
+            "task_type",
+            "title",
+            "truncation",
+            "safety_settings",
+            "response_mime_type",
+        ],
+    )?;
+    require_string_keys(
+        provider_id,
+        map,
+        &[
+            "api_key_env",
+            "base_url",
+            "task_type",
+            "title",
+            "response_mime_type",
+        ],
+    )?;
+    require_http_url(provider_id, map, "base_url")?;
+    require_google_generate_options(provider_id, task, map)?;
+    require_embed_only_truncation(provider_id, task, options, &["none", "end"])?;
+
+    for key in ["task_type", "title"] {
+        if map.contains_key(key) && task != ModelTask::Embed {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' is only valid for embed tasks",
+                key
+            )));
+        }
+    }
+
+    let task_type = map.get("task_type").and_then(|v| v.as_str());
+    if let Some(task_type) = task_type
+        && !GEMINI_TASK_TYPES.contains(&task_type)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'task_type' for provider '{}' must be one of: {}",
+            provider_id,
+            GEMINI_TASK_TYPES.join(", ")
+        )));
+    }
+
+    if map.contains_key("title") && task_type != Some("RETRIEVAL_DOCUMENT") {
+        return Err(RuntimeError::Config(format!(
+            "Option 'title' for provider '{}' requires task_type RETRIEVAL_DOCUMENT",
+            provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Validate the generation options shared by Gemini and Vertex AI:
+/// `safety_settings` must be an array of `{category, threshold}` string
+/// objects, and neither it nor `response_mime_type` applies to other tasks.
+fn require_google_generate_options(
+    provider_id: &str,
+    task: ModelTask,
+    map: &serde_json::Map<String, Value>,
+) -> Result<()> {
+    for key in ["safety_settings", "response_mime_type"] {
+        if map.contains_key(key) && task != ModelTask::Generate {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' is only valid for generate tasks",
+                key
+            )));
+        }
+    }
+    let Some(settings) = map.get("safety_settings") else {
+        return Ok(());
+    };
+    let valid = settings.as_array().is_some_and(|items| {
+        items.iter().all(|item| {
+            item.as_object().is_some_and(|setting| {
+                setting.len() == 2
+                    && ["category", "threshold"]
+                        .iter()
+                        .all(|k| setting.get(*k).is_some_and(|v| v.is_string()))
+            })
+        })
+    });
+    if !valid {
+        return Err(RuntimeError::Config(format!(
+            "Option 'safety_settings' for provider '{}' must be an array of {{category, threshold}} string objects",
+            provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Vector lengths Voyage accepts for `output_dimension`.
+const VOYAGE_OUTPUT_DIMENSIONS: &[u64] = &[256, 512, 1024, 2048];
+
+/// Validate Voyage AI options: `api_key_env` and `base_url`, plus the embed-only
+/// `input_type`, `truncation`, `output_dimension`, and `output_dtype`.
+/// Validate Cohere options: string keys, with the embed-only `embedding_type`
+/// limited to the types Cohere returns and the embed-only `truncation`.
+fn validate_cohere_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let keys = &[
+        "api_key_env",
+        "base_url",
+        "input_type",
+        "embedding_type",
+        "truncation",
+    ];
+    validate_string_keys_only(provider_id, options, keys)?;
+    require_embed_only_truncation(provider_id, task, options, &["none", "start", "end"])?;
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    if let Some(embedding_type) = map.get("embedding_type").and_then(|v| v.as_str()) {
+        if task != ModelTask::Embed {
+            return Err(RuntimeError::Config(
+                "Option 'embedding_type' is only valid for embed tasks".to_string(),
+            ));
+        }
+        if !["float", "int8", "binary", "ubinary"].contains(&embedding_type) {
+            return Err(RuntimeError::Config(format!(
+                "Option 'embedding_type' for provider '{}' must be one of: float, int8, binary, ubinary",
+                provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_voyageai_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "api_key_env",
+            "base_url",
+            "input_type",
+            "truncation",
+            "output_dimension",
+            "output_dtype",
+        ],
+    )?;
+    require_string_keys(
+        provider_id,
+        map,
+        &["api_key_env", "base_url", "input_type", "output_dtype"],
+    )?;
+    // `true`/`false` is the original form of `none`/`end`.
+    if !map.get("truncation").is_some_and(|v| v.is_boolean()) {
+        require_truncation(provider_id, map, &["none", "end"])?;
+    }
+    require_http_url(provider_id, map, "base_url")?;
+
+    for key in [
+        "input_type",
+        "truncation",
+        "output_dimension",
+        "output_dtype",
+    ] {
+        if map.contains_key(key) && task != ModelTask::Embed {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' is only valid for embed tasks",
+                key
+            )));
+        }
+    }
+
+    if let Some(input_type) = map.get("input_type").and_then(|v| v.as_str())
+        && !["query", "document"].contains(&input_type)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'input_type' for provider '{}' must be one of: query, document",
+            provider_id
+        )));
+    }
+    if let Some(value) = map.get("output_dimension")
+        && !value
+            .as_u64()
+            .is_some_and(|d| VOYAGE_OUTPUT_DIMENSIONS.contains(&d))
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'output_dimension' for provider '{}' must be one of: 256, 512, 1024, 2048",
+            provider_id
+        )));
+    }
+    if let Some(output_dtype) = map.get("output_dtype").and_then(|v| v.as_str())
+        && !["float", "int8", "uint8", "binary", "ubinary"].contains(&output_dtype)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'output_dtype' for provider '{}' must be one of: float, int8, uint8, binary, ubinary",
+            provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Task adapters accepted by Jina's embeddings endpoint.
+const JINA_TASKS: &[&str] = &[
+    "retrieval.query",
+    "retrieval.passage",
+    "separation",
+    "classification",
+    "text-matching",
+];
+
+/// Validate Jina options: `api_key_env` and `base_url`, plus the embed-only
+/// `task`, `dimensions`, and `truncation`.
+fn validate_jina_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "api_key_env",
+            "base_url",
+            "task",
+            "dimensions",
+            "truncation",
+        ],
+    )?;
+    require_string_keys(provider_id, map, &["api_key_env", "base_url", "task"])?;
+    require_positive_u64(provider_id, map, "dimensions")?;
+    require_http_url(provider_id, map, "base_url")?;
+    require_truncation(provider_id, map, &["none", "end"])?;
+
+    for key in ["task", "dimensions", "truncation"] {
+        if map.contains_key(key) && task != ModelTask::Embed {
             return Err(RuntimeError::Config(format!(
-                "Option '{}' for provider '{}' must be a string",
-                key, provider_id
+                "Option '{}' is only valid for embed tasks",
+                key
             )));
         }
     }
+
+    if let Some(jina_task) = map.get("task").and_then(|v| v.as_str())
+        && !JINA_TASKS.contains(&jina_task)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'task' for provider '{}' must be one of: {}",
+            provider_id,
+            JINA_TASKS.join(", ")
+        )));
+    }
     Ok(())
 }
 
-/// Require that the named key, if present, is a positive (> 0) integer.
-fn require_positive_u64(
-    provider_id: &str,
-    map: &serde_json::Map<String, Value>,
-    key: &str,
-) -> Result<()> {
-    if let Some(value) = map.get(key) {
-        let Some(v) = value.as_u64() else {
-            return Err(RuntimeError::Config(format!(
-                "Option '{}' for provider '{}' must be a positive integer",
-                key, provider_id
-            )));
-        };
-        if v == 0 {
+/// Validate NVIDIA NIM options: string keys, the embed-only `input_type` and
+/// `embedding_dimensions`, and `truncate` or its provider-neutral form
+/// `truncation` for embed and rerank.
+fn validate_nim_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "base_url",
+            "api_key_env",
+            "input_type",
+            "truncate",
+            "truncation",
+            "embedding_dimensions",
+        ],
+    )?;
+    require_string_keys(
+        provider_id,
+        map,
+        &["base_url", "api_key_env", "input_type", "truncate"],
+    )?;
+    require_embedding_dimensions(provider_id, task, map)?;
+
+    if map.contains_key("input_type") && task != ModelTask::Embed {
+        return Err(RuntimeError::Config(
+            "Option 'input_type' is only valid for embed tasks".to_string(),
+        ));
+    }
+    if let Some(input_type) = map.get("input_type").and_then(|v| v.as_str())
+        && !["query", "passage"].contains(&input_type)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'input_type' for provider '{}' must be one of: query, passage",
+            provider_id
+        )));
+    }
+
+    for key in ["truncate", "truncation"] {
+        if map.contains_key(key) && task == ModelTask::Generate {
             return Err(RuntimeError::Config(format!(
-                "Option '{}' for provider '{}' must be greater than 0",
-                key, provider_id
+                "Option '{}' is only valid for embed and rerank tasks",
+                key
             )));
         }
     }
-    Ok(())
+    if map.contains_key("truncate") && map.contains_key("truncation") {
+        return Err(RuntimeError::Config(format!(
+            "Options 'truncate' and 'truncation' for provider '{}' are mutually exclusive",
+            provider_id
+        )));
+    }
+    require_truncation(provider_id, map, &["none", "start", "end"])?;
+    if let Some(truncate) = map.get("truncate").and_then(|v| v.as_str())
+        && !["NONE", "START", "END"].contains(&truncate)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'truncate' for provider '{}' must be one of: NONE, START, END",
+            provider_id
+        )));
+    }
+
+    require_http_url(provider_id, map, "base_url")
 }
 
-/// Validate that the embedding_dimensions option is a positive integer and only
-/// used for embed tasks.
-fn require_embedding_dimensions(
-    provider_id: &str,
-    task: ModelTask,
-    map: &serde_json::Map<String, Value>,
-) -> Result<()> {
-    if map.contains_key("embedding_dimensions") {
-        require_positive_u64(provider_id, map, "embedding_dimensions")?;
-        if task != ModelTask::Embed {
-            return Err(RuntimeError::Config(
-                "Option 'embedding_dimensions' is only valid for embed tasks".to_string(),
-            ));
-        }
-    }
-    Ok(())
+/// Validate Anthropic options: string keys plus the prompt-caching flags.
+fn validate_anthropic_options(provider_id: &str, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "api_key_env",
+            "base_url",
+            "anthropic_version",
+            "cache_system_prompt",
+            "cache_last_message",
+            "user_id",
+        ],
+    )?;
+    require_string_keys(
+        provider_id,
+        map,
+        &["api_key_env", "base_url", "anthropic_version", "user_id"],
+    )?;
+    require_bool_keys(
+        provider_id,
+        map,
+        &["cache_system_prompt", "cache_last_message"],
+    )?;
+    require_http_url(provider_id, map, "base_url")
 }
 
-/// Validate providers whose options are all optional string keys.
-fn validate_string_keys_only(
-    provider_id: &str,
-    options: &Value,
-    allowed_keys: &[&str],
-) -> Result<()> {
+/// Validate Replicate options: `input` must be an object of extra model inputs.
+fn validate_replicate_options(provider_id: &str, options: &Value) -> Result<()> {
     let Some(map) = as_object(provider_id, options)? else {
         return Ok(());
     };
-    reject_unknown_keys(provider_id, map, allowed_keys)?;
-    require_string_keys(provider_id, map, allowed_keys)
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &["api_key_env", "base_url", "poll_interval_ms", "input"],
+    )?;
+    require_string_keys(provider_id, map, &["api_key_env", "base_url"])?;
+    require_positive_u64(provider_id, map, "poll_interval_ms")?;
+    require_http_url(provider_id, map, "base_url")?;
+    if let Some(input) = map.get("input")
+        && !input.is_object()
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'input' for provider '{}' must be a JSON object",
+            provider_id
+        )));
+    }
+    Ok(())
 }
 
-/// Validate Vertex AI-specific options: string keys plus optional
-/// `embedding_dimensions`.
+/// Validate Vertex AI-specific options: string keys, optional
+/// `embedding_dimensions` and `truncation`, the generate-only `safety_settings` and
+/// `response_mime_type`, and at most one authentication source.
 fn validate_vertexai_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
     let Some(map) = as_object(provider_id, options)? else {
         return Ok(());
@@ -158,18 +1002,218 @@ fn validate_vertexai_options(provider_id: &str, task: ModelTask, options: &Value
         map,
         &[
             "api_token_env",
+            "credentials_path",
+            "use_adc",
+            "project_id",
+            "location",
+            "publisher",
+            "base_url",
+            "embedding_dimensions",
+            "truncation",
+            "safety_settings",
+            "response_mime_type",
+        ],
+    )?;
+    require_string_keys(
+        provider_id,
+        map,
+        &[
+            "api_token_env",
+            "credentials_path",
             "project_id",
             "location",
             "publisher",
+            "base_url",
+            "response_mime_type",
+        ],
+    )?;
+    require_bool_keys(provider_id, map, &["use_adc"])?;
+    require_http_url(provider_id, map, "base_url")?;
+    require_embedding_dimensions(provider_id, task, map)?;
+    require_google_generate_options(provider_id, task, map)?;
+    require_embed_only_truncation(provider_id, task, options, &["none", "end"])?;
+
+    let auth_sources = [
+        map.contains_key("api_token_env"),
+        map.contains_key("credentials_path"),
+        map.get("use_adc").and_then(|v| v.as_bool()) == Some(true),
+    ];
+    if auth_sources.iter().filter(|set| **set).count() > 1 {
+        return Err(RuntimeError::Config(format!(
+            "Options 'api_token_env', 'credentials_path', and 'use_adc' for provider '{}' are mutually exclusive",
+            provider_id
+        )));
+    }
+    Ok(())
+}
+
+/// Validate Candle options: `cache_dir`, `device`, `dtype`, `hf_endpoint`,
+/// `embedding_dimensions`, and `max_batch_size`.
+fn validate_candle_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "cache_dir",
+            "device",
+            "dtype",
             "embedding_dimensions",
+            "hf_endpoint",
+            "max_batch_size",
+            "model_path",
         ],
     )?;
     require_string_keys(
         provider_id,
         map,
-        &["api_token_env", "project_id", "location", "publisher"],
+        &["cache_dir", "device", "dtype", "hf_endpoint", "model_path"],
+    )?;
+    require_embedding_dimensions(provider_id, task, map)?;
+    require_positive_u64(provider_id, map, "max_batch_size")?;
+    if map.contains_key("max_batch_size") && task != ModelTask::Embed {
+        return Err(RuntimeError::Config(
+            "Option 'max_batch_size' is only valid for embed tasks".to_string(),
+        ));
+    }
+    require_http_url(provider_id, map, "hf_endpoint")?;
+
+    if let Some(dtype) = map.get("dtype").and_then(|v| v.as_str())
+        && !["f32", "f16", "bf16"].contains(&dtype.to_lowercase().as_str())
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'dtype' for provider '{}' must be one of: f32, f16, bf16",
+            provider_id
+        )));
+    }
+
+    if let Some(device) = map.get("device").and_then(|v| v.as_str()) {
+        let device = device.to_lowercase();
+        let valid = match device.strip_prefix("cuda") {
+            Some("") => true,
+            Some(rest) => rest
+                .strip_prefix(':')
+                .is_some_and(|n| n.parse::<usize>().is_ok()),
+            None => device == "cpu" || device == "metal",
+        };
+        if !valid {
+            return Err(RuntimeError::Config(format!(
+                "Option 'device' for provider '{}' must be one of: cpu, cuda, cuda:N, metal",
+                provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate FastEmbed options: `cache_dir` for catalog models, or
+/// `model_path` + `dimensions` (+ optional `pooling`) for a user-supplied
+/// ONNX embedding directory. Rerank aliases only take `cache_dir`.
+fn validate_fastembed_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &["cache_dir", "model_path", "dimensions", "pooling"],
+    )?;
+    require_string_keys(provider_id, map, &["cache_dir", "model_path", "pooling"])?;
+    require_positive_u64(provider_id, map, "dimensions")?;
+
+    if let Some(pooling) = map.get("pooling").and_then(|v| v.as_str())
+        && !["mean", "cls"].contains(&pooling)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'pooling' for provider '{}' must be one of: mean, cls",
+            provider_id
+        )));
+    }
+
+    if task == ModelTask::Rerank {
+        for key in ["model_path", "dimensions", "pooling"] {
+            if map.contains_key(key) {
+                return Err(RuntimeError::Config(format!(
+                    "Option '{}' for provider '{}' is not supported for rerank models",
+                    key, provider_id
+                )));
+            }
+        }
+    } else if map.contains_key("model_path") {
+        if !map.contains_key("dimensions") {
+            return Err(RuntimeError::Config(format!(
+                "Option 'dimensions' for provider '{}' is required when 'model_path' is set",
+                provider_id
+            )));
+        }
+    } else {
+        for key in ["dimensions", "pooling"] {
+            if map.contains_key(key) {
+                return Err(RuntimeError::Config(format!(
+                    "Option '{}' for provider '{}' is only valid with 'model_path'",
+                    key, provider_id
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate ONNX options: `model_path`, `device`, and `max_length`, plus the
+/// embed-only `pooling` and `embedding_dimensions`.
+fn validate_onnx_options(provider_id: &str, task: ModelTask, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(
+        provider_id,
+        map,
+        &[
+            "model_path",
+            "device",
+            "max_length",
+            "pooling",
+            "embedding_dimensions",
+        ],
     )?;
-    require_embedding_dimensions(provider_id, task, map)
+    require_string_keys(provider_id, map, &["model_path", "device", "pooling"])?;
+    require_positive_u64(provider_id, map, "max_length")?;
+    require_embedding_dimensions(provider_id, task, map)?;
+
+    if map.contains_key("pooling") && task != ModelTask::Embed {
+        return Err(RuntimeError::Config(
+            "Option 'pooling' is only valid for embed tasks".to_string(),
+        ));
+    }
+    if let Some(pooling) = map.get("pooling").and_then(|v| v.as_str())
+        && !["mean", "cls"].contains(&pooling)
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'pooling' for provider '{}' must be one of: mean, cls",
+            provider_id
+        )));
+    }
+
+    if let Some(device) = map.get("device").and_then(|v| v.as_str()) {
+        let device = device.to_lowercase();
+        let valid = match device.strip_prefix("cuda") {
+            Some("") => true,
+            Some(rest) => rest
+                .strip_prefix(':')
+                .is_some_and(|n| n.parse::<usize>().is_ok()),
+            None => device == "cpu",
+        };
+        if !valid {
+            return Err(RuntimeError::Config(format!(
+                "Option 'device' for provider '{}' must be one of: cpu, cuda, cuda:N",
+                provider_id
+            )));
+        }
+    }
+    Ok(())
 }
 
 /// Validate mistral.rs-specific options: ISQ type, boolean flags, GGUF files,
@@ -187,6 +1231,8 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
             "isq",
             "force_cpu",
             "paged_attention",
+            "paged_attn_block_size",
+            "kv_cache_mem_mb",
             "max_num_seqs",
             "chat_template",
             "tokenizer_json",
@@ -196,6 +1242,7 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
             "pipeline",
             "diffusion_loader_type",
             "speech_loader_type",
+            "hf_endpoint",
         ],
     )?;
 
@@ -220,7 +1267,8 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
     };
 
     // Shared validation across all pipelines
-    require_string_keys(provider_id, map, &["dtype"])?;
+    require_string_keys(provider_id, map, &["dtype", "hf_endpoint"])?;
+    require_http_url(provider_id, map, "hf_endpoint")?;
 
     if let Some(value) = map.get("dtype") {
         if let Some(s) = value.as_str() {
@@ -262,14 +1310,7 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
                 map,
                 &["isq", "chat_template", "tokenizer_json"],
             )?;
-            if let Some(value) = map.get("paged_attention")
-                && !value.is_boolean()
-            {
-                return Err(RuntimeError::Config(format!(
-                    "Option 'paged_attention' for provider '{}' must be a boolean",
-                    provider_id
-                )));
-            }
+            validate_mistralrs_paged_attention(provider_id, map)?;
             require_positive_u64(provider_id, map, "max_num_seqs")?;
         }
         "diffusion" => {
@@ -293,6 +1334,8 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
             for key in [
                 "isq",
                 "paged_attention",
+                "paged_attn_block_size",
+                "kv_cache_mem_mb",
                 "max_num_seqs",
                 "chat_template",
                 "tokenizer_json",
@@ -329,6 +1372,8 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
             for key in [
                 "isq",
                 "paged_attention",
+                "paged_attn_block_size",
+                "kv_cache_mem_mb",
                 "max_num_seqs",
                 "chat_template",
                 "tokenizer_json",
@@ -352,15 +1397,7 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
                 &["isq", "chat_template", "tokenizer_json"],
             )?;
 
-            if let Some(value) = map.get("paged_attention")
-                && !value.is_boolean()
-            {
-                return Err(RuntimeError::Config(format!(
-                    "Option 'paged_attention' for provider '{}' must be a boolean",
-                    provider_id
-                )));
-            }
-
+            validate_mistralrs_paged_attention(provider_id, map)?;
             require_positive_u64(provider_id, map, "max_num_seqs")?;
             require_embedding_dimensions(provider_id, task, map)?;
 
@@ -383,3 +1420,48 @@ fn validate_mistralrs_options(provider_id: &str, task: ModelTask, options: &Valu
 
     Ok(())
 }
+
+/// Validate `paged_attention` and the tuning keys that only apply with it.
+fn validate_mistralrs_paged_attention(
+    provider_id: &str,
+    map: &serde_json::Map<String, Value>,
+) -> Result<()> {
+    let enabled = match map.get("paged_attention") {
+        None => false,
+        Some(Value::Bool(enabled)) => *enabled,
+        Some(_) => {
+            return Err(RuntimeError::Config(format!(
+                "Option 'paged_attention' for provider '{}' must be a boolean",
+                provider_id
+            )));
+        }
+    };
+    for key in ["paged_attn_block_size", "kv_cache_mem_mb"] {
+        require_positive_u64(provider_id, map, key)?;
+        if map.contains_key(key) && !enabled {
+            return Err(RuntimeError::Config(format!(
+                "Option '{}' for provider '{}' requires 'paged_attention': true",
+                key, provider_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate null test provider options: `dimensions` and `fill`.
+fn validate_null_options(provider_id: &str, options: &Value) -> Result<()> {
+    let Some(map) = as_object(provider_id, options)? else {
+        return Ok(());
+    };
+    reject_unknown_keys(provider_id, map, &["dimensions", "fill"])?;
+    require_positive_u64(provider_id, map, "dimensions")?;
+    if let Some(fill) = map.get("fill")
+        && !matches!(fill.as_str(), Some("zero" | "hash"))
+    {
+        return Err(RuntimeError::Config(format!(
+            "Option 'fill' for provider '{}' must be one of: zero, hash",
+            provider_id
+        )));
+    }
+    Ok(())
+}
@@ -3,6 +3,10 @@
 //! Each sub-module is gated behind a Cargo feature flag (e.g. `provider-candle`,
 //! `provider-openai`). Only providers whose features are enabled will be compiled.
 //!
+//! Each provider module also exports a typed options struct (e.g. `OpenAIOptions`)
+//! that converts into the JSON accepted in
+//! [`ModelAliasSpec::options`](crate::api::ModelAliasSpec::options).
+//!
 //! ## Local providers
 //!
 //! | Module | Feature | Engine |
@@ -10,6 +14,7 @@
 //! | `candle` | `provider-candle` | [Candle](https://github.com/huggingface/candle) |
 //! | `fastembed` | `provider-fastembed` | [FastEmbed](https://github.com/Anush008/fastembed-rs) (ONNX Runtime) |
 //! | `mistralrs` | `provider-mistralrs` | [mistral.rs](https://github.com/EricLBuehler/mistral.rs) |
+//! | `onnx` | `provider-onnx` | [ONNX Runtime](https://onnxruntime.ai/) via [ort](https://github.com/pykeio/ort) |
 //!
 //! ## Remote providers
 //!
@@ -23,6 +28,15 @@
 //! | `voyageai` | `provider-voyageai` | Voyage AI |
 //! | `cohere` | `provider-cohere` | Cohere |
 //! | `azure_openai` | `provider-azure-openai` | Azure OpenAI |
+//! | `jina` | `provider-jina` | Jina AI |
+//! | `nim` | `provider-nim` | NVIDIA NIM (self-hosted or hosted) |
+//! | `replicate` | `provider-replicate` | Replicate |
+//!
+//! ## Test providers
+//!
+//! | Module | Feature | Behavior |
+//! |--------|---------|----------|
+//! | `null` | `testing` | Zero or hash-derived embeddings, no I/O |
 
 #[cfg(feature = "provider-candle")]
 pub mod candle;
@@ -36,24 +50,40 @@ pub mod candle;
     feature = "provider-voyageai",
     feature = "provider-cohere",
     feature = "provider-azure-openai",
+    feature = "provider-jina",
+    feature = "provider-nim",
+    feature = "provider-replicate",
 ))]
 pub(crate) mod remote_common;
 
 #[cfg(feature = "provider-openai")]
 pub mod openai;
 
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-nim",
+))]
+pub(crate) mod openai_stream;
+
 #[cfg(feature = "provider-fastembed")]
 pub mod fastembed;
 
 #[cfg(feature = "provider-gemini")]
 pub mod gemini;
 
+#[cfg(feature = "provider-vertexai")]
+pub(crate) mod google_auth;
+
 #[cfg(feature = "provider-vertexai")]
 pub mod vertexai;
 
 #[cfg(feature = "provider-mistralrs")]
 pub mod mistralrs;
 
+#[cfg(feature = "provider-onnx")]
+pub mod onnx;
+
 #[cfg(feature = "provider-mistral")]
 pub mod mistral;
 
@@ -69,36 +99,80 @@ pub mod cohere;
 #[cfg(feature = "provider-azure-openai")]
 pub mod azure_openai;
 
+#[cfg(feature = "provider-jina")]
+pub mod jina;
+
+#[cfg(feature = "provider-nim")]
+pub mod nim;
+
+#[cfg(feature = "provider-replicate")]
+pub mod replicate;
+
+#[cfg(feature = "testing")]
+pub mod null;
+
 // Re-exports (same order as module declarations above).
+#[cfg(any(feature = "provider-gemini", feature = "provider-vertexai"))]
+pub use remote_common::SafetySetting;
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-anthropic",
+    feature = "provider-voyageai",
+    feature = "provider-cohere",
+    feature = "provider-azure-openai",
+    feature = "provider-jina",
+    feature = "provider-nim",
+    feature = "provider-replicate",
+))]
+pub use remote_common::{DEFAULT_USER_AGENT, RemoteHttpConfig, Truncation};
+
 #[cfg(feature = "provider-candle")]
-pub use candle::LocalCandleProvider;
+pub use candle::{CandleOptions, LocalCandleProvider};
 
 #[cfg(feature = "provider-openai")]
-pub use openai::RemoteOpenAIProvider;
+pub use openai::{OpenAIOptions, RemoteOpenAIProvider};
 
 #[cfg(feature = "provider-fastembed")]
-pub use fastembed::LocalFastEmbedProvider;
+pub use fastembed::{FastEmbedOptions, LocalFastEmbedProvider};
 
 #[cfg(feature = "provider-gemini")]
-pub use gemini::RemoteGeminiProvider;
+pub use gemini::{GeminiOptions, RemoteGeminiProvider};
 
 #[cfg(feature = "provider-vertexai")]
-pub use vertexai::RemoteVertexAIProvider;
+pub use vertexai::{RemoteVertexAIProvider, VertexAIOptions};
 
 #[cfg(feature = "provider-mistralrs")]
-pub use self::mistralrs::LocalMistralRsProvider;
+pub use self::mistralrs::{LocalMistralRsProvider, MistralRsOptions};
+
+#[cfg(feature = "provider-onnx")]
+pub use onnx::{LocalOnnxProvider, OnnxOptions};
 
 #[cfg(feature = "provider-mistral")]
-pub use mistral::RemoteMistralProvider;
+pub use mistral::{MistralOptions, RemoteMistralProvider};
 
 #[cfg(feature = "provider-anthropic")]
-pub use anthropic::RemoteAnthropicProvider;
+pub use anthropic::{AnthropicOptions, RemoteAnthropicProvider};
 
 #[cfg(feature = "provider-voyageai")]
-pub use voyageai::RemoteVoyageAIProvider;
+pub use voyageai::{RemoteVoyageAIProvider, VoyageAIOptions};
 
 #[cfg(feature = "provider-cohere")]
-pub use cohere::RemoteCohereProvider;
+pub use cohere::{CohereOptions, RemoteCohereProvider};
 
 #[cfg(feature = "provider-azure-openai")]
-pub use azure_openai::RemoteAzureOpenAIProvider;
+pub use azure_openai::{AzureOpenAIOptions, RemoteAzureOpenAIProvider};
+
+#[cfg(feature = "provider-jina")]
+pub use jina::{JinaOptions, RemoteJinaProvider};
+
+#[cfg(feature = "provider-nim")]
+pub use nim::{NimOptions, RemoteNimProvider};
+
+#[cfg(feature = "provider-replicate")]
+pub use replicate::{RemoteReplicateProvider, ReplicateOptions};
+
+#[cfg(feature = "testing")]
+pub use null::{NullEmbeddingOptions, NullEmbeddingProvider};
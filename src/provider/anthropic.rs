@@ -1,20 +1,103 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::provider::remote_common::{
+    RateLimitState, RemoteProviderBase, RequestIdExt, check_http_status, probe_request,
+    resolve_api_key, resolve_base_url, verify_credentials,
+};
 use crate::traits::{
     GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle, Message, MessageRole,
-    ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage,
+    ModelProvider, ProviderCapabilities, ProviderHealth, RateLimitSnapshot, TokenUsage,
+    generate_each,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+/// Typed options for `remote/anthropic` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnthropicOptions {
+    /// Environment variable holding the API key (default `ANTHROPIC_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.anthropic.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// `anthropic-version` header value (default `2023-06-01`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anthropic_version: Option<String>,
+    /// Mark the system prompt as a prompt-cache breakpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_system_prompt: Option<bool>,
+    /// Mark the final message as a prompt-cache breakpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_last_message: Option<bool>,
+    /// Opaque end-user identifier sent as `metadata.user_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+impl AnthropicOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`anthropic_version`](Self::anthropic_version).
+    pub fn with_anthropic_version(mut self, anthropic_version: impl Into<String>) -> Self {
+        self.anthropic_version = Some(anthropic_version.into());
+        self
+    }
+
+    /// Set [`cache_system_prompt`](Self::cache_system_prompt).
+    pub fn with_cache_system_prompt(mut self, cache_system_prompt: bool) -> Self {
+        self.cache_system_prompt = Some(cache_system_prompt);
+        self
+    }
+
+    /// Set [`cache_last_message`](Self::cache_last_message).
+    pub fn with_cache_last_message(mut self, cache_last_message: bool) -> Self {
+        self.cache_last_message = Some(cache_last_message);
+        self
+    }
+
+    /// Set [`user_id`](Self::user_id).
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+}
+
+impl From<&AnthropicOptions> for serde_json::Value {
+    fn from(options: &AnthropicOptions) -> Self {
+        serde_json::to_value(options).expect("AnthropicOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Anthropic Messages API](https://docs.anthropic.com/en/api/messages)
 /// for text generation. Does not support embedding or reranking.
 ///
 /// Requires the `ANTHROPIC_API_KEY` environment variable (or a custom env var
-/// name via the `api_key_env` option).
+/// name via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
 pub struct RemoteAnthropicProvider {
     base: RemoteProviderBase,
 }
@@ -32,6 +115,23 @@ impl RemoteAnthropicProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .get(format!("{DEFAULT_BASE_URL}/v1/models?limit=1"))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -63,6 +163,7 @@ impl ModelProvider for RemoteAnthropicProvider {
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "ANTHROPIC_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         let anthropic_version = spec
             .options
@@ -70,15 +171,37 @@ impl ModelProvider for RemoteAnthropicProvider {
             .and_then(|v| v.as_str())
             .unwrap_or("2023-06-01")
             .to_string();
+        let cache_system_prompt = spec
+            .options
+            .get("cache_system_prompt")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let cache_last_message = spec
+            .options
+            .get("cache_last_message")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let user_id = spec
+            .options
+            .get("user_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
 
         match spec.task {
             ModelTask::Generate => {
                 let model = AnthropicGeneratorModel {
                     client: self.base.client.clone(),
                     cb,
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                     anthropic_version,
+                    cache: PromptCaching {
+                        system: cache_system_prompt,
+                        last_message: cache_last_message,
+                    },
+                    user_id,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -93,14 +216,89 @@ impl ModelProvider for RemoteAnthropicProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Lists models via `GET /v1/models`, which is free.
+    /// Uses the default `ANTHROPIC_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "ANTHROPIC_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `ANTHROPIC_API_KEY`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/anthropic", "ANTHROPIC_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/anthropic.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "anthropic_version",
+            "api_key_env",
+            "base_url",
+            "cache_last_message",
+            "cache_system_prompt",
+            "user_id",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 struct AnthropicGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.anthropic.com`.
+    base_url: String,
     api_key: String,
     anthropic_version: String,
+    cache: PromptCaching,
+    /// Opaque end-user identifier sent as `metadata.user_id`, which Anthropic
+    /// uses for abuse detection.
+    user_id: Option<String>,
+}
+
+/// Where to place `cache_control` breakpoints in the request.
+///
+/// Anthropic caches the prompt prefix up to and including each marked block,
+/// so marking the system prompt caches it across calls, and marking the last
+/// message additionally caches the conversation so far.
+#[derive(Debug, Clone, Copy, Default)]
+struct PromptCaching {
+    system: bool,
+    last_message: bool,
+}
+
+fn ephemeral_text_block(text: &str) -> serde_json::Value {
+    json!([{
+        "type": "text",
+        "text": text,
+        "cache_control": { "type": "ephemeral" }
+    }])
 }
 
 fn build_anthropic_payload(
@@ -108,8 +306,23 @@ fn build_anthropic_payload(
     messages: &[serde_json::Value],
     options: &GenerationOptions,
     system: Option<&str>,
+    cache: PromptCaching,
+    user_id: Option<&str>,
 ) -> serde_json::Value {
-    let max_tokens = options.max_tokens.unwrap_or(1024);
+    let thinking_budget = options.thinking_budget_tokens();
+    // Anthropic rejects requests without `max_tokens`, and with thinking
+    // enabled `max_tokens` must exceed the budget.
+    let max_tokens = options
+        .max_tokens
+        .unwrap_or_else(|| thinking_budget.map_or(1024, |budget| budget as usize + 1024));
+
+    let mut messages = messages.to_vec();
+    if cache.last_message
+        && let Some(last) = messages.last_mut()
+        && let Some(text) = last["content"].as_str().map(str::to_string)
+    {
+        last["content"] = ephemeral_text_block(&text);
+    }
 
     let mut body = json!({
         "model": model_id,
@@ -118,7 +331,11 @@ fn build_anthropic_payload(
     });
 
     if let Some(system_text) = system {
-        body["system"] = json!(system_text);
+        body["system"] = if cache.system {
+            ephemeral_text_block(system_text)
+        } else {
+            json!(system_text)
+        };
     }
     if let Some(temperature) = options.temperature {
         body["temperature"] = json!(temperature);
@@ -126,10 +343,71 @@ fn build_anthropic_payload(
     if let Some(top_p) = options.top_p {
         body["top_p"] = json!(top_p);
     }
+    if let Some(top_k) = options.top_k {
+        body["top_k"] = json!(top_k);
+    }
+    if let Some(stop) = &options.stop {
+        body["stop_sequences"] = json!(stop);
+    }
+    if let Some(user_id) = user_id {
+        body["metadata"] = json!({ "user_id": user_id });
+    }
+    if let Some(budget) = thinking_budget {
+        body["thinking"] = json!({ "type": "enabled", "budget_tokens": budget });
+    }
 
     body
 }
 
+/// Split a Messages API `content` array into the answer (the `text` blocks)
+/// and the reasoning (the `thinking` blocks). Redacted thinking is skipped.
+///
+/// A body without a `content` array is an [`RuntimeError::ApiError`], and a
+/// `refusal` stop without text is [`RuntimeError::ContentFiltered`].
+fn parse_anthropic_content(body: &serde_json::Value) -> Result<(String, Option<String>)> {
+    let blocks = body
+        .get("content")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| RuntimeError::api_error("remote/anthropic", "No content returned"))?;
+    let mut text = String::new();
+    let mut reasoning: Option<String> = None;
+    for block in blocks {
+        match block["type"].as_str() {
+            Some("text") => text.push_str(block["text"].as_str().unwrap_or("")),
+            Some("thinking") => reasoning
+                .get_or_insert_with(String::new)
+                .push_str(block["thinking"].as_str().unwrap_or("")),
+            _ => {}
+        }
+    }
+    if text.is_empty() && body["stop_reason"].as_str() == Some("refusal") {
+        return Err(RuntimeError::content_filtered(
+            "remote/anthropic",
+            "refusal",
+        ));
+    }
+    Ok((text, reasoning))
+}
+
+/// Map Anthropic's usage object to [`TokenUsage`].
+///
+/// Anthropic's `input_tokens` excludes cached tokens, so `prompt_tokens` is
+/// the sum of uncached, cache-read, and cache-creation input tokens.
+fn parse_anthropic_usage(u: &serde_json::Value) -> TokenUsage {
+    let input = u["input_tokens"].as_u64().unwrap_or(0) as usize;
+    let output = u["output_tokens"].as_u64().unwrap_or(0) as usize;
+    let cache_read = u["cache_read_input_tokens"].as_u64().unwrap_or(0) as usize;
+    let cache_creation = u["cache_creation_input_tokens"].as_u64().unwrap_or(0) as usize;
+    let prompt = input + cache_read + cache_creation;
+    TokenUsage {
+        prompt_tokens: prompt,
+        completion_tokens: output,
+        total_tokens: prompt + output,
+        cache_read_tokens: cache_read,
+        cache_creation_tokens: cache_creation,
+    }
+}
+
 #[async_trait]
 impl GeneratorModel for AnthropicGeneratorModel {
     async fn generate(
@@ -137,6 +415,9 @@ impl GeneratorModel for AnthropicGeneratorModel {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         // Extract system messages into a single combined string
         let system_parts: Vec<String> = messages
             .iter()
@@ -169,50 +450,55 @@ impl GeneratorModel for AnthropicGeneratorModel {
                     &messages,
                     &options,
                     system_text.as_deref(),
+                    self.cache,
+                    self.user_id.as_deref(),
                 );
 
                 let response = self
                     .client
-                    .post("https://api.anthropic.com/v1/messages")
+                    .post(format!("{}/v1/messages", self.base_url))
                     .header("x-api-key", &self.api_key)
                     .header("anthropic-version", &self.anthropic_version)
                     .header("content-type", "application/json")
                     .json(&body)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/anthropic", e))?;
 
-                let body: serde_json::Value = check_http_status("Anthropic", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/anthropic", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let text = body
-                    .get("content")
-                    .and_then(|c| c.as_array())
-                    .and_then(|arr| arr.first())
-                    .and_then(|item| item.get("text"))
-                    .and_then(|t| t.as_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                let usage = body.get("usage").map(|u| TokenUsage {
-                    prompt_tokens: u["input_tokens"].as_u64().unwrap_or(0) as usize,
-                    completion_tokens: u["output_tokens"].as_u64().unwrap_or(0) as usize,
-                    total_tokens: (u["input_tokens"].as_u64().unwrap_or(0)
-                        + u["output_tokens"].as_u64().unwrap_or(0))
-                        as usize,
-                });
+                    .map_err(|e| RuntimeError::api_error("remote/anthropic", e))?;
+
+                let (text, reasoning) = parse_anthropic_content(&body)?;
+
+                let usage = body.get("usage").map(parse_anthropic_usage);
+                let finish_reason = body
+                    .get("stop_reason")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string);
 
                 Ok(GenerationResult {
                     text,
                     usage,
                     images: vec![],
                     audio: None,
+                    alternatives: vec![],
+                    finish_reason,
+                    raw: options.include_raw.then_some(body),
+                    logprobs: None,
+                    reasoning,
                 })
             })
             .await
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 #[cfg(test)]
@@ -236,6 +522,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -335,6 +622,8 @@ mod tests {
             &messages,
             &GenerationOptions::default(),
             None,
+            PromptCaching::default(),
+            None,
         );
         assert_eq!(payload["max_tokens"], 1024);
     }
@@ -350,10 +639,117 @@ mod tests {
                 ..Default::default()
             },
             None,
+            PromptCaching::default(),
+            None,
         );
         assert_eq!(payload["max_tokens"], 512);
     }
 
+    #[test]
+    fn payload_maps_top_k_and_user_id() {
+        let messages = vec![json!({"role": "user", "content": "hello"})];
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions {
+                top_k: Some(40),
+                ..Default::default()
+            },
+            None,
+            PromptCaching::default(),
+            Some("user-1234"),
+        );
+        assert_eq!(payload["top_k"], 40);
+        assert_eq!(payload["metadata"]["user_id"], "user-1234");
+        assert_eq!(payload["max_tokens"], 1024);
+
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions::default(),
+            None,
+            PromptCaching::default(),
+            None,
+        );
+        assert!(payload.get("top_k").is_none());
+        assert!(payload.get("metadata").is_none());
+    }
+
+    #[test]
+    fn payload_enables_thinking_from_budget_or_effort() {
+        let messages = vec![json!({"role": "user", "content": "hello"})];
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions {
+                thinking_budget: Some(2048),
+                ..Default::default()
+            },
+            None,
+            PromptCaching::default(),
+            None,
+        );
+        assert_eq!(
+            payload["thinking"],
+            json!({ "type": "enabled", "budget_tokens": 2048 })
+        );
+        assert_eq!(payload["max_tokens"], 3072);
+
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions {
+                reasoning_effort: Some(crate::traits::ReasoningEffort::Low),
+                max_tokens: Some(8000),
+                ..Default::default()
+            },
+            None,
+            PromptCaching::default(),
+            None,
+        );
+        assert_eq!(payload["thinking"]["budget_tokens"], 1024);
+        assert_eq!(payload["max_tokens"], 8000);
+    }
+
+    #[test]
+    fn content_separates_thinking_from_text() {
+        let (text, reasoning) = parse_anthropic_content(&json!({
+            "content": [
+                { "type": "thinking", "thinking": "Check units.", "signature": "sig" },
+                { "type": "redacted_thinking", "data": "opaque" },
+                { "type": "text", "text": "42 km" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(text, "42 km");
+        assert_eq!(reasoning.as_deref(), Some("Check units."));
+
+        let (text, reasoning) =
+            parse_anthropic_content(&json!({ "content": [{ "type": "text", "text": "hi" }] }))
+                .unwrap();
+        assert_eq!(text, "hi");
+        assert!(reasoning.is_none());
+    }
+
+    #[test]
+    fn content_missing_or_refused_is_an_error() {
+        let err = parse_anthropic_content(&json!({ "type": "message" })).unwrap_err();
+        assert!(matches!(err, RuntimeError::ApiError { .. }));
+
+        let err = parse_anthropic_content(&json!({ "content": [], "stop_reason": "refusal" }))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::ContentFiltered { provider, reason }
+                if provider == "remote/anthropic" && reason == "refusal"
+        ));
+
+        // An empty end_turn (e.g. after an assistant prefill) is a valid answer.
+        let (text, _) =
+            parse_anthropic_content(&json!({ "content": [], "stop_reason": "end_turn" })).unwrap();
+        assert_eq!(text, "");
+    }
+
     #[test]
     fn payload_includes_system_field() {
         let messages = vec![json!({"role": "user", "content": "hello"})];
@@ -362,6 +758,8 @@ mod tests {
             &messages,
             &GenerationOptions::default(),
             Some("you are helpful"),
+            PromptCaching::default(),
+            None,
         );
         assert_eq!(payload["system"], "you are helpful");
     }
@@ -374,7 +772,69 @@ mod tests {
             &messages,
             &GenerationOptions::default(),
             None,
+            PromptCaching::default(),
+            None,
         );
         assert!(payload.get("system").is_none());
     }
+
+    #[test]
+    fn payload_marks_system_prompt_cacheable() {
+        let messages = vec![json!({"role": "user", "content": "hello"})];
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions::default(),
+            Some("long system prompt"),
+            PromptCaching {
+                system: true,
+                last_message: false,
+            },
+            None,
+        );
+        assert_eq!(payload["system"][0]["text"], "long system prompt");
+        assert_eq!(payload["system"][0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(payload["messages"][0]["content"], "hello");
+    }
+
+    #[test]
+    fn payload_marks_last_message_cacheable() {
+        let messages = vec![
+            json!({"role": "user", "content": "first"}),
+            json!({"role": "assistant", "content": "reply"}),
+            json!({"role": "user", "content": "second"}),
+        ];
+        let payload = build_anthropic_payload(
+            "claude-sonnet-4-5-20250929",
+            &messages,
+            &GenerationOptions::default(),
+            None,
+            PromptCaching {
+                system: false,
+                last_message: true,
+            },
+            None,
+        );
+        assert_eq!(payload["messages"][0]["content"], "first");
+        assert_eq!(payload["messages"][2]["content"][0]["text"], "second");
+        assert_eq!(
+            payload["messages"][2]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+    }
+
+    #[test]
+    fn usage_includes_cache_tokens() {
+        let usage = parse_anthropic_usage(&json!({
+            "input_tokens": 20,
+            "output_tokens": 5,
+            "cache_read_input_tokens": 10000,
+            "cache_creation_input_tokens": 0
+        }));
+        assert_eq!(usage.prompt_tokens, 10020);
+        assert_eq!(usage.completion_tokens, 5);
+        assert_eq!(usage.total_tokens, 10025);
+        assert_eq!(usage.cache_read_tokens, 10000);
+        assert_eq!(usage.cache_creation_tokens, 0);
+    }
 }
@@ -1,20 +1,104 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::provider::openai_stream::spawn_stream;
+use crate::provider::remote_common::{
+    RateLimitState, RemoteProviderBase, RequestIdExt, Truncation, check_http_status,
+    default_credential, parse_openai_chat_completion, parse_openai_embeddings, resolve_api_key,
+    resolve_base_url, wants_base64_embeddings,
+};
 use crate::traits::{
-    EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
-    Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage,
+    EmbeddingModel, GenerationOptions, GenerationResult, GenerationStream, GeneratorModel,
+    LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RateLimitSnapshot,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// Typed options for `remote/azure-openai` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AzureOpenAIOptions {
+    /// Environment variable holding the API key (default `AZURE_OPENAI_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://{resource_name}.openai.azure.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Azure resource name, the subdomain of `{resource}.openai.azure.com`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_name: Option<String>,
+    /// Azure OpenAI API version (default `2024-10-21`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    /// Embedding wire format: `"float"` (default) or `"base64"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_format: Option<String>,
+    /// Only `none` is accepted: Azure OpenAI rejects over-long embedding inputs
+    /// rather than truncating them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+}
+
+impl AzureOpenAIOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`resource_name`](Self::resource_name).
+    pub fn with_resource_name(mut self, resource_name: impl Into<String>) -> Self {
+        self.resource_name = Some(resource_name.into());
+        self
+    }
+
+    /// Set [`api_version`](Self::api_version).
+    pub fn with_api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Set [`encoding_format`](Self::encoding_format).
+    pub fn with_encoding_format(mut self, encoding_format: impl Into<String>) -> Self {
+        self.encoding_format = Some(encoding_format.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+}
+
+impl From<&AzureOpenAIOptions> for serde_json::Value {
+    fn from(options: &AzureOpenAIOptions) -> Self {
+        serde_json::to_value(options).expect("AzureOpenAIOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Azure OpenAI Service](https://learn.microsoft.com/en-us/azure/ai-services/openai/)
 /// for embedding and text generation.
 ///
 /// Requires the `AZURE_OPENAI_API_KEY` environment variable (or a custom env
-/// var name via the `api_key_env` option) and the `resource_name` option.
+/// var name via the `api_key_env` option) and the `resource_name` option. The
+/// `base_url` option replaces the resource endpoint, e.g. to route an alias
+/// through a proxy, and makes `resource_name` optional.
 pub struct RemoteAzureOpenAIProvider {
     base: RemoteProviderBase,
 }
@@ -32,6 +116,14 @@ impl RemoteAzureOpenAIProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -53,7 +145,9 @@ impl RemoteAzureOpenAIProvider {
 #[derive(Clone)]
 struct AzureResolvedOptions {
     api_key: String,
-    resource_name: String,
+    /// Endpoint root without a trailing slash, e.g.
+    /// `https://my-resource.openai.azure.com`.
+    base_url: String,
     api_version: String,
 }
 
@@ -61,16 +155,18 @@ impl AzureResolvedOptions {
     fn from_spec(spec: &ModelAliasSpec) -> Result<Self> {
         let api_key = resolve_api_key(&spec.options, "api_key_env", "AZURE_OPENAI_API_KEY")?;
 
-        let resource_name = spec
-            .options
-            .get("resource_name")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| {
-                RuntimeError::Config(
+        // `base_url` takes precedence, so `resource_name` is only needed
+        // without it.
+        let resource_url = match spec.options.get("resource_name").and_then(|v| v.as_str()) {
+            Some(resource_name) => format!("https://{}.openai.azure.com", resource_name),
+            None if spec.options.get("base_url").is_some() => String::new(),
+            None => {
+                return Err(RuntimeError::Config(
                     "Option 'resource_name' is required for Azure OpenAI provider".to_string(),
-                )
-            })?
-            .to_string();
+                ));
+            }
+        };
+        let base_url = resolve_base_url(&spec.options, &resource_url);
 
         let api_version = spec
             .options
@@ -81,22 +177,22 @@ impl AzureResolvedOptions {
 
         Ok(Self {
             api_key,
-            resource_name,
+            base_url,
             api_version,
         })
     }
 
     fn embed_url(&self, deployment: &str) -> String {
         format!(
-            "https://{}.openai.azure.com/openai/deployments/{}/embeddings?api-version={}",
-            self.resource_name, deployment, self.api_version
+            "{}/openai/deployments/{}/embeddings?api-version={}",
+            self.base_url, deployment, self.api_version
         )
     }
 
     fn chat_url(&self, deployment: &str) -> String {
         format!(
-            "https://{}.openai.azure.com/openai/deployments/{}/chat/completions?api-version={}",
-            self.resource_name, deployment, self.api_version
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            self.base_url, deployment, self.api_version
         )
     }
 }
@@ -122,8 +218,10 @@ impl ModelProvider for RemoteAzureOpenAIProvider {
                 let model = AzureOpenAIEmbeddingModel {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
+                    rate_limits: RateLimitState::default(),
                     deployment: spec.model_id.clone(),
                     options: resolved,
+                    base64: wants_base64_embeddings(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -132,6 +230,7 @@ impl ModelProvider for RemoteAzureOpenAIProvider {
                 let model = AzureOpenAIGeneratorModel {
                     client: self.base.client.clone(),
                     cb,
+                    rate_limits: RateLimitState::default(),
                     deployment: spec.model_id.clone(),
                     options: resolved,
                 };
@@ -148,19 +247,58 @@ impl ModelProvider for RemoteAzureOpenAIProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Validates the format of `AZURE_OPENAI_API_KEY` when it is set.
+    /// The endpoint depends on each alias's `resource_name`, so the key is not
+    /// sent.
+    async fn warmup(&self) -> Result<()> {
+        default_credential("remote/azure-openai", "AZURE_OPENAI_API_KEY").map(|_| ())
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/azure-openai.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "api_version",
+            "base_url",
+            "encoding_format",
+            "resource_name",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 struct AzureOpenAIEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     deployment: String,
     options: AzureResolvedOptions,
+    /// Request `encoding_format: "base64"` instead of float arrays.
+    base64: bool,
 }
 
 #[async_trait]
 impl EmbeddingModel for AzureOpenAIEmbeddingModel {
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let mut payload = json!({ "input": texts });
+        if self.base64 {
+            payload["encoding_format"] = json!("base64");
+        }
 
         self.cb
             .call(move || async move {
@@ -170,31 +308,20 @@ impl EmbeddingModel for AzureOpenAIEmbeddingModel {
                     .client
                     .post(&url)
                     .header("api-key", &self.options.api_key)
-                    .json(&json!({
-                        "input": texts
-                    }))
+                    .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/azure-openai", e))?;
 
-                let body: serde_json::Value = check_http_status("Azure OpenAI", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/azure-openai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let mut embeddings = Vec::new();
-                if let Some(data) = body.get("data").and_then(|d| d.as_array()) {
-                    for item in data {
-                        if let Some(embedding) = item.get("embedding").and_then(|e| e.as_array()) {
-                            let vec: Vec<f32> = embedding
-                                .iter()
-                                .filter_map(|v| v.as_f64().map(|f| f as f32))
-                                .collect();
-                            embeddings.push(vec);
-                        }
-                    }
-                }
-                Ok(embeddings)
+                    .map_err(|e| RuntimeError::api_error("remote/azure-openai", e))?;
+
+                parse_openai_embeddings("remote/azure-openai", &body)
             })
             .await
     }
@@ -208,15 +335,69 @@ impl EmbeddingModel for AzureOpenAIEmbeddingModel {
     fn model_id(&self) -> &str {
         &self.deployment
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 struct AzureOpenAIGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     deployment: String,
     options: AzureResolvedOptions,
 }
 
+/// Build the chat completions request body. The model is implied by the
+/// deployment in the URL, so unlike OpenAI the body carries no `model`.
+fn build_azure_chat_body(messages: &[Message], options: &GenerationOptions) -> serde_json::Value {
+    let messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|msg| {
+            let role = match msg.role {
+                MessageRole::System => "system",
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            };
+            json!({ "role": role, "content": msg.text() })
+        })
+        .collect();
+
+    let mut body = json!({
+        "messages": messages,
+    });
+
+    if let Some(max_tokens) = options.max_tokens {
+        body["max_tokens"] = json!(max_tokens);
+    }
+    if let Some(temperature) = options.temperature {
+        body["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = options.top_p {
+        body["top_p"] = json!(top_p);
+    }
+    if let Some(n) = options.n {
+        body["n"] = json!(n);
+    }
+    if let Some(stop) = &options.stop {
+        body["stop"] = json!(stop);
+    }
+    if let Some(logit_bias) = &options.logit_bias {
+        body["logit_bias"] = json!(logit_bias);
+    }
+    if options.logprobs || options.top_logprobs.is_some() {
+        body["logprobs"] = json!(true);
+    }
+    if let Some(top_logprobs) = options.top_logprobs {
+        body["top_logprobs"] = json!(top_logprobs);
+    }
+    if let Some(effort) = options.reasoning_effort {
+        body["reasoning_effort"] = json!(effort.as_str());
+    }
+    body
+}
+
 #[async_trait]
 impl GeneratorModel for AzureOpenAIGeneratorModel {
     async fn generate(
@@ -224,70 +405,70 @@ impl GeneratorModel for AzureOpenAIGeneratorModel {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
-        let messages: Vec<serde_json::Value> = messages
-            .iter()
-            .map(|msg| {
-                let role = match msg.role {
-                    MessageRole::System => "system",
-                    MessageRole::User => "user",
-                    MessageRole::Assistant => "assistant",
-                };
-                json!({ "role": role, "content": msg.text() })
-            })
-            .collect();
+        let body = build_azure_chat_body(messages, &options);
 
+        let include_raw = options.include_raw;
         self.cb
             .call(move || async move {
                 let url = self.options.chat_url(&self.deployment);
 
-                let mut body = json!({
-                    "messages": messages,
-                });
-
-                if let Some(max_tokens) = options.max_tokens {
-                    body["max_tokens"] = json!(max_tokens);
-                }
-                if let Some(temperature) = options.temperature {
-                    body["temperature"] = json!(temperature);
-                }
-                if let Some(top_p) = options.top_p {
-                    body["top_p"] = json!(top_p);
-                }
-
                 let response = self
                     .client
                     .post(&url)
                     .header("api-key", &self.options.api_key)
                     .json(&body)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/azure-openai", e))?;
 
-                let body: serde_json::Value = check_http_status("Azure OpenAI", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/azure-openai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let text = body["choices"][0]["message"]["content"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-
-                let usage = body.get("usage").map(|u| TokenUsage {
-                    prompt_tokens: u["prompt_tokens"].as_u64().unwrap_or(0) as usize,
-                    completion_tokens: u["completion_tokens"].as_u64().unwrap_or(0) as usize,
-                    total_tokens: u["total_tokens"].as_u64().unwrap_or(0) as usize,
-                });
-
-                Ok(GenerationResult {
-                    text,
-                    usage,
-                    images: vec![],
-                    audio: None,
-                })
+                    .map_err(|e| RuntimeError::api_error("remote/azure-openai", e))?;
+
+                let mut result = parse_openai_chat_completion("remote/azure-openai", &body)?;
+                result.raw = include_raw.then_some(body);
+                Ok(result)
             })
             .await
     }
+
+    /// Streams `chat.completion.chunk` events; the final chunk carries usage.
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        let mut body = build_azure_chat_body(messages, &options);
+        body["stream"] = json!(true);
+        body["stream_options"] = json!({ "include_usage": true });
+
+        let response = self
+            .cb
+            .call(move || async move {
+                let url = self.options.chat_url(&self.deployment);
+                let response = self
+                    .client
+                    .post(&url)
+                    .header("api-key", &self.options.api_key)
+                    .json(&body)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/azure-openai", e))?;
+                self.rate_limits.record(response.headers());
+                check_http_status("remote/azure-openai", response).await
+            })
+            .await?;
+        Ok(spawn_stream("remote/azure-openai", response))
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 #[cfg(test)]
@@ -316,6 +497,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options,
         }
@@ -423,11 +605,31 @@ mod tests {
         unsafe { std::env::remove_var("AZURE_OPENAI_API_KEY") };
     }
 
+    #[tokio::test]
+    async fn base_url_replaces_resource_endpoint() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("AZURE_OPENAI_API_KEY", "test-key") };
+
+        let s = spec_with_opts(
+            "chat/a",
+            ModelTask::Generate,
+            "gpt-4o",
+            json!({ "base_url": "https://proxy.internal/azure/" }),
+        );
+        let opts = AzureResolvedOptions::from_spec(&s).unwrap();
+        assert_eq!(
+            opts.chat_url("gpt-4o"),
+            "https://proxy.internal/azure/openai/deployments/gpt-4o/chat/completions?api-version=2024-10-21"
+        );
+
+        unsafe { std::env::remove_var("AZURE_OPENAI_API_KEY") };
+    }
+
     #[test]
     fn azure_url_construction() {
         let opts = AzureResolvedOptions {
             api_key: "key".to_string(),
-            resource_name: "my-resource".to_string(),
+            base_url: "https://my-resource.openai.azure.com".to_string(),
             api_version: "2024-10-21".to_string(),
         };
 
@@ -1,29 +1,34 @@
 use crate::api::{ModelAliasSpec, ModelTask};
+use crate::batching::{SUB_BATCH_CONCURRENCY, embed_in_sub_batches};
 use crate::error::{Result, RuntimeError};
 use crate::traits::{
-    EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    ClassifierModel, DownloadProgress, DownloadProgressFn, EmbeddingModel, EmbeddingStream,
+    LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth, ScoredLabel,
 };
 use async_trait::async_trait;
 use candle_core::{DType, Device, Module, Tensor};
-use candle_nn::VarBuilder;
+use candle_nn::{Linear, VarBuilder};
 use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
 use candle_transformers::models::gemma::{Config as GemmaConfig, Model as GemmaModel};
 use candle_transformers::models::jina_bert::{
     BertModel as JinaBertModel, Config as JinaBertConfig,
 };
 use hf_hub::{
-    Repo, RepoType,
-    api::tokio::{Api, ApiBuilder},
+    Cache, CacheRepo, Repo, RepoType,
+    api::tokio::{Api, ApiBuilder, ApiError, ApiRepo, Progress},
 };
-use serde::Deserialize;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use tokenizers::{Encoding, PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
 use tokio::sync::Mutex;
 
 #[derive(Deserialize, Debug)]
 struct BaseConfig {
     architectures: Option<Vec<String>>,
+    hidden_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -53,17 +58,141 @@ impl ModelArchitecture {
     }
 }
 
+/// Typed options for `local/candle` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CandleOptions {
+    /// Per-model cache directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
+    /// Compute device: `cpu` (default), `cuda`, `cuda:N`, or `metal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Weight precision: `f32` (default), `f16`, or `bf16`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtype: Option<String>,
+    /// HuggingFace Hub mirror to download from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hf_endpoint: Option<String>,
+    /// Local model directory to load instead of downloading `model_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    /// Maximum number of texts run through the model at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_batch_size: Option<usize>,
+    /// Expected embedding dimension; loading fails if the model disagrees.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
+}
+
+impl CandleOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`cache_dir`](Self::cache_dir).
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<String>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Set [`device`](Self::device).
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    /// Set [`dtype`](Self::dtype).
+    pub fn with_dtype(mut self, dtype: impl Into<String>) -> Self {
+        self.dtype = Some(dtype.into());
+        self
+    }
+
+    /// Set [`hf_endpoint`](Self::hf_endpoint).
+    pub fn with_hf_endpoint(mut self, hf_endpoint: impl Into<String>) -> Self {
+        self.hf_endpoint = Some(hf_endpoint.into());
+        self
+    }
+
+    /// Set [`model_path`](Self::model_path).
+    pub fn with_model_path(mut self, model_path: impl Into<String>) -> Self {
+        self.model_path = Some(model_path.into());
+        self
+    }
+
+    /// Set [`max_batch_size`](Self::max_batch_size).
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Set [`embedding_dimensions`](Self::embedding_dimensions).
+    pub fn with_embedding_dimensions(mut self, embedding_dimensions: u32) -> Self {
+        self.embedding_dimensions = Some(embedding_dimensions);
+        self
+    }
+}
+
+impl From<&CandleOptions> for serde_json::Value {
+    fn from(options: &CandleOptions) -> Self {
+        serde_json::to_value(options).expect("CandleOptions serializes to a JSON object")
+    }
+}
+
 /// Local embedding provider using the [Candle](https://github.com/huggingface/candle)
 /// ML framework.
 ///
 /// Supports Bert, JinaBert, and Gemma architectures with lazy weight loading
-/// from HuggingFace Hub and mean-pooled, L2-normalized embeddings.
+/// from HuggingFace Hub (or a local `model_path` directory) and mean-pooled,
+/// L2-normalized embeddings.
 #[derive(Default)]
-pub struct LocalCandleProvider;
+pub struct LocalCandleProvider {
+    download_progress: std::sync::RwLock<Option<DownloadProgressFn>>,
+}
 
 impl LocalCandleProvider {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    fn load_classifier(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        let model_path = spec.options.get("model_path").and_then(|v| v.as_str());
+        if !spec.model_id.contains('/') && model_path.is_none() {
+            return Err(RuntimeError::Config(format!(
+                "Unsupported Candle classifier: {} (use a HuggingFace repo id of an NLI model such as 'org/model')",
+                spec.model_id
+            )));
+        }
+        let cache_dir = crate::cache::resolve_cache_dir("candle", &spec.model_id, &spec.options);
+        tracing::info!(repo = %spec.model_id, "Initializing Candle classifier");
+        let mut model =
+            CandleClassifierModel::from_repo(&spec.model_id, spec.revision.clone(), cache_dir);
+        if let Some(path) = model_path {
+            model = model.with_model_path(path);
+        }
+        if let Some(device) = spec.options.get("device").and_then(|v| v.as_str()) {
+            model = model.with_device(device);
+        }
+        if let Some(endpoint) = crate::cache::resolve_hf_endpoint(&spec.options) {
+            model = model.with_hf_endpoint(endpoint);
+        }
+        if let Some(callback) = self.download_progress.read().unwrap().clone() {
+            model = model.with_download_progress(callback);
+        }
+        if let Some(dtype) = spec.options.get("dtype").and_then(|v| v.as_str()) {
+            let dtype = parse_dtype(dtype).ok_or_else(|| {
+                RuntimeError::Config(format!(
+                    "Unsupported Candle dtype '{}'; expected f32, f16, or bf16",
+                    dtype
+                ))
+            })?;
+            model = model.with_dtype(dtype);
+        }
+
+        let handle: Arc<dyn ClassifierModel> = Arc::new(model);
+        Ok(Arc::new(handle) as LoadedModelHandle)
     }
 }
 
@@ -75,11 +204,14 @@ impl ModelProvider for LocalCandleProvider {
 
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
-            supported_tasks: vec![ModelTask::Embed],
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Classify],
         }
     }
 
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        if spec.task == ModelTask::Classify {
+            return self.load_classifier(spec);
+        }
         if spec.task != ModelTask::Embed {
             return Err(RuntimeError::CapabilityMismatch(format!(
                 "Candle provider does not support task {:?}",
@@ -87,15 +219,61 @@ impl ModelProvider for LocalCandleProvider {
             )));
         }
 
-        let model_type = CandleTextModel::from_name(&spec.model_id).ok_or_else(|| {
-            RuntimeError::Config(format!("Unsupported Candle model: {}", spec.model_id))
-        })?;
-
-        let cache_dir =
-            crate::cache::resolve_cache_dir("candle", model_type.model_id(), &spec.options);
-
-        tracing::info!(model = ?model_type, "Initializing Candle model");
-        let model = CandleEmbeddingModel::new(model_type, spec.revision.clone(), cache_dir);
+        let model_path = spec.options.get("model_path").and_then(|v| v.as_str());
+        let mut model = match CandleTextModel::from_name(&spec.model_id) {
+            Some(model_type) => {
+                let cache_dir =
+                    crate::cache::resolve_cache_dir("candle", model_type.model_id(), &spec.options);
+                tracing::info!(model = ?model_type, "Initializing Candle model");
+                CandleEmbeddingModel::new(model_type, spec.revision.clone(), cache_dir)
+            }
+            // Any other HuggingFace repo id is accepted, as is any model_id
+            // naming a local model_path; architecture and dimensions are read
+            // from its config.json on load.
+            None if spec.model_id.contains('/') || model_path.is_some() => {
+                let cache_dir =
+                    crate::cache::resolve_cache_dir("candle", &spec.model_id, &spec.options);
+                tracing::info!(repo = %spec.model_id, "Initializing Candle model from repo");
+                CandleEmbeddingModel::from_repo(&spec.model_id, spec.revision.clone(), cache_dir)
+            }
+            None => {
+                return Err(RuntimeError::Config(format!(
+                    "Unsupported Candle model: {} (use a known model name or a HuggingFace repo id such as 'org/model')",
+                    spec.model_id
+                )));
+            }
+        };
+        if let Some(path) = model_path {
+            model = model.with_model_path(path);
+        }
+        if let Some(device) = spec.options.get("device").and_then(|v| v.as_str()) {
+            model = model.with_device(device);
+        }
+        if let Some(endpoint) = crate::cache::resolve_hf_endpoint(&spec.options) {
+            model = model.with_hf_endpoint(endpoint);
+        }
+        if let Some(callback) = self.download_progress.read().unwrap().clone() {
+            model = model.with_download_progress(callback);
+        }
+        if let Some(dims) = spec
+            .options
+            .get("embedding_dimensions")
+            .and_then(|v| v.as_u64())
+        {
+            model = model.with_expected_dimensions(dims as u32);
+        }
+        if let Some(max_batch_size) = spec.options.get("max_batch_size").and_then(|v| v.as_u64()) {
+            model = model.with_max_batch_size(max_batch_size as usize);
+        }
+        if let Some(dtype) = spec.options.get("dtype").and_then(|v| v.as_str()) {
+            let dtype = parse_dtype(dtype).ok_or_else(|| {
+                RuntimeError::Config(format!(
+                    "Unsupported Candle dtype '{}'; expected f32, f16, or bf16",
+                    dtype
+                ))
+            })?;
+            model = model.with_dtype(dtype);
+        }
 
         let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
         Ok(Arc::new(handle) as LoadedModelHandle)
@@ -105,6 +283,29 @@ impl ModelProvider for LocalCandleProvider {
         ProviderHealth::Healthy
     }
 
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/candle.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "cache_dir",
+            "device",
+            "dtype",
+            "embedding_dimensions",
+            "hf_endpoint",
+            "max_batch_size",
+            "model_path",
+        ])
+    }
+
+    fn set_download_progress(&self, callback: DownloadProgressFn) {
+        *self.download_progress.write().unwrap() = Some(callback);
+    }
+
     async fn warmup(&self) -> Result<()> {
         tracing::info!("Warming up LocalCandleProvider");
         // Pre-initialize HF API to warm up network/cache
@@ -113,7 +314,10 @@ impl ModelProvider for LocalCandleProvider {
     }
 }
 
-/// Supported text embedding models.
+/// Text embedding models known by name.
+///
+/// Any other BERT-family HuggingFace repo can be loaded by passing its repo id
+/// as `model_id`; these variants only add short names and known dimensions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CandleTextModel {
     /// all-MiniLM-L6-v2: 384 dims, fastest, English-optimized
@@ -123,6 +327,20 @@ pub enum CandleTextModel {
     BgeSmallEnV15,
     /// BGE-base-en-v1.5: 768 dimensions, higher quality English
     BgeBaseEnV15,
+    /// BGE-large-en-v1.5: 1024 dimensions, highest quality English
+    BgeLargeEnV15,
+    /// e5-small-v2: 384 dims, English
+    E5SmallV2,
+    /// e5-base-v2: 768 dims, English
+    E5BaseV2,
+    /// e5-large-v2: 1024 dims, English
+    E5LargeV2,
+    /// gte-small: 384 dims, English
+    GteSmall,
+    /// gte-base: 768 dims, English
+    GteBase,
+    /// paraphrase-multilingual-MiniLM-L12-v2: 384 dims, 50+ languages
+    ParaphraseMultilingualMiniLmL12V2,
 }
 
 impl CandleTextModel {
@@ -131,13 +349,27 @@ impl CandleTextModel {
             Self::AllMiniLmL6V2 => "sentence-transformers/all-MiniLM-L6-v2",
             Self::BgeSmallEnV15 => "BAAI/bge-small-en-v1.5",
             Self::BgeBaseEnV15 => "BAAI/bge-base-en-v1.5",
+            Self::BgeLargeEnV15 => "BAAI/bge-large-en-v1.5",
+            Self::E5SmallV2 => "intfloat/e5-small-v2",
+            Self::E5BaseV2 => "intfloat/e5-base-v2",
+            Self::E5LargeV2 => "intfloat/e5-large-v2",
+            Self::GteSmall => "thenlper/gte-small",
+            Self::GteBase => "thenlper/gte-base",
+            Self::ParaphraseMultilingualMiniLmL12V2 => {
+                "sentence-transformers/paraphrase-multilingual-MiniLM-L12-v2"
+            }
         }
     }
 
     pub fn dimensions(&self) -> u32 {
         match self {
-            Self::AllMiniLmL6V2 | Self::BgeSmallEnV15 => 384,
-            Self::BgeBaseEnV15 => 768,
+            Self::AllMiniLmL6V2
+            | Self::BgeSmallEnV15
+            | Self::E5SmallV2
+            | Self::GteSmall
+            | Self::ParaphraseMultilingualMiniLmL12V2 => 384,
+            Self::BgeBaseEnV15 | Self::E5BaseV2 | Self::GteBase => 768,
+            Self::BgeLargeEnV15 | Self::E5LargeV2 => 1024,
         }
     }
 
@@ -146,6 +378,13 @@ impl CandleTextModel {
             Self::AllMiniLmL6V2 => "all-MiniLM-L6-v2",
             Self::BgeSmallEnV15 => "bge-small-en-v1.5",
             Self::BgeBaseEnV15 => "bge-base-en-v1.5",
+            Self::BgeLargeEnV15 => "bge-large-en-v1.5",
+            Self::E5SmallV2 => "e5-small-v2",
+            Self::E5BaseV2 => "e5-base-v2",
+            Self::E5LargeV2 => "e5-large-v2",
+            Self::GteSmall => "gte-small",
+            Self::GteBase => "gte-base",
+            Self::ParaphraseMultilingualMiniLmL12V2 => "paraphrase-multilingual-MiniLM-L12-v2",
         }
     }
 
@@ -154,15 +393,393 @@ impl CandleTextModel {
             "all-minilm-l6-v2" | "allminilml6v2" | "default" => Some(Self::AllMiniLmL6V2),
             "bge-small-en-v1.5" | "bgesmallenv15" => Some(Self::BgeSmallEnV15),
             "bge-base-en-v1.5" | "bgebaseenv15" => Some(Self::BgeBaseEnV15),
+            "bge-large-en-v1.5" | "bgelargeenv15" => Some(Self::BgeLargeEnV15),
+            "e5-small-v2" => Some(Self::E5SmallV2),
+            "e5-base-v2" => Some(Self::E5BaseV2),
+            "e5-large-v2" => Some(Self::E5LargeV2),
+            "gte-small" => Some(Self::GteSmall),
+            "gte-base" => Some(Self::GteBase),
+            "paraphrase-multilingual-minilm-l12-v2" => {
+                Some(Self::ParaphraseMultilingualMiniLmL12V2)
+            }
             // Map known HF IDs to enum
             "sentence-transformers/all-minilm-l6-v2" => Some(Self::AllMiniLmL6V2),
             "baai/bge-small-en-v1.5" => Some(Self::BgeSmallEnV15),
             "baai/bge-base-en-v1.5" => Some(Self::BgeBaseEnV15),
+            "baai/bge-large-en-v1.5" => Some(Self::BgeLargeEnV15),
+            "intfloat/e5-small-v2" => Some(Self::E5SmallV2),
+            "intfloat/e5-base-v2" => Some(Self::E5BaseV2),
+            "intfloat/e5-large-v2" => Some(Self::E5LargeV2),
+            "thenlper/gte-small" => Some(Self::GteSmall),
+            "thenlper/gte-base" => Some(Self::GteBase),
+            "sentence-transformers/paraphrase-multilingual-minilm-l12-v2" => {
+                Some(Self::ParaphraseMultilingualMiniLmL12V2)
+            }
             _ => None,
         }
     }
 }
 
+/// Fetches files from one hub repo, reporting downloads of files that are not
+/// yet cached to an optional progress callback.
+struct RepoFiles {
+    api_repo: ApiRepo,
+    cache: CacheRepo,
+    repo_id: String,
+    progress: Option<DownloadProgressFn>,
+}
+
+impl RepoFiles {
+    async fn get(&self, file: &str) -> std::result::Result<PathBuf, ApiError> {
+        let Some(callback) = &self.progress else {
+            return self.api_repo.get(file).await;
+        };
+        if let Some(path) = self.cache.get(file) {
+            return Ok(path);
+        }
+        let progress = HubProgress {
+            callback: callback.clone(),
+            repo_id: self.repo_id.clone(),
+            file: file.to_string(),
+            state: Arc::default(),
+        };
+        self.api_repo.download_with_progress(file, progress).await
+    }
+}
+
+/// Adapts hf-hub's download progress to [`DownloadProgress`] events.
+///
+/// hf-hub clones the handle for parallel chunk downloads, so the counters are
+/// shared.
+#[derive(Clone)]
+struct HubProgress {
+    callback: DownloadProgressFn,
+    repo_id: String,
+    file: String,
+    state: Arc<HubProgressState>,
+}
+
+#[derive(Default)]
+struct HubProgressState {
+    total: AtomicU64,
+    downloaded: AtomicU64,
+}
+
+impl HubProgress {
+    fn emit(&self, downloaded: u64) {
+        (self.callback)(&DownloadProgress {
+            provider_id: "local/candle",
+            model_id: self.repo_id.clone(),
+            file: self.file.clone(),
+            downloaded,
+            total: Some(self.state.total.load(Ordering::Relaxed)),
+        });
+    }
+}
+
+impl Progress for HubProgress {
+    async fn init(&mut self, size: usize, _filename: &str) {
+        self.state.total.store(size as u64, Ordering::Relaxed);
+        self.emit(0);
+    }
+
+    async fn update(&mut self, size: usize) {
+        let downloaded = self
+            .state
+            .downloaded
+            .fetch_add(size as u64, Ordering::Relaxed)
+            + size as u64;
+        self.emit(downloaded);
+    }
+
+    async fn finish(&mut self) {}
+}
+
+/// Downloaded model weights, in the format they were found.
+enum WeightFiles {
+    /// One or more safetensors files (a single file or every shard).
+    Safetensors(Vec<PathBuf>),
+    /// A legacy PyTorch `pytorch_model.bin` checkpoint.
+    Pth(PathBuf),
+}
+
+/// Download model weights, preferring a single `model.safetensors`, then the
+/// shards listed in `model.safetensors.index.json`, and finally falling back
+/// to `pytorch_model.bin` for older repos that ship no safetensors.
+async fn fetch_weight_files(files: &RepoFiles) -> Result<WeightFiles> {
+    let single_err = match files.get("model.safetensors").await {
+        Ok(path) => return Ok(WeightFiles::Safetensors(vec![path])),
+        Err(e) => e,
+    };
+
+    let index_path = match files.get("model.safetensors.index.json").await {
+        Ok(path) => path,
+        Err(index_err) => {
+            return match files.get("pytorch_model.bin").await {
+                Ok(path) => {
+                    tracing::info!("No safetensors weights found; loading pytorch_model.bin");
+                    Ok(WeightFiles::Pth(path))
+                }
+                Err(pth_err) => Err(RuntimeError::Load(format!(
+                    "Failed to fetch model.safetensors ({}), model.safetensors.index.json ({}), or pytorch_model.bin ({})",
+                    single_err, index_err, pth_err
+                ))),
+            };
+        }
+    };
+    let index_contents =
+        std::fs::read_to_string(&index_path).map_err(|e| RuntimeError::Load(e.to_string()))?;
+    let shards = shard_files_from_index(&index_contents)?;
+    tracing::info!(
+        shards = shards.len(),
+        "Loading sharded safetensors checkpoint"
+    );
+
+    let mut paths = Vec::with_capacity(shards.len());
+    for shard in &shards {
+        let path = files
+            .get(shard)
+            .await
+            .map_err(|e| RuntimeError::Load(format!("Failed to fetch shard {}: {}", shard, e)))?;
+        paths.push(path);
+    }
+    Ok(WeightFiles::Safetensors(paths))
+}
+
+/// Files a local model directory must contain.
+const LOCAL_MODEL_FILES: [&str; 3] = ["config.json", "tokenizer.json", "model.safetensors"];
+
+/// Longest token sequence fed to the model; the tokenizer truncates to it.
+const MAX_SEQ_LEN: usize = 512;
+
+/// Resolve the config, tokenizer, and weights in a local model directory,
+/// naming every required file that is missing.
+fn local_model_files(dir: &Path) -> Result<(PathBuf, PathBuf, WeightFiles)> {
+    let missing: Vec<&str> = LOCAL_MODEL_FILES
+        .into_iter()
+        .filter(|file| !dir.join(file).is_file())
+        .collect();
+    if !missing.is_empty() {
+        return Err(RuntimeError::Load(format!(
+            "Candle model directory {} is missing required file(s): {}",
+            dir.display(),
+            missing.join(", ")
+        )));
+    }
+    Ok((
+        dir.join("config.json"),
+        dir.join("tokenizer.json"),
+        WeightFiles::Safetensors(vec![dir.join("model.safetensors")]),
+    ))
+}
+
+/// Extract the unique shard file names from a `model.safetensors.index.json`
+/// `weight_map`, in sorted order.
+fn shard_files_from_index(contents: &str) -> Result<Vec<String>> {
+    let index: serde_json::Value =
+        serde_json::from_str(contents).map_err(|e| RuntimeError::Load(e.to_string()))?;
+    let weight_map = index
+        .get("weight_map")
+        .and_then(|m| m.as_object())
+        .ok_or_else(|| {
+            RuntimeError::Load("Safetensors index is missing 'weight_map'".to_string())
+        })?;
+
+    let shards: std::collections::BTreeSet<String> = weight_map
+        .values()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect();
+    if shards.is_empty() {
+        return Err(RuntimeError::Load(
+            "Safetensors index lists no shard files".to_string(),
+        ));
+    }
+    Ok(shards.into_iter().collect())
+}
+
+/// Parse the `dtype` option (`"f32"`, `"f16"`, `"bf16"`).
+fn parse_dtype(s: &str) -> Option<DType> {
+    match s.to_lowercase().as_str() {
+        "f32" => Some(DType::F32),
+        "f16" => Some(DType::F16),
+        "bf16" => Some(DType::BF16),
+        _ => None,
+    }
+}
+
+/// Resolve the `device` option (`"cpu"`, `"cuda"`, `"cuda:N"`, `"metal"`) into
+/// a Candle [`Device`].
+///
+/// Falls back to CPU with a warning when the requested backend was not
+/// compiled in (`gpu-cuda` / `gpu-metal` features) or fails to initialize.
+#[allow(unexpected_cfgs)]
+fn resolve_device(requested: Option<&str>) -> Device {
+    let Some(requested) = requested else {
+        return Device::Cpu;
+    };
+    let requested = requested.trim().to_lowercase();
+
+    if requested == "cpu" {
+        return Device::Cpu;
+    }
+
+    if let Some(rest) = requested.strip_prefix("cuda") {
+        let ordinal = match rest.strip_prefix(':') {
+            Some(n) => n.parse::<usize>().ok(),
+            None if rest.is_empty() => Some(0),
+            None => None,
+        };
+        let Some(ordinal) = ordinal else {
+            tracing::warn!(device = %requested, "Invalid CUDA device; falling back to CPU");
+            return Device::Cpu;
+        };
+
+        #[cfg(feature = "gpu-cuda")]
+        match Device::new_cuda(ordinal) {
+            Ok(device) => return device,
+            Err(e) => {
+                tracing::warn!(device = %requested, error = %e, "CUDA device unavailable; falling back to CPU");
+            }
+        }
+        #[cfg(not(feature = "gpu-cuda"))]
+        tracing::warn!(
+            device = %requested,
+            ordinal,
+            "CUDA support not compiled in (enable the gpu-cuda feature); falling back to CPU"
+        );
+        return Device::Cpu;
+    }
+
+    if requested == "metal" {
+        #[cfg(feature = "gpu-metal")]
+        match Device::new_metal(0) {
+            Ok(device) => return device,
+            Err(e) => {
+                tracing::warn!(error = %e, "Metal device unavailable; falling back to CPU");
+            }
+        }
+        #[cfg(not(feature = "gpu-metal"))]
+        tracing::warn!(
+            "Metal support not compiled in (enable the gpu-metal feature); falling back to CPU"
+        );
+        return Device::Cpu;
+    }
+
+    tracing::warn!(device = %requested, "Unknown Candle device; falling back to CPU");
+    Device::Cpu
+}
+
+/// Where a Candle model's config, tokenizer, and weights come from: a local
+/// directory, or a HuggingFace repo through the local cache.
+struct ModelSource {
+    repo_id: String,
+    revision: Option<String>,
+    cache_dir: PathBuf,
+    /// Local model directory; when set the Hub is never contacted.
+    model_path: Option<PathBuf>,
+    hf_endpoint: Option<String>,
+    download_progress: Option<DownloadProgressFn>,
+}
+
+impl ModelSource {
+    fn new(repo_id: &str, revision: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            revision,
+            cache_dir,
+            model_path: None,
+            hf_endpoint: None,
+            download_progress: None,
+        }
+    }
+
+    fn build_api(&self) -> Result<Api> {
+        let mut builder = ApiBuilder::new().with_cache_dir(self.cache_dir.clone());
+        if let Some(endpoint) = &self.hf_endpoint {
+            builder = builder.with_endpoint(endpoint.clone());
+        }
+        builder
+            .build()
+            .map_err(|e| RuntimeError::Load(e.to_string()))
+    }
+
+    /// Fetch the config, tokenizer, and weights from the hub (or its cache).
+    async fn fetch_hub_files(&self) -> Result<(PathBuf, PathBuf, WeightFiles)> {
+        let api = self.build_api()?;
+        let repo = match &self.revision {
+            Some(rev) => Repo::with_revision(self.repo_id.clone(), RepoType::Model, rev.clone()),
+            None => Repo::model(self.repo_id.clone()),
+        };
+        let files = RepoFiles {
+            api_repo: api.repo(repo.clone()),
+            cache: Cache::new(self.cache_dir.clone()).repo(repo),
+            repo_id: self.repo_id.clone(),
+            progress: self.download_progress.clone(),
+        };
+
+        let config_path = files
+            .get("config.json")
+            .await
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let tokenizer_path = files
+            .get("tokenizer.json")
+            .await
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let weights = fetch_weight_files(&files).await?;
+        Ok((config_path, tokenizer_path, weights))
+    }
+
+    /// Resolve the config, tokenizer, and weights from `model_path` if set,
+    /// otherwise from the hub.
+    async fn fetch(&self) -> Result<(PathBuf, PathBuf, WeightFiles)> {
+        match &self.model_path {
+            Some(dir) => {
+                tracing::info!(path = %dir.display(), "Loading Candle model from local directory");
+                local_model_files(dir)
+            }
+            None => self.fetch_hub_files().await,
+        }
+    }
+}
+
+/// Load a tokenizer that pads each batch to its longest input and truncates
+/// inputs at [`MAX_SEQ_LEN`] tokens.
+fn load_tokenizer(path: &Path) -> Result<Tokenizer> {
+    let mut tokenizer = Tokenizer::from_file(path)
+        .map_err(|e| RuntimeError::Load(format!("Failed to load tokenizer: {}", e)))?;
+
+    let padding = PaddingParams {
+        strategy: PaddingStrategy::BatchLongest,
+        ..Default::default()
+    };
+    tokenizer.with_padding(Some(padding));
+
+    // Gemma usually handles truncation differently or defaults are fine.
+    tokenizer
+        .with_truncation(Some(TruncationParams {
+            max_length: MAX_SEQ_LEN,
+            ..Default::default()
+        }))
+        .map_err(|e| RuntimeError::Load(format!("Failed to set truncation: {}", e)))?;
+    Ok(tokenizer)
+}
+
+/// Open the weight files as a [`VarBuilder`] in `dtype` on `device`.
+fn load_weights(
+    weights: &WeightFiles,
+    dtype: DType,
+    device: &Device,
+) -> Result<VarBuilder<'static>> {
+    match weights {
+        WeightFiles::Safetensors(paths) => unsafe {
+            VarBuilder::from_mmaped_safetensors(paths, dtype, device)
+                .map_err(|e| RuntimeError::Load(e.to_string()))
+        },
+        WeightFiles::Pth(path) => VarBuilder::from_pth(path, dtype, device)
+            .map_err(|e| RuntimeError::Load(format!("Failed to load {}: {}", path.display(), e))),
+    }
+}
+
 enum InnerModel {
     Bert(BertModel),
     JinaBert(JinaBertModel),
@@ -175,57 +792,207 @@ struct LoadedModel {
     device: Device,
 }
 
+/// Model inputs for one batch, flattened row-major to `batch_size x seq_len`.
+#[derive(Debug, PartialEq)]
+struct TokenBatch {
+    input_ids: Vec<i64>,
+    attention_mask: Vec<i64>,
+    token_type_ids: Vec<i64>,
+    batch_size: usize,
+    seq_len: usize,
+}
+
+impl TokenBatch {
+    /// Right-pad caller-supplied token sequences to the longest one with
+    /// `pad_id`, masking the padding out. Every sequence uses token type 0.
+    fn from_ids(sequences: &[Vec<u32>], pad_id: u32) -> Result<Self> {
+        let seq_len = sequences.iter().map(Vec::len).max().unwrap_or(0);
+        if let Some(index) = sequences.iter().position(Vec::is_empty) {
+            return Err(RuntimeError::Config(format!(
+                "Token sequence {} is empty",
+                index
+            )));
+        }
+        if let Some(index) = sequences.iter().position(|ids| ids.len() > MAX_SEQ_LEN) {
+            return Err(RuntimeError::Config(format!(
+                "Token sequence {} has {} tokens; the model accepts at most {}",
+                index,
+                sequences[index].len(),
+                MAX_SEQ_LEN
+            )));
+        }
+
+        let mut batch = Self {
+            input_ids: Vec::with_capacity(sequences.len() * seq_len),
+            attention_mask: Vec::with_capacity(sequences.len() * seq_len),
+            token_type_ids: vec![0; sequences.len() * seq_len],
+            batch_size: sequences.len(),
+            seq_len,
+        };
+        for ids in sequences {
+            let padding = seq_len - ids.len();
+            batch.input_ids.extend(ids.iter().map(|&id| id as i64));
+            batch
+                .input_ids
+                .extend(std::iter::repeat_n(pad_id as i64, padding));
+            batch
+                .attention_mask
+                .extend(std::iter::repeat_n(1, ids.len()));
+            batch.attention_mask.extend(std::iter::repeat_n(0, padding));
+        }
+        Ok(batch)
+    }
+
+    /// Flatten tokenizer output, already padded to a common length.
+    fn from_encodings(encodings: &[Encoding]) -> Self {
+        let mut batch = Self {
+            input_ids: Vec::new(),
+            attention_mask: Vec::new(),
+            token_type_ids: Vec::new(),
+            batch_size: encodings.len(),
+            seq_len: encodings.first().map_or(0, |e| e.get_ids().len()),
+        };
+        for encoding in encodings {
+            batch
+                .input_ids
+                .extend(encoding.get_ids().iter().map(|&x| x as i64));
+            batch
+                .attention_mask
+                .extend(encoding.get_attention_mask().iter().map(|&x| x as i64));
+            batch
+                .token_type_ids
+                .extend(encoding.get_type_ids().iter().map(|&x| x as i64));
+        }
+        batch
+    }
+
+    /// The `(input_ids, attention_mask, token_type_ids)` tensors on `device`.
+    fn tensors(self, device: &Device) -> Result<(Tensor, Tensor, Tensor)> {
+        let shape = (self.batch_size, self.seq_len);
+        let tensor = |values: Vec<i64>| {
+            Tensor::from_vec(values, shape, device)
+                .map_err(|e| RuntimeError::InferenceError(e.to_string()))
+        };
+        Ok((
+            tensor(self.input_ids)?,
+            tensor(self.attention_mask)?,
+            tensor(self.token_type_ids)?,
+        ))
+    }
+}
+
 /// A lazily-loaded embedding model backed by Candle.
 ///
 /// On first [`embed`](crate::traits::EmbeddingModel::embed) call (or explicit
 /// [`warmup`](crate::traits::EmbeddingModel::warmup)), the model weights and
-/// tokenizer are downloaded from HuggingFace Hub and loaded into memory.
+/// tokenizer are downloaded from HuggingFace Hub, or read from the
+/// [`with_model_path`](Self::with_model_path) directory, and loaded into
+/// memory.
 pub struct CandleEmbeddingModel {
-    model_type: CandleTextModel,
-    revision: Option<String>,
-    cache_dir: PathBuf,
+    source: ModelSource,
+    /// Known up front for [`CandleTextModel`] variants or when an expected
+    /// size is supplied; otherwise 0 until `config.json` has been read on
+    /// load, after which it always reflects the config's `hidden_size`.
+    dimensions: AtomicU32,
+    expected_dimensions: Option<u32>,
+    device: Option<String>,
+    dtype: DType,
+    max_batch_size: Option<usize>,
     state: Arc<Mutex<Option<LoadedModel>>>,
 }
 
 impl CandleEmbeddingModel {
     pub fn new(model_type: CandleTextModel, revision: Option<String>, cache_dir: PathBuf) -> Self {
+        let mut model = Self::from_repo(model_type.model_id(), revision, cache_dir);
+        model.dimensions = AtomicU32::new(model_type.dimensions());
+        model
+    }
+
+    /// Create a model for an arbitrary HuggingFace repo. Dimensions are taken
+    /// from `hidden_size` in the repo's `config.json` once loaded.
+    pub fn from_repo(repo_id: &str, revision: Option<String>, cache_dir: PathBuf) -> Self {
         Self {
-            model_type,
-            revision,
-            cache_dir,
+            source: ModelSource::new(repo_id, revision, cache_dir),
+            dimensions: AtomicU32::new(0),
+            expected_dimensions: None,
+            device: None,
+            dtype: DTYPE,
+            max_batch_size: None,
             state: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Declare the expected embedding size.
+    ///
+    /// [`dimensions`](EmbeddingModel::dimensions) reports this value before
+    /// the model is loaded, and loading fails if the repo's `config.json`
+    /// disagrees.
+    pub fn with_expected_dimensions(mut self, dimensions: u32) -> Self {
+        self.expected_dimensions = Some(dimensions);
+        self.dimensions = AtomicU32::new(dimensions);
+        self
+    }
+
+    /// Load `config.json`, `tokenizer.json`, and `model.safetensors` from a
+    /// local directory instead of HuggingFace Hub, e.g. for vendored or
+    /// fine-tuned models and offline deployments. Revision, cache, and
+    /// endpoint settings are ignored.
+    pub fn with_model_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.source.model_path = Some(path.into());
+        self
+    }
+
+    /// Request a compute device (`"cpu"`, `"cuda:0"`, `"metal"`). Resolved on
+    /// load; unavailable devices fall back to CPU with a warning.
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    /// Load weights in `dtype` instead of the default f32.
+    ///
+    /// `F16`/`BF16` roughly halve memory use. Pooling and normalization are
+    /// still computed in f32, but the transformer itself runs at reduced
+    /// precision, so vectors differ slightly from the f32 baseline (typically
+    /// well under 0.01 cosine distance). Do not mix vectors produced at
+    /// different precisions in one index if exact reproducibility matters.
+    pub fn with_dtype(mut self, dtype: DType) -> Self {
+        self.dtype = dtype;
+        self
+    }
+
+    /// Download from a HuggingFace Hub mirror (e.g. `https://hf-mirror.com`)
+    /// instead of `huggingface.co`.
+    pub fn with_hf_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.source.hf_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Report progress of weight and tokenizer downloads to `callback`.
+    /// Files already in the cache are not reported.
+    pub fn with_download_progress(mut self, callback: DownloadProgressFn) -> Self {
+        self.source.download_progress = Some(callback);
+        self
+    }
+
+    /// Run at most `max_batch_size` texts through the model at once, so a
+    /// large [`embed`](EmbeddingModel::embed) call is split into chunks
+    /// instead of building one tensor over every input. Results keep input
+    /// order.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size.max(1));
+        self
+    }
+
     async fn ensure_loaded(&self) -> Result<()> {
         let mut state = self.state.lock().await;
         if state.is_some() {
             return Ok(());
         }
 
-        tracing::info!(
-            model = self.model_type.name(),
-            "Loading Candle embedding model"
-        );
-
-        let api = ApiBuilder::new()
-            .with_cache_dir(self.cache_dir.clone())
-            .build()
-            .map_err(|e| RuntimeError::Load(e.to_string()))?;
-        let repo = match &self.revision {
-            Some(rev) => Repo::with_revision(
-                self.model_type.model_id().to_string(),
-                RepoType::Model,
-                rev.clone(),
-            ),
-            None => Repo::model(self.model_type.model_id().to_string()),
-        };
-        let api_repo = api.repo(repo);
+        tracing::info!(model = %self.source.repo_id, "Loading Candle embedding model");
 
-        let config_path = api_repo
-            .get("config.json")
-            .await
-            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let (config_path, tokenizer_path, weights) = self.source.fetch().await?;
 
         let config_contents =
             std::fs::read_to_string(&config_path).map_err(|e| RuntimeError::Load(e.to_string()))?;
@@ -236,37 +1003,11 @@ impl CandleEmbeddingModel {
         let arch = ModelArchitecture::from_config(&base_config)?;
         tracing::info!(architecture = ?arch, "Detected model architecture");
 
-        let tokenizer_path = api_repo
-            .get("tokenizer.json")
-            .await
-            .map_err(|e| RuntimeError::Load(e.to_string()))?;
-        let weights_path = api_repo
-            .get("model.safetensors")
-            .await
-            .map_err(|e| RuntimeError::Load(e.to_string()))?;
-
-        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
-            .map_err(|e| RuntimeError::Load(format!("Failed to load tokenizer: {}", e)))?;
+        let tokenizer = load_tokenizer(&tokenizer_path)?;
 
-        let padding = PaddingParams {
-            strategy: PaddingStrategy::BatchLongest,
-            ..Default::default()
-        };
-        tokenizer.with_padding(Some(padding));
-
-        // Gemma usually handles truncation differently or defaults are fine.
-        tokenizer
-            .with_truncation(Some(TruncationParams {
-                max_length: 512,
-                ..Default::default()
-            }))
-            .map_err(|e| RuntimeError::Load(format!("Failed to set truncation: {}", e)))?;
-
-        let device = Device::Cpu;
-        let vb = unsafe {
-            VarBuilder::from_mmaped_safetensors(&[weights_path], DTYPE, &device)
-                .map_err(|e| RuntimeError::Load(e.to_string()))?
-        };
+        let device = resolve_device(self.device.as_deref());
+        tracing::info!(device = ?device, "Selected Candle device");
+        let vb = load_weights(&weights, self.dtype, &device)?;
 
         let model = match arch {
             ModelArchitecture::Bert => {
@@ -292,9 +1033,21 @@ impl CandleEmbeddingModel {
             }
         };
 
+        if let Some(hidden_size) = base_config.hidden_size {
+            if let Some(expected) = self.expected_dimensions
+                && expected != hidden_size
+            {
+                return Err(RuntimeError::Load(format!(
+                    "Candle model {} has hidden_size {} but embedding_dimensions is {}",
+                    self.source.repo_id, hidden_size, expected
+                )));
+            }
+            self.dimensions.store(hidden_size, Ordering::Relaxed);
+        }
+
         tracing::info!(
-            model = self.model_type.name(),
-            dimensions = self.model_type.dimensions(),
+            model = %self.source.repo_id,
+            dimensions = self.dimensions.load(Ordering::Relaxed),
             "Candle embedding model loaded"
         );
 
@@ -306,70 +1059,31 @@ impl CandleEmbeddingModel {
 
         Ok(())
     }
-}
-
-#[async_trait]
-impl EmbeddingModel for CandleEmbeddingModel {
-    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
-        self.ensure_loaded().await?;
-
-        let state_guard = self.state.lock().await;
-        let loaded = state_guard
-            .as_ref()
-            .ok_or_else(|| RuntimeError::Load("Model state missing".to_string()))?;
-
-        if texts.is_empty() {
-            return Ok(vec![]);
-        }
 
+    /// Embed one batch of texts: tokenize, then [`forward_pooled`](Self::forward_pooled).
+    fn embed_batch(loaded: &LoadedModel, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         let encodings = loaded
             .tokenizer
             .encode_batch(texts.to_vec(), true)
             .map_err(|e| RuntimeError::InferenceError(format!("Tokenization failed: {}", e)))?;
 
-        let mut all_input_ids = Vec::new();
-        let mut all_attention_masks = Vec::new();
-        let mut all_token_type_ids = Vec::new();
-
-        for encoding in &encodings {
-            all_input_ids.push(
-                encoding
-                    .get_ids()
-                    .iter()
-                    .map(|&x| x as i64)
-                    .collect::<Vec<_>>(),
-            );
-            all_attention_masks.push(
-                encoding
-                    .get_attention_mask()
-                    .iter()
-                    .map(|&x| x as i64)
-                    .collect::<Vec<_>>(),
-            );
-            all_token_type_ids.push(
-                encoding
-                    .get_type_ids()
-                    .iter()
-                    .map(|&x| x as i64)
-                    .collect::<Vec<_>>(),
-            );
-        }
-
-        let batch_size = texts.len();
-        let seq_len = all_input_ids[0].len();
-
-        let input_ids_flat: Vec<i64> = all_input_ids.into_iter().flatten().collect();
-        let attention_mask_flat: Vec<i64> = all_attention_masks.into_iter().flatten().collect();
-        let token_type_ids_flat: Vec<i64> = all_token_type_ids.into_iter().flatten().collect();
-
-        let input_ids = Tensor::from_vec(input_ids_flat, (batch_size, seq_len), &loaded.device)
-            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
-        let attention_mask =
-            Tensor::from_vec(attention_mask_flat, (batch_size, seq_len), &loaded.device)
-                .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
-        let token_type_ids =
-            Tensor::from_vec(token_type_ids_flat, (batch_size, seq_len), &loaded.device)
-                .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+        Self::forward_pooled(loaded, TokenBatch::from_encodings(&encodings))
+    }
+
+    /// Embed one batch of caller-supplied token sequences, padded with the
+    /// tokenizer's pad token.
+    fn embed_token_batch(loaded: &LoadedModel, sequences: &[Vec<u32>]) -> Result<Vec<Vec<f32>>> {
+        let pad_id = loaded
+            .tokenizer
+            .get_padding()
+            .map_or(0, |padding| padding.pad_id);
+        Self::forward_pooled(loaded, TokenBatch::from_ids(sequences, pad_id)?)
+    }
+
+    /// Run a single forward pass, mean-pool, and L2-normalize.
+    fn forward_pooled(loaded: &LoadedModel, batch: TokenBatch) -> Result<Vec<Vec<f32>>> {
+        let (batch_size, seq_len) = (batch.batch_size, batch.seq_len);
+        let (input_ids, attention_mask, token_type_ids) = batch.tensors(&loaded.device)?;
 
         let embeddings = match &loaded.model {
             InnerModel::Bert(m) => m
@@ -408,6 +1122,11 @@ impl EmbeddingModel for CandleEmbeddingModel {
             }
         };
 
+        // Pool in f32 regardless of the weight dtype for numerical stability.
+        let embeddings = embeddings
+            .to_dtype(DType::F32)
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+
         // Mean pooling
         let attention_mask_f32 = attention_mask
             .to_dtype(DType::F32)
@@ -463,16 +1182,570 @@ impl EmbeddingModel for CandleEmbeddingModel {
 
         Ok(embeddings_vec)
     }
+}
+
+#[async_trait]
+impl EmbeddingModel for CandleEmbeddingModel {
+    /// Embeds one `max_batch_size` chunk at a time (all texts at once when
+    /// unset). The model is locked per chunk, so other calls interleave;
+    /// vectors come back in input order.
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+        self.ensure_loaded().await?;
+
+        let chunk_size = self.max_batch_size.unwrap_or(texts.len()).max(1);
+        let chunks = texts.chunks(chunk_size).map(<[_]>::to_vec).collect();
+        embed_in_sub_batches(chunks, SUB_BATCH_CONCURRENCY, |chunk| async move {
+            let state_guard = self.state.lock().await;
+            let loaded = state_guard
+                .as_ref()
+                .ok_or_else(|| RuntimeError::Load("Model state missing".to_string()))?;
+            Ok((Self::embed_batch(loaded, &chunk)?, None))
+        })
+        .await
+        .map(|(vectors, _)| vectors)
+    }
+
+    /// Sequences longer than 512 tokens are rejected rather than truncated.
+    async fn embed_tokens(&self, batches: Vec<Vec<u32>>) -> Result<Vec<Vec<f32>>> {
+        if batches.is_empty() {
+            return Ok(vec![]);
+        }
+        self.ensure_loaded().await?;
+
+        let chunk_size = self.max_batch_size.unwrap_or(batches.len()).max(1);
+        let chunks = batches.chunks(chunk_size).map(<[_]>::to_vec).collect();
+        embed_in_sub_batches(chunks, SUB_BATCH_CONCURRENCY, |chunk| async move {
+            let state_guard = self.state.lock().await;
+            let loaded = state_guard
+                .as_ref()
+                .ok_or_else(|| RuntimeError::Load("Model state missing".to_string()))?;
+            Ok((Self::embed_token_batch(loaded, &chunk)?, None))
+        })
+        .await
+        .map(|(vectors, _)| vectors)
+    }
+
+    /// Streams one `max_batch_size` chunk at a time (all texts at once when
+    /// unset). The model is locked per chunk, so other calls interleave.
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        let chunk_size = self.max_batch_size.unwrap_or(texts.len()).max(1);
+        let (tx, rx) = tokio::sync::mpsc::channel(chunk_size);
+        if texts.is_empty() {
+            return Ok(rx);
+        }
+        self.ensure_loaded().await?;
+
+        let texts: Vec<String> = texts.into_iter().map(str::to_owned).collect();
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            for (chunk_index, chunk) in texts.chunks(chunk_size).enumerate() {
+                let chunk: Vec<&str> = chunk.iter().map(String::as_str).collect();
+                let result = {
+                    let state_guard = state.lock().await;
+                    match state_guard.as_ref() {
+                        Some(loaded) => Self::embed_batch(loaded, &chunk),
+                        None => Err(RuntimeError::Load("Model state missing".to_string())),
+                    }
+                };
+                let vectors = match result {
+                    Ok(vectors) => vectors,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                let offset = chunk_index * chunk_size;
+                for (i, vector) in vectors.into_iter().enumerate() {
+                    if tx.send(Ok((offset + i, vector))).await.is_err() {
+                        // The caller stopped listening.
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
 
     fn dimensions(&self) -> u32 {
-        self.model_type.dimensions()
+        self.dimensions.load(Ordering::Relaxed)
     }
 
     fn model_id(&self) -> &str {
-        self.model_type.model_id()
+        &self.source.repo_id
     }
 
     async fn warmup(&self) -> Result<()> {
         self.ensure_loaded().await
     }
 }
+
+/// Hypothesis paired with the input text for each candidate label.
+const HYPOTHESIS_TEMPLATE: &str = "This example is {}.";
+
+#[derive(Deserialize, Debug)]
+struct ClassifierConfig {
+    architectures: Option<Vec<String>>,
+    hidden_size: usize,
+    id2label: HashMap<String, String>,
+}
+
+impl ClassifierConfig {
+    /// Index of the `entailment` logit, from the model's `id2label` map.
+    fn entailment_index(&self) -> Option<usize> {
+        self.id2label.iter().find_map(|(id, label)| {
+            label
+                .to_lowercase()
+                .starts_with("entail")
+                .then(|| id.parse().ok())
+                .flatten()
+        })
+    }
+}
+
+/// Softmax of `logits`, shifted by the maximum for numerical stability.
+fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = logits.iter().map(|&x| (x - max).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|x| x / sum).collect()
+}
+
+struct LoadedClassifier {
+    bert: BertModel,
+    pooler: Linear,
+    head: Linear,
+    entailment_index: usize,
+    tokenizer: Tokenizer,
+    device: Device,
+}
+
+/// A lazily-loaded zero-shot classifier backed by a Candle NLI model.
+///
+/// The repo must be a `BertForSequenceClassification` model trained on
+/// natural language inference with an `entailment` label. Each candidate
+/// label is scored by pairing the text with the hypothesis
+/// `"This example is {label}."`; the entailment logits are softmaxed across
+/// labels, so scores sum to 1.
+pub struct CandleClassifierModel {
+    source: ModelSource,
+    device: Option<String>,
+    dtype: DType,
+    state: Mutex<Option<LoadedClassifier>>,
+}
+
+impl CandleClassifierModel {
+    pub fn from_repo(repo_id: &str, revision: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            source: ModelSource::new(repo_id, revision, cache_dir),
+            device: None,
+            dtype: DTYPE,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// See [`CandleEmbeddingModel::with_model_path`].
+    pub fn with_model_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.source.model_path = Some(path.into());
+        self
+    }
+
+    /// See [`CandleEmbeddingModel::with_device`].
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    /// Load weights in `dtype` instead of the default f32. Logits are
+    /// compared in f32.
+    pub fn with_dtype(mut self, dtype: DType) -> Self {
+        self.dtype = dtype;
+        self
+    }
+
+    /// See [`CandleEmbeddingModel::with_hf_endpoint`].
+    pub fn with_hf_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.source.hf_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// See [`CandleEmbeddingModel::with_download_progress`].
+    pub fn with_download_progress(mut self, callback: DownloadProgressFn) -> Self {
+        self.source.download_progress = Some(callback);
+        self
+    }
+
+    async fn ensure_loaded(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        if state.is_some() {
+            return Ok(());
+        }
+
+        tracing::info!(model = %self.source.repo_id, "Loading Candle classifier");
+
+        let (config_path, tokenizer_path, weights) = self.source.fetch().await?;
+        let config_contents =
+            std::fs::read_to_string(&config_path).map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let classifier_config: ClassifierConfig = serde_json::from_str(&config_contents)
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+
+        let is_sequence_classifier = classifier_config
+            .architectures
+            .iter()
+            .flatten()
+            .any(|arch| arch == "BertForSequenceClassification");
+        if !is_sequence_classifier {
+            return Err(RuntimeError::Config(format!(
+                "Candle classifier {} must be a BertForSequenceClassification model",
+                self.source.repo_id
+            )));
+        }
+        let entailment_index = classifier_config.entailment_index().ok_or_else(|| {
+            RuntimeError::Config(format!(
+                "Candle classifier {} has no 'entailment' label in id2label; zero-shot classification needs an NLI model",
+                self.source.repo_id
+            ))
+        })?;
+        let bert_config: BertConfig = serde_json::from_str(&config_contents)
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+
+        let tokenizer = load_tokenizer(&tokenizer_path)?;
+        let device = resolve_device(self.device.as_deref());
+        tracing::info!(device = ?device, "Selected Candle device");
+        let vb = load_weights(&weights, self.dtype, &device)?;
+
+        let hidden_size = classifier_config.hidden_size;
+        let num_labels = classifier_config.id2label.len();
+        let bert = BertModel::load(vb.pp("bert"), &bert_config)
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let pooler = candle_nn::linear(hidden_size, hidden_size, vb.pp("bert.pooler.dense"))
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+        let head = candle_nn::linear(hidden_size, num_labels, vb.pp("classifier"))
+            .map_err(|e| RuntimeError::Load(e.to_string()))?;
+
+        tracing::info!(model = %self.source.repo_id, "Candle classifier loaded");
+
+        *state = Some(LoadedClassifier {
+            bert,
+            pooler,
+            head,
+            entailment_index,
+            tokenizer,
+            device,
+        });
+        Ok(())
+    }
+
+    /// Entailment logit of `(text, hypothesis)` for each label.
+    fn entailment_logits(
+        loaded: &LoadedClassifier,
+        text: &str,
+        labels: &[&str],
+    ) -> Result<Vec<f32>> {
+        let pairs: Vec<(&str, String)> = labels
+            .iter()
+            .map(|label| (text, HYPOTHESIS_TEMPLATE.replace("{}", label)))
+            .collect();
+        let encodings = loaded
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(|e| RuntimeError::InferenceError(format!("Tokenization failed: {}", e)))?;
+        let (input_ids, attention_mask, token_type_ids) =
+            TokenBatch::from_encodings(&encodings).tensors(&loaded.device)?;
+
+        let hidden = loaded
+            .bert
+            .forward(&input_ids, &token_type_ids, Some(&attention_mask))
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+        // Classify from the [CLS] token through the BERT pooler.
+        let cls = hidden
+            .narrow(1, 0, 1)
+            .and_then(|t| t.squeeze(1))
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+        let pooled = loaded
+            .pooler
+            .forward(&cls)
+            .and_then(|t| t.tanh())
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+        let logits: Vec<Vec<f32>> = loaded
+            .head
+            .forward(&pooled)
+            .and_then(|t| t.to_dtype(DType::F32))
+            .and_then(|t| t.to_vec2())
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+
+        Ok(logits
+            .iter()
+            .map(|row| row[loaded.entailment_index])
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ClassifierModel for CandleClassifierModel {
+    async fn classify(&self, text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        if labels.is_empty() {
+            return Ok(vec![]);
+        }
+        self.ensure_loaded().await?;
+
+        let state_guard = self.state.lock().await;
+        let loaded = state_guard
+            .as_ref()
+            .ok_or_else(|| RuntimeError::Load("Model state missing".to_string()))?;
+
+        let scores = softmax(&Self::entailment_logits(loaded, text, labels)?);
+        let mut scored: Vec<ScoredLabel> = labels
+            .iter()
+            .zip(scores)
+            .map(|(label, score)| ScoredLabel {
+                label: label.to_string(),
+                score,
+            })
+            .collect();
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(scored)
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.ensure_loaded().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_device_defaults_to_cpu() {
+        assert!(resolve_device(None).is_cpu());
+        assert!(resolve_device(Some("cpu")).is_cpu());
+        assert!(resolve_device(Some("CPU")).is_cpu());
+    }
+
+    #[test]
+    fn resolve_device_falls_back_to_cpu_for_unknown_device() {
+        assert!(resolve_device(Some("tpu")).is_cpu());
+        assert!(resolve_device(Some("cuda:abc")).is_cpu());
+    }
+
+    #[test]
+    fn from_name_accepts_short_names_and_repo_ids() {
+        assert_eq!(
+            CandleTextModel::from_name("e5-small-v2"),
+            Some(CandleTextModel::E5SmallV2)
+        );
+        assert_eq!(
+            CandleTextModel::from_name("thenlper/GTE-base"),
+            Some(CandleTextModel::GteBase)
+        );
+        assert_eq!(CandleTextModel::from_name("org/unknown-model"), None);
+    }
+
+    #[tokio::test]
+    async fn empty_batch_returns_without_loading() {
+        let model = CandleEmbeddingModel::new(
+            CandleTextModel::AllMiniLmL6V2,
+            None,
+            PathBuf::from("/tmp/unused"),
+        );
+        assert!(model.embed(vec![]).await.unwrap().is_empty());
+        assert!(model.state.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn empty_token_batch_returns_without_loading() {
+        let model = CandleEmbeddingModel::new(
+            CandleTextModel::AllMiniLmL6V2,
+            None,
+            PathBuf::from("/tmp/unused"),
+        );
+        assert!(model.embed_tokens(vec![]).await.unwrap().is_empty());
+        assert!(model.state.lock().await.is_none());
+    }
+
+    #[test]
+    fn token_batch_pads_to_longest_sequence() {
+        let batch = TokenBatch::from_ids(&[vec![101, 7, 102], vec![101, 102]], 0).unwrap();
+        assert_eq!(batch.batch_size, 2);
+        assert_eq!(batch.seq_len, 3);
+        assert_eq!(batch.input_ids, vec![101, 7, 102, 101, 102, 0]);
+        assert_eq!(batch.attention_mask, vec![1, 1, 1, 1, 1, 0]);
+        assert_eq!(batch.token_type_ids, vec![0; 6]);
+    }
+
+    #[test]
+    fn token_batch_rejects_empty_and_overlong_sequences() {
+        let err = TokenBatch::from_ids(&[vec![101], vec![]], 0).unwrap_err();
+        assert!(
+            err.to_string().contains("Token sequence 1 is empty"),
+            "{err}"
+        );
+
+        let err = TokenBatch::from_ids(&[vec![1; MAX_SEQ_LEN + 1]], 0).unwrap_err();
+        assert!(err.to_string().contains("at most 512"), "{err}");
+    }
+
+    #[test]
+    fn known_model_reports_dimensions_before_load() {
+        let model = CandleEmbeddingModel::new(
+            CandleTextModel::E5LargeV2,
+            None,
+            PathBuf::from("/tmp/unused"),
+        );
+        assert_eq!(model.dimensions(), 1024);
+        assert_eq!(model.model_id(), "intfloat/e5-large-v2");
+    }
+
+    #[test]
+    fn arbitrary_repo_has_unknown_dimensions_before_load() {
+        let model =
+            CandleEmbeddingModel::from_repo("org/custom-bert", None, PathBuf::from("/tmp/unused"));
+        assert_eq!(model.dimensions(), 0);
+        assert_eq!(model.model_id(), "org/custom-bert");
+    }
+
+    #[test]
+    fn expected_dimensions_reported_before_load() {
+        let model =
+            CandleEmbeddingModel::from_repo("org/custom-bert", None, PathBuf::from("/tmp/unused"))
+                .with_expected_dimensions(512);
+        assert_eq!(model.dimensions(), 512);
+    }
+
+    #[test]
+    fn hf_endpoint_is_used_for_download_urls() {
+        let model =
+            CandleEmbeddingModel::from_repo("org/custom-bert", None, PathBuf::from("/tmp/unused"))
+                .with_hf_endpoint("https://hf-mirror.example");
+        let api = model.source.build_api().unwrap();
+        let url = api.model("org/custom-bert".to_string()).url("config.json");
+        assert!(url.starts_with("https://hf-mirror.example/org/custom-bert/"));
+    }
+
+    #[tokio::test]
+    async fn hub_progress_accumulates_across_clones() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut progress = HubProgress {
+            callback: Arc::new(move |p: &DownloadProgress| {
+                sink.lock().unwrap().push((p.downloaded, p.total))
+            }),
+            repo_id: "org/custom-bert".to_string(),
+            file: "model.safetensors".to_string(),
+            state: Arc::default(),
+        };
+        progress.init(100, "model.safetensors").await;
+        let mut chunk = progress.clone();
+        progress.update(40).await;
+        chunk.update(60).await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![(0, Some(100)), (40, Some(100)), (100, Some(100))]
+        );
+    }
+
+    #[tokio::test]
+    async fn local_model_path_reports_every_missing_file() {
+        let dir = std::env::temp_dir().join(format!("uni-xervo-candle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("config.json"), "{}").unwrap();
+
+        let err = local_model_files(&dir).err().unwrap().to_string();
+        assert!(err.contains("tokenizer.json, model.safetensors"), "{err}");
+        assert!(!err.contains("config.json"), "{err}");
+
+        // Loading goes straight to the directory, never to the hub.
+        let model =
+            CandleEmbeddingModel::from_repo("vendored", None, dir.clone()).with_model_path(&dir);
+        let err = model.warmup().await.unwrap_err().to_string();
+        assert!(err.contains("missing required file(s)"), "{err}");
+
+        std::fs::write(dir.join("tokenizer.json"), "{}").unwrap();
+        std::fs::write(dir.join("model.safetensors"), "").unwrap();
+        let (config, tokenizer, weights) = local_model_files(&dir).unwrap();
+        assert_eq!(config, dir.join("config.json"));
+        assert_eq!(tokenizer, dir.join("tokenizer.json"));
+        assert!(
+            matches!(weights, WeightFiles::Safetensors(paths) if paths == vec![dir.join("model.safetensors")])
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_dtype_accepts_supported_precisions() {
+        assert_eq!(parse_dtype("f32"), Some(DType::F32));
+        assert_eq!(parse_dtype("F16"), Some(DType::F16));
+        assert_eq!(parse_dtype("bf16"), Some(DType::BF16));
+        assert_eq!(parse_dtype("f64"), None);
+    }
+
+    #[test]
+    fn shard_files_from_index_dedupes_and_sorts() {
+        let index = r#"{
+            "metadata": {"total_size": 123},
+            "weight_map": {
+                "encoder.layer.1.weight": "model-00002-of-00002.safetensors",
+                "embeddings.weight": "model-00001-of-00002.safetensors",
+                "encoder.layer.0.weight": "model-00001-of-00002.safetensors"
+            }
+        }"#;
+        assert_eq!(
+            shard_files_from_index(index).unwrap(),
+            vec![
+                "model-00001-of-00002.safetensors".to_string(),
+                "model-00002-of-00002.safetensors".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn shard_files_from_index_rejects_missing_weight_map() {
+        assert!(shard_files_from_index(r#"{"metadata": {}}"#).is_err());
+        assert!(shard_files_from_index(r#"{"weight_map": {}}"#).is_err());
+    }
+
+    #[test]
+    fn entailment_index_reads_id2label() {
+        let config: ClassifierConfig = serde_json::from_str(
+            r#"{
+                "architectures": ["BertForSequenceClassification"],
+                "hidden_size": 768,
+                "id2label": {"0": "contradiction", "1": "neutral", "2": "ENTAILMENT"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.entailment_index(), Some(2));
+
+        let config: ClassifierConfig = serde_json::from_str(
+            r#"{"hidden_size": 768, "id2label": {"0": "negative", "1": "positive"}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.entailment_index(), None);
+    }
+
+    #[test]
+    fn softmax_normalizes_logits() {
+        let scores = softmax(&[2.0, 1.0, 0.0]);
+        assert!((scores.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert!(scores[0] > scores[1] && scores[1] > scores[2]);
+        assert_eq!(softmax(&[1000.0, 1000.0]), vec![0.5, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn classifier_empty_labels_return_without_loading() {
+        let model = CandleClassifierModel::from_repo("org/nli", None, PathBuf::from("/tmp/unused"));
+        assert!(model.classify("text", &[]).await.unwrap().is_empty());
+        assert!(model.state.lock().await.is_none());
+    }
+
+    #[cfg(not(feature = "gpu-cuda"))]
+    #[test]
+    fn resolve_device_falls_back_without_cuda_feature() {
+        assert!(resolve_device(Some("cuda:0")).is_cpu());
+    }
+}
@@ -1,21 +1,97 @@
 use crate::api::{ModelAliasSpec, ModelTask};
+use crate::batching::{SUB_BATCH_CONCURRENCY, embed_in_sub_batches};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::options_validation::merge_embed_overrides;
+use crate::provider::remote_common::{
+    RateLimitState, RemoteProviderBase, RequestIdExt, Truncation, check_http_status, probe_request,
+    resolve_api_key, resolve_base_url, split_embed_batches, verify_credentials,
+};
 use crate::traits::{
-    EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
-    Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth, RerankerModel,
-    ScoredDoc, TokenUsage,
+    ClassifierModel, EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel,
+    LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RateLimitSnapshot, RerankOptions, RerankerModel, ScoredDoc, ScoredLabel, TokenUsage,
+    generate_each,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.cohere.com";
+
+/// Typed options for `remote/cohere` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CohereOptions {
+    /// Environment variable holding the API key (default `CO_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.cohere.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Embedding input type, e.g. `"search_document"` or `"search_query"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+    /// Compact type for `embed_int8`/`embed_binary`: `float`, `int8`, `binary`, or `ubinary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_type: Option<String>,
+    /// How over-long embedding inputs are handled (server default `end`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+}
+
+impl CohereOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`input_type`](Self::input_type).
+    pub fn with_input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.input_type = Some(input_type.into());
+        self
+    }
+
+    /// Set [`embedding_type`](Self::embedding_type).
+    pub fn with_embedding_type(mut self, embedding_type: impl Into<String>) -> Self {
+        self.embedding_type = Some(embedding_type.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+}
+
+impl From<&CohereOptions> for serde_json::Value {
+    fn from(options: &CohereOptions) -> Self {
+        serde_json::to_value(options).expect("CohereOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Cohere API](https://docs.cohere.com/reference/about)
 /// for embedding, text generation (chat), and reranking.
 ///
 /// Requires the `CO_API_KEY` environment variable (or a custom env var name
-/// via the `api_key_env` option).
+/// via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
 pub struct RemoteCohereProvider {
     base: RemoteProviderBase,
 }
@@ -33,6 +109,22 @@ impl RemoteCohereProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .get(format!("{DEFAULT_BASE_URL}/v1/models?page_size=1"))
+            .header("Authorization", format!("Bearer {}", api_key))
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -57,29 +149,50 @@ impl ModelProvider for RemoteCohereProvider {
 
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
-            supported_tasks: vec![ModelTask::Embed, ModelTask::Generate, ModelTask::Rerank],
+            supported_tasks: vec![
+                ModelTask::Embed,
+                ModelTask::Generate,
+                ModelTask::Rerank,
+                ModelTask::Classify,
+            ],
         }
     }
 
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "CO_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         let input_type = spec
             .options
             .get("input_type")
             .and_then(|v| v.as_str())
-            .unwrap_or("search_document")
+            .unwrap_or(DEFAULT_INPUT_TYPE)
             .to_string();
 
         match spec.task {
             ModelTask::Embed => {
+                let embedding_type = CohereEmbeddingType::from_option(
+                    spec.options.get("embedding_type").and_then(|v| v.as_str()),
+                );
+                if embedding_type != CohereEmbeddingType::Float
+                    && COHERE_FLOAT_ONLY_MODELS.contains(&spec.model_id.as_str())
+                {
+                    return Err(RuntimeError::Config(format!(
+                        "Cohere model '{}' only supports embedding_type 'float'",
+                        spec.model_id
+                    )));
+                }
                 let model = CohereEmbeddingModel {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                     input_type,
+                    embedding_type,
+                    truncation: Truncation::from_options(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -88,7 +201,9 @@ impl ModelProvider for RemoteCohereProvider {
                 let model = CohereGeneratorModel {
                     client: self.base.client.clone(),
                     cb,
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
@@ -98,79 +213,362 @@ impl ModelProvider for RemoteCohereProvider {
                 let model = CohereRerankerModel {
                     client: self.base.client.clone(),
                     cb,
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn RerankerModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
             }
+            ModelTask::Classify => {
+                let model = CohereClassifierModel {
+                    client: self.base.client.clone(),
+                    cb,
+                    rate_limits: RateLimitState::default(),
+                    model_id: spec.model_id.clone(),
+                    base_url,
+                    api_key,
+                };
+                let handle: Arc<dyn ClassifierModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
         }
     }
 
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Lists models via `GET /v1/models`, which is free.
+    /// Uses the default `CO_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key =
+                    match resolve_api_key(&serde_json::Value::Null, "api_key_env", "CO_API_KEY") {
+                        Ok(key) => key,
+                        Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                    };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `CO_API_KEY`, when set, with the health probe request so eager
+    /// warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/cohere", "CO_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/cohere.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "base_url",
+            "embedding_type",
+            "input_type",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
+}
+
+/// `input_type` sent when the alias does not set one.
+const DEFAULT_INPUT_TYPE: &str = "search_document";
+
+/// Most texts Cohere's `/v2/embed` accepts in one request.
+const COHERE_MAX_TEXTS: usize = 96;
+
+/// Models that only return `float` embeddings. Cohere's v3 and later embed
+/// models also return `int8`, `uint8`, `binary`, and `ubinary`.
+const COHERE_FLOAT_ONLY_MODELS: &[&str] = &[
+    "embed-english-v2.0",
+    "embed-english-light-v2.0",
+    "embed-multilingual-v2.0",
+];
+
+/// Representation requested from `/v2/embed`, set by the `embedding_type`
+/// option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum CohereEmbeddingType {
+    #[default]
+    Float,
+    Int8,
+    Binary,
+    Ubinary,
+}
+
+impl CohereEmbeddingType {
+    /// Parse a validated `embedding_type` option value.
+    fn from_option(value: Option<&str>) -> Self {
+        match value {
+            Some("int8") => Self::Int8,
+            Some("binary") => Self::Binary,
+            Some("ubinary") => Self::Ubinary,
+            _ => Self::Float,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Float => "float",
+            Self::Int8 => "int8",
+            Self::Binary => "binary",
+            Self::Ubinary => "ubinary",
+        }
+    }
 }
 
 struct CohereEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.cohere.com`.
+    base_url: String,
     api_key: String,
     input_type: String,
+    /// Type returned by `embed_int8` or `embed_binary`; `embed` always
+    /// requests `float`.
+    embedding_type: CohereEmbeddingType,
+    /// Sent as `truncate`; the server default (`END`) applies when unset.
+    truncation: Option<Truncation>,
 }
 
-#[async_trait]
-impl EmbeddingModel for CohereEmbeddingModel {
-    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+/// Build the JSON body for Cohere's `/v2/embed` endpoint.
+fn build_cohere_embed_payload(
+    model_id: &str,
+    texts: &[String],
+    input_type: &str,
+    embedding_type: CohereEmbeddingType,
+    truncation: Option<Truncation>,
+) -> serde_json::Value {
+    let mut body = json!({
+        "texts": texts,
+        "model": model_id,
+        "input_type": input_type,
+        "embedding_types": [embedding_type.as_str()]
+    });
+    if let Some(truncation) = truncation {
+        body["truncate"] = json!(truncation.as_upper());
+    }
+    body
+}
+
+/// Extract the `embeddings.<type>` vectors from a `/v2/embed` response,
+/// converting each component with `component`. Components it rejects are
+/// skipped.
+fn parse_cohere_embeddings<T>(
+    body: &serde_json::Value,
+    embedding_type: CohereEmbeddingType,
+    component: fn(&serde_json::Value) -> Option<T>,
+) -> Result<Vec<Vec<T>>> {
+    let embeddings = body
+        .get("embeddings")
+        .and_then(|e| e.get(embedding_type.as_str()))
+        .and_then(|f| f.as_array())
+        .ok_or_else(|| {
+            RuntimeError::api_error("remote/cohere", "Invalid Cohere embedding response format")
+        })?;
+    Ok(embeddings
+        .iter()
+        .filter_map(|embedding| embedding.as_array())
+        .map(|values| values.iter().filter_map(component).collect())
+        .collect())
+}
+
+impl CohereEmbeddingModel {
+    /// Embed `texts`, splitting into requests of at most
+    /// [`COHERE_MAX_TEXTS`] sent concurrently, and summing the billed usage
+    /// across them. Vectors come back in input order.
+    async fn embed_request<T: Send>(
+        &self,
+        texts: Vec<&str>,
+        input_type: &str,
+        embedding_type: CohereEmbeddingType,
+        component: fn(&serde_json::Value) -> Option<T>,
+    ) -> Result<(Vec<Vec<T>>, Option<TokenUsage>)> {
+        embed_in_sub_batches(
+            split_embed_batches(&texts, COHERE_MAX_TEXTS, None),
+            SUB_BATCH_CONCURRENCY,
+            |batch| async move {
+                self.embed_batch(&batch, input_type, embedding_type, component)
+                    .await
+            },
+        )
+        .await
+    }
+
+    async fn embed_batch<T: Send>(
+        &self,
+        texts: &[&str],
+        input_type: &str,
+        embedding_type: CohereEmbeddingType,
+        component: fn(&serde_json::Value) -> Option<T>,
+    ) -> Result<(Vec<Vec<T>>, Option<TokenUsage>)> {
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
 
         self.cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.cohere.com/v2/embed")
+                    .post(format!("{}/v2/embed", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
-                    .json(&json!({
-                        "texts": texts,
-                        "model": self.model_id,
-                        "input_type": self.input_type,
-                        "embedding_types": ["float"]
-                    }))
+                    .json(&build_cohere_embed_payload(
+                        &self.model_id,
+                        &texts,
+                        input_type,
+                        embedding_type,
+                        self.truncation,
+                    ))
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
 
-                let body: serde_json::Value = check_http_status("Cohere", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/cohere", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let float_embeddings = body
-                    .get("embeddings")
-                    .and_then(|e| e.get("float"))
-                    .and_then(|f| f.as_array())
-                    .ok_or_else(|| {
-                        RuntimeError::ApiError(
-                            "Invalid Cohere embedding response format".to_string(),
-                        )
-                    })?;
-
-                let mut result = Vec::new();
-                for embedding in float_embeddings {
-                    if let Some(values) = embedding.as_array() {
-                        let vec: Vec<f32> = values
-                            .iter()
-                            .filter_map(|v| v.as_f64().map(|f| f as f32))
-                            .collect();
-                        result.push(vec);
-                    }
-                }
-                Ok(result)
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
+
+                let result = parse_cohere_embeddings(&body, embedding_type, component)?;
+
+                let usage = body
+                    .pointer("/meta/billed_units/input_tokens")
+                    .and_then(|t| t.as_u64())
+                    .map(|t| TokenUsage {
+                        prompt_tokens: t as usize,
+                        completion_tokens: 0,
+                        total_tokens: t as usize,
+                        ..Default::default()
+                    });
+
+                Ok((result, usage))
             })
             .await
     }
 
+    /// Fail unless the alias's `embedding_type` is one of `accepted`.
+    fn require_embedding_type(&self, accepted: &[CohereEmbeddingType]) -> Result<()> {
+        if accepted.contains(&self.embedding_type) {
+            return Ok(());
+        }
+        let names: Vec<&str> = accepted.iter().map(|t| t.as_str()).collect();
+        Err(RuntimeError::CapabilityMismatch(format!(
+            "Cohere model '{}' is configured with embedding_type '{}'; set it to {} for this call",
+            self.model_id,
+            self.embedding_type.as_str(),
+            names.join(" or ")
+        )))
+    }
+}
+
+/// Component converters for each [`CohereEmbeddingType`].
+fn float_component(v: &serde_json::Value) -> Option<f32> {
+    v.as_f64().map(|f| f as f32)
+}
+
+fn int8_component(v: &serde_json::Value) -> Option<i8> {
+    v.as_i64().and_then(|i| i8::try_from(i).ok())
+}
+
+/// `binary` packs bits into signed bytes; reinterpret them as unsigned.
+fn binary_component(v: &serde_json::Value) -> Option<u8> {
+    int8_component(v).map(|i| i as u8)
+}
+
+fn ubinary_component(v: &serde_json::Value) -> Option<u8> {
+    v.as_u64().and_then(|i| u8::try_from(i).ok())
+}
+
+#[async_trait]
+impl EmbeddingModel for CohereEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        self.embed_request(
+            texts,
+            &self.input_type,
+            CohereEmbeddingType::Float,
+            float_component,
+        )
+        .await
+    }
+
+    /// Accepts an `input_type` override, e.g. `search_query` for queries
+    /// against an alias configured for `search_document`.
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        let merged = merge_embed_overrides(
+            "remote/cohere",
+            &json!({ "input_type": self.input_type }),
+            &overrides,
+        )?;
+        let input_type = merged
+            .get("input_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_INPUT_TYPE);
+        self.embed_request(
+            texts,
+            input_type,
+            CohereEmbeddingType::Float,
+            float_component,
+        )
+        .await
+        .map(|(embeddings, _)| embeddings)
+    }
+
+    /// Requires `embedding_type: "int8"`.
+    async fn embed_int8(&self, texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+        self.require_embedding_type(&[CohereEmbeddingType::Int8])?;
+        self.embed_request(
+            texts,
+            &self.input_type,
+            CohereEmbeddingType::Int8,
+            int8_component,
+        )
+        .await
+        .map(|(embeddings, _)| embeddings)
+    }
+
+    /// Requires `embedding_type: "binary"` or `"ubinary"`. Both carry the
+    /// same bits; Cohere only differs in whether bytes are signed.
+    async fn embed_binary(&self, texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+        self.require_embedding_type(&[CohereEmbeddingType::Binary, CohereEmbeddingType::Ubinary])?;
+        let component = match self.embedding_type {
+            CohereEmbeddingType::Binary => binary_component,
+            _ => ubinary_component,
+        };
+        self.embed_request(texts, &self.input_type, self.embedding_type, component)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
     fn dimensions(&self) -> u32 {
         match self.model_id.as_str() {
             "embed-english-light-v3.0" | "embed-multilingual-light-v3.0" => 384,
@@ -181,12 +579,19 @@ impl EmbeddingModel for CohereEmbeddingModel {
     fn model_id(&self) -> &str {
         &self.model_id
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 struct CohereGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.cohere.com`.
+    base_url: String,
     api_key: String,
 }
 
@@ -197,6 +602,9 @@ impl GeneratorModel for CohereGeneratorModel {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         let messages: Vec<serde_json::Value> = messages
             .iter()
             .map(|msg| {
@@ -225,20 +633,26 @@ impl GeneratorModel for CohereGeneratorModel {
                 if let Some(top_p) = options.top_p {
                     body["p"] = json!(top_p);
                 }
+                if let Some(stop) = &options.stop {
+                    body["stop_sequences"] = json!(stop);
+                }
 
                 let response = self
                     .client
-                    .post("https://api.cohere.com/v2/chat")
+                    .post(format!("{}/v2/chat", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
                     .json(&body)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
 
-                let body: serde_json::Value = check_http_status("Cohere", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/cohere", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
 
                 let text = body
                     .get("message")
@@ -265,58 +679,115 @@ impl GeneratorModel for CohereGeneratorModel {
                         prompt_tokens: input as usize,
                         completion_tokens: output as usize,
                         total_tokens: (input + output) as usize,
+                        ..Default::default()
                     }
                 });
 
+                let finish_reason = body
+                    .get("finish_reason")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string);
+
                 Ok(GenerationResult {
                     text,
                     usage,
                     images: vec![],
                     audio: None,
+                    alternatives: vec![],
+                    finish_reason,
+                    raw: options.include_raw.then_some(body),
+                    logprobs: None,
+                    reasoning: None,
                 })
             })
             .await
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 struct CohereRerankerModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.cohere.com`.
+    base_url: String,
     api_key: String,
 }
 
+/// Build the Cohere v2 rerank request body. The v2 API has no
+/// `return_documents` flag, so document text is filled in locally.
+fn build_cohere_rerank_payload(
+    model_id: &str,
+    query: &str,
+    docs: &[String],
+    options: &RerankOptions,
+) -> serde_json::Value {
+    let mut body = json!({
+        "query": query,
+        "documents": docs,
+        "model": model_id,
+    });
+    if let Some(top_n) = options.top_n {
+        body["top_n"] = json!(top_n);
+    }
+    body
+}
+
 #[async_trait]
 impl RerankerModel for CohereRerankerModel {
     async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
-        let query = query.to_string();
-        let docs: Vec<String> = docs.iter().map(|s| s.to_string()).collect();
+        self.rerank_with_options(query, docs, &RerankOptions::default())
+            .await
+    }
 
-        self.cb
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = build_cohere_rerank_payload(
+            &self.model_id,
+            query,
+            &docs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            options,
+        );
+
+        let results = self
+            .cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.cohere.com/v2/rerank")
+                    .post(format!("{}/v2/rerank", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
-                    .json(&json!({
-                        "query": query,
-                        "documents": docs,
-                        "model": self.model_id,
-                    }))
+                    .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
 
-                let body: serde_json::Value = check_http_status("Cohere", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/cohere", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
 
                 let results_json =
                     body.get("results")
                         .and_then(|r| r.as_array())
                         .ok_or_else(|| {
-                            RuntimeError::ApiError("Invalid rerank response format".to_string())
+                            RuntimeError::api_error(
+                                "remote/cohere",
+                                "Invalid rerank response format",
+                            )
                         })?;
 
                 let mut results = Vec::new();
@@ -334,8 +805,105 @@ impl RerankerModel for CohereRerankerModel {
                 }
                 Ok(results)
             })
+            .await?;
+        Ok(options.apply(results, docs))
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
+}
+
+/// Classifies through `POST /v1/classify`. The model must be a fine-tuned
+/// Cohere classifier; its response scores every label it was trained on, and
+/// the requested labels are picked from those.
+struct CohereClassifierModel {
+    client: Client,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
+    model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.cohere.com`.
+    base_url: String,
+    api_key: String,
+}
+
+/// Pick the confidences of `labels` out of a classify response, sorted by
+/// descending score. A label the model does not know is a config error.
+fn parse_cohere_classification(
+    model_id: &str,
+    body: &serde_json::Value,
+    labels: &[&str],
+) -> Result<Vec<ScoredLabel>> {
+    let scores = body
+        .get("classifications")
+        .and_then(|c| c.get(0))
+        .and_then(|c| c.get("labels"))
+        .and_then(|l| l.as_object())
+        .ok_or_else(|| {
+            RuntimeError::api_error("remote/cohere", "Invalid classify response format")
+        })?;
+
+    let mut results = labels
+        .iter()
+        .map(|&label| {
+            let score = scores
+                .get(label)
+                .and_then(|s| s.get("confidence"))
+                .and_then(|c| c.as_f64())
+                .ok_or_else(|| {
+                    RuntimeError::Config(format!(
+                        "Cohere model '{}' has no label '{}'",
+                        model_id, label
+                    ))
+                })?;
+            Ok(ScoredLabel {
+                label: label.to_string(),
+                score: score as f32,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(results)
+}
+
+#[async_trait]
+impl ClassifierModel for CohereClassifierModel {
+    async fn classify(&self, text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        if labels.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = json!({
+            "model": self.model_id,
+            "inputs": [text],
+        });
+
+        self.cb
+            .call(move || async move {
+                let response = self
+                    .client
+                    .post(format!("{}/v1/classify", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
+
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/cohere", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/cohere", e))?;
+
+                parse_cohere_classification(&self.model_id, &body, labels)
+            })
             .await
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 #[cfg(test)]
@@ -359,6 +927,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -381,6 +950,88 @@ mod tests {
         unsafe { std::env::remove_var("CO_API_KEY") };
     }
 
+    /// Serve `/v2/embed` on a local port, embedding each text (a decimal
+    /// number) as `[number]`. Each request is answered after a delay picked
+    /// by its first text, so sub-batches finish out of order.
+    async fn scrambled_embed_server() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 8192];
+                    let body = loop {
+                        let n = socket.read(&mut buf).await.unwrap();
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request);
+                        let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                            continue;
+                        };
+                        let length: usize = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse().unwrap())
+                            })
+                            .unwrap();
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    };
+                    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+                    let numbers: Vec<u64> = body["texts"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|t| t.as_str().unwrap().parse().unwrap())
+                        .collect();
+                    // Delays for sub-batches 0, 1, 2, ... are 40, 10, 70, 0, 20 ms.
+                    let delay = [40, 10, 70, 0, 20][(numbers[0] as usize / COHERE_MAX_TEXTS) % 5];
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    let response = json!({
+                        "embeddings": { "float": numbers.iter().map(|&n| vec![n]).collect::<Vec<_>>() },
+                        "meta": { "billed_units": { "input_tokens": numbers.len() } }
+                    })
+                    .to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        response.len(),
+                        response
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn embed_keeps_input_order_when_sub_batches_finish_out_of_order() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("CO_API_KEY", "test-key") };
+
+        let provider = RemoteCohereProvider::new();
+        let mut embed = spec("embed/a", ModelTask::Embed, "embed-english-v3.0");
+        embed.options = json!({ "base_url": scrambled_embed_server().await });
+        let handle = provider.load(&embed).await.unwrap();
+        let model = handle.downcast_ref::<Arc<dyn EmbeddingModel>>().unwrap();
+
+        let count = 4 * COHERE_MAX_TEXTS + 10;
+        let owned: Vec<String> = (0..count).map(|i| i.to_string()).collect();
+        let texts: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let (vectors, usage) = model.embed_with_usage(texts).await.unwrap();
+
+        let expected: Vec<Vec<f32>> = (0..count).map(|i| vec![i as f32]).collect();
+        assert_eq!(vectors, expected);
+        assert_eq!(usage.unwrap().prompt_tokens, count);
+
+        unsafe { std::env::remove_var("CO_API_KEY") };
+    }
+
     #[tokio::test]
     async fn breaker_isolated_by_task_and_model() {
         let _lock = ENV_LOCK.lock().await;
@@ -441,4 +1092,179 @@ mod tests {
 
         unsafe { std::env::remove_var("CO_API_KEY") };
     }
+
+    #[test]
+    fn classification_picks_requested_labels_by_score() {
+        let body = json!({
+            "classifications": [{
+                "prediction": "spam",
+                "labels": {
+                    "spam": {"confidence": 0.8},
+                    "ham": {"confidence": 0.15},
+                    "other": {"confidence": 0.05}
+                }
+            }]
+        });
+        let scored = parse_cohere_classification("clf", &body, &["ham", "spam"]).unwrap();
+        assert_eq!(
+            scored,
+            vec![
+                ScoredLabel {
+                    label: "spam".to_string(),
+                    score: 0.8
+                },
+                ScoredLabel {
+                    label: "ham".to_string(),
+                    score: 0.15
+                },
+            ]
+        );
+
+        let err = parse_cohere_classification("clf", &body, &["eggs"]).unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        assert!(err.to_string().contains("no label 'eggs'"));
+    }
+
+    #[test]
+    fn rerank_payload_maps_top_n() {
+        let docs = vec!["a".to_string(), "b".to_string()];
+        let payload = build_cohere_rerank_payload(
+            "rerank-english-v3.0",
+            "q",
+            &docs,
+            &RerankOptions {
+                top_n: Some(1),
+                return_documents: true,
+                sort: true,
+            },
+        );
+        assert_eq!(payload["top_n"], 1);
+        assert!(payload.get("return_documents").is_none());
+
+        let payload =
+            build_cohere_rerank_payload("rerank-english-v3.0", "q", &docs, &Default::default());
+        assert!(payload.get("top_n").is_none());
+    }
+
+    fn embedding_model(input_type: &str) -> CohereEmbeddingModel {
+        CohereEmbeddingModel {
+            client: Client::new(),
+            cb: crate::reliability::CircuitBreakerWrapper::new(Default::default()),
+            rate_limits: RateLimitState::default(),
+            model_id: "embed-english-v3.0".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: "test-key".to_string(),
+            input_type: input_type.to_string(),
+            embedding_type: CohereEmbeddingType::Float,
+            truncation: None,
+        }
+    }
+
+    #[test]
+    fn embed_override_replaces_input_type() {
+        let model = embedding_model("search_document");
+        let merged = merge_embed_overrides(
+            "remote/cohere",
+            &json!({ "input_type": model.input_type }),
+            &json!({ "input_type": "search_query" }),
+        )
+        .unwrap();
+        let payload = build_cohere_embed_payload(
+            &model.model_id,
+            &["q".to_string()],
+            merged["input_type"].as_str().unwrap(),
+            CohereEmbeddingType::Float,
+            model.truncation,
+        );
+        assert_eq!(payload["input_type"], "search_query");
+        assert!(payload.get("truncate").is_none());
+    }
+
+    #[tokio::test]
+    async fn embed_with_rejects_non_overridable_options() {
+        let model = embedding_model("search_document");
+        let err = model
+            .embed_with(vec!["q"], json!({ "api_key_env": "OTHER" }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        assert!(err.to_string().contains("cannot be overridden"));
+
+        let err = model
+            .embed_with(vec!["q"], json!({ "input_type": 5 }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+    }
+
+    #[test]
+    fn embed_payload_requests_configured_type() {
+        let payload = build_cohere_embed_payload(
+            "embed-english-v3.0",
+            &["q".to_string()],
+            "search_query",
+            CohereEmbeddingType::Int8,
+            Some(Truncation::Start),
+        );
+        assert_eq!(payload["embedding_types"], json!(["int8"]));
+        assert_eq!(payload["truncate"], "START");
+    }
+
+    #[test]
+    fn typed_embeddings_parsed_without_widening() {
+        let body = json!({
+            "embeddings": {
+                "int8": [[-128, 0, 127]],
+                "binary": [[-1, 5]],
+                "ubinary": [[255, 5]]
+            }
+        });
+        let int8 =
+            parse_cohere_embeddings(&body, CohereEmbeddingType::Int8, int8_component).unwrap();
+        assert_eq!(int8, vec![vec![-128i8, 0, 127]]);
+        let binary =
+            parse_cohere_embeddings(&body, CohereEmbeddingType::Binary, binary_component).unwrap();
+        let ubinary =
+            parse_cohere_embeddings(&body, CohereEmbeddingType::Ubinary, ubinary_component)
+                .unwrap();
+        assert_eq!(binary, vec![vec![255u8, 5]]);
+        assert_eq!(binary, ubinary);
+
+        let err = parse_cohere_embeddings(&body, CohereEmbeddingType::Float, float_component)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid Cohere embedding response")
+        );
+    }
+
+    #[tokio::test]
+    async fn typed_embedding_calls_require_matching_embedding_type() {
+        let model = embedding_model("search_document");
+        let err = model.embed_int8(vec!["q"]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::CapabilityMismatch(_)));
+        let err = model.embed_binary(vec!["q"]).await.unwrap_err();
+        assert!(err.to_string().contains("binary or ubinary"));
+    }
+
+    #[tokio::test]
+    async fn load_rejects_compact_types_for_float_only_models() {
+        // SAFETY: only this test reads this variable.
+        unsafe { std::env::set_var("UNI_XERVO_COHERE_TYPE_TEST_KEY", "test-key") };
+        let provider = RemoteCohereProvider::new();
+
+        let mut embed = spec("embed/a", ModelTask::Embed, "embed-english-v2.0");
+        embed.options = json!({
+            "api_key_env": "UNI_XERVO_COHERE_TYPE_TEST_KEY",
+            "embedding_type": "int8"
+        });
+        let err = provider.load(&embed).await.err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("only supports embedding_type 'float'")
+        );
+
+        embed.model_id = "embed-english-v3.0".to_string();
+        assert!(provider.load(&embed).await.is_ok());
+    }
 }
@@ -2,29 +2,159 @@ use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
 use crate::traits::{
     EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RerankerModel, ScoredDoc,
 };
 use anyhow::anyhow;
 use async_trait::async_trait;
-use fastembed::{InitOptions, TextEmbedding};
+use fastembed::{
+    InitOptions, InitOptionsUserDefined, Pooling, RerankInitOptions, TextEmbedding, TextRerank,
+    TokenizerFiles, UserDefinedEmbeddingModel,
+};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use tokio::sync::oneshot;
 
-/// Local embedding provider using [FastEmbed](https://github.com/Anush008/fastembed-rs)
-/// (ONNX Runtime).
+/// Typed options for `local/fastembed` aliases.
 ///
-/// Supports a wide range of embedding models. Inference is offloaded to a
-/// dedicated thread with an enlarged stack to accommodate ONNX Runtime's
-/// requirements.
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FastEmbedOptions {
+    /// Per-model cache directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
+    /// Directory with a user-supplied ONNX model; `model_id` becomes a label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    /// Embedding dimension of the model at `model_path`; required with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    /// Pooling for the model at `model_path`: `mean` (default) or `cls`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pooling: Option<String>,
+}
+
+impl FastEmbedOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`cache_dir`](Self::cache_dir).
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<String>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Set [`model_path`](Self::model_path).
+    pub fn with_model_path(mut self, model_path: impl Into<String>) -> Self {
+        self.model_path = Some(model_path.into());
+        self
+    }
+
+    /// Set [`dimensions`](Self::dimensions).
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Set [`pooling`](Self::pooling).
+    pub fn with_pooling(mut self, pooling: impl Into<String>) -> Self {
+        self.pooling = Some(pooling.into());
+        self
+    }
+}
+
+impl From<&FastEmbedOptions> for serde_json::Value {
+    fn from(options: &FastEmbedOptions) -> Self {
+        serde_json::to_value(options).expect("FastEmbedOptions serializes to a JSON object")
+    }
+}
+
+/// Local embedding and reranking provider using
+/// [FastEmbed](https://github.com/Anush008/fastembed-rs) (ONNX Runtime).
+///
+/// Supports a wide range of embedding models and cross-encoder rerankers.
+/// Inference is offloaded to a dedicated thread with an enlarged stack to
+/// accommodate ONNX Runtime's requirements.
+///
+/// Rerank aliases take a model name from
+/// [`supported_rerank_models`](Self::supported_rerank_models).
+/// Models are selected by name (see [`supported_models`](Self::supported_models)).
+/// Alternatively, set the `model_path` option to a directory containing a
+/// user-supplied ONNX export (`model.onnx`, `tokenizer.json`, `config.json`,
+/// `special_tokens_map.json`, `tokenizer_config.json`); the `dimensions`
+/// option is then required and `model_id` is used only as a label.
 pub struct LocalFastEmbedProvider;
 
 impl LocalFastEmbedProvider {
     pub fn new() -> Self {
         Self
     }
+
+    /// Model names accepted as `model_id` when `model_path` is not set.
+    pub fn supported_models() -> &'static [&'static str] {
+        SUPPORTED_MODELS
+    }
+
+    /// Model names accepted as `model_id` for rerank aliases.
+    pub fn supported_rerank_models() -> &'static [&'static str] {
+        SUPPORTED_RERANK_MODELS
+    }
 }
 
+/// Every name recognised by [`FastEmbedService::new`], including aliases.
+const SUPPORTED_MODELS: &[&str] = &[
+    "AllMiniLML6V2",
+    "all-MiniLM-L6-v2",
+    "AllMiniLML6V2Q",
+    "AllMiniLML12V2",
+    "AllMiniLML12V2Q",
+    "AllMpnetBaseV2",
+    "all-mpnet-base-v2",
+    "BGEBaseENV15",
+    "bge-base-en-v1.5",
+    "BGEBaseENV15Q",
+    "BGELargeENV15",
+    "bge-large-en-v1.5",
+    "BGELargeENV15Q",
+    "BGESmallENV15",
+    "bge-small-en-v1.5",
+    "BGESmallENV15Q",
+    "NomicEmbedTextV1",
+    "NomicEmbedTextV15",
+    "nomic-embed-text-v1.5",
+    "NomicEmbedTextV15Q",
+    "ParaphraseMLMiniLML12V2",
+    "ParaphraseMLMiniLML12V2Q",
+    "ParaphraseMLMpnetBaseV2",
+    "BGESmallZHV15",
+    "BGELargeZHV15",
+    "BGEM3",
+    "ModernBertEmbedLarge",
+    "MultilingualE5Small",
+    "multilingual-e5-small",
+    "MultilingualE5Base",
+    "multilingual-e5-base",
+    "MultilingualE5Large",
+    "multilingual-e5-large",
+    "MxbaiEmbedLargeV1",
+    "mxbai-embed-large-v1",
+];
+
+/// Every name recognised by [`FastEmbedReranker::new`], including aliases.
+const SUPPORTED_RERANK_MODELS: &[&str] = &[
+    "BGERerankerBase",
+    "bge-reranker-base",
+    "BGERerankerV2M3",
+    "bge-reranker-v2-m3",
+    "JINARerankerV1TurboEn",
+    "jina-reranker-v1-turbo-en",
+    "JINARerankerV2BaseMultilingual",
+    "jina-reranker-v2-base-multilingual",
+];
+
 impl Default for LocalFastEmbedProvider {
     fn default() -> Self {
         Self::new()
@@ -39,28 +169,71 @@ impl ModelProvider for LocalFastEmbedProvider {
 
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
-            supported_tasks: vec![ModelTask::Embed],
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Rerank],
         }
     }
 
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
-        if spec.task != ModelTask::Embed {
-            return Err(RuntimeError::CapabilityMismatch(format!(
-                "FastEmbed provider does not support task {:?}",
-                spec.task
-            )));
+        match spec.task {
+            ModelTask::Embed => {}
+            ModelTask::Rerank => {
+                let model_name = spec.model_id.clone();
+                let cache_dir =
+                    crate::cache::resolve_cache_dir("fastembed", &model_name, &spec.options);
+                let reranker = tokio::task::spawn_blocking(move || {
+                    FastEmbedReranker::new(&model_name, &cache_dir)
+                })
+                .await
+                .map_err(|e| RuntimeError::Load(format!("Join error: {}", e)))?
+                .map_err(|e| RuntimeError::Load(e.to_string()))?;
+                let handle: Arc<dyn RerankerModel> = Arc::new(reranker);
+                return Ok(Arc::new(handle) as LoadedModelHandle);
+            }
+            _ => {
+                return Err(RuntimeError::CapabilityMismatch(format!(
+                    "FastEmbed provider does not support task {:?}",
+                    spec.task
+                )));
+            }
         }
 
         let model_name = spec.model_id.clone();
-        let cache_dir = crate::cache::resolve_cache_dir("fastembed", &model_name, &spec.options);
 
-        // Offload initialization to a blocking thread because it can refer to onnxruntime which might be heavy
-        // fastembed init might block.
         let service =
-            tokio::task::spawn_blocking(move || FastEmbedService::new(&model_name, &cache_dir))
+            if let Some(model_path) = spec.options.get("model_path").and_then(|v| v.as_str()) {
+                let model_path = std::path::PathBuf::from(model_path);
+                let dimensions = spec
+                    .options
+                    .get("dimensions")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| {
+                        RuntimeError::Config(
+                            "FastEmbed option 'dimensions' is required when 'model_path' is set"
+                                .to_string(),
+                        )
+                    })? as u32;
+                let pooling = match spec.options.get("pooling").and_then(|v| v.as_str()) {
+                    Some("cls") => Pooling::Cls,
+                    _ => Pooling::Mean,
+                };
+
+                tokio::task::spawn_blocking(move || {
+                    FastEmbedService::from_dir(&model_name, &model_path, dimensions, pooling)
+                })
                 .await
                 .map_err(|e| RuntimeError::Load(format!("Join error: {}", e)))?
-                .map_err(|e| RuntimeError::Load(e.to_string()))?;
+                .map_err(|e| RuntimeError::Load(e.to_string()))?
+            } else {
+                let cache_dir =
+                    crate::cache::resolve_cache_dir("fastembed", &model_name, &spec.options);
+
+                // Offload initialization to a blocking thread because it can refer to onnxruntime which might be heavy
+                // fastembed init might block.
+                tokio::task::spawn_blocking(move || FastEmbedService::new(&model_name, &cache_dir))
+                    .await
+                    .map_err(|e| RuntimeError::Load(format!("Join error: {}", e)))?
+                    .map_err(|e| RuntimeError::Load(e.to_string()))?
+            };
 
         let handle: Arc<dyn EmbeddingModel> = Arc::new(service);
         Ok(Arc::new(handle) as LoadedModelHandle)
@@ -69,11 +242,49 @@ impl ModelProvider for LocalFastEmbedProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/fastembed.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&["cache_dir", "dimensions", "model_path", "pooling"])
+    }
 }
 
 /// Stack size for embedding threads.
 const EMBEDDING_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
 
+/// Run `work` on a short-lived worker thread with an enlarged stack and await
+/// its result. `kind` names the work in error messages.
+async fn run_on_worker<T, F>(kind: &str, work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+
+    // Spawn a dedicated thread with larger stack for ONNX Runtime
+    thread::Builder::new()
+        .name("fastembed-worker".to_string())
+        .stack_size(EMBEDDING_THREAD_STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(work());
+        })
+        .map_err(|e| {
+            RuntimeError::InferenceError(format!("Failed to spawn {} thread: {}", kind, e))
+        })?;
+
+    let result = rx
+        .await
+        .map_err(|_| RuntimeError::InferenceError(format!("FastEmbed {} thread panicked", kind)))?;
+
+    result.map_err(|e| RuntimeError::InferenceError(e.to_string()))
+}
+
 /// Wrapper around a [`TextEmbedding`] instance that implements
 /// [`EmbeddingModel`].
 ///
@@ -185,40 +396,69 @@ impl FastEmbedService {
             dimensions,
         })
     }
+
+    /// Load a user-supplied ONNX model from `model_dir`.
+    ///
+    /// FastEmbed cannot infer the output size of an arbitrary export, so
+    /// `dimensions` must be supplied by the caller.
+    pub fn from_dir(
+        model_name: &str,
+        model_dir: &Path,
+        dimensions: u32,
+        pooling: Pooling,
+    ) -> anyhow::Result<Self> {
+        let read = |file: &str| {
+            let path = model_dir.join(file);
+            std::fs::read(&path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))
+        };
+
+        let tokenizer_files = TokenizerFiles {
+            tokenizer_file: read("tokenizer.json")?,
+            config_file: read("config.json")?,
+            special_tokens_map_file: read("special_tokens_map.json")?,
+            tokenizer_config_file: read("tokenizer_config.json")?,
+        };
+        let user_model = UserDefinedEmbeddingModel::new(read("model.onnx")?, tokenizer_files)
+            .with_pooling(pooling);
+
+        let model =
+            TextEmbedding::try_new_from_user_defined(user_model, InitOptionsUserDefined::default())
+                .map_err(|e| {
+                    anyhow!(
+                        "Failed to initialize FastEmbed model from {}: {}",
+                        model_dir.display(),
+                        e
+                    )
+                })?;
+
+        Ok(Self {
+            model: Arc::new(Mutex::new(model)),
+            model_name: model_name.to_string(),
+            dimensions,
+        })
+    }
 }
 
 #[async_trait]
 impl EmbeddingModel for FastEmbedService {
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
         let texts_vec: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
         let model = self.model.clone();
 
-        let (tx, rx) = oneshot::channel();
-
-        // Spawn a dedicated thread with larger stack for ONNX Runtime
-        thread::Builder::new()
-            .name("fastembed-worker".to_string())
-            .stack_size(EMBEDDING_THREAD_STACK_SIZE)
-            .spawn(move || {
-                let result = model
-                    .lock()
-                    .map_err(|_| anyhow!("Failed to lock embedding model"))
-                    .and_then(|mut guard| {
-                        guard
-                            .embed(texts_vec, None)
-                            .map_err(|e| anyhow!("FastEmbed error: {}", e))
-                    });
-                let _ = tx.send(result);
-            })
-            .map_err(|e| {
-                RuntimeError::InferenceError(format!("Failed to spawn embedding thread: {}", e))
-            })?;
-
-        let result = rx
-            .await
-            .map_err(|_| RuntimeError::InferenceError("Embedding thread panicked".to_string()))?;
-
-        result.map_err(|e| RuntimeError::InferenceError(e.to_string()))
+        run_on_worker("embedding", move || {
+            model
+                .lock()
+                .map_err(|_| anyhow!("Failed to lock embedding model"))
+                .and_then(|mut guard| {
+                    guard
+                        .embed(texts_vec, None)
+                        .map_err(|e| anyhow!("FastEmbed error: {}", e))
+                })
+        })
+        .await
     }
 
     fn dimensions(&self) -> u32 {
@@ -229,3 +469,84 @@ impl EmbeddingModel for FastEmbedService {
         &self.model_name
     }
 }
+
+/// Wrapper around a FastEmbed [`TextRerank`] cross-encoder that implements
+/// [`RerankerModel`].
+///
+/// Like [`FastEmbedService`], each call runs on a short-lived worker thread
+/// with a larger stack.
+pub struct FastEmbedReranker {
+    model: Arc<Mutex<TextRerank>>,
+    model_name: String,
+}
+
+impl FastEmbedReranker {
+    pub fn new(model_name: &str, cache_dir: &Path) -> anyhow::Result<Self> {
+        let model_enum = match model_name {
+            "BGERerankerBase" | "bge-reranker-base" => fastembed::RerankerModel::BGERerankerBase,
+            "BGERerankerV2M3" | "bge-reranker-v2-m3" => fastembed::RerankerModel::BGERerankerV2M3,
+            "JINARerankerV1TurboEn" | "jina-reranker-v1-turbo-en" => {
+                fastembed::RerankerModel::JINARerankerV1TurboEn
+            }
+            // FastEmbed's variant name carries the typo.
+            "JINARerankerV2BaseMultilingual" | "jina-reranker-v2-base-multilingual" => {
+                fastembed::RerankerModel::JINARerankerV2BaseMultiligual
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Unsupported FastEmbed rerank model: {}. Supported: {}",
+                    model_name,
+                    SUPPORTED_RERANK_MODELS.join(", ")
+                ));
+            }
+        };
+
+        let options = RerankInitOptions::new(model_enum).with_cache_dir(cache_dir.to_path_buf());
+        let model = TextRerank::try_new(options)
+            .map_err(|e| anyhow!("Failed to initialize FastEmbed reranker: {}", e))?;
+
+        Ok(Self {
+            model: Arc::new(Mutex::new(model)),
+            model_name: model_name.to_string(),
+        })
+    }
+
+    /// The model name this reranker was created with.
+    pub fn model_id(&self) -> &str {
+        &self.model_name
+    }
+}
+
+#[async_trait]
+impl RerankerModel for FastEmbedReranker {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = query.to_string();
+        let docs: Vec<String> = docs.iter().map(|d| d.to_string()).collect();
+        let model = self.model.clone();
+
+        let results = run_on_worker("rerank", move || {
+            model
+                .lock()
+                .map_err(|_| anyhow!("Failed to lock rerank model"))
+                .and_then(|mut guard| {
+                    let docs: Vec<&str> = docs.iter().map(String::as_str).collect();
+                    guard
+                        .rerank(query.as_str(), docs, false, None)
+                        .map_err(|e| anyhow!("FastEmbed error: {}", e))
+                })
+        })
+        .await?;
+
+        Ok(results
+            .into_iter()
+            .map(|r| ScoredDoc {
+                index: r.index,
+                score: r.score,
+                text: None,
+            })
+            .collect())
+    }
+}
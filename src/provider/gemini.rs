@@ -1,7 +1,11 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
+use crate::options_validation::merge_embed_overrides;
 use crate::provider::remote_common::{
-    RemoteProviderBase, build_google_generate_payload, check_http_status, resolve_api_key,
+    GoogleGenerateSettings, RemoteProviderBase, RequestIdExt, SafetySetting, Truncation,
+    build_google_generate_payload, check_http_status, parse_google_candidate_texts,
+    parse_google_reasoning, parse_google_usage, probe_request, reject_overlong_inputs,
+    resolve_api_key, resolve_base_url, verify_credentials,
 };
 use crate::traits::{
     EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
@@ -9,14 +13,106 @@ use crate::traits::{
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+
+/// Typed options for `remote/gemini` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GeminiOptions {
+    /// Environment variable holding the API key (default `GEMINI_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://generativelanguage.googleapis.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Embedding task type sent as `taskType`, e.g. `"RETRIEVAL_QUERY"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_type: Option<String>,
+    /// Document title sent with every embed request; requires task type `RETRIEVAL_DOCUMENT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// How over-long embedding inputs are handled: `end` (what Gemini does
+    /// by default) or `none`, which rejects them before sending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+    /// Harm-category blocking thresholds sent as `safetySettings` (generate only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_settings: Option<Vec<SafetySetting>>,
+    /// Response MIME type, e.g. `"text/plain"` or `"application/json"` (generate only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+}
+
+impl GeminiOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`task_type`](Self::task_type).
+    pub fn with_task_type(mut self, task_type: impl Into<String>) -> Self {
+        self.task_type = Some(task_type.into());
+        self
+    }
+
+    /// Set [`title`](Self::title).
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
+    /// Set [`safety_settings`](Self::safety_settings).
+    pub fn with_safety_settings(
+        mut self,
+        safety_settings: impl IntoIterator<Item = SafetySetting>,
+    ) -> Self {
+        self.safety_settings = Some(safety_settings.into_iter().collect());
+        self
+    }
+
+    /// Set [`response_mime_type`](Self::response_mime_type).
+    pub fn with_response_mime_type(mut self, response_mime_type: impl Into<String>) -> Self {
+        self.response_mime_type = Some(response_mime_type.into());
+        self
+    }
+}
+
+impl From<&GeminiOptions> for serde_json::Value {
+    fn from(options: &GeminiOptions) -> Self {
+        serde_json::to_value(options).expect("GeminiOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Google Gemini API](https://ai.google.dev/api)
 /// for embedding (`batchEmbedContents`) and text generation (`generateContent`).
 ///
 /// Requires the `GEMINI_API_KEY` environment variable (or a custom env var name
-/// via the `api_key_env` option).
+/// via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
 pub struct RemoteGeminiProvider {
     base: RemoteProviderBase,
 }
@@ -26,6 +122,22 @@ impl RemoteGeminiProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base.client.get(format!(
+            "{}/v1beta/models?pageSize=1&key={}",
+            DEFAULT_BASE_URL, api_key
+        ))
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -65,14 +177,25 @@ impl ModelProvider for RemoteGeminiProvider {
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "GEMINI_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         match spec.task {
             ModelTask::Embed => {
+                let option_str = |key: &str| {
+                    spec.options
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                };
                 let model = GeminiEmbeddingModel {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
+                    task_type: option_str("task_type"),
+                    title: option_str("title"),
+                    truncation: Truncation::from_options(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -82,7 +205,9 @@ impl ModelProvider for RemoteGeminiProvider {
                     client: self.base.client.clone(),
                     cb,
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
+                    settings: GoogleGenerateSettings::from_options("remote/gemini", &spec.options)?,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -97,6 +222,56 @@ impl ModelProvider for RemoteGeminiProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Lists models via `GET /v1beta/models`, which is free.
+    /// Uses the default `GEMINI_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "GEMINI_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `GEMINI_API_KEY`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/gemini", "GEMINI_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/gemini.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "base_url",
+            "response_mime_type",
+            "safety_settings",
+            "task_type",
+            "title",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 /// Embedding model backed by the Gemini batch embedding API.
@@ -104,59 +279,121 @@ pub struct GeminiEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://generativelanguage.googleapis.com`.
+    base_url: String,
     api_key: String,
+    task_type: Option<String>,
+    title: Option<String>,
+    /// Gemini silently truncates over-long inputs and has no switch for it,
+    /// so `none` is enforced locally against [`GEMINI_EMBED_MAX_TOKENS`].
+    truncation: Option<Truncation>,
 }
 
-#[async_trait]
-impl EmbeddingModel for GeminiEmbeddingModel {
-    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+/// Input token limit of Gemini's embedding models.
+const GEMINI_EMBED_MAX_TOKENS: usize = 2048;
+
+/// Build the `requests` array for `batchEmbedContents`, attaching the
+/// optional `taskType` and `title` to every element.
+fn build_gemini_embed_requests(
+    model_id: &str,
+    texts: &[String],
+    task_type: Option<&str>,
+    title: Option<&str>,
+) -> Vec<serde_json::Value> {
+    texts
+        .iter()
+        .map(|t| {
+            let mut request = json!({
+                "model": format!("models/{}", model_id),
+                "content": { "parts": [{ "text": t }] }
+            });
+            if let Some(task_type) = task_type {
+                request["taskType"] = json!(task_type);
+            }
+            if let Some(title) = title {
+                request["title"] = json!(title);
+            }
+            request
+        })
+        .collect()
+}
+
+impl GeminiEmbeddingModel {
+    /// The alias-level `task_type` and `title` as an options object, the base
+    /// that per-call overrides are merged onto.
+    fn embed_options(&self) -> serde_json::Value {
+        let mut options = serde_json::Map::new();
+        if let Some(task_type) = &self.task_type {
+            options.insert("task_type".to_string(), json!(task_type));
+        }
+        if let Some(title) = &self.title {
+            options.insert("title".to_string(), json!(title));
+        }
+        serde_json::Value::Object(options)
+    }
+
+    async fn embed_request(
+        &self,
+        texts: Vec<&str>,
+        task_type: Option<&str>,
+        title: Option<&str>,
+    ) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.truncation == Some(Truncation::None) {
+            reject_overlong_inputs(
+                "remote/gemini",
+                &self.model_id,
+                &texts,
+                GEMINI_EMBED_MAX_TOKENS,
+            )?;
+        }
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
 
         self.cb
             .call(move || async move {
                 let url = format!(
-                    "https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents?key={}",
-                    self.model_id, self.api_key
+                    "{}/v1beta/models/{}:batchEmbedContents?key={}",
+                    self.base_url, self.model_id, self.api_key
                 );
 
-                let requests: Vec<_> = texts
-                    .iter()
-                    .map(|t| {
-                        json!({
-                            "model": format!("models/{}", self.model_id),
-                            "content": { "parts": [{ "text": t }] }
-                        })
-                    })
-                    .collect();
+                let requests =
+                    build_gemini_embed_requests(&self.model_id, &texts, task_type, title);
 
                 let response = self
                     .client
                     .post(&url)
                     .json(&json!({ "requests": requests }))
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/gemini", e))?;
 
-                let body: serde_json::Value = check_http_status("Gemini", response)?
+                let body: serde_json::Value = check_http_status("remote/gemini", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/gemini", e))?;
 
                 let embeddings_json = body
                     .get("embeddings")
                     .and_then(|v| v.as_array())
                     .ok_or_else(|| {
-                        RuntimeError::ApiError("Invalid response format".to_string())
+                        RuntimeError::api_error("remote/gemini", "Invalid response format")
                     })?;
 
                 let mut result = Vec::new();
                 for item in embeddings_json {
-                    let values = item
-                        .get("values")
-                        .and_then(|v| v.as_array())
-                        .ok_or_else(|| {
-                            RuntimeError::ApiError("Missing values in embedding".to_string())
-                        })?;
+                    let values =
+                        item.get("values")
+                            .and_then(|v| v.as_array())
+                            .ok_or_else(|| {
+                                RuntimeError::api_error(
+                                    "remote/gemini",
+                                    "Missing values in embedding",
+                                )
+                            })?;
 
                     let vec: Vec<f32> = values
                         .iter()
@@ -168,6 +405,28 @@ impl EmbeddingModel for GeminiEmbeddingModel {
             })
             .await
     }
+}
+
+#[async_trait]
+impl EmbeddingModel for GeminiEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_request(texts, self.task_type.as_deref(), self.title.as_deref())
+            .await
+    }
+
+    /// Accepts `task_type` and `title` overrides, e.g. `RETRIEVAL_QUERY` for
+    /// queries against an alias configured for `RETRIEVAL_DOCUMENT`. Pass
+    /// `"title": null` to drop an alias-level title.
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        let merged = merge_embed_overrides("remote/gemini", &self.embed_options(), &overrides)?;
+        let option_str = |key: &str| merged.get(key).and_then(|v| v.as_str());
+        self.embed_request(texts, option_str("task_type"), option_str("title"))
+            .await
+    }
 
     fn dimensions(&self) -> u32 {
         // All current Gemini embedding models use 768 dimensions.
@@ -184,7 +443,10 @@ pub struct GeminiGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://generativelanguage.googleapis.com`.
+    base_url: String,
     api_key: String,
+    settings: GoogleGenerateSettings,
 }
 
 #[async_trait]
@@ -199,52 +461,44 @@ impl GeneratorModel for GeminiGeneratorModel {
         self.cb
             .call(move || async move {
                 let url = format!(
-                    "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-                    self.model_id, self.api_key
+                    "{}/v1beta/models/{}:generateContent?key={}",
+                    self.base_url, self.model_id, self.api_key
                 );
 
-                let payload = build_google_generate_payload(&messages, &options);
+                let payload = build_google_generate_payload(&messages, &options, &self.settings);
 
                 let response = self
                     .client
                     .post(&url)
                     .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/gemini", e))?;
 
-                let body: serde_json::Value = check_http_status("Gemini", response)?
+                let body: serde_json::Value = check_http_status("remote/gemini", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let candidates = body
-                    .get("candidates")
-                    .and_then(|v| v.as_array())
-                    .ok_or_else(|| RuntimeError::ApiError("No candidates returned".to_string()))?;
-
-                let first_candidate = candidates
-                    .first()
-                    .ok_or_else(|| RuntimeError::ApiError("Empty candidates".to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/gemini", e))?;
 
-                let content_parts = first_candidate
-                    .get("content")
-                    .and_then(|c| c.get("parts"))
-                    .and_then(|p| p.as_array())
-                    .ok_or_else(|| RuntimeError::ApiError("Invalid content format".to_string()))?;
-
-                let text = content_parts
-                    .first()
-                    .and_then(|p| p.get("text"))
-                    .and_then(|t| t.as_str())
-                    .unwrap_or("")
-                    .to_string();
+                let mut texts = parse_google_candidate_texts("remote/gemini", &body)?.into_iter();
+                let finish_reason = body
+                    .pointer("/candidates/0/finishReason")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string);
+                let reasoning = parse_google_reasoning(&body);
 
                 Ok(GenerationResult {
-                    text,
-                    usage: None,
+                    text: texts.next().unwrap_or_default(),
+                    usage: parse_google_usage(&body),
                     images: vec![],
                     audio: None,
+                    alternatives: texts.collect(),
+                    finish_reason,
+                    raw: options.include_raw.then_some(body),
+                    logprobs: None,
+                    reasoning,
                 })
             })
             .await
@@ -272,6 +526,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -348,7 +603,11 @@ mod tests {
             Message::assistant("assistant answer"),
             Message::user("user follow-up"),
         ];
-        let payload = build_google_generate_payload(&messages, &GenerationOptions::default());
+        let payload = build_google_generate_payload(
+            &messages,
+            &GenerationOptions::default(),
+            &GoogleGenerateSettings::default(),
+        );
         let contents = payload["contents"].as_array().unwrap();
 
         assert_eq!(contents[0]["role"], "user");
@@ -368,6 +627,7 @@ mod tests {
                 top_p: Some(0.9),
                 ..Default::default()
             },
+            &GoogleGenerateSettings::default(),
         );
 
         assert_eq!(payload["generationConfig"]["maxOutputTokens"], 64);
@@ -381,7 +641,11 @@ mod tests {
     fn generation_payload_extracts_system_instruction() {
         use crate::traits::Message;
         let messages = vec![Message::system("you are helpful"), Message::user("hello")];
-        let payload = build_google_generate_payload(&messages, &GenerationOptions::default());
+        let payload = build_google_generate_payload(
+            &messages,
+            &GenerationOptions::default(),
+            &GoogleGenerateSettings::default(),
+        );
 
         // System message should be extracted into system_instruction
         let si = &payload["system_instruction"];
@@ -397,7 +661,11 @@ mod tests {
     fn generation_payload_no_system_instruction_without_system_messages() {
         use crate::traits::Message;
         let messages = vec![Message::user("hello"), Message::assistant("hi")];
-        let payload = build_google_generate_payload(&messages, &GenerationOptions::default());
+        let payload = build_google_generate_payload(
+            &messages,
+            &GenerationOptions::default(),
+            &GoogleGenerateSettings::default(),
+        );
 
         // No system_instruction field should be present
         assert!(payload.get("system_instruction").is_none());
@@ -405,4 +673,157 @@ mod tests {
         let contents = payload["contents"].as_array().unwrap();
         assert_eq!(contents.len(), 2);
     }
+
+    #[test]
+    fn generation_usage_parsed_from_response() {
+        // Trimmed generateContent response from gemini-2.0-flash.
+        let body = json!({
+            "candidates": [{
+                "content": { "parts": [{ "text": "Hello!" }], "role": "model" },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 12,
+                "candidatesTokenCount": 3,
+                "totalTokenCount": 15,
+                "cachedContentTokenCount": 8,
+                "promptTokensDetails": [{ "modality": "TEXT", "tokenCount": 12 }]
+            },
+            "modelVersion": "gemini-2.0-flash"
+        });
+        let usage = parse_google_usage(&body).unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 3);
+        assert_eq!(usage.total_tokens, 15);
+        assert_eq!(usage.cache_read_tokens, 8);
+        assert_eq!(usage.cache_creation_tokens, 0);
+
+        assert!(parse_google_usage(&json!({ "candidates": [] })).is_none());
+    }
+
+    #[test]
+    fn generation_without_candidates_or_blocked_is_an_error() {
+        // Prompt blocked: Gemini returns only promptFeedback.
+        let blocked_prompt = json!({
+            "promptFeedback": {
+                "blockReason": "PROHIBITED_CONTENT",
+                "safetyRatings": []
+            },
+            "usageMetadata": { "promptTokenCount": 9, "totalTokenCount": 9 }
+        });
+        let err = parse_google_candidate_texts("remote/gemini", &blocked_prompt).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::ContentFiltered { reason, .. } if reason == "PROHIBITED_CONTENT"
+        ));
+
+        // Response blocked: the candidate has a finish reason but no content.
+        let blocked_response = json!({
+            "candidates": [{
+                "finishReason": "SAFETY",
+                "safetyRatings": [
+                    { "category": "HARM_CATEGORY_DANGEROUS_CONTENT", "probability": "HIGH", "blocked": true }
+                ]
+            }]
+        });
+        let err = parse_google_candidate_texts("remote/gemini", &blocked_response).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::ContentFiltered { reason, .. } if reason == "SAFETY"
+        ));
+
+        let no_candidates = json!({ "usageMetadata": { "promptTokenCount": 3 } });
+        let err = parse_google_candidate_texts("remote/gemini", &no_candidates).unwrap_err();
+        assert!(matches!(err, RuntimeError::ApiError { .. }));
+
+        let no_content = json!({ "candidates": [{ "finishReason": "OTHER" }] });
+        let err = parse_google_candidate_texts("remote/gemini", &no_content).unwrap_err();
+        assert!(err.to_string().contains("OTHER"));
+    }
+
+    #[test]
+    fn embed_requests_include_task_type_and_title() {
+        let texts = vec!["a".to_string(), "b".to_string()];
+        let requests = build_gemini_embed_requests(
+            "text-embedding-004",
+            &texts,
+            Some("RETRIEVAL_DOCUMENT"),
+            Some("Doc title"),
+        );
+
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(request["model"], "models/text-embedding-004");
+            assert_eq!(request["taskType"], "RETRIEVAL_DOCUMENT");
+            assert_eq!(request["title"], "Doc title");
+        }
+        assert_eq!(requests[1]["content"]["parts"][0]["text"], "b");
+    }
+
+    #[test]
+    fn embed_requests_omit_unset_task_type_and_title() {
+        let texts = vec!["a".to_string()];
+        let requests = build_gemini_embed_requests("text-embedding-004", &texts, None, None);
+
+        assert!(requests[0].get("taskType").is_none());
+        assert!(requests[0].get("title").is_none());
+    }
+
+    fn embedding_model(task_type: Option<&str>, title: Option<&str>) -> GeminiEmbeddingModel {
+        GeminiEmbeddingModel {
+            client: Client::new(),
+            cb: crate::reliability::CircuitBreakerWrapper::new(Default::default()),
+            model_id: "text-embedding-004".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: "test-key".to_string(),
+            task_type: task_type.map(str::to_string),
+            title: title.map(str::to_string),
+            truncation: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn truncation_none_rejects_overlong_inputs_before_sending() {
+        let model = GeminiEmbeddingModel {
+            truncation: Some(Truncation::None),
+            ..embedding_model(None, None)
+        };
+        let long = "x".repeat(GEMINI_EMBED_MAX_TOKENS * 4);
+        let err = model.embed(vec!["short", &long]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        assert!(err.to_string().contains("Input 1"));
+        assert!(err.to_string().contains("truncation is 'none'"));
+    }
+
+    #[test]
+    fn embed_overrides_merge_onto_alias_options() {
+        let model = embedding_model(Some("RETRIEVAL_DOCUMENT"), Some("Doc title"));
+        let merged = merge_embed_overrides(
+            "remote/gemini",
+            &model.embed_options(),
+            &json!({ "task_type": "RETRIEVAL_QUERY", "title": null }),
+        )
+        .unwrap();
+        assert_eq!(merged, json!({ "task_type": "RETRIEVAL_QUERY" }));
+    }
+
+    #[tokio::test]
+    async fn embed_with_validates_merged_options() {
+        // The alias-level title is kept, which RETRIEVAL_QUERY does not allow.
+        let model = embedding_model(Some("RETRIEVAL_DOCUMENT"), Some("Doc title"));
+        let err = model
+            .embed_with(vec!["q"], json!({ "task_type": "RETRIEVAL_QUERY" }))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires task_type RETRIEVAL_DOCUMENT")
+        );
+
+        let err = model
+            .embed_with(vec!["q"], json!({ "task_type": "NOT_A_TASK" }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+    }
 }
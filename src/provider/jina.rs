@@ -0,0 +1,688 @@
+use crate::api::{ModelAliasSpec, ModelTask};
+use crate::error::{Result, RuntimeError};
+use crate::provider::remote_common::{
+    RemoteProviderBase, RequestIdExt, Truncation, check_http_status, probe_request,
+    resolve_api_key, resolve_base_url, verify_credentials,
+};
+use crate::traits::{
+    EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RerankOptions, RerankerModel, ScoredDoc, TokenUsage,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.jina.ai";
+
+/// Typed options for `remote/jina` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JinaOptions {
+    /// Environment variable holding the API key (default `JINA_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.jina.ai`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Task adapter for jina-embeddings-v3 and later, e.g. `"retrieval.query"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// Truncate vectors to this length (Matryoshka).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    /// How over-long inputs are handled: `none` (the default) or `end`.
+    /// Jina cannot truncate from the start.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+}
+
+impl JinaOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`task`](Self::task).
+    pub fn with_task(mut self, task: impl Into<String>) -> Self {
+        self.task = Some(task.into());
+        self
+    }
+
+    /// Set [`dimensions`](Self::dimensions).
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+}
+
+impl From<&JinaOptions> for serde_json::Value {
+    fn from(options: &JinaOptions) -> Self {
+        serde_json::to_value(options).expect("JinaOptions serializes to a JSON object")
+    }
+}
+
+/// Remote provider that calls the [Jina AI API](https://jina.ai/embeddings/)
+/// for embedding and reranking. Does not support text generation.
+///
+/// Requires the `JINA_API_KEY` environment variable (or a custom env var name
+/// via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
+pub struct RemoteJinaProvider {
+    base: RemoteProviderBase,
+}
+
+impl Default for RemoteJinaProvider {
+    fn default() -> Self {
+        Self {
+            base: RemoteProviderBase::new(),
+        }
+    }
+}
+
+impl RemoteJinaProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .post(format!("{DEFAULT_BASE_URL}/v1/embeddings"))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&json!({ "model": "jina-embeddings-v3", "input": ["ping"] }))
+    }
+
+    #[cfg(test)]
+    fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
+        self.base.insert_test_breaker(key, age);
+    }
+
+    #[cfg(test)]
+    fn breaker_count(&self) -> usize {
+        self.base.breaker_count()
+    }
+
+    #[cfg(test)]
+    fn force_cleanup_now_for_test(&self) {
+        self.base.force_cleanup_now_for_test();
+    }
+}
+
+#[async_trait]
+impl ModelProvider for RemoteJinaProvider {
+    fn provider_id(&self) -> &'static str {
+        "remote/jina"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Rerank],
+        }
+    }
+
+    async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        let cb = self.base.circuit_breaker_for(spec);
+        let api_key = resolve_api_key(&spec.options, "api_key_env", "JINA_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
+
+        match spec.task {
+            ModelTask::Embed => {
+                let model = JinaEmbeddingModel {
+                    client: self.base.client.clone(),
+                    cb,
+                    model_id: spec.model_id.clone(),
+                    base_url,
+                    api_key,
+                    task: spec
+                        .options
+                        .get("task")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    dimensions: spec
+                        .options
+                        .get("dimensions")
+                        .and_then(|v| v.as_u64())
+                        .map(|d| d as u32),
+                    truncation: Truncation::from_options(&spec.options),
+                };
+                let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            ModelTask::Rerank => {
+                let model = JinaRerankerModel {
+                    client: self.base.client.clone(),
+                    cb,
+                    model_id: spec.model_id.clone(),
+                    base_url,
+                    api_key,
+                };
+                let handle: Arc<dyn RerankerModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            _ => Err(RuntimeError::CapabilityMismatch(format!(
+                "Jina provider does not support task {:?}",
+                spec.task
+            ))),
+        }
+    }
+
+    async fn health(&self) -> ProviderHealth {
+        ProviderHealth::Healthy
+    }
+
+    /// Jina AI has no free endpoint, so this embeds one short string with
+    /// `jina-embeddings-v3` (a few billed tokens per probe).
+    /// Uses the default `JINA_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "JINA_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `JINA_API_KEY`, when set, with the health probe request so eager
+    /// warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/jina", "JINA_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/jina.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "base_url",
+            "dimensions",
+            "task",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
+}
+
+struct JinaEmbeddingModel {
+    client: Client,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.jina.ai`.
+    base_url: String,
+    api_key: String,
+    /// Task adapter, e.g. `retrieval.query`; omitted from the request when
+    /// unset.
+    task: Option<String>,
+    /// Truncated (Matryoshka) output length; the model default when unset.
+    dimensions: Option<u32>,
+    /// Sent as the boolean `truncate` (`end` only); the server rejects
+    /// over-long inputs when unset.
+    truncation: Option<Truncation>,
+}
+
+/// Build the JSON body for Jina's `/v1/embeddings` endpoint.
+fn build_jina_embed_payload(
+    model_id: &str,
+    texts: &[String],
+    task: Option<&str>,
+    dimensions: Option<u32>,
+    truncation: Option<Truncation>,
+) -> serde_json::Value {
+    let mut body = json!({
+        "model": model_id,
+        "input": texts,
+        "embedding_type": "float",
+    });
+    if let Some(task) = task {
+        body["task"] = json!(task);
+    }
+    if let Some(dimensions) = dimensions {
+        body["dimensions"] = json!(dimensions);
+    }
+    if let Some(truncation) = truncation {
+        body["truncate"] = json!(truncation == Truncation::End);
+    }
+    body
+}
+
+/// Extract vectors from an embeddings response, ordered by their `index`.
+fn parse_jina_embeddings(body: &serde_json::Value) -> Result<Vec<Vec<f32>>> {
+    let data = body.get("data").and_then(|d| d.as_array()).ok_or_else(|| {
+        RuntimeError::api_error("remote/jina", "Invalid embedding response format")
+    })?;
+
+    let mut indexed: Vec<(u64, Vec<f32>)> = data
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let index = item
+                .get("index")
+                .and_then(|i| i.as_u64())
+                .unwrap_or(position as u64);
+            let vector = item
+                .get("embedding")
+                .and_then(|e| e.as_array())
+                .map(|e| {
+                    e.iter()
+                        .filter_map(|v| v.as_f64().map(|f| f as f32))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (index, vector)
+        })
+        .collect();
+    indexed.sort_by_key(|(index, _)| *index);
+    Ok(indexed.into_iter().map(|(_, vector)| vector).collect())
+}
+
+#[async_trait]
+impl EmbeddingModel for JinaEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        if texts.is_empty() {
+            return Ok((Vec::new(), None));
+        }
+        let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let payload = build_jina_embed_payload(
+            &self.model_id,
+            &texts,
+            self.task.as_deref(),
+            self.dimensions,
+            self.truncation,
+        );
+
+        self.cb
+            .call(move || async move {
+                let response = self
+                    .client
+                    .post(format!("{}/v1/embeddings", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/jina", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/jina", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/jina", e))?;
+
+                let embeddings = parse_jina_embeddings(&body)?;
+                let usage = body
+                    .pointer("/usage/total_tokens")
+                    .and_then(|t| t.as_u64())
+                    .map(|t| TokenUsage {
+                        prompt_tokens: t as usize,
+                        completion_tokens: 0,
+                        total_tokens: t as usize,
+                        ..Default::default()
+                    });
+
+                Ok((embeddings, usage))
+            })
+            .await
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.dimensions.unwrap_or(match self.model_id.as_str() {
+            "jina-embeddings-v4" => 2048,
+            "jina-embeddings-v2-base-en"
+            | "jina-embeddings-v2-base-de"
+            | "jina-embeddings-v2-base-es"
+            | "jina-embeddings-v2-base-zh"
+            | "jina-embeddings-v2-base-code" => 768,
+            "jina-embeddings-v2-small-en" => 512,
+            _ => 1024,
+        })
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
+
+struct JinaRerankerModel {
+    client: Client,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.jina.ai`.
+    base_url: String,
+    api_key: String,
+}
+
+/// Build the Jina rerank request body.
+fn build_jina_rerank_payload(
+    model_id: &str,
+    query: &str,
+    docs: &[String],
+    options: &RerankOptions,
+) -> serde_json::Value {
+    let mut body = json!({
+        "model": model_id,
+        "query": query,
+        "documents": docs,
+        "return_documents": options.return_documents,
+    });
+    if let Some(top_n) = options.top_n {
+        body["top_n"] = json!(top_n);
+    }
+    body
+}
+
+#[async_trait]
+impl RerankerModel for JinaRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        self.rerank_with_options(query, docs, &RerankOptions::default())
+            .await
+    }
+
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = build_jina_rerank_payload(
+            &self.model_id,
+            query,
+            &docs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            options,
+        );
+
+        let results = self
+            .cb
+            .call(move || async move {
+                let response = self
+                    .client
+                    .post(format!("{}/v1/rerank", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/jina", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/jina", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/jina", e))?;
+
+                let results_json =
+                    body.get("results")
+                        .and_then(|r| r.as_array())
+                        .ok_or_else(|| {
+                            RuntimeError::api_error("remote/jina", "Invalid rerank response format")
+                        })?;
+
+                let mut results = Vec::new();
+                for item in results_json {
+                    let index = item.get("index").and_then(|i| i.as_u64()).unwrap_or(0) as usize;
+                    let score = item
+                        .get("relevance_score")
+                        .and_then(|s| s.as_f64())
+                        .unwrap_or(0.0) as f32;
+                    // `document` is `{"text": ...}` when `return_documents` is set.
+                    let text = item
+                        .pointer("/document/text")
+                        .and_then(|t| t.as_str())
+                        .map(str::to_string);
+                    results.push(ScoredDoc { index, score, text });
+                }
+                Ok(results)
+            })
+            .await?;
+        Ok(options.apply(results, docs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ModelRuntimeKey;
+    use std::time::Duration;
+
+    static ENV_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    fn spec(alias: &str, task: ModelTask, model_id: &str) -> ModelAliasSpec {
+        ModelAliasSpec {
+            alias: alias.to_string(),
+            task,
+            provider_id: "remote/jina".to_string(),
+            model_id: model_id.to_string(),
+            revision: None,
+            warmup: crate::api::WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn breaker_reused_for_same_runtime_key() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("JINA_API_KEY", "test-key") };
+
+        let provider = RemoteJinaProvider::new();
+        let s1 = spec("embed/a", ModelTask::Embed, "jina-embeddings-v3");
+        let s2 = spec("embed/b", ModelTask::Embed, "jina-embeddings-v3");
+
+        let _ = provider.load(&s1).await.unwrap();
+        let _ = provider.load(&s2).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+
+        unsafe { std::env::remove_var("JINA_API_KEY") };
+    }
+
+    #[tokio::test]
+    async fn breaker_isolated_by_task_and_model() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("JINA_API_KEY", "test-key") };
+
+        let provider = RemoteJinaProvider::new();
+        let embed = spec("embed/a", ModelTask::Embed, "jina-embeddings-v3");
+        let rerank = spec(
+            "rerank/a",
+            ModelTask::Rerank,
+            "jina-reranker-v2-base-multilingual",
+        );
+
+        let _ = provider.load(&embed).await.unwrap();
+        let _ = provider.load(&rerank).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 2);
+
+        unsafe { std::env::remove_var("JINA_API_KEY") };
+    }
+
+    #[tokio::test]
+    async fn breaker_cleanup_evicts_stale_entries() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("JINA_API_KEY", "test-key") };
+
+        let provider = RemoteJinaProvider::new();
+        let stale = spec("embed/stale", ModelTask::Embed, "jina-embeddings-v3");
+        let fresh = spec(
+            "rerank/fresh",
+            ModelTask::Rerank,
+            "jina-reranker-v2-base-multilingual",
+        );
+        provider.insert_test_breaker(
+            ModelRuntimeKey::new(&stale),
+            RemoteProviderBase::BREAKER_TTL + Duration::from_secs(5),
+        );
+        provider.insert_test_breaker(ModelRuntimeKey::new(&fresh), Duration::from_secs(1));
+        assert_eq!(provider.breaker_count(), 2);
+
+        provider.force_cleanup_now_for_test();
+        let _ = provider.load(&fresh).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+
+        unsafe { std::env::remove_var("JINA_API_KEY") };
+    }
+
+    #[tokio::test]
+    async fn generate_capability_mismatch() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("JINA_API_KEY", "test-key") };
+
+        let provider = RemoteJinaProvider::new();
+        let s = spec("gen/a", ModelTask::Generate, "jina-embeddings-v3");
+        let result = provider.load(&s).await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("does not support task")
+        );
+
+        unsafe { std::env::remove_var("JINA_API_KEY") };
+    }
+
+    #[test]
+    fn embed_payload_includes_task_and_dimensions_when_set() {
+        let texts = vec!["a".to_string()];
+        let payload = build_jina_embed_payload(
+            "jina-embeddings-v3",
+            &texts,
+            Some("retrieval.query"),
+            Some(256),
+            Some(Truncation::End),
+        );
+        assert_eq!(payload["task"], "retrieval.query");
+        assert_eq!(payload["dimensions"], 256);
+        assert_eq!(payload["embedding_type"], "float");
+        assert_eq!(payload["truncate"], true);
+
+        let payload = build_jina_embed_payload("jina-embeddings-v3", &texts, None, None, None);
+        assert!(payload.get("task").is_none());
+        assert!(payload.get("dimensions").is_none());
+        assert!(payload.get("truncate").is_none());
+    }
+
+    #[test]
+    fn embeddings_are_ordered_by_index() {
+        let body = json!({
+            "data": [
+                { "index": 1, "embedding": [2.0] },
+                { "index": 0, "embedding": [1.0] }
+            ],
+            "usage": { "total_tokens": 4 }
+        });
+        assert_eq!(
+            parse_jina_embeddings(&body).unwrap(),
+            vec![vec![1.0], vec![2.0]]
+        );
+        assert!(parse_jina_embeddings(&json!({})).is_err());
+    }
+
+    #[test]
+    fn rerank_payload_maps_top_n_and_return_documents() {
+        let docs = vec!["a".to_string(), "b".to_string()];
+        let payload = build_jina_rerank_payload(
+            "jina-reranker-v2-base-multilingual",
+            "q",
+            &docs,
+            &RerankOptions {
+                top_n: Some(1),
+                return_documents: true,
+                sort: true,
+            },
+        );
+        assert_eq!(payload["top_n"], 1);
+        assert_eq!(payload["return_documents"], true);
+
+        let payload = build_jina_rerank_payload(
+            "jina-reranker-v2-base-multilingual",
+            "q",
+            &docs,
+            &Default::default(),
+        );
+        assert!(payload.get("top_n").is_none());
+        assert_eq!(payload["return_documents"], false);
+    }
+
+    #[test]
+    fn dimensions_follow_option_then_model_default() {
+        let model = |model_id: &str, dimensions: Option<u32>| JinaEmbeddingModel {
+            client: Client::new(),
+            cb: crate::reliability::CircuitBreakerWrapper::new(Default::default()),
+            model_id: model_id.to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: "test-key".to_string(),
+            task: None,
+            dimensions,
+            truncation: None,
+        };
+        assert_eq!(model("jina-embeddings-v3", None).dimensions(), 1024);
+        assert_eq!(model("jina-embeddings-v3", Some(256)).dimensions(), 256);
+        assert_eq!(model("jina-embeddings-v2-base-en", None).dimensions(), 768);
+        assert_eq!(model("jina-embeddings-v4", None).dimensions(), 2048);
+    }
+}
@@ -1,20 +1,77 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::provider::remote_common::{
+    RemoteProviderBase, RequestIdExt, Truncation, check_http_status, parse_openai_chat_completion,
+    probe_request, resolve_api_key, resolve_base_url, verify_credentials,
+};
 use crate::traits::{
     EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
-    Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage,
+    Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth, RerankOptions,
+    RerankerModel, ScoredDoc,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.mistral.ai";
+
+/// Typed options for `remote/mistral` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MistralOptions {
+    /// Environment variable holding the API key (default `MISTRAL_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.mistral.ai`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Only `none` is accepted: Mistral rejects over-long embedding inputs
+    /// rather than truncating them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+}
+
+impl MistralOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+}
+
+impl From<&MistralOptions> for serde_json::Value {
+    fn from(options: &MistralOptions) -> Self {
+        serde_json::to_value(options).expect("MistralOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Mistral AI API](https://docs.mistral.ai/api/)
-/// for embedding and text generation (chat completions).
+/// for embedding, reranking, and text generation (chat completions).
 ///
 /// Requires the `MISTRAL_API_KEY` environment variable (or a custom env var
-/// name via the `api_key_env` option).
+/// name via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
 pub struct RemoteMistralProvider {
     base: RemoteProviderBase,
 }
@@ -32,6 +89,22 @@ impl RemoteMistralProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .get(format!("{DEFAULT_BASE_URL}/v1/models"))
+            .header("Authorization", format!("Bearer {}", api_key))
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -56,13 +129,14 @@ impl ModelProvider for RemoteMistralProvider {
 
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
-            supported_tasks: vec![ModelTask::Embed, ModelTask::Generate],
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Rerank, ModelTask::Generate],
         }
     }
 
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "MISTRAL_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         match spec.task {
             ModelTask::Embed => {
@@ -70,16 +144,29 @@ impl ModelProvider for RemoteMistralProvider {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
             }
+            ModelTask::Rerank => {
+                let model = MistralRerankerModel {
+                    client: self.base.client.clone(),
+                    cb: cb.clone(),
+                    model_id: spec.model_id.clone(),
+                    base_url,
+                    api_key,
+                };
+                let handle: Arc<dyn RerankerModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
             ModelTask::Generate => {
                 let model = MistralGeneratorModel {
                     client: self.base.client.clone(),
                     cb,
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
@@ -95,38 +182,87 @@ impl ModelProvider for RemoteMistralProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Lists models via `GET /v1/models`, which is free.
+    /// Uses the default `MISTRAL_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "MISTRAL_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `MISTRAL_API_KEY`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/mistral", "MISTRAL_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/mistral.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&["api_key_env", "base_url", "truncation"])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 struct MistralEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.mistral.ai`.
+    base_url: String,
     api_key: String,
 }
 
 #[async_trait]
 impl EmbeddingModel for MistralEmbeddingModel {
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
 
         self.cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.mistral.ai/v1/embeddings")
+                    .post(format!("{}/v1/embeddings", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
                     .json(&json!({
                         "model": self.model_id,
                         "input": texts
                     }))
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
 
-                let body: serde_json::Value = check_http_status("Mistral", response)?
+                let body: serde_json::Value = check_http_status("remote/mistral", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
 
                 let mut embeddings = Vec::new();
                 if let Some(data) = body.get("data").and_then(|d| d.as_array()) {
@@ -155,10 +291,119 @@ impl EmbeddingModel for MistralEmbeddingModel {
     }
 }
 
+struct MistralRerankerModel {
+    client: Client,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.mistral.ai`.
+    base_url: String,
+    api_key: String,
+}
+
+/// Build the Mistral rerank request body.
+fn build_mistral_rerank_payload(
+    model_id: &str,
+    query: &str,
+    docs: &[String],
+    options: &RerankOptions,
+) -> serde_json::Value {
+    let mut body = json!({
+        "model": model_id,
+        "query": query,
+        "documents": docs,
+    });
+    if let Some(top_n) = options.top_n {
+        body["top_n"] = json!(top_n);
+    }
+    if options.return_documents {
+        body["return_documents"] = json!(true);
+    }
+    body
+}
+
+/// Parse the `results` array of a Mistral rerank response.
+fn parse_mistral_rerank_results(body: &serde_json::Value) -> Result<Vec<ScoredDoc>> {
+    let results = body
+        .get("results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| {
+            RuntimeError::api_error("remote/mistral", "Invalid rerank response format")
+        })?;
+
+    Ok(results
+        .iter()
+        .map(|item| ScoredDoc {
+            index: item.get("index").and_then(|i| i.as_u64()).unwrap_or(0) as usize,
+            score: item
+                .get("relevance_score")
+                .and_then(|s| s.as_f64())
+                .unwrap_or(0.0) as f32,
+            text: item
+                .get("document")
+                .and_then(|d| {
+                    d.as_str()
+                        .or_else(|| d.get("text").and_then(|t| t.as_str()))
+                })
+                .map(str::to_string),
+        })
+        .collect())
+}
+
+#[async_trait]
+impl RerankerModel for MistralRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        self.rerank_with_options(query, docs, &RerankOptions::default())
+            .await
+    }
+
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = build_mistral_rerank_payload(
+            &self.model_id,
+            query,
+            &docs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            options,
+        );
+
+        let results = self
+            .cb
+            .call(move || async move {
+                let response = self
+                    .client
+                    .post(format!("{}/v1/rerank", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/mistral", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
+
+                parse_mistral_rerank_results(&body)
+            })
+            .await?;
+        Ok(options.apply(results, docs))
+    }
+}
+
 struct MistralGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.mistral.ai`.
+    base_url: String,
     api_key: String,
 }
 
@@ -181,6 +426,7 @@ impl GeneratorModel for MistralGeneratorModel {
             })
             .collect();
 
+        let include_raw = options.include_raw;
         self.cb
             .call(move || async move {
                 let mut body = json!({
@@ -197,38 +443,32 @@ impl GeneratorModel for MistralGeneratorModel {
                 if let Some(top_p) = options.top_p {
                     body["top_p"] = json!(top_p);
                 }
+                if let Some(n) = options.n {
+                    body["n"] = json!(n);
+                }
+                if let Some(stop) = &options.stop {
+                    body["stop"] = json!(stop);
+                }
 
                 let response = self
                     .client
-                    .post("https://api.mistral.ai/v1/chat/completions")
+                    .post(format!("{}/v1/chat/completions", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
                     .json(&body)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
 
-                let body: serde_json::Value = check_http_status("Mistral", response)?
+                let body: serde_json::Value = check_http_status("remote/mistral", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/mistral", e))?;
 
-                let text = body["choices"][0]["message"]["content"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-
-                let usage = body.get("usage").map(|u| TokenUsage {
-                    prompt_tokens: u["prompt_tokens"].as_u64().unwrap_or(0) as usize,
-                    completion_tokens: u["completion_tokens"].as_u64().unwrap_or(0) as usize,
-                    total_tokens: u["total_tokens"].as_u64().unwrap_or(0) as usize,
-                });
-
-                Ok(GenerationResult {
-                    text,
-                    usage,
-                    images: vec![],
-                    audio: None,
-                })
+                let mut result = parse_openai_chat_completion("remote/mistral", &body)?;
+                result.raw = include_raw.then_some(body);
+                Ok(result)
             })
             .await
     }
@@ -255,6 +495,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -318,21 +559,71 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn rerank_capability_mismatch() {
+    async fn rerank_loads_reranker_model() {
         let _lock = ENV_LOCK.lock().await;
         unsafe { std::env::set_var("MISTRAL_API_KEY", "test-key") };
 
         let provider = RemoteMistralProvider::new();
-        let s = spec("rerank/a", ModelTask::Rerank, "mistral-embed");
-        let result = provider.load(&s).await;
-        assert!(result.is_err());
         assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("does not support task")
+            provider
+                .capabilities()
+                .supported_tasks
+                .contains(&ModelTask::Rerank)
         );
+        let s = spec("rerank/a", ModelTask::Rerank, "mistral-rerank");
+        let handle = provider.load(&s).await.unwrap();
+        assert!(handle.downcast_ref::<Arc<dyn RerankerModel>>().is_some());
 
         unsafe { std::env::remove_var("MISTRAL_API_KEY") };
     }
+
+    #[test]
+    fn rerank_payload_includes_options() {
+        let docs = vec!["a".to_string(), "b".to_string()];
+        let body =
+            build_mistral_rerank_payload("mistral-rerank", "q", &docs, &RerankOptions::default());
+        assert_eq!(body["model"], "mistral-rerank");
+        assert_eq!(body["documents"], json!(["a", "b"]));
+        assert!(body.get("top_n").is_none());
+        assert!(body.get("return_documents").is_none());
+
+        let body = build_mistral_rerank_payload(
+            "mistral-rerank",
+            "q",
+            &docs,
+            &RerankOptions {
+                top_n: Some(1),
+                return_documents: true,
+                sort: true,
+            },
+        );
+        assert_eq!(body["top_n"], 1);
+        assert_eq!(body["return_documents"], true);
+    }
+
+    #[test]
+    fn rerank_results_sorted_by_relevance() {
+        let body = json!({
+            "results": [
+                { "index": 0, "relevance_score": 0.1 },
+                { "index": 2, "relevance_score": 0.9, "document": { "text": "c" } },
+                { "index": 1, "relevance_score": 0.5 }
+            ]
+        });
+        let parsed = parse_mistral_rerank_results(&body).unwrap();
+        assert_eq!(parsed[1].text.as_deref(), Some("c"));
+
+        let ranked = RerankOptions {
+            top_n: Some(2),
+            return_documents: true,
+            sort: true,
+        }
+        .apply(parsed, &["a", "b", "c"]);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].index, 2);
+        assert_eq!(ranked[1].index, 1);
+        assert_eq!(ranked[1].text.as_deref(), Some("b"));
+
+        assert!(parse_mistral_rerank_results(&json!({})).is_err());
+    }
 }
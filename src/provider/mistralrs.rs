@@ -1,16 +1,17 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
 use crate::traits::{
-    ContentBlock, EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel,
-    LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth,
-    TokenUsage,
+    ContentBlock, EmbeddingModel, EmbeddingStream, GenerationOptions, GenerationResult,
+    GeneratorModel, LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities,
+    ProviderHealth, TokenLogprob, TokenUsage, generate_each,
 };
 use async_trait::async_trait;
 use mistralrs::{
-    EmbeddingModelBuilder, EmbeddingRequestBuilder, GgufModelBuilder, IsqType, Model, ModelDType,
-    PagedAttentionMetaBuilder, RequestBuilder, TextMessageRole, TextModelBuilder,
+    EmbeddingModelBuilder, EmbeddingRequestBuilder, GgufModelBuilder, IsqType, MemoryGpuConfig,
+    Model, ModelDType, PagedAttentionMetaBuilder, RequestBuilder, StopTokens, TextMessageRole,
+    TextModelBuilder,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Local inference provider using the mistral.rs engine.
@@ -43,6 +44,22 @@ impl LocalMistralRsProvider {
             std::env::set_var("HF_HOME", &cache_root);
         }
     }
+
+    /// Point mistralrs downloads at a HuggingFace Hub mirror.
+    ///
+    /// mistralrs builds its own hub client, which reads `HF_ENDPOINT` from the
+    /// environment, so the `hf_endpoint` option is applied by exporting it.
+    /// The variable is process-wide: it also affects aliases loaded later.
+    fn init_hf_endpoint(endpoint: &str) {
+        // SAFETY: same contract as `init_hf_cache` -- set before the builder
+        // reads it, and every writer stores a complete value.
+        unsafe {
+            std::env::set_var(
+                crate::cache::HF_ENDPOINT_ENV,
+                endpoint.trim_end_matches('/'),
+            );
+        }
+    }
 }
 
 impl Default for LocalMistralRsProvider {
@@ -85,6 +102,9 @@ impl ModelProvider for LocalMistralRsProvider {
         } else {
             MistralRsOptions::default()
         };
+        if let Some(endpoint) = &opts.hf_endpoint {
+            Self::init_hf_endpoint(endpoint);
+        }
 
         match spec.task {
             ModelTask::Embed => self.load_embedding(spec, &opts).await,
@@ -99,6 +119,33 @@ impl ModelProvider for LocalMistralRsProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/mistralrs.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "chat_template",
+            "diffusion_loader_type",
+            "dtype",
+            "embedding_dimensions",
+            "force_cpu",
+            "gguf_files",
+            "hf_endpoint",
+            "isq",
+            "kv_cache_mem_mb",
+            "max_num_seqs",
+            "paged_attention",
+            "paged_attn_block_size",
+            "pipeline",
+            "speech_loader_type",
+            "tokenizer_json",
+        ])
+    }
 }
 
 impl LocalMistralRsProvider {
@@ -187,9 +234,10 @@ impl LocalMistralRsProvider {
         );
 
         let service = MistralRsEmbeddingService {
-            model,
+            model: Arc::new(model),
             model_id: spec.model_id.clone(),
             dimensions,
+            stream_batch_size: opts.max_num_seqs.unwrap_or(DEFAULT_STREAM_BATCH_SIZE),
         };
 
         let handle: Arc<dyn EmbeddingModel> = Arc::new(service);
@@ -235,7 +283,7 @@ impl LocalMistralRsProvider {
             }
             if opts.paged_attention {
                 builder = builder
-                    .with_paged_attn(|| PagedAttentionMetaBuilder::default().build())
+                    .with_paged_attn(|| paged_attn_meta(opts).build())
                     .map_err(|e| {
                         RuntimeError::Load(format!("Failed to configure paged attention: {}", e))
                     })?;
@@ -269,7 +317,7 @@ impl LocalMistralRsProvider {
 
             if opts.paged_attention {
                 builder = builder
-                    .with_paged_attn(|| PagedAttentionMetaBuilder::default().build())
+                    .with_paged_attn(|| paged_attn_meta(opts).build())
                     .map_err(|e| {
                         RuntimeError::Load(format!("Failed to configure paged attention: {}", e))
                     })?;
@@ -336,7 +384,7 @@ impl LocalMistralRsProvider {
         }
         if opts.paged_attention {
             builder = builder
-                .with_paged_attn(|| PagedAttentionMetaBuilder::default().build())
+                .with_paged_attn(|| paged_attn_meta(opts).build())
                 .map_err(|e| {
                     RuntimeError::Load(format!("Failed to configure paged attention: {}", e))
                 })?;
@@ -456,35 +504,177 @@ impl LocalMistralRsProvider {
 // Configuration
 // ---------------------------------------------------------------------------
 
-#[derive(Deserialize, Default)]
+/// Typed options for `local/mistralrs` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-struct MistralRsOptions {
+pub struct MistralRsOptions {
     /// ISQ quantization type, e.g. "Q4K", "Q8_0"
-    isq: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isq: Option<String>,
     /// Force CPU inference (default: false)
-    #[serde(default)]
-    force_cpu: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub force_cpu: bool,
     /// Enable paged attention (default: false)
-    #[serde(default)]
-    paged_attention: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paged_attention: bool,
     /// Maximum number of sequences for batching
-    max_num_seqs: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_seqs: Option<usize>,
+    /// Paged-attention block size in tokens (mistral.rs default: 32)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paged_attn_block_size: Option<usize>,
+    /// GPU memory reserved for the paged-attention KV cache, in MB
+    /// (mistral.rs default: 90% of free GPU memory)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kv_cache_mem_mb: Option<usize>,
     /// Override chat template
-    chat_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<String>,
     /// Override tokenizer JSON path
-    tokenizer_json: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokenizer_json: Option<String>,
     /// Override embedding dimensions (probed at load if absent)
-    embedding_dimensions: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
     /// List of GGUF filenames (enables GGUF mode)
-    gguf_files: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gguf_files: Option<Vec<String>>,
     /// Model data type: "auto", "f16", "bf16", "f32"
-    dtype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtype: Option<String>,
     /// Pipeline type: "text" (default), "vision", "diffusion", "speech"
-    pipeline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
     /// Diffusion loader type: "flux", "flux_offloaded"
-    diffusion_loader_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diffusion_loader_type: Option<String>,
     /// Speech loader type: "dia"
-    speech_loader_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speech_loader_type: Option<String>,
+    /// HuggingFace Hub mirror, exported as `HF_ENDPOINT`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hf_endpoint: Option<String>,
+}
+
+impl MistralRsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`isq`](Self::isq).
+    pub fn with_isq(mut self, isq: impl Into<String>) -> Self {
+        self.isq = Some(isq.into());
+        self
+    }
+
+    /// Set [`force_cpu`](Self::force_cpu).
+    pub fn with_force_cpu(mut self, force_cpu: bool) -> Self {
+        self.force_cpu = force_cpu;
+        self
+    }
+
+    /// Set [`paged_attention`](Self::paged_attention).
+    pub fn with_paged_attention(mut self, paged_attention: bool) -> Self {
+        self.paged_attention = paged_attention;
+        self
+    }
+
+    /// Set [`max_num_seqs`](Self::max_num_seqs).
+    pub fn with_max_num_seqs(mut self, max_num_seqs: usize) -> Self {
+        self.max_num_seqs = Some(max_num_seqs);
+        self
+    }
+
+    /// Set [`paged_attn_block_size`](Self::paged_attn_block_size).
+    pub fn with_paged_attn_block_size(mut self, paged_attn_block_size: usize) -> Self {
+        self.paged_attn_block_size = Some(paged_attn_block_size);
+        self
+    }
+
+    /// Set [`kv_cache_mem_mb`](Self::kv_cache_mem_mb).
+    pub fn with_kv_cache_mem_mb(mut self, kv_cache_mem_mb: usize) -> Self {
+        self.kv_cache_mem_mb = Some(kv_cache_mem_mb);
+        self
+    }
+
+    /// Set [`chat_template`](Self::chat_template).
+    pub fn with_chat_template(mut self, chat_template: impl Into<String>) -> Self {
+        self.chat_template = Some(chat_template.into());
+        self
+    }
+
+    /// Set [`tokenizer_json`](Self::tokenizer_json).
+    pub fn with_tokenizer_json(mut self, tokenizer_json: impl Into<String>) -> Self {
+        self.tokenizer_json = Some(tokenizer_json.into());
+        self
+    }
+
+    /// Set [`embedding_dimensions`](Self::embedding_dimensions).
+    pub fn with_embedding_dimensions(mut self, embedding_dimensions: u32) -> Self {
+        self.embedding_dimensions = Some(embedding_dimensions);
+        self
+    }
+
+    /// Set [`gguf_files`](Self::gguf_files).
+    pub fn with_gguf_files(
+        mut self,
+        gguf_files: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.gguf_files = Some(gguf_files.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set [`dtype`](Self::dtype).
+    pub fn with_dtype(mut self, dtype: impl Into<String>) -> Self {
+        self.dtype = Some(dtype.into());
+        self
+    }
+
+    /// Set [`pipeline`](Self::pipeline).
+    pub fn with_pipeline(mut self, pipeline: impl Into<String>) -> Self {
+        self.pipeline = Some(pipeline.into());
+        self
+    }
+
+    /// Set [`diffusion_loader_type`](Self::diffusion_loader_type).
+    pub fn with_diffusion_loader_type(mut self, diffusion_loader_type: impl Into<String>) -> Self {
+        self.diffusion_loader_type = Some(diffusion_loader_type.into());
+        self
+    }
+
+    /// Set [`speech_loader_type`](Self::speech_loader_type).
+    pub fn with_speech_loader_type(mut self, speech_loader_type: impl Into<String>) -> Self {
+        self.speech_loader_type = Some(speech_loader_type.into());
+        self
+    }
+
+    /// Set [`hf_endpoint`](Self::hf_endpoint).
+    pub fn with_hf_endpoint(mut self, hf_endpoint: impl Into<String>) -> Self {
+        self.hf_endpoint = Some(hf_endpoint.into());
+        self
+    }
+}
+
+impl From<&MistralRsOptions> for serde_json::Value {
+    fn from(options: &MistralRsOptions) -> Self {
+        serde_json::to_value(options).expect("MistralRsOptions serializes to a JSON object")
+    }
+}
+
+/// Paged-attention settings from `paged_attn_block_size` and
+/// `kv_cache_mem_mb`; unset values keep the mistral.rs defaults.
+fn paged_attn_meta(opts: &MistralRsOptions) -> PagedAttentionMetaBuilder {
+    let mut meta = PagedAttentionMetaBuilder::default();
+    if let Some(block_size) = opts.paged_attn_block_size {
+        meta = meta.with_block_size(block_size);
+    }
+    if let Some(mb) = opts.kv_cache_mem_mb {
+        meta = meta.with_gpu_memory(MemoryGpuConfig::MbAmount(mb));
+    }
+    meta
 }
 
 // ---------------------------------------------------------------------------
@@ -599,10 +789,31 @@ fn validate_embeddings(embeddings: &[Vec<f32>]) -> Result<()> {
 // Embedding service
 // ---------------------------------------------------------------------------
 
+/// Texts per request in [`EmbeddingModel::embed_stream`] when
+/// `max_num_seqs` is unset.
+const DEFAULT_STREAM_BATCH_SIZE: usize = 32;
+
 struct MistralRsEmbeddingService {
-    model: Model,
+    model: Arc<Model>,
     model_id: String,
     dimensions: u32,
+    /// Texts per request when streaming: `max_num_seqs`, so each request
+    /// fits one scheduler batch.
+    stream_batch_size: usize,
+}
+
+/// Embed `texts` in one request, checking the vectors for NaN/Inf.
+async fn embed_texts(model: &Model, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+    let request = EmbeddingRequestBuilder::new().add_prompts(texts.iter().map(|s| s.to_string()));
+
+    let embeddings = model
+        .generate_embeddings(request)
+        .await
+        .map_err(|e| RuntimeError::InferenceError(format!("Embedding inference failed: {}", e)))?;
+
+    validate_embeddings(&embeddings)?;
+
+    Ok(embeddings)
 }
 
 #[async_trait]
@@ -611,17 +822,35 @@ impl EmbeddingModel for MistralRsEmbeddingService {
         if texts.is_empty() {
             return Ok(vec![]);
         }
+        embed_texts(&self.model, &texts).await
+    }
 
-        let request =
-            EmbeddingRequestBuilder::new().add_prompts(texts.iter().map(|s| s.to_string()));
-
-        let embeddings = self.model.generate_embeddings(request).await.map_err(|e| {
-            RuntimeError::InferenceError(format!("Embedding inference failed: {}", e))
-        })?;
-
-        validate_embeddings(&embeddings)?;
-
-        Ok(embeddings)
+    /// Streams one request of `max_num_seqs` texts (default 32) at a time.
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        let chunk_size = self.stream_batch_size.max(1);
+        let (tx, rx) = tokio::sync::mpsc::channel(chunk_size);
+        let texts: Vec<String> = texts.into_iter().map(str::to_owned).collect();
+        let model = self.model.clone();
+        tokio::spawn(async move {
+            for (chunk_index, chunk) in texts.chunks(chunk_size).enumerate() {
+                let chunk: Vec<&str> = chunk.iter().map(String::as_str).collect();
+                let vectors = match embed_texts(&model, &chunk).await {
+                    Ok(vectors) => vectors,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                let offset = chunk_index * chunk_size;
+                for (i, vector) in vectors.into_iter().enumerate() {
+                    if tx.send(Ok((offset + i, vector))).await.is_err() {
+                        // The caller stopped listening.
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(rx)
     }
 
     fn dimensions(&self) -> u32 {
@@ -650,6 +879,9 @@ impl GeneratorModel for MistralRsGeneratorService {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         let mut request = RequestBuilder::new();
 
         for msg in messages {
@@ -679,6 +911,8 @@ impl GeneratorModel for MistralRsGeneratorService {
         } else {
             request = request.set_deterministic_sampler();
         }
+        // Applied after the sampler preset so the deterministic reset does not clear them.
+        request = apply_sampler_options(request, &options);
 
         let response = self.model.send_chat_request(request).await.map_err(|e| {
             RuntimeError::InferenceError(format!("Generation inference failed: {}", e))
@@ -695,6 +929,7 @@ impl GeneratorModel for MistralRsGeneratorService {
             prompt_tokens: response.usage.prompt_tokens,
             completion_tokens: response.usage.completion_tokens,
             total_tokens: response.usage.total_tokens,
+            ..Default::default()
         };
 
         Ok(GenerationResult {
@@ -702,6 +937,16 @@ impl GeneratorModel for MistralRsGeneratorService {
             usage: Some(usage),
             images: vec![],
             audio: None,
+            alternatives: vec![],
+            finish_reason: response.choices.first().map(|c| c.finish_reason.clone()),
+            raw: None,
+            logprobs: response
+                .choices
+                .first()
+                .and_then(|c| c.logprobs.as_ref())
+                .and_then(|l| l.content.as_deref())
+                .map(convert_logprobs),
+            reasoning: None,
         })
     }
 }
@@ -723,6 +968,9 @@ impl GeneratorModel for MistralRsVisionService {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         let mut request = RequestBuilder::new();
 
         for msg in messages {
@@ -783,6 +1031,8 @@ impl GeneratorModel for MistralRsVisionService {
         } else {
             request = request.set_deterministic_sampler();
         }
+        // Applied after the sampler preset so the deterministic reset does not clear them.
+        request = apply_sampler_options(request, &options);
 
         let response =
             self.model.send_chat_request(request).await.map_err(|e| {
@@ -800,6 +1050,7 @@ impl GeneratorModel for MistralRsVisionService {
             prompt_tokens: response.usage.prompt_tokens,
             completion_tokens: response.usage.completion_tokens,
             total_tokens: response.usage.total_tokens,
+            ..Default::default()
         };
 
         Ok(GenerationResult {
@@ -807,10 +1058,62 @@ impl GeneratorModel for MistralRsVisionService {
             usage: Some(usage),
             images: vec![],
             audio: None,
+            alternatives: vec![],
+            finish_reason: response.choices.first().map(|c| c.finish_reason.clone()),
+            raw: None,
+            logprobs: response
+                .choices
+                .first()
+                .and_then(|c| c.logprobs.as_ref())
+                .and_then(|l| l.content.as_deref())
+                .map(convert_logprobs),
+            reasoning: None,
         })
     }
 }
 
+/// Map `stop`, `logit_bias`, and logprobs generation options onto the
+/// mistral.rs request.
+fn apply_sampler_options(
+    mut request: RequestBuilder,
+    options: &GenerationOptions,
+) -> RequestBuilder {
+    if let Some(stop) = &options.stop {
+        request = request.set_sampler_stop_toks(StopTokens::Seqs(stop.clone()));
+    }
+    if let Some(bias) = &options.logit_bias {
+        request = request.set_sampler_logits_bias(bias.clone());
+    }
+    if options.logprobs || options.top_logprobs.is_some() {
+        request = request.return_logprobs(true);
+    }
+    if let Some(top_n) = options.top_logprobs {
+        request = request.set_sampler_topn_logprobs(top_n as usize);
+    }
+    request
+}
+
+/// Convert mistral.rs per-token logprobs. Alternatives carry token IDs, so
+/// their decoded bytes are used as the token text when available.
+fn convert_logprobs(content: &[mistralrs::ResponseLogprob]) -> Vec<TokenLogprob> {
+    content
+        .iter()
+        .map(|token| TokenLogprob {
+            token: token.token.clone(),
+            logprob: token.logprob,
+            top_logprobs: token
+                .top_logprobs
+                .iter()
+                .map(|top| TokenLogprob {
+                    token: top.bytes.clone().unwrap_or_else(|| top.token.to_string()),
+                    logprob: top.logprob,
+                    top_logprobs: vec![],
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Diffusion service
 // ---------------------------------------------------------------------------
@@ -828,6 +1131,11 @@ impl GeneratorModel for MistralRsDiffusionService {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return Err(RuntimeError::Config(
+                "Diffusion pipelines do not support n > 1".to_string(),
+            ));
+        }
         use mistralrs::DiffusionGenerationParams;
 
         // Extract the text prompt from the last user message
@@ -868,6 +1176,11 @@ impl GeneratorModel for MistralRsDiffusionService {
                 media_type: "image/png".to_string(),
             }],
             audio: None,
+            alternatives: vec![],
+            finish_reason: None,
+            raw: None,
+            logprobs: None,
+            reasoning: None,
         })
     }
 }
@@ -887,8 +1200,13 @@ impl GeneratorModel for MistralRsSpeechService {
     async fn generate(
         &self,
         messages: &[Message],
-        _options: GenerationOptions,
+        options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return Err(RuntimeError::Config(
+                "Speech pipelines do not support n > 1".to_string(),
+            ));
+        }
         // Extract the text prompt from the last user message
         let prompt = extract_last_user_prompt(messages);
 
@@ -906,6 +1224,11 @@ impl GeneratorModel for MistralRsSpeechService {
                 sample_rate,
                 channels,
             }),
+            alternatives: vec![],
+            finish_reason: None,
+            raw: None,
+            logprobs: None,
+            reasoning: None,
         })
     }
 }
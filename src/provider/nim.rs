@@ -0,0 +1,853 @@
+use crate::api::{ModelAliasSpec, ModelTask};
+use crate::error::{Result, RuntimeError};
+use crate::provider::openai_stream::spawn_stream;
+use crate::provider::remote_common::{
+    RemoteProviderBase, RequestIdExt, Truncation, check_http_status, default_credential,
+    parse_openai_chat_completion, resolve_api_key, resolve_base_url,
+};
+use crate::traits::{
+    EmbeddingModel, GenerationOptions, GenerationResult, GenerationStream, GeneratorModel,
+    LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RerankOptions, RerankerModel, ScoredDoc, TokenUsage,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Base URL used when the `base_url` option is not set (a NIM container on
+/// its default port).
+const DEFAULT_BASE_URL: &str = "http://localhost:8000";
+
+/// Typed options for `remote/nim` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NimOptions {
+    /// Root URL of the NIM server without `/v1` (default `http://localhost:8000`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the bearer token (default `NVIDIA_API_KEY` when set).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Embedding input type: `"query"` (default) or `"passage"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+    /// Server-side truncation: `NONE`, `START`, or `END`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<String>,
+    /// Provider-neutral form of [`truncate`](Self::truncate); set at most one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+    /// Output dimension; required for models other than NVIDIA's retrieval embedders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
+}
+
+impl NimOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`input_type`](Self::input_type).
+    pub fn with_input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.input_type = Some(input_type.into());
+        self
+    }
+
+    /// Set [`truncate`](Self::truncate).
+    pub fn with_truncate(mut self, truncate: impl Into<String>) -> Self {
+        self.truncate = Some(truncate.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
+    /// Set [`embedding_dimensions`](Self::embedding_dimensions).
+    pub fn with_embedding_dimensions(mut self, embedding_dimensions: u32) -> Self {
+        self.embedding_dimensions = Some(embedding_dimensions);
+        self
+    }
+}
+
+impl From<&NimOptions> for serde_json::Value {
+    fn from(options: &NimOptions) -> Self {
+        serde_json::to_value(options).expect("NimOptions serializes to a JSON object")
+    }
+}
+
+/// Remote provider for [NVIDIA NIM](https://docs.nvidia.com/nim/) microservices
+/// (and other servers exposing the same API, such as Triton's OpenAI
+/// frontend): embedding (`/v1/embeddings`), reranking (`/v1/ranking`), and
+/// text generation (`/v1/chat/completions`).
+///
+/// Each alias points at its own deployment through the `base_url` option.
+/// Self-hosted NIMs usually run without authentication, so a bearer token is
+/// only sent when `NVIDIA_API_KEY` (or the env var named by `api_key_env`) is
+/// set.
+pub struct RemoteNimProvider {
+    base: RemoteProviderBase,
+}
+
+impl Default for RemoteNimProvider {
+    fn default() -> Self {
+        Self {
+            base: RemoteProviderBase::new(),
+        }
+    }
+}
+
+impl RemoteNimProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    #[cfg(test)]
+    fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
+        self.base.insert_test_breaker(key, age);
+    }
+
+    #[cfg(test)]
+    fn breaker_count(&self) -> usize {
+        self.base.breaker_count()
+    }
+
+    #[cfg(test)]
+    fn force_cleanup_now_for_test(&self) {
+        self.base.force_cleanup_now_for_test();
+    }
+}
+
+/// Resolve the optional bearer token for a NIM endpoint.
+///
+/// An explicit `api_key_env` must name a set variable; otherwise
+/// `NVIDIA_API_KEY` is used when present and no token is sent when it is not.
+fn resolve_nim_api_key(options: &serde_json::Value) -> Result<Option<String>> {
+    if options.get("api_key_env").is_some() {
+        return resolve_api_key(options, "api_key_env", "NVIDIA_API_KEY").map(Some);
+    }
+    Ok(std::env::var("NVIDIA_API_KEY").ok())
+}
+
+/// Connection details shared by all NIM model handles.
+struct NimEndpoint {
+    client: Client,
+    /// Server root without a trailing slash, e.g. `http://nim:8000`.
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl NimEndpoint {
+    fn from_spec(client: Client, spec: &ModelAliasSpec) -> Result<Self> {
+        Ok(Self {
+            client,
+            base_url: resolve_base_url(&spec.options, DEFAULT_BASE_URL),
+            api_key: resolve_nim_api_key(&spec.options)?,
+        })
+    }
+
+    /// Start a POST to `path` (e.g. `/v1/ranking`), with auth when configured.
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.post(format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(api_key) => request.header("Authorization", format!("Bearer {}", api_key)),
+            None => request,
+        }
+    }
+}
+
+fn option_string(spec: &ModelAliasSpec, key: &str) -> Option<String> {
+    spec.options
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// The `truncate` value to send: the `truncate` option as given, or the
+/// upper-case form of `truncation`.
+fn truncate_option(spec: &ModelAliasSpec) -> Option<String> {
+    option_string(spec, "truncate")
+        .or_else(|| Truncation::from_options(&spec.options).map(|t| t.as_upper().to_string()))
+}
+
+#[async_trait]
+impl ModelProvider for RemoteNimProvider {
+    fn provider_id(&self) -> &'static str {
+        "remote/nim"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Rerank, ModelTask::Generate],
+        }
+    }
+
+    async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        let cb = self.base.circuit_breaker_for(spec);
+        let endpoint = NimEndpoint::from_spec(self.base.client.clone(), spec)?;
+
+        match spec.task {
+            ModelTask::Embed => {
+                let dimensions = spec
+                    .options
+                    .get("embedding_dimensions")
+                    .and_then(|v| v.as_u64())
+                    .map(|d| d as u32)
+                    .or_else(|| known_nim_dimensions(&spec.model_id))
+                    .ok_or_else(|| {
+                        RuntimeError::Config(format!(
+                            "Unknown NIM embedding model '{}'; set the 'embedding_dimensions' option",
+                            spec.model_id
+                        ))
+                    })?;
+                let model = NimEmbeddingModel {
+                    endpoint,
+                    cb,
+                    model_id: spec.model_id.clone(),
+                    dimensions,
+                    input_type: option_string(spec, "input_type")
+                        .unwrap_or_else(|| "query".to_string()),
+                    truncate: truncate_option(spec),
+                };
+                let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            ModelTask::Rerank => {
+                let model = NimRerankerModel {
+                    endpoint,
+                    cb,
+                    model_id: spec.model_id.clone(),
+                    truncate: truncate_option(spec),
+                };
+                let handle: Arc<dyn RerankerModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            ModelTask::Generate => {
+                let model = NimGeneratorModel {
+                    endpoint,
+                    cb,
+                    model_id: spec.model_id.clone(),
+                };
+                let handle: Arc<dyn GeneratorModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            _ => Err(RuntimeError::CapabilityMismatch(format!(
+                "NIM provider does not support task {:?}",
+                spec.task
+            ))),
+        }
+    }
+
+    async fn health(&self) -> ProviderHealth {
+        ProviderHealth::Healthy
+    }
+
+    /// Validates the format of `NVIDIA_API_KEY` when it is set.
+    /// Self-hosted endpoints are per alias, so the key is not sent.
+    async fn warmup(&self) -> Result<()> {
+        default_credential("remote/nim", "NVIDIA_API_KEY").map(|_| ())
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/nim.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "base_url",
+            "embedding_dimensions",
+            "input_type",
+            "truncate",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
+}
+
+/// Output dimensions of NVIDIA's published retrieval embedding NIMs.
+fn known_nim_dimensions(model_id: &str) -> Option<u32> {
+    match model_id {
+        "nvidia/nv-embedqa-e5-v5" | "snowflake/arctic-embed-l" => Some(1024),
+        "nvidia/llama-3.2-nv-embedqa-1b-v2" => Some(2048),
+        "nvidia/nv-embedqa-mistral-7b-v2" | "nvidia/nv-embed-v1" => Some(4096),
+        _ => None,
+    }
+}
+
+struct NimEmbeddingModel {
+    endpoint: NimEndpoint,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+    dimensions: u32,
+    /// `query` or `passage`; asymmetric retrieval models require it.
+    input_type: String,
+    /// `NONE`, `START`, or `END`; the server default when unset.
+    truncate: Option<String>,
+}
+
+/// Build the JSON body for NIM's `/v1/embeddings` endpoint.
+fn build_nim_embed_payload(
+    model_id: &str,
+    texts: &[String],
+    input_type: &str,
+    truncate: Option<&str>,
+) -> serde_json::Value {
+    let mut body = json!({
+        "model": model_id,
+        "input": texts,
+        "input_type": input_type,
+        "encoding_format": "float",
+    });
+    if let Some(truncate) = truncate {
+        body["truncate"] = json!(truncate);
+    }
+    body
+}
+
+/// Extract vectors from an embeddings response, ordered by their `index`.
+fn parse_nim_embeddings(body: &serde_json::Value) -> Result<Vec<Vec<f32>>> {
+    let data = body.get("data").and_then(|d| d.as_array()).ok_or_else(|| {
+        RuntimeError::api_error("remote/nim", "Invalid embedding response format")
+    })?;
+
+    let mut indexed: Vec<(u64, Vec<f32>)> = data
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let index = item
+                .get("index")
+                .and_then(|i| i.as_u64())
+                .unwrap_or(position as u64);
+            let vector = item
+                .get("embedding")
+                .and_then(|e| e.as_array())
+                .map(|e| {
+                    e.iter()
+                        .filter_map(|v| v.as_f64().map(|f| f as f32))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (index, vector)
+        })
+        .collect();
+    indexed.sort_by_key(|(index, _)| *index);
+    Ok(indexed.into_iter().map(|(_, vector)| vector).collect())
+}
+
+#[async_trait]
+impl EmbeddingModel for NimEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        if texts.is_empty() {
+            return Ok((Vec::new(), None));
+        }
+        let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let payload = build_nim_embed_payload(
+            &self.model_id,
+            &texts,
+            &self.input_type,
+            self.truncate.as_deref(),
+        );
+
+        self.cb
+            .call(move || async move {
+                let response = self
+                    .endpoint
+                    .post("/v1/embeddings")
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/nim", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                let embeddings = parse_nim_embeddings(&body)?;
+                let usage = body.get("usage").map(|u| {
+                    let prompt_tokens = u["prompt_tokens"].as_u64().unwrap_or(0) as usize;
+                    TokenUsage {
+                        prompt_tokens,
+                        completion_tokens: 0,
+                        total_tokens: u["total_tokens"]
+                            .as_u64()
+                            .map_or(prompt_tokens, |t| t as usize),
+                        ..Default::default()
+                    }
+                });
+
+                Ok((embeddings, usage))
+            })
+            .await
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.dimensions
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
+
+struct NimRerankerModel {
+    endpoint: NimEndpoint,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+    truncate: Option<String>,
+}
+
+/// Build the JSON body for NIM's `/v1/ranking` endpoint.
+///
+/// Unlike Cohere-style rerank APIs, the query and passages are `{"text": ...}`
+/// objects and there is no `top_n` or `return_documents`; those are applied
+/// locally by [`RerankOptions::apply`].
+fn build_nim_rerank_payload(
+    model_id: &str,
+    query: &str,
+    docs: &[String],
+    truncate: Option<&str>,
+) -> serde_json::Value {
+    let passages: Vec<serde_json::Value> = docs.iter().map(|d| json!({ "text": d })).collect();
+    let mut body = json!({
+        "model": model_id,
+        "query": { "text": query },
+        "passages": passages,
+    });
+    if let Some(truncate) = truncate {
+        body["truncate"] = json!(truncate);
+    }
+    body
+}
+
+/// Convert a `/v1/ranking` response (`{"rankings": [{"index", "logit"}]}`)
+/// into scored documents. Scores are the raw logits.
+fn parse_nim_rankings(body: &serde_json::Value) -> Result<Vec<ScoredDoc>> {
+    let rankings = body
+        .get("rankings")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| RuntimeError::api_error("remote/nim", "Invalid ranking response format"))?;
+
+    Ok(rankings
+        .iter()
+        .map(|item| ScoredDoc {
+            index: item.get("index").and_then(|i| i.as_u64()).unwrap_or(0) as usize,
+            score: item.get("logit").and_then(|s| s.as_f64()).unwrap_or(0.0) as f32,
+            text: None,
+        })
+        .collect())
+}
+
+#[async_trait]
+impl RerankerModel for NimRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        self.rerank_with_options(query, docs, &RerankOptions::default())
+            .await
+    }
+
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = build_nim_rerank_payload(
+            &self.model_id,
+            query,
+            &docs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            self.truncate.as_deref(),
+        );
+
+        let results = self
+            .cb
+            .call(move || async move {
+                let response = self
+                    .endpoint
+                    .post("/v1/ranking")
+                    .json(&payload)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/nim", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                parse_nim_rankings(&body)
+            })
+            .await?;
+        Ok(options.apply(results, docs))
+    }
+}
+
+struct NimGeneratorModel {
+    endpoint: NimEndpoint,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    model_id: String,
+}
+
+/// Build an OpenAI-compatible `/v1/chat/completions` request body.
+fn build_nim_chat_body(
+    model_id: &str,
+    messages: &[Message],
+    options: &GenerationOptions,
+) -> serde_json::Value {
+    let messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|msg| {
+            let role = match msg.role {
+                MessageRole::System => "system",
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            };
+            json!({ "role": role, "content": msg.text() })
+        })
+        .collect();
+
+    let mut body = json!({
+        "model": model_id,
+        "messages": messages,
+    });
+
+    if let Some(max_tokens) = options.max_tokens {
+        body["max_tokens"] = json!(max_tokens);
+    }
+    if let Some(temperature) = options.temperature {
+        body["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = options.top_p {
+        body["top_p"] = json!(top_p);
+    }
+    if let Some(n) = options.n {
+        body["n"] = json!(n);
+    }
+    if let Some(stop) = &options.stop {
+        body["stop"] = json!(stop);
+    }
+    if let Some(logit_bias) = &options.logit_bias {
+        body["logit_bias"] = json!(logit_bias);
+    }
+    if let Some(effort) = options.reasoning_effort {
+        body["reasoning_effort"] = json!(effort.as_str());
+    }
+    body
+}
+
+#[async_trait]
+impl GeneratorModel for NimGeneratorModel {
+    async fn generate(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationResult> {
+        let body = build_nim_chat_body(&self.model_id, messages, &options);
+
+        let include_raw = options.include_raw;
+        self.cb
+            .call(move || async move {
+                let response = self
+                    .endpoint
+                    .post("/v1/chat/completions")
+                    .json(&body)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                let body: serde_json::Value = check_http_status("remote/nim", response)
+                    .await?
+                    .json()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+
+                let mut result = parse_openai_chat_completion("remote/nim", &body)?;
+                result.raw = include_raw.then_some(body);
+                Ok(result)
+            })
+            .await
+    }
+
+    /// Streams OpenAI-style `chat.completion.chunk` events.
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        let mut body = build_nim_chat_body(&self.model_id, messages, &options);
+        body["stream"] = json!(true);
+        body["stream_options"] = json!({ "include_usage": true });
+
+        let response = self
+            .cb
+            .call(move || async move {
+                let response = self
+                    .endpoint
+                    .post("/v1/chat/completions")
+                    .json(&body)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/nim", e))?;
+                check_http_status("remote/nim", response).await
+            })
+            .await?;
+        Ok(spawn_stream("remote/nim", response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ModelRuntimeKey;
+    use std::time::Duration;
+
+    static ENV_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    fn spec(alias: &str, task: ModelTask, model_id: &str) -> ModelAliasSpec {
+        ModelAliasSpec {
+            alias: alias.to_string(),
+            task,
+            provider_id: "remote/nim".to_string(),
+            model_id: model_id.to_string(),
+            revision: None,
+            warmup: crate::api::WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn breaker_reused_for_same_runtime_key() {
+        let provider = RemoteNimProvider::new();
+        let s1 = spec("embed/a", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+        let s2 = spec("embed/b", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+
+        let _ = provider.load(&s1).await.unwrap();
+        let _ = provider.load(&s2).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn breaker_isolated_by_task_and_model() {
+        let provider = RemoteNimProvider::new();
+        let embed = spec("embed/a", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+        let rerank = spec(
+            "rerank/a",
+            ModelTask::Rerank,
+            "nvidia/nv-rerankqa-mistral-4b-v3",
+        );
+        let generate = spec("gen/a", ModelTask::Generate, "meta/llama-3.1-8b-instruct");
+
+        let _ = provider.load(&embed).await.unwrap();
+        let _ = provider.load(&rerank).await.unwrap();
+        let _ = provider.load(&generate).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn breaker_cleanup_evicts_stale_entries() {
+        let provider = RemoteNimProvider::new();
+        let stale = spec("embed/stale", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+        let fresh = spec(
+            "gen/fresh",
+            ModelTask::Generate,
+            "meta/llama-3.1-8b-instruct",
+        );
+        provider.insert_test_breaker(
+            ModelRuntimeKey::new(&stale),
+            RemoteProviderBase::BREAKER_TTL + Duration::from_secs(5),
+        );
+        provider.insert_test_breaker(ModelRuntimeKey::new(&fresh), Duration::from_secs(1));
+        assert_eq!(provider.breaker_count(), 2);
+
+        provider.force_cleanup_now_for_test();
+        let _ = provider.load(&fresh).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_embedding_model_requires_dimensions() {
+        let provider = RemoteNimProvider::new();
+        let mut s = spec("embed/a", ModelTask::Embed, "acme/custom-embed");
+        let err = provider.load(&s).await.err().unwrap();
+        assert!(err.to_string().contains("embedding_dimensions"));
+
+        s.options = json!({ "embedding_dimensions": 384 });
+        let handle = provider.load(&s).await.unwrap();
+        let model = handle
+            .downcast_ref::<Arc<dyn EmbeddingModel>>()
+            .unwrap()
+            .clone();
+        assert_eq!(model.dimensions(), 384);
+    }
+
+    #[tokio::test]
+    async fn explicit_api_key_env_must_be_set() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::remove_var("NIM_TEST_MISSING_KEY") };
+
+        let provider = RemoteNimProvider::new();
+        let mut s = spec("gen/a", ModelTask::Generate, "meta/llama-3.1-8b-instruct");
+        s.options = json!({ "api_key_env": "NIM_TEST_MISSING_KEY" });
+        let err = provider.load(&s).await.err().unwrap();
+        assert!(err.to_string().contains("NIM_TEST_MISSING_KEY"));
+    }
+
+    #[test]
+    fn base_url_defaults_and_drops_trailing_slash() {
+        let mut s = spec("embed/a", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+        let endpoint = NimEndpoint::from_spec(Client::new(), &s).unwrap();
+        assert_eq!(endpoint.base_url, DEFAULT_BASE_URL);
+
+        s.options = json!({ "base_url": "http://gpu-node-1:9000/" });
+        let endpoint = NimEndpoint::from_spec(Client::new(), &s).unwrap();
+        assert_eq!(endpoint.base_url, "http://gpu-node-1:9000");
+    }
+
+    #[test]
+    fn embed_payload_sets_input_type_and_optional_truncate() {
+        let texts = vec!["a".to_string()];
+        let payload =
+            build_nim_embed_payload("nvidia/nv-embedqa-e5-v5", &texts, "passage", Some("END"));
+        assert_eq!(payload["input_type"], "passage");
+        assert_eq!(payload["truncate"], "END");
+        assert_eq!(payload["encoding_format"], "float");
+
+        let payload = build_nim_embed_payload("nvidia/nv-embedqa-e5-v5", &texts, "query", None);
+        assert_eq!(payload["input_type"], "query");
+        assert!(payload.get("truncate").is_none());
+    }
+
+    #[test]
+    fn truncation_option_maps_to_truncate() {
+        let mut s = spec("embed/a", ModelTask::Embed, "nvidia/nv-embedqa-e5-v5");
+        assert_eq!(truncate_option(&s), None);
+        s.options = json!({ "truncation": "start" });
+        assert_eq!(truncate_option(&s).as_deref(), Some("START"));
+        s.options = json!({ "truncate": "NONE" });
+        assert_eq!(truncate_option(&s).as_deref(), Some("NONE"));
+    }
+
+    #[test]
+    fn embeddings_are_ordered_by_index() {
+        let body = json!({
+            "data": [
+                { "index": 1, "embedding": [2.0] },
+                { "index": 0, "embedding": [1.0] }
+            ]
+        });
+        assert_eq!(
+            parse_nim_embeddings(&body).unwrap(),
+            vec![vec![1.0], vec![2.0]]
+        );
+        assert!(parse_nim_embeddings(&json!({})).is_err());
+    }
+
+    #[test]
+    fn rerank_payload_wraps_query_and_passages() {
+        let docs = vec!["a".to_string(), "b".to_string()];
+        let payload =
+            build_nim_rerank_payload("nvidia/nv-rerankqa-mistral-4b-v3", "q", &docs, Some("END"));
+        assert_eq!(payload["query"], json!({ "text": "q" }));
+        assert_eq!(
+            payload["passages"],
+            json!([{ "text": "a" }, { "text": "b" }])
+        );
+        assert_eq!(payload["truncate"], "END");
+        assert!(payload.get("top_n").is_none());
+    }
+
+    #[test]
+    fn rankings_use_logit_as_score() {
+        let body = json!({
+            "rankings": [
+                { "index": 1, "logit": 3.5 },
+                { "index": 0, "logit": -1.25 }
+            ]
+        });
+        let results = parse_nim_rankings(&body).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].index, 1);
+        assert_eq!(results[0].score, 3.5);
+        assert_eq!(results[1].score, -1.25);
+        assert!(results.iter().all(|r| r.text.is_none()));
+        assert!(parse_nim_rankings(&json!({})).is_err());
+    }
+
+    #[test]
+    fn rankings_respect_top_n_and_return_documents_locally() {
+        let body = json!({
+            "rankings": [
+                { "index": 1, "logit": 3.5 },
+                { "index": 0, "logit": -1.25 }
+            ]
+        });
+        let results = parse_nim_rankings(&body).unwrap();
+        let options = RerankOptions {
+            top_n: Some(1),
+            return_documents: true,
+            sort: true,
+        };
+        let results = options.apply(results, &["first", "second"]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].index, 1);
+        assert_eq!(results[0].text.as_deref(), Some("second"));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::api::{ModelAliasSpec, ModelTask};
+use crate::error::{Result, RuntimeError};
+use crate::traits::{
+    EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Vector length used when the `dimensions` option is not set.
+const DEFAULT_DIMENSIONS: u32 = 384;
+
+/// Typed options for `test/null` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NullEmbeddingOptions {
+    /// Length of the returned vectors (default 384).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    /// `"zero"` (default) or `"hash"` for deterministic unit vectors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+}
+
+impl NullEmbeddingOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`dimensions`](Self::dimensions).
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Set [`fill`](Self::fill).
+    pub fn with_fill(mut self, fill: impl Into<String>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+}
+
+impl From<&NullEmbeddingOptions> for serde_json::Value {
+    fn from(options: &NullEmbeddingOptions) -> Self {
+        serde_json::to_value(options).expect("NullEmbeddingOptions serializes to a JSON object")
+    }
+}
+
+/// Dependency-free embedding provider for tests of code built on the runtime.
+///
+/// Loads instantly and embeds without I/O. Options:
+///
+/// - `dimensions`: vector length, default 384.
+/// - `fill`: `"zero"` (default) returns all-zero vectors; `"hash"` returns
+///   unit vectors derived from a hash of each text, so equal texts get equal
+///   vectors on every run and platform.
+///
+/// `model_id` is used only as a label. Requires the `testing` feature.
+pub struct NullEmbeddingProvider;
+
+impl NullEmbeddingProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NullEmbeddingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ModelProvider for NullEmbeddingProvider {
+    fn provider_id(&self) -> &'static str {
+        "test/null"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_tasks: vec![ModelTask::Embed],
+        }
+    }
+
+    async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        if spec.task != ModelTask::Embed {
+            return Err(RuntimeError::CapabilityMismatch(format!(
+                "Null provider does not support task {:?}",
+                spec.task
+            )));
+        }
+        let dimensions = spec
+            .options
+            .get("dimensions")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_DIMENSIONS, |d| d as u32);
+        let hashed = spec.options.get("fill").and_then(|v| v.as_str()) == Some("hash");
+
+        let handle: Arc<dyn EmbeddingModel> = Arc::new(NullEmbeddingModel {
+            model_id: spec.model_id.clone(),
+            dimensions,
+            hashed,
+        });
+        Ok(Arc::new(handle) as LoadedModelHandle)
+    }
+
+    async fn health(&self) -> ProviderHealth {
+        ProviderHealth::Healthy
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/null.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&["dimensions", "fill"])
+    }
+}
+
+struct NullEmbeddingModel {
+    model_id: String,
+    dimensions: u32,
+    hashed: bool,
+}
+
+#[async_trait]
+impl EmbeddingModel for NullEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        Ok(texts
+            .into_iter()
+            .map(|text| {
+                if self.hashed {
+                    hashed_vector(text, self.dimensions as usize)
+                } else {
+                    vec![0.0; self.dimensions as usize]
+                }
+            })
+            .collect())
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.dimensions
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
+
+/// Unit vector seeded by a 64-bit FNV-1a hash of `text`, expanded with
+/// splitmix64. Both are fixed algorithms, so the output never changes
+/// between runs, platforms, or Rust versions.
+fn hashed_vector(text: &str, dimensions: usize) -> Vec<f32> {
+    let mut state = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let mut vector: Vec<f32> = (0..dimensions)
+        .map(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            // Top 24 bits mapped onto [-1, 1).
+            (z >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        })
+        .collect();
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(options: serde_json::Value) -> ModelAliasSpec {
+        ModelAliasSpec {
+            alias: "embed/null".to_string(),
+            task: ModelTask::Embed,
+            provider_id: "test/null".to_string(),
+            model_id: "null".to_string(),
+            revision: None,
+            warmup: crate::api::WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options,
+        }
+    }
+
+    async fn load(options: serde_json::Value) -> Arc<dyn EmbeddingModel> {
+        let handle = NullEmbeddingProvider::new()
+            .load(&spec(options))
+            .await
+            .unwrap();
+        handle
+            .downcast_ref::<Arc<dyn EmbeddingModel>>()
+            .unwrap()
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn zero_vectors_by_default() {
+        let model = load(serde_json::Value::Null).await;
+        let vectors = model.embed(vec!["a", "b"]).await.unwrap();
+        assert_eq!(model.dimensions(), 384);
+        assert_eq!(vectors, vec![vec![0.0; 384]; 2]);
+    }
+
+    #[tokio::test]
+    async fn hashed_vectors_are_deterministic_unit_vectors() {
+        let model = load(serde_json::json!({ "dimensions": 8, "fill": "hash" })).await;
+        let vectors = model.embed(vec!["a", "b", "a"]).await.unwrap();
+        assert_eq!(vectors[0].len(), 8);
+        assert_eq!(vectors[0], vectors[2]);
+        assert_ne!(vectors[0], vectors[1]);
+        let norm = vectors[1].iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+    }
+}
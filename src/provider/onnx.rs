@@ -0,0 +1,728 @@
+use crate::api::{ModelAliasSpec, ModelTask};
+use crate::error::{Result, RuntimeError};
+use crate::traits::{
+    EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RerankOptions, RerankerModel, ScoredDoc,
+};
+use async_trait::async_trait;
+use ort::session::builder::{GraphOptimizationLevel, SessionBuilder};
+use ort::session::{Session, SessionInputValue};
+use ort::value::Tensor;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tokenizers::{
+    EncodeInput, Encoding, PaddingParams, PaddingStrategy, Tokenizer, TruncationParams,
+};
+use tokio::sync::oneshot;
+
+/// Typed options for `local/onnx` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OnnxOptions {
+    /// Directory containing `model.onnx` and `tokenizer.json` (default `model_id`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    /// Compute device: `cpu` (default), `cuda`, or `cuda:N`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Maximum tokens per input; longer inputs are truncated (default 512).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Pooling for models that output token states: `mean` (default) or `cls`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pooling: Option<String>,
+    /// Embedding dimension when the model has no `config.json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
+}
+
+impl OnnxOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`model_path`](Self::model_path).
+    pub fn with_model_path(mut self, model_path: impl Into<String>) -> Self {
+        self.model_path = Some(model_path.into());
+        self
+    }
+
+    /// Set [`device`](Self::device).
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    /// Set [`max_length`](Self::max_length).
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Set [`pooling`](Self::pooling).
+    pub fn with_pooling(mut self, pooling: impl Into<String>) -> Self {
+        self.pooling = Some(pooling.into());
+        self
+    }
+
+    /// Set [`embedding_dimensions`](Self::embedding_dimensions).
+    pub fn with_embedding_dimensions(mut self, embedding_dimensions: u32) -> Self {
+        self.embedding_dimensions = Some(embedding_dimensions);
+        self
+    }
+}
+
+impl From<&OnnxOptions> for serde_json::Value {
+    fn from(options: &OnnxOptions) -> Self {
+        serde_json::to_value(options).expect("OnnxOptions serializes to a JSON object")
+    }
+}
+
+/// Local provider that runs exported ONNX models with
+/// [ONNX Runtime](https://onnxruntime.ai/) via the [`ort`](https://docs.rs/ort) crate.
+///
+/// Loads a model directory containing `model.onnx` and `tokenizer.json`,
+/// taken from the `model_path` option or, when unset, from `model_id`.
+/// Embedding models may output token states (`[batch, seq, hidden]`, pooled
+/// per the `pooling` option) or pooled vectors (`[batch, hidden]`); vectors
+/// are L2-normalized. Rerank models are cross-encoders that output one logit
+/// per query/document pair (or two, for binary classification heads); scores
+/// are the sigmoid of that logit, or the positive-class probability.
+///
+/// Unlike [`LocalCandleProvider`](super::candle::LocalCandleProvider) this
+/// does not download anything: export the model (e.g. with
+/// `optimum-cli export onnx`) and point `model_path` at the result.
+pub struct LocalOnnxProvider;
+
+impl LocalOnnxProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LocalOnnxProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ModelProvider for LocalOnnxProvider {
+    fn provider_id(&self) -> &'static str {
+        "local/onnx"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_tasks: vec![ModelTask::Embed, ModelTask::Rerank],
+        }
+    }
+
+    async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        if !matches!(spec.task, ModelTask::Embed | ModelTask::Rerank) {
+            return Err(RuntimeError::CapabilityMismatch(format!(
+                "ONNX provider does not support task {:?}",
+                spec.task
+            )));
+        }
+
+        let settings = OnnxSettings::from_spec(spec)?;
+        let dimensions = match spec.task {
+            ModelTask::Embed => Some(settings.dimensions()?),
+            _ => None,
+        };
+        tracing::info!(
+            model_dir = %settings.model_dir.display(),
+            device = ?settings.device,
+            "Initializing ONNX model"
+        );
+
+        // Creating a session parses and optimizes the whole graph.
+        let session = tokio::task::spawn_blocking(move || OnnxSession::load(&settings))
+            .await
+            .map_err(|e| RuntimeError::Load(format!("Join error: {}", e)))??;
+        let session = Arc::new(session);
+
+        match dimensions {
+            Some(dimensions) => {
+                let model = OnnxEmbeddingModel {
+                    session,
+                    model_id: spec.model_id.clone(),
+                    pooling: Pooling::from_options(&spec.options),
+                    dimensions,
+                };
+                let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+            None => {
+                let model = OnnxRerankerModel { session };
+                let handle: Arc<dyn RerankerModel> = Arc::new(model);
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
+        }
+    }
+
+    async fn health(&self) -> ProviderHealth {
+        ProviderHealth::Healthy
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/onnx.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "device",
+            "embedding_dimensions",
+            "max_length",
+            "model_path",
+            "pooling",
+        ])
+    }
+}
+
+/// Default token limit when `max_length` is not set.
+const DEFAULT_MAX_LENGTH: usize = 512;
+
+/// Stack size for inference threads; ONNX Runtime needs more than the
+/// default.
+const INFERENCE_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Compute device for a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnnxDevice {
+    Cpu,
+    Cuda(usize),
+}
+
+impl OnnxDevice {
+    /// Parse the `device` option (`"cpu"`, `"cuda"`, `"cuda:N"`).
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        if s == "cpu" {
+            return Some(Self::Cpu);
+        }
+        match s.strip_prefix("cuda")? {
+            "" => Some(Self::Cuda(0)),
+            rest => rest.strip_prefix(':')?.parse().ok().map(Self::Cuda),
+        }
+    }
+}
+
+/// Token pooling for models that output per-token states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pooling {
+    /// Attention-masked mean over tokens.
+    Mean,
+    /// The first (`[CLS]`) token.
+    Cls,
+}
+
+impl Pooling {
+    fn from_options(options: &serde_json::Value) -> Self {
+        match options.get("pooling").and_then(|v| v.as_str()) {
+            Some("cls") => Self::Cls,
+            _ => Self::Mean,
+        }
+    }
+}
+
+/// Options needed to create a session, resolved from a spec.
+struct OnnxSettings {
+    model_dir: PathBuf,
+    device: OnnxDevice,
+    max_length: usize,
+    embedding_dimensions: Option<u32>,
+}
+
+impl OnnxSettings {
+    fn from_spec(spec: &ModelAliasSpec) -> Result<Self> {
+        let options = &spec.options;
+        let model_dir = options
+            .get("model_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&spec.model_id);
+        let device = match options.get("device").and_then(|v| v.as_str()) {
+            None => OnnxDevice::Cpu,
+            Some(device) => OnnxDevice::parse(device).ok_or_else(|| {
+                RuntimeError::Config(format!(
+                    "Unsupported ONNX device '{}'; expected cpu, cuda, or cuda:N",
+                    device
+                ))
+            })?,
+        };
+        Ok(Self {
+            model_dir: PathBuf::from(model_dir),
+            device,
+            max_length: options
+                .get("max_length")
+                .and_then(|v| v.as_u64())
+                .map_or(DEFAULT_MAX_LENGTH, |n| n as usize),
+            embedding_dimensions: options
+                .get("embedding_dimensions")
+                .and_then(|v| v.as_u64())
+                .map(|d| d as u32),
+        })
+    }
+
+    /// Embedding size: the `embedding_dimensions` option, else `hidden_size`
+    /// from the directory's `config.json`.
+    fn dimensions(&self) -> Result<u32> {
+        if let Some(dimensions) = self.embedding_dimensions {
+            return Ok(dimensions);
+        }
+        let config_path = self.model_dir.join("config.json");
+        std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            .and_then(|config| config.get("hidden_size").and_then(|h| h.as_u64()))
+            .map(|h| h as u32)
+            .ok_or_else(|| {
+                RuntimeError::Config(format!(
+                    "Cannot read hidden_size from {}; set the 'embedding_dimensions' option",
+                    config_path.display()
+                ))
+            })
+    }
+}
+
+/// Register the execution provider for `device` on `builder`.
+///
+/// ONNX Runtime falls back to CPU, logging a warning, when the CUDA provider
+/// fails to initialize.
+#[allow(unused_variables)]
+fn with_device(builder: SessionBuilder, device: OnnxDevice) -> ort::Result<SessionBuilder> {
+    match device {
+        OnnxDevice::Cpu => Ok(builder),
+        OnnxDevice::Cuda(ordinal) => {
+            #[cfg(feature = "gpu-cuda")]
+            {
+                builder.with_execution_providers([
+                    ort::execution_providers::CUDAExecutionProvider::default()
+                        .with_device_id(ordinal as i32)
+                        .build(),
+                ])
+            }
+            #[cfg(not(feature = "gpu-cuda"))]
+            {
+                tracing::warn!(
+                    ordinal,
+                    "CUDA support not compiled in (enable the gpu-cuda feature); falling back to CPU"
+                );
+                Ok(builder)
+            }
+        }
+    }
+}
+
+/// Tokenized batch laid out row-major as `[size, seq_len]`.
+#[derive(Debug)]
+struct Batch {
+    size: usize,
+    seq_len: usize,
+    input_ids: Vec<i64>,
+    attention_mask: Vec<i64>,
+    token_type_ids: Vec<i64>,
+}
+
+impl Batch {
+    /// Flatten encodings that were padded to a common length.
+    fn from_encodings(encodings: &[Encoding]) -> Self {
+        let seq_len = encodings.first().map_or(0, |e| e.get_ids().len());
+        let flatten = |field: fn(&Encoding) -> &[u32]| -> Vec<i64> {
+            encodings
+                .iter()
+                .flat_map(|e| field(e).iter().map(|&x| x as i64))
+                .collect()
+        };
+        Self {
+            size: encodings.len(),
+            seq_len,
+            input_ids: flatten(Encoding::get_ids),
+            attention_mask: flatten(Encoding::get_attention_mask),
+            token_type_ids: flatten(Encoding::get_type_ids),
+        }
+    }
+}
+
+/// A loaded session and its tokenizer, shared by the model handles.
+struct OnnxSession {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    /// BERT-style exports take `token_type_ids`; others reject it.
+    uses_token_type_ids: bool,
+}
+
+impl OnnxSession {
+    fn load(settings: &OnnxSettings) -> Result<Self> {
+        let model_path = settings.model_dir.join("model.onnx");
+        let tokenizer_path = settings.model_dir.join("tokenizer.json");
+        for path in [&model_path, &tokenizer_path] {
+            if !path.is_file() {
+                return Err(RuntimeError::Load(format!(
+                    "ONNX model file not found: {}",
+                    path.display()
+                )));
+            }
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| RuntimeError::Load(format!("Failed to load tokenizer: {}", e)))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: settings.max_length,
+                ..Default::default()
+            }))
+            .map_err(|e| RuntimeError::Load(format!("Failed to set truncation: {}", e)))?;
+
+        let session = Session::builder()
+            .and_then(|b| b.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|b| with_device(b, settings.device))
+            .and_then(|b| b.commit_from_file(&model_path))
+            .map_err(|e| {
+                RuntimeError::Load(format!(
+                    "Failed to load ONNX model {}: {}",
+                    model_path.display(),
+                    e
+                ))
+            })?;
+        let uses_token_type_ids = session.inputs.iter().any(|i| i.name == "token_type_ids");
+
+        Ok(Self {
+            session: Mutex::new(session),
+            tokenizer,
+            uses_token_type_ids,
+        })
+    }
+
+    /// Tokenize `inputs` and run the session, returning its first output.
+    fn run<'s, I>(&self, inputs: Vec<I>) -> Result<RunOutput>
+    where
+        I: Into<EncodeInput<'s>> + Send,
+    {
+        let encodings = self
+            .tokenizer
+            .encode_batch(inputs, true)
+            .map_err(|e| RuntimeError::InferenceError(format!("Tokenization failed: {}", e)))?;
+        let batch = Batch::from_encodings(&encodings);
+
+        let shape = [batch.size, batch.seq_len];
+        let tensor = |values: &[i64]| -> Result<SessionInputValue<'static>> {
+            Tensor::from_array((shape, values.to_vec()))
+                .map(Into::into)
+                .map_err(|e| RuntimeError::InferenceError(e.to_string()))
+        };
+        let mut session_inputs: Vec<(Cow<'static, str>, SessionInputValue<'static>)> = vec![
+            ("input_ids".into(), tensor(&batch.input_ids)?),
+            ("attention_mask".into(), tensor(&batch.attention_mask)?),
+        ];
+        if self.uses_token_type_ids {
+            session_inputs.push(("token_type_ids".into(), tensor(&batch.token_type_ids)?));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| RuntimeError::InferenceError("Failed to lock ONNX session".to_string()))?;
+        let outputs = session
+            .run(session_inputs)
+            .map_err(|e| RuntimeError::InferenceError(format!("ONNX Runtime error: {}", e)))?;
+        let (output_shape, values) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| RuntimeError::InferenceError(e.to_string()))?;
+        Ok(RunOutput {
+            shape: output_shape.to_vec(),
+            values: values.to_vec(),
+            attention_mask: batch.attention_mask,
+        })
+    }
+}
+
+/// First output of a session run, with the mask of the batch that produced
+/// it.
+struct RunOutput {
+    shape: Vec<i64>,
+    values: Vec<f32>,
+    attention_mask: Vec<i64>,
+}
+
+/// Run `f` on a short-lived thread with an enlarged stack, as ONNX Runtime
+/// requires.
+async fn run_on_worker<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    thread::Builder::new()
+        .name("onnx-worker".to_string())
+        .stack_size(INFERENCE_THREAD_STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(f());
+        })
+        .map_err(|e| {
+            RuntimeError::InferenceError(format!("Failed to spawn inference thread: {}", e))
+        })?;
+    rx.await
+        .map_err(|_| RuntimeError::InferenceError("Inference thread panicked".to_string()))?
+}
+
+/// Turn an output of shape `[batch, hidden]` or `[batch, seq, hidden]` into
+/// L2-normalized vectors, pooling token states with `attention_mask`.
+fn pool_embeddings(
+    shape: &[i64],
+    values: &[f32],
+    attention_mask: &[i64],
+    pooling: Pooling,
+) -> Result<Vec<Vec<f32>>> {
+    let mut vectors: Vec<Vec<f32>> = match *shape {
+        [_, hidden] => values
+            .chunks(hidden as usize)
+            .map(<[f32]>::to_vec)
+            .collect(),
+        [batch, seq, hidden] => {
+            let (seq, hidden) = (seq as usize, hidden as usize);
+            (0..batch as usize)
+                .map(|b| {
+                    let tokens = &values[b * seq * hidden..(b + 1) * seq * hidden];
+                    match pooling {
+                        Pooling::Cls => tokens[..hidden].to_vec(),
+                        Pooling::Mean => {
+                            let mask = &attention_mask[b * seq..(b + 1) * seq];
+                            let mut sum = vec![0.0f32; hidden];
+                            let mut count = 0.0f32;
+                            for (token, &m) in tokens.chunks(hidden).zip(mask) {
+                                if m == 0 {
+                                    continue;
+                                }
+                                count += 1.0;
+                                for (s, v) in sum.iter_mut().zip(token) {
+                                    *s += v;
+                                }
+                            }
+                            sum.iter().map(|s| s / count.max(1.0)).collect()
+                        }
+                    }
+                })
+                .collect()
+        }
+        _ => {
+            return Err(RuntimeError::InferenceError(format!(
+                "Unexpected ONNX embedding output shape {:?}",
+                shape
+            )));
+        }
+    };
+
+    for vector in &mut vectors {
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt().max(1e-12);
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    Ok(vectors)
+}
+
+/// Turn cross-encoder logits of shape `[batch]`, `[batch, 1]`, or
+/// `[batch, 2]` into relevance scores in `[0, 1]`.
+fn relevance_scores(shape: &[i64], values: &[f32]) -> Result<Vec<f32>> {
+    let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+    match *shape {
+        [_] | [_, 1] => Ok(values.iter().map(|&x| sigmoid(x)).collect()),
+        // Softmax over two classes is the sigmoid of their difference.
+        [_, 2] => Ok(values.chunks(2).map(|l| sigmoid(l[1] - l[0])).collect()),
+        _ => Err(RuntimeError::InferenceError(format!(
+            "Unexpected ONNX reranker output shape {:?}",
+            shape
+        ))),
+    }
+}
+
+struct OnnxEmbeddingModel {
+    session: Arc<OnnxSession>,
+    model_id: String,
+    pooling: Pooling,
+    dimensions: u32,
+}
+
+#[async_trait]
+impl EmbeddingModel for OnnxEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let session = self.session.clone();
+        let pooling = self.pooling;
+
+        run_on_worker(move || {
+            let output = session.run(texts)?;
+            pool_embeddings(
+                &output.shape,
+                &output.values,
+                &output.attention_mask,
+                pooling,
+            )
+        })
+        .await
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.dimensions
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
+
+struct OnnxRerankerModel {
+    session: Arc<OnnxSession>,
+}
+
+#[async_trait]
+impl RerankerModel for OnnxRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pairs: Vec<(String, String)> = docs
+            .iter()
+            .map(|doc| (query.to_string(), doc.to_string()))
+            .collect();
+        let session = self.session.clone();
+
+        let scores = run_on_worker(move || {
+            let output = session.run(pairs)?;
+            relevance_scores(&output.shape, &output.values)
+        })
+        .await?;
+
+        let results = scores
+            .into_iter()
+            .enumerate()
+            .map(|(index, score)| ScoredDoc {
+                index,
+                score,
+                text: None,
+            })
+            .collect();
+        Ok(RerankOptions::default().apply(results, docs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
+        ModelAliasSpec {
+            alias: "test/onnx".to_string(),
+            task,
+            provider_id: "local/onnx".to_string(),
+            model_id: "/nonexistent/onnx-model".to_string(),
+            revision: None,
+            warmup: crate::api::WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options,
+        }
+    }
+
+    #[test]
+    fn parses_devices() {
+        assert_eq!(OnnxDevice::parse("cpu"), Some(OnnxDevice::Cpu));
+        assert_eq!(OnnxDevice::parse("CUDA"), Some(OnnxDevice::Cuda(0)));
+        assert_eq!(OnnxDevice::parse("cuda:1"), Some(OnnxDevice::Cuda(1)));
+        assert_eq!(OnnxDevice::parse("cuda:x"), None);
+        assert_eq!(OnnxDevice::parse("metal"), None);
+    }
+
+    #[test]
+    fn mean_pooling_ignores_padding_and_normalizes() {
+        // Two sequences of two tokens, hidden size 2; the second is padded.
+        let values = [1.0, 0.0, 3.0, 0.0, 0.0, 2.0, 9.0, 9.0];
+        let mask = [1, 1, 1, 0];
+        let vectors = pool_embeddings(&[2, 2, 2], &values, &mask, Pooling::Mean).unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+        let cls = pool_embeddings(&[2, 2, 2], &values, &mask, Pooling::Cls).unwrap();
+        assert_eq!(cls[0], vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn pooled_outputs_are_only_normalized() {
+        let vectors = pool_embeddings(&[1, 2], &[3.0, 4.0], &[1], Pooling::Mean).unwrap();
+        assert_eq!(vectors, vec![vec![0.6, 0.8]]);
+        assert!(pool_embeddings(&[4], &[0.0; 4], &[], Pooling::Mean).is_err());
+    }
+
+    #[test]
+    fn relevance_scores_handle_single_and_binary_heads() {
+        let single = relevance_scores(&[2, 1], &[0.0, 100.0]).unwrap();
+        assert_eq!(single[0], 0.5);
+        assert!(single[1] > 0.99);
+
+        let binary = relevance_scores(&[1, 2], &[1.0, 1.0]).unwrap();
+        assert_eq!(binary, vec![0.5]);
+        assert!(relevance_scores(&[1, 3], &[0.0; 3]).is_err());
+    }
+
+    #[test]
+    fn dimensions_prefer_option_then_config() {
+        let dir = std::env::temp_dir().join(format!("uni-xervo-onnx-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("config.json"), r#"{"hidden_size": 384}"#).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        let from_config = OnnxSettings::from_spec(&spec(
+            ModelTask::Embed,
+            serde_json::json!({ "model_path": path }),
+        ))
+        .unwrap();
+        assert_eq!(from_config.dimensions().unwrap(), 384);
+
+        let from_option = OnnxSettings::from_spec(&spec(
+            ModelTask::Embed,
+            serde_json::json!({ "model_path": path, "embedding_dimensions": 768 }),
+        ))
+        .unwrap();
+        assert_eq!(from_option.dimensions().unwrap(), 768);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn generate_capability_mismatch() {
+        let err = LocalOnnxProvider::new()
+            .load(&spec(ModelTask::Generate, serde_json::Value::Null))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("does not support task"));
+    }
+
+    #[tokio::test]
+    async fn missing_model_directory_fails_to_load() {
+        let err = LocalOnnxProvider::new()
+            .load(&spec(ModelTask::Rerank, serde_json::Value::Null))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, RuntimeError::Load(_)));
+        assert!(err.to_string().contains("model.onnx"));
+    }
+}
@@ -1,20 +1,88 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::provider::openai_stream::spawn_stream;
+use crate::provider::remote_common::{
+    RateLimitState, RemoteProviderBase, RequestIdExt, Truncation, check_http_status,
+    parse_openai_chat_completion, parse_openai_embeddings, probe_request, resolve_api_key,
+    resolve_base_url, verify_credentials, wants_base64_embeddings,
+};
 use crate::traits::{
-    EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
-    Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage,
+    EmbeddingModel, GenerationOptions, GenerationResult, GenerationStream, GeneratorModel,
+    LoadedModelHandle, Message, MessageRole, ModelProvider, ProviderCapabilities, ProviderHealth,
+    RateLimitSnapshot, TokenUsage,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+
+/// Typed options for `remote/openai` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAIOptions {
+    /// Environment variable holding the API key (default `OPENAI_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.openai.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Embedding wire format: `"float"` (default) or `"base64"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_format: Option<String>,
+    /// Only `none` is accepted: OpenAI rejects over-long embedding inputs
+    /// rather than truncating them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+}
+
+impl OpenAIOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`encoding_format`](Self::encoding_format).
+    pub fn with_encoding_format(mut self, encoding_format: impl Into<String>) -> Self {
+        self.encoding_format = Some(encoding_format.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+}
+
+impl From<&OpenAIOptions> for serde_json::Value {
+    fn from(options: &OpenAIOptions) -> Self {
+        serde_json::to_value(options).expect("OpenAIOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [OpenAI API](https://platform.openai.com/docs/api-reference)
 /// for embedding (`/v1/embeddings`) and text generation (`/v1/chat/completions`).
 ///
 /// Requires the `OPENAI_API_KEY` environment variable (or a custom env var name
-/// via the `api_key_env` option).
+/// via the `api_key_env` option). The `base_url` option replaces the API root,
+/// e.g. to route an alias through a proxy.
 pub struct RemoteOpenAIProvider {
     base: RemoteProviderBase,
 }
@@ -32,6 +100,22 @@ impl RemoteOpenAIProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .get(format!("{DEFAULT_BASE_URL}/v1/models"))
+            .header("Authorization", format!("Bearer {}", api_key))
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -63,14 +147,18 @@ impl ModelProvider for RemoteOpenAIProvider {
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "OPENAI_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         match spec.task {
             ModelTask::Embed => {
                 let model = OpenAIEmbeddingModel {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
+                    base64: wants_base64_embeddings(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -79,7 +167,9 @@ impl ModelProvider for RemoteOpenAIProvider {
                 let model = OpenAIGeneratorModel {
                     client: self.base.client.clone(),
                     cb,
+                    rate_limits: RateLimitState::default(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
@@ -95,53 +185,122 @@ impl ModelProvider for RemoteOpenAIProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Lists models via `GET /v1/models`, which is free.
+    /// Uses the default `OPENAI_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "OPENAI_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `OPENAI_API_KEY`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/openai", "OPENAI_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/openai.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&["api_key_env", "base_url", "encoding_format", "truncation"])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 /// Embedding model backed by the OpenAI embeddings API.
 pub struct OpenAIEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.openai.com`.
+    base_url: String,
     api_key: String,
+    /// Request `encoding_format: "base64"` instead of float arrays.
+    base64: bool,
 }
 
 #[async_trait]
 impl EmbeddingModel for OpenAIEmbeddingModel {
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        if texts.is_empty() {
+            return Ok((Vec::new(), None));
+        }
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let mut payload = json!({
+            "model": self.model_id,
+            "input": texts
+        });
+        if self.base64 {
+            payload["encoding_format"] = json!("base64");
+        }
 
         self.cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.openai.com/v1/embeddings")
+                    .post(format!("{}/v1/embeddings", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
-                    .json(&json!({
-                        "model": self.model_id,
-                        "input": texts
-                    }))
+                    .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/openai", e))?;
 
-                let body: serde_json::Value = check_http_status("OpenAI", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/openai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let mut embeddings = Vec::new();
-                if let Some(data) = body.get("data").and_then(|d| d.as_array()) {
-                    for item in data {
-                        if let Some(embedding) = item.get("embedding").and_then(|e| e.as_array()) {
-                            let vec: Vec<f32> = embedding
-                                .iter()
-                                .filter_map(|v| v.as_f64().map(|f| f as f32))
-                                .collect();
-                            embeddings.push(vec);
-                        }
+                    .map_err(|e| RuntimeError::api_error("remote/openai", e))?;
+
+                let embeddings = parse_openai_embeddings("remote/openai", &body)?;
+
+                let usage = body.get("usage").map(|u| {
+                    let prompt_tokens = u["prompt_tokens"].as_u64().unwrap_or(0) as usize;
+                    TokenUsage {
+                        prompt_tokens,
+                        completion_tokens: 0,
+                        total_tokens: u["total_tokens"]
+                            .as_u64()
+                            .map(|t| t as usize)
+                            .unwrap_or(prompt_tokens),
+                        ..Default::default()
                     }
-                }
-                Ok(embeddings)
+                });
+
+                Ok((embeddings, usage))
             })
             .await
     }
@@ -156,6 +315,10 @@ impl EmbeddingModel for OpenAIEmbeddingModel {
     fn model_id(&self) -> &str {
         &self.model_id
     }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -165,10 +328,66 @@ impl EmbeddingModel for OpenAIEmbeddingModel {
 struct OpenAIGeneratorModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
+    rate_limits: RateLimitState,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.openai.com`.
+    base_url: String,
     api_key: String,
 }
 
+/// Build the `/v1/chat/completions` request body.
+fn build_openai_chat_body(
+    model_id: &str,
+    messages: &[Message],
+    options: &GenerationOptions,
+) -> serde_json::Value {
+    let messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|msg| {
+            let role = match msg.role {
+                MessageRole::System => "system",
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            };
+            json!({ "role": role, "content": msg.text() })
+        })
+        .collect();
+
+    let mut body = json!({
+        "model": model_id,
+        "messages": messages,
+    });
+
+    if let Some(max_tokens) = options.max_tokens {
+        body["max_tokens"] = json!(max_tokens);
+    }
+    if let Some(temperature) = options.temperature {
+        body["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = options.top_p {
+        body["top_p"] = json!(top_p);
+    }
+    if let Some(n) = options.n {
+        body["n"] = json!(n);
+    }
+    if let Some(stop) = &options.stop {
+        body["stop"] = json!(stop);
+    }
+    if let Some(logit_bias) = &options.logit_bias {
+        body["logit_bias"] = json!(logit_bias);
+    }
+    if options.logprobs || options.top_logprobs.is_some() {
+        body["logprobs"] = json!(true);
+    }
+    if let Some(top_logprobs) = options.top_logprobs {
+        body["top_logprobs"] = json!(top_logprobs);
+    }
+    if let Some(effort) = options.reasoning_effort {
+        body["reasoning_effort"] = json!(effort.as_str());
+    }
+    body
+}
+
 #[async_trait]
 impl GeneratorModel for OpenAIGeneratorModel {
     async fn generate(
@@ -176,69 +395,77 @@ impl GeneratorModel for OpenAIGeneratorModel {
         messages: &[Message],
         options: GenerationOptions,
     ) -> Result<GenerationResult> {
-        let messages: Vec<serde_json::Value> = messages
-            .iter()
-            .map(|msg| {
-                let role = match msg.role {
-                    MessageRole::System => "system",
-                    MessageRole::User => "user",
-                    MessageRole::Assistant => "assistant",
-                };
-                json!({ "role": role, "content": msg.text() })
-            })
-            .collect();
+        let body = build_openai_chat_body(&self.model_id, messages, &options);
+        let idempotency_key = options.idempotency_key.as_deref();
 
+        let include_raw = options.include_raw;
         self.cb
             .call(move || async move {
-                let mut body = json!({
-                    "model": self.model_id,
-                    "messages": messages,
-                });
-
-                if let Some(max_tokens) = options.max_tokens {
-                    body["max_tokens"] = json!(max_tokens);
-                }
-                if let Some(temperature) = options.temperature {
-                    body["temperature"] = json!(temperature);
-                }
-                if let Some(top_p) = options.top_p {
-                    body["top_p"] = json!(top_p);
-                }
-
-                let response = self
+                let mut request = self
                     .client
-                    .post("https://api.openai.com/v1/chat/completions")
-                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .post(format!("{}/v1/chat/completions", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key));
+                if let Some(key) = idempotency_key {
+                    request = request.header("Idempotency-Key", key);
+                }
+                let response = request
                     .json(&body)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/openai", e))?;
 
-                let body: serde_json::Value = check_http_status("OpenAI", response)?
+                self.rate_limits.record(response.headers());
+                let body: serde_json::Value = check_http_status("remote/openai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
-
-                let text = body["choices"][0]["message"]["content"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
+                    .map_err(|e| RuntimeError::api_error("remote/openai", e))?;
 
-                let usage = body.get("usage").map(|u| TokenUsage {
-                    prompt_tokens: u["prompt_tokens"].as_u64().unwrap_or(0) as usize,
-                    completion_tokens: u["completion_tokens"].as_u64().unwrap_or(0) as usize,
-                    total_tokens: u["total_tokens"].as_u64().unwrap_or(0) as usize,
-                });
-
-                Ok(GenerationResult {
-                    text,
-                    usage,
-                    images: vec![],
-                    audio: None,
-                })
+                let mut result = parse_openai_chat_completion("remote/openai", &body)?;
+                result.raw = include_raw.then_some(body);
+                Ok(result)
             })
             .await
     }
+
+    /// Streams `chat.completion.chunk` events; the final chunk carries usage.
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        let mut body = build_openai_chat_body(&self.model_id, messages, &options);
+        body["stream"] = json!(true);
+        body["stream_options"] = json!({ "include_usage": true });
+        let idempotency_key = options.idempotency_key.as_deref();
+
+        let response = self
+            .cb
+            .call(move || async move {
+                let mut request = self
+                    .client
+                    .post(format!("{}/v1/chat/completions", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key));
+                if let Some(key) = idempotency_key {
+                    request = request.header("Idempotency-Key", key);
+                }
+                let response = request
+                    .json(&body)
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/openai", e))?;
+                self.rate_limits.record(response.headers());
+                check_http_status("remote/openai", response).await
+            })
+            .await?;
+        Ok(spawn_stream("remote/openai", response))
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limits.snapshot()
+    }
 }
 
 #[cfg(test)]
@@ -262,6 +489,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -286,6 +514,24 @@ mod tests {
         unsafe { std::env::remove_var("OPENAI_API_KEY") };
     }
 
+    #[tokio::test]
+    async fn providers_built_with_shared_client_load_models() {
+        let _lock = ENV_LOCK.lock().await;
+        // SAFETY: protected by ENV_LOCK
+        unsafe { std::env::set_var("OPENAI_API_KEY", "test-key") };
+
+        let client = Client::builder().pool_max_idle_per_host(4).build().unwrap();
+        let a = RemoteOpenAIProvider::with_client(client.clone());
+        let b = RemoteOpenAIProvider::with_client(client);
+
+        let s = spec("embed/a", ModelTask::Embed, "text-embedding-3-small");
+        assert!(a.load(&s).await.is_ok());
+        assert!(b.load(&s).await.is_ok());
+
+        // SAFETY: protected by ENV_LOCK
+        unsafe { std::env::remove_var("OPENAI_API_KEY") };
+    }
+
     #[tokio::test]
     async fn breaker_isolated_by_task_and_model() {
         let _lock = ENV_LOCK.lock().await;
@@ -329,4 +575,58 @@ mod tests {
         // SAFETY: protected by ENV_LOCK
         unsafe { std::env::remove_var("OPENAI_API_KEY") };
     }
+
+    #[test]
+    fn chat_body_includes_stop_and_logit_bias() {
+        let options = GenerationOptions {
+            stop: Some(vec!["END".to_string()]),
+            logit_bias: Some(std::collections::HashMap::from([(50256, -100.0)])),
+            ..Default::default()
+        };
+        let body = build_openai_chat_body("gpt-4o-mini", &[Message::user("hi")], &options);
+        assert_eq!(body["stop"], json!(["END"]));
+        assert_eq!(body["logit_bias"], json!({ "50256": -100.0 }));
+
+        let body = build_openai_chat_body(
+            "gpt-4o-mini",
+            &[Message::user("hi")],
+            &GenerationOptions::default(),
+        );
+        assert!(body.get("stop").is_none());
+        assert!(body.get("logit_bias").is_none());
+    }
+
+    #[test]
+    fn chat_body_requests_logprobs() {
+        let body = build_openai_chat_body(
+            "gpt-4o-mini",
+            &[Message::user("hi")],
+            &GenerationOptions {
+                top_logprobs: Some(3),
+                ..Default::default()
+            },
+        );
+        assert_eq!(body["logprobs"], json!(true));
+        assert_eq!(body["top_logprobs"], json!(3));
+
+        let body = build_openai_chat_body(
+            "gpt-4o-mini",
+            &[Message::user("hi")],
+            &GenerationOptions::default(),
+        );
+        assert!(body.get("logprobs").is_none());
+    }
+
+    #[test]
+    fn chat_body_sends_reasoning_effort() {
+        let body = build_openai_chat_body(
+            "o4-mini",
+            &[Message::user("hi")],
+            &GenerationOptions {
+                reasoning_effort: Some(crate::traits::ReasoningEffort::High),
+                ..Default::default()
+            },
+        );
+        assert_eq!(body["reasoning_effort"], json!("high"));
+    }
 }
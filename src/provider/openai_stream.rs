@@ -0,0 +1,231 @@
+//! Streaming for OpenAI-compatible chat completion APIs: Server-Sent Events
+//! decoding and translation of `chat.completion.chunk` events into
+//! [`GenerationChunk`]s.
+//!
+//! Requests are sent with `"stream": true` and
+//! `"stream_options": {"include_usage": true}`, so the last event before
+//! `[DONE]` carries token usage with an empty `choices` array.
+
+use crate::error::{Result, RuntimeError};
+use crate::traits::{GenerationChunk, GenerationStream, TokenUsage};
+use tokio::sync::mpsc;
+
+/// Chunks buffered between the reader task and the consumer.
+const STREAM_BUFFER: usize = 32;
+
+/// Incremental decoder for a `text/event-stream` body.
+///
+/// Bytes may be pushed in arbitrarily split pieces; only complete events are
+/// returned. Only `data` fields are kept (multiple `data` lines in one event
+/// are joined with `\n`); comments and other fields are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes after the last complete line.
+    partial: Vec<u8>,
+    /// `data` lines of the event being assembled.
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Feed the next piece of the body and return the `data` payloads of the
+    /// events it completes.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.partial.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(newline) = self.partial.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.partial.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+        events
+    }
+}
+
+/// Translate one event payload into a chunk.
+///
+/// Returns `Ok(None)` for events with nothing to report (such as the initial
+/// role-only delta) and an error for `{"error": ...}` frames.
+pub(crate) fn parse_stream_event(provider_id: &str, data: &str) -> Result<Option<GenerationChunk>> {
+    let event: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+        RuntimeError::api_error(provider_id, format!("Invalid stream event: {}", e))
+    })?;
+
+    if let Some(error) = event.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Err(RuntimeError::api_error(provider_id, message));
+    }
+
+    let text = event
+        .pointer("/choices/0/delta/content")
+        .and_then(|c| c.as_str())
+        .unwrap_or("")
+        .to_string();
+    let usage = event
+        .get("usage")
+        .filter(|u| !u.is_null())
+        .map(|u| TokenUsage {
+            prompt_tokens: u["prompt_tokens"].as_u64().unwrap_or(0) as usize,
+            completion_tokens: u["completion_tokens"].as_u64().unwrap_or(0) as usize,
+            total_tokens: u["total_tokens"].as_u64().unwrap_or(0) as usize,
+            ..Default::default()
+        });
+
+    if text.is_empty() && usage.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(GenerationChunk { text, usage }))
+}
+
+/// Read `response` as an event stream in a background task and forward its
+/// chunks.
+///
+/// The stream ends after `[DONE]`. A transport error, an error frame, or a
+/// body that ends before `[DONE]` is forwarded as a final `Err` item. The
+/// task stops, dropping the connection, once the receiver is dropped.
+pub(crate) fn spawn_stream(
+    provider_id: &'static str,
+    response: reqwest::Response,
+) -> GenerationStream {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(forward_events(provider_id, response, tx));
+    rx
+}
+
+async fn forward_events(
+    provider_id: &'static str,
+    mut response: reqwest::Response,
+    tx: mpsc::Sender<Result<GenerationChunk>>,
+) {
+    let mut decoder = SseDecoder::default();
+    loop {
+        let bytes = match response.chunk().await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                let _ = tx
+                    .send(Err(RuntimeError::api_error(
+                        provider_id,
+                        "Stream ended before [DONE]",
+                    )))
+                    .await;
+                return;
+            }
+            Err(e) => {
+                let _ = tx.send(Err(RuntimeError::api_error(provider_id, e))).await;
+                return;
+            }
+        };
+
+        for data in decoder.push(&bytes) {
+            if data.trim() == "[DONE]" {
+                return;
+            }
+            match parse_stream_event(provider_id, &data) {
+                Ok(None) => {}
+                Ok(Some(chunk)) => {
+                    if tx.send(Ok(chunk)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e)).await;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recorded `/v1/chat/completions` stream with `include_usage` set.
+    const FIXTURE: &str = include_str!("../../tests/fixtures/openai_chat_stream.sse");
+
+    fn response(body: &str) -> reqwest::Response {
+        http::Response::builder()
+            .status(200)
+            .header("content-type", "text/event-stream")
+            .body(body.to_string())
+            .unwrap()
+            .into()
+    }
+
+    async fn collect(mut stream: GenerationStream) -> Vec<Result<GenerationChunk>> {
+        let mut items = Vec::new();
+        while let Some(item) = stream.recv().await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn decoder_handles_split_lines_and_multiline_data() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b": keep-alive\n\nda").is_empty());
+        assert!(decoder.push(b"ta: {\"a\":\r\n").is_empty());
+        assert_eq!(decoder.push(b"data: 1}\r\n\r\n"), vec!["{\"a\":\n1}"]);
+    }
+
+    #[tokio::test]
+    async fn fixture_stream_accumulates_text_and_usage() {
+        let items = collect(spawn_stream("remote/openai", response(FIXTURE))).await;
+        let chunks: Vec<GenerationChunk> = items.into_iter().map(|c| c.unwrap()).collect();
+
+        let text: String = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(text, "Hello! How can I help you today?");
+
+        let last = chunks.last().unwrap();
+        assert!(last.text.is_empty());
+        let usage = last.usage.as_ref().unwrap();
+        assert_eq!(usage.prompt_tokens, 9);
+        assert_eq!(usage.completion_tokens, 9);
+        assert_eq!(usage.total_tokens, 18);
+        assert!(chunks[..chunks.len() - 1].iter().all(|c| c.usage.is_none()));
+    }
+
+    #[tokio::test]
+    async fn error_frame_yields_error_and_ends_stream() {
+        let body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"}}]}\n\n",
+            "data: {\"error\":{\"message\":\"The server had an error\",\"type\":\"server_error\"}}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"ignored\"}}]}\n\n",
+        );
+        let items = collect(spawn_stream("remote/openai", response(body))).await;
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap().text, "Hi");
+        let err = items[1].as_ref().unwrap_err();
+        assert_eq!(err.provider(), Some("remote/openai"));
+        assert!(err.to_string().contains("The server had an error"));
+    }
+
+    #[tokio::test]
+    async fn truncated_stream_is_an_error() {
+        let body = "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"}}]}\n\n";
+        let items = collect(spawn_stream("remote/openai", response(body))).await;
+
+        assert_eq!(items.len(), 2);
+        assert!(
+            items[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("[DONE]")
+        );
+    }
+}
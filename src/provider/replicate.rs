@@ -0,0 +1,726 @@
+use crate::api::{ModelAliasSpec, ModelTask};
+use crate::error::{Result, RuntimeError};
+use crate::provider::remote_common::{
+    RemoteProviderBase, RequestIdExt, check_http_status, probe_request, resolve_api_key,
+    resolve_base_url, verify_credentials,
+};
+use crate::traits::{
+    GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle, Message, MessageRole,
+    ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage, generate_each,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.replicate.com";
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Typed options for `remote/replicate` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplicateOptions {
+    /// Environment variable holding the API key (default `REPLICATE_API_TOKEN`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.replicate.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Delay between prediction status polls, in milliseconds (default 1000).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll_interval_ms: Option<u64>,
+    /// Extra model-specific inputs merged into every prediction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ReplicateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`poll_interval_ms`](Self::poll_interval_ms).
+    pub fn with_poll_interval_ms(mut self, poll_interval_ms: u64) -> Self {
+        self.poll_interval_ms = Some(poll_interval_ms);
+        self
+    }
+
+    /// Set [`input`](Self::input).
+    pub fn with_input(mut self, input: serde_json::Map<String, serde_json::Value>) -> Self {
+        self.input = Some(input);
+        self
+    }
+}
+
+impl From<&ReplicateOptions> for serde_json::Value {
+    fn from(options: &ReplicateOptions) -> Self {
+        serde_json::to_value(options).expect("ReplicateOptions serializes to a JSON object")
+    }
+}
+
+/// Remote provider that runs text generation models hosted on
+/// [Replicate](https://replicate.com/docs/reference/http) via its
+/// predictions API. Does not support embedding or reranking.
+///
+/// Predictions are asynchronous: each call creates a prediction and polls it
+/// until it succeeds, fails, or is canceled. If the call is dropped before the
+/// prediction finishes (for example because the alias `timeout` elapsed), the
+/// prediction is canceled so it stops consuming compute.
+///
+/// Requires the `REPLICATE_API_TOKEN` environment variable (or a custom env
+/// var name via the `api_key_env` option). The `base_url` option replaces the
+/// API root, e.g. to route an alias through a proxy.
+pub struct RemoteReplicateProvider {
+    base: RemoteProviderBase,
+}
+
+impl Default for RemoteReplicateProvider {
+    fn default() -> Self {
+        Self {
+            base: RemoteProviderBase::new(),
+        }
+    }
+}
+
+impl RemoteReplicateProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .get(format!("{DEFAULT_BASE_URL}/v1/account"))
+            .bearer_auth(api_key)
+    }
+
+    #[cfg(test)]
+    fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
+        self.base.insert_test_breaker(key, age);
+    }
+
+    #[cfg(test)]
+    fn breaker_count(&self) -> usize {
+        self.base.breaker_count()
+    }
+
+    #[cfg(test)]
+    fn force_cleanup_now_for_test(&self) {
+        self.base.force_cleanup_now_for_test();
+    }
+}
+
+#[async_trait]
+impl ModelProvider for RemoteReplicateProvider {
+    fn provider_id(&self) -> &'static str {
+        "remote/replicate"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_tasks: vec![ModelTask::Generate],
+        }
+    }
+
+    async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+        if spec.task != ModelTask::Generate {
+            return Err(RuntimeError::CapabilityMismatch(format!(
+                "Replicate provider does not support task {:?}",
+                spec.task
+            )));
+        }
+
+        let cb = self.base.circuit_breaker_for(spec);
+        let api_key = resolve_api_key(&spec.options, "api_key_env", "REPLICATE_API_TOKEN")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
+        let target = PredictionTarget::parse(&spec.model_id)?;
+
+        let poll_interval = Duration::from_millis(
+            spec.options
+                .get("poll_interval_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_POLL_INTERVAL_MS),
+        );
+        let extra_input = spec
+            .options
+            .get("input")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        let model = ReplicateGeneratorModel {
+            client: self.base.client.clone(),
+            cb,
+            target,
+            base_url,
+            api_key,
+            poll_interval,
+            extra_input,
+        };
+        let handle: Arc<dyn GeneratorModel> = Arc::new(model);
+        Ok(Arc::new(handle) as LoadedModelHandle)
+    }
+
+    async fn health(&self) -> ProviderHealth {
+        ProviderHealth::Healthy
+    }
+
+    /// Fetches the account via `GET /v1/account`, which is free.
+    /// Uses the default `REPLICATE_API_TOKEN` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "REPLICATE_API_TOKEN",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `REPLICATE_API_TOKEN`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/replicate", "REPLICATE_API_TOKEN", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/replicate.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&["api_key_env", "base_url", "input", "poll_interval_ms"])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
+}
+
+/// Which endpoint a prediction is created against, derived from `model_id`.
+///
+/// `owner/name` runs the latest version of an official model; `owner/name:version`
+/// pins a specific model version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PredictionTarget {
+    Model { owner: String, name: String },
+    Version(String),
+}
+
+impl PredictionTarget {
+    fn parse(model_id: &str) -> Result<Self> {
+        let (model, version) = match model_id.split_once(':') {
+            Some((model, version)) => (model, Some(version)),
+            None => (model_id, None),
+        };
+        let parts = model
+            .split_once('/')
+            .filter(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'));
+        match (parts, version) {
+            (Some(_), Some(version)) if !version.is_empty() => {
+                Ok(Self::Version(version.to_string()))
+            }
+            (Some((owner, name)), None) => Ok(Self::Model {
+                owner: owner.to_string(),
+                name: name.to_string(),
+            }),
+            _ => Err(RuntimeError::Config(format!(
+                "Replicate model_id '{}' must be 'owner/name' or 'owner/name:version'",
+                model_id
+            ))),
+        }
+    }
+
+    fn create_url(&self, base_url: &str) -> String {
+        match self {
+            Self::Model { owner, name } => {
+                format!("{base_url}/v1/models/{owner}/{name}/predictions")
+            }
+            Self::Version(_) => format!("{base_url}/v1/predictions"),
+        }
+    }
+
+    fn create_body(&self, input: serde_json::Value) -> serde_json::Value {
+        match self {
+            Self::Model { .. } => json!({ "input": input }),
+            Self::Version(version) => json!({ "version": version, "input": input }),
+        }
+    }
+}
+
+struct ReplicateGeneratorModel {
+    client: Client,
+    cb: crate::reliability::CircuitBreakerWrapper,
+    target: PredictionTarget,
+    /// API root without a trailing slash, e.g. `https://api.replicate.com`.
+    base_url: String,
+    api_key: String,
+    poll_interval: Duration,
+    extra_input: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Build the prediction `input` object.
+///
+/// Replicate models take a flat prompt rather than a message list, so system
+/// messages are joined into `system_prompt` and the rest become `prompt`
+/// (a `User:`/`Assistant:` transcript when there is more than one turn).
+/// Call options override keys from the alias-level `input` option.
+fn build_replicate_input(
+    messages: &[Message],
+    options: &GenerationOptions,
+    extra_input: &serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    let system: Vec<String> = messages
+        .iter()
+        .filter(|m| m.role == MessageRole::System)
+        .map(|m| m.text())
+        .collect();
+    let turns: Vec<&Message> = messages
+        .iter()
+        .filter(|m| m.role != MessageRole::System)
+        .collect();
+    let prompt = match turns.as_slice() {
+        [only] => only.text(),
+        _ => turns
+            .iter()
+            .map(|m| {
+                let speaker = match m.role {
+                    MessageRole::User => "User",
+                    MessageRole::Assistant => "Assistant",
+                    MessageRole::System => unreachable!("system messages filtered above"),
+                };
+                format!("{speaker}: {}", m.text())
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+
+    let mut input = extra_input.clone();
+    input.insert("prompt".to_string(), json!(prompt));
+    if !system.is_empty() {
+        input.insert("system_prompt".to_string(), json!(system.join("\n")));
+    }
+    if let Some(max_tokens) = options.max_tokens {
+        input.insert("max_tokens".to_string(), json!(max_tokens));
+    }
+    if let Some(temperature) = options.temperature {
+        input.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = options.top_p {
+        input.insert("top_p".to_string(), json!(top_p));
+    }
+    if let Some(stop) = &options.stop {
+        input.insert("stop_sequences".to_string(), json!(stop.join(",")));
+    }
+    serde_json::Value::Object(input)
+}
+
+/// Join a prediction's `output`: language models stream tokens into an array
+/// of strings, while some models return a single string. Any other output
+/// (e.g. `null`) is an error rather than an empty answer.
+fn parse_replicate_output(output: &serde_json::Value) -> Result<String> {
+    match output {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        serde_json::Value::Array(parts) => Ok(parts.iter().filter_map(|p| p.as_str()).collect()),
+        _ => Err(RuntimeError::api_error(
+            "remote/replicate",
+            "Prediction succeeded without text output",
+        )),
+    }
+}
+
+fn parse_replicate_usage(metrics: &serde_json::Value) -> Option<TokenUsage> {
+    let prompt = metrics.get("input_token_count")?.as_u64()? as usize;
+    let completion = metrics.get("output_token_count")?.as_u64()? as usize;
+    Some(TokenUsage {
+        prompt_tokens: prompt,
+        completion_tokens: completion,
+        total_tokens: prompt + completion,
+        ..Default::default()
+    })
+}
+
+/// Cancels a prediction when dropped unless [`disarm`](Self::disarm) was
+/// called first, so predictions abandoned by a timeout stop running.
+struct CancelGuard {
+    client: Client,
+    api_key: String,
+    url: Option<String>,
+}
+
+impl CancelGuard {
+    fn disarm(&mut self) {
+        self.url = None;
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        let Some(url) = self.url.take() else {
+            return;
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let request = self.client.post(url).bearer_auth(&self.api_key);
+        handle.spawn(async move {
+            if let Err(e) = request.send().await {
+                tracing::warn!(error = %e, "Failed to cancel abandoned Replicate prediction");
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl GeneratorModel for ReplicateGeneratorModel {
+    async fn generate(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
+        let input = build_replicate_input(messages, &options, &self.extra_input);
+        let include_raw = options.include_raw;
+
+        self.cb
+            .call(move || async move {
+                let response = self
+                    .client
+                    .post(self.target.create_url(&self.base_url))
+                    .bearer_auth(&self.api_key)
+                    .json(&self.target.create_body(input))
+                    .request_id_header()
+                    .send()
+                    .await
+                    .map_err(|e| RuntimeError::api_error("remote/replicate", e))?;
+
+                let mut prediction: serde_json::Value =
+                    check_http_status("remote/replicate", response)
+                        .await?
+                        .json()
+                        .await
+                        .map_err(|e| RuntimeError::api_error("remote/replicate", e))?;
+
+                // Poll and cancel through `base_url` rather than the returned
+                // `urls`, which always point at the public API.
+                let Some(id) = prediction["id"].as_str().map(str::to_string) else {
+                    return Err(RuntimeError::api_error(
+                        "remote/replicate",
+                        "prediction response has no id",
+                    ));
+                };
+                let get_url = format!("{}/v1/predictions/{id}", self.base_url);
+                let mut guard = CancelGuard {
+                    client: self.client.clone(),
+                    api_key: self.api_key.clone(),
+                    url: Some(format!("{get_url}/cancel")),
+                };
+
+                loop {
+                    match prediction["status"].as_str().unwrap_or("") {
+                        "succeeded" => break,
+                        "failed" | "canceled" => {
+                            guard.disarm();
+                            let status = prediction["status"].as_str().unwrap_or("");
+                            let error = prediction["error"].as_str().unwrap_or("no error message");
+                            return Err(RuntimeError::api_error(
+                                "remote/replicate",
+                                format!("prediction {id} {status}: {error}"),
+                            ));
+                        }
+                        _ => {}
+                    }
+                    tokio::time::sleep(self.poll_interval).await;
+                    let response = self
+                        .client
+                        .get(&get_url)
+                        .bearer_auth(&self.api_key)
+                        .request_id_header()
+                        .send()
+                        .await
+                        .map_err(|e| RuntimeError::api_error("remote/replicate", e))?;
+                    prediction = check_http_status("remote/replicate", response)
+                        .await?
+                        .json()
+                        .await
+                        .map_err(|e| RuntimeError::api_error("remote/replicate", e))?;
+                }
+                guard.disarm();
+
+                Ok(GenerationResult {
+                    text: parse_replicate_output(&prediction["output"])?,
+                    usage: parse_replicate_usage(&prediction["metrics"]),
+                    images: vec![],
+                    audio: None,
+                    alternatives: vec![],
+                    finish_reason: None,
+                    raw: include_raw.then_some(prediction),
+                    logprobs: None,
+                    reasoning: None,
+                })
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ModelRuntimeKey;
+    use crate::provider::remote_common::RemoteProviderBase;
+    use crate::traits::ModelProvider;
+    use std::time::Duration;
+
+    static ENV_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    fn spec(alias: &str, task: ModelTask, model_id: &str) -> ModelAliasSpec {
+        ModelAliasSpec {
+            alias: alias.to_string(),
+            task,
+            provider_id: "remote/replicate".to_string(),
+            model_id: model_id.to_string(),
+            revision: None,
+            warmup: crate::api::WarmupPolicy::Lazy,
+            required: false,
+            timeout: None,
+            load_timeout: None,
+            warmup_timeout: None,
+            retry: None,
+            options: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn breaker_reused_for_same_runtime_key() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("REPLICATE_API_TOKEN", "test-key") };
+
+        let provider = RemoteReplicateProvider::new();
+        let s1 = spec(
+            "gen/a",
+            ModelTask::Generate,
+            "meta/meta-llama-3-8b-instruct",
+        );
+        let s2 = spec(
+            "gen/b",
+            ModelTask::Generate,
+            "meta/meta-llama-3-8b-instruct",
+        );
+
+        let _ = provider.load(&s1).await.unwrap();
+        let _ = provider.load(&s2).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+
+        unsafe { std::env::remove_var("REPLICATE_API_TOKEN") };
+    }
+
+    #[tokio::test]
+    async fn breaker_cleanup_evicts_stale_entries() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("REPLICATE_API_TOKEN", "test-key") };
+
+        let provider = RemoteReplicateProvider::new();
+        let stale = spec(
+            "gen/stale",
+            ModelTask::Generate,
+            "meta/meta-llama-3-8b-instruct",
+        );
+        let fresh = spec(
+            "gen/fresh",
+            ModelTask::Generate,
+            "meta/meta-llama-3-70b-instruct",
+        );
+        provider.insert_test_breaker(
+            ModelRuntimeKey::new(&stale),
+            RemoteProviderBase::BREAKER_TTL + Duration::from_secs(5),
+        );
+        provider.insert_test_breaker(ModelRuntimeKey::new(&fresh), Duration::from_secs(1));
+        assert_eq!(provider.breaker_count(), 2);
+
+        provider.force_cleanup_now_for_test();
+        let _ = provider.load(&fresh).await.unwrap();
+
+        assert_eq!(provider.breaker_count(), 1);
+
+        unsafe { std::env::remove_var("REPLICATE_API_TOKEN") };
+    }
+
+    #[tokio::test]
+    async fn embed_capability_mismatch() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("REPLICATE_API_TOKEN", "test-key") };
+
+        let provider = RemoteReplicateProvider::new();
+        let s = spec("embed/a", ModelTask::Embed, "meta/meta-llama-3-8b-instruct");
+        let result = provider.load(&s).await;
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("does not support task")
+        );
+
+        unsafe { std::env::remove_var("REPLICATE_API_TOKEN") };
+    }
+
+    #[tokio::test]
+    async fn rerank_capability_mismatch() {
+        let _lock = ENV_LOCK.lock().await;
+        unsafe { std::env::set_var("REPLICATE_API_TOKEN", "test-key") };
+
+        let provider = RemoteReplicateProvider::new();
+        let s = spec(
+            "rerank/a",
+            ModelTask::Rerank,
+            "meta/meta-llama-3-8b-instruct",
+        );
+        let result = provider.load(&s).await;
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("does not support task")
+        );
+
+        unsafe { std::env::remove_var("REPLICATE_API_TOKEN") };
+    }
+
+    #[test]
+    fn target_parses_official_model() {
+        let target = PredictionTarget::parse("meta/meta-llama-3-8b-instruct").unwrap();
+        assert_eq!(
+            target.create_url(DEFAULT_BASE_URL),
+            "https://api.replicate.com/v1/models/meta/meta-llama-3-8b-instruct/predictions"
+        );
+        let body = target.create_body(json!({"prompt": "hi"}));
+        assert!(body.get("version").is_none());
+        assert_eq!(body["input"]["prompt"], "hi");
+    }
+
+    #[test]
+    fn target_parses_pinned_version() {
+        let target = PredictionTarget::parse("acme/custom-llm:5c7d5dc6dd8b").unwrap();
+        assert_eq!(
+            target.create_url("https://proxy.internal/replicate"),
+            "https://proxy.internal/replicate/v1/predictions"
+        );
+        assert_eq!(target.create_body(json!({}))["version"], "5c7d5dc6dd8b");
+    }
+
+    #[test]
+    fn target_rejects_malformed_model_id() {
+        for model_id in ["llama", "a/b/c", "/name", "owner/", "owner/name:"] {
+            let err = PredictionTarget::parse(model_id).unwrap_err();
+            assert!(
+                matches!(err, RuntimeError::Config(_)),
+                "expected config error for {model_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn input_maps_messages_and_options() {
+        let mut extra = serde_json::Map::new();
+        extra.insert("temperature".to_string(), json!(0.1));
+        extra.insert("min_tokens".to_string(), json!(4));
+        let input = build_replicate_input(
+            &[
+                Message::system("be brief"),
+                Message::user("hello"),
+                Message::assistant("hi"),
+                Message::user("bye"),
+            ],
+            &GenerationOptions {
+                max_tokens: Some(64),
+                temperature: Some(0.7),
+                stop: Some(vec!["\n\n".to_string(), "END".to_string()]),
+                ..Default::default()
+            },
+            &extra,
+        );
+        assert_eq!(input["system_prompt"], "be brief");
+        assert_eq!(input["prompt"], "User: hello\nAssistant: hi\nUser: bye");
+        assert_eq!(input["max_tokens"], 64);
+        assert_eq!(input["temperature"].as_f64().unwrap() as f32, 0.7);
+        assert_eq!(input["min_tokens"], 4);
+        assert_eq!(input["stop_sequences"], "\n\n,END");
+        assert!(input.get("top_p").is_none());
+    }
+
+    #[test]
+    fn input_uses_single_message_text_as_prompt() {
+        let input = build_replicate_input(
+            &[Message::user("hello")],
+            &GenerationOptions::default(),
+            &serde_json::Map::new(),
+        );
+        assert_eq!(input["prompt"], "hello");
+        assert!(input.get("system_prompt").is_none());
+    }
+
+    #[test]
+    fn output_joins_token_array() {
+        assert_eq!(
+            parse_replicate_output(&json!(["Hel", "lo", " world"])).unwrap(),
+            "Hello world"
+        );
+        assert_eq!(parse_replicate_output(&json!("done")).unwrap(), "done");
+        assert!(parse_replicate_output(&serde_json::Value::Null).is_err());
+    }
+
+    #[test]
+    fn usage_reads_token_counts() {
+        let usage = parse_replicate_usage(&json!({
+            "input_token_count": 12,
+            "output_token_count": 30,
+            "predict_time": 1.2
+        }))
+        .unwrap();
+        assert_eq!(usage.prompt_tokens, 12);
+        assert_eq!(usage.completion_tokens, 30);
+        assert_eq!(usage.total_tokens, 42);
+        assert!(parse_replicate_usage(&json!({"predict_time": 1.2})).is_none());
+    }
+}
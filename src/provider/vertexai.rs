@@ -1,16 +1,22 @@
 use crate::api::{ModelAliasSpec, ModelTask};
 use crate::error::{Result, RuntimeError};
+use crate::provider::google_auth::{GoogleCredentials, GoogleTokenSource};
 use crate::provider::remote_common::{
-    RemoteProviderBase, build_google_generate_payload, check_http_status,
+    GoogleGenerateSettings, RemoteProviderBase, RequestIdExt, SafetySetting, Truncation,
+    build_google_generate_payload, check_http_status, default_credential,
+    parse_google_candidate_texts, parse_google_reasoning, parse_google_usage, resolve_base_url,
 };
 use crate::traits::{
     EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, LoadedModelHandle,
-    Message, ModelProvider, ProviderCapabilities, ProviderHealth, TokenUsage,
+    Message, ModelProvider, ProviderCapabilities, ProviderHealth,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 fn options_map<'a>(
     provider_id: &str,
@@ -46,6 +52,26 @@ fn option_string(
     Ok(Some(s.to_string()))
 }
 
+fn option_bool(
+    provider_id: &str,
+    map: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Result<Option<bool>> {
+    let Some(map) = map else {
+        return Ok(None);
+    };
+    let Some(value) = map.get(key) else {
+        return Ok(None);
+    };
+    let b = value.as_bool().ok_or_else(|| {
+        RuntimeError::Config(format!(
+            "Option '{}' for provider '{}' must be a boolean",
+            key, provider_id
+        ))
+    })?;
+    Ok(Some(b))
+}
+
 fn option_u32(
     provider_id: &str,
     map: Option<&serde_json::Map<String, serde_json::Value>>,
@@ -78,31 +104,112 @@ fn option_u32(
     Ok(Some(n_u32))
 }
 
+/// How requests are authorized.
+#[derive(Clone)]
+enum VertexAiAuth {
+    /// A pre-minted bearer token read from the environment at load time.
+    /// Never refreshed, so only suitable for short-lived jobs.
+    Static(String),
+    /// Tokens minted from a service account or ADC and refreshed near expiry.
+    Refreshing(Arc<GoogleTokenSource>),
+}
+
+impl VertexAiAuth {
+    async fn token(&self) -> Result<String> {
+        match self {
+            Self::Static(token) => Ok(token.clone()),
+            Self::Refreshing(source) => source.token().await,
+        }
+    }
+
+    /// Discard the current token. Returns `false` when there is nothing to
+    /// refresh, i.e. a retry would reuse the same rejected token.
+    async fn invalidate(&self) -> bool {
+        match self {
+            Self::Static(_) => false,
+            Self::Refreshing(source) => {
+                source.invalidate().await;
+                true
+            }
+        }
+    }
+}
+
+/// POST `body` to `url` with a bearer token. A 401 with refreshable
+/// credentials discards the cached token and retries once with a fresh one.
+async fn post_authorized(
+    client: &Client,
+    auth: &VertexAiAuth,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response> {
+    let send = |token: String| async move {
+        client
+            .post(url)
+            .bearer_auth(token)
+            .json(body)
+            .request_id_header()
+            .send()
+            .await
+            .map_err(|e| RuntimeError::api_error("remote/vertexai", e))
+    };
+
+    let response = send(auth.token().await?).await?;
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED && auth.invalidate().await {
+        return send(auth.token().await?).await;
+    }
+    Ok(response)
+}
+
 /// Resolved and validated Vertex AI configuration extracted from a
 /// [`ModelAliasSpec`]'s options and environment variables.
 #[derive(Clone)]
 struct VertexAiResolvedOptions {
-    token: String,
+    auth: VertexAiAuth,
     project_id: String,
     location: String,
     publisher: String,
+    /// Endpoint root without a trailing slash, e.g.
+    /// `https://us-central1-aiplatform.googleapis.com`.
+    base_url: String,
     embedding_dimensions: Option<u32>,
 }
 
 impl VertexAiResolvedOptions {
-    fn from_spec(spec: &ModelAliasSpec) -> Result<Self> {
+    fn from_spec(spec: &ModelAliasSpec, provider: &RemoteVertexAIProvider) -> Result<Self> {
         let provider_id = "remote/vertexai";
         let map = options_map(provider_id, &spec.options)?;
 
-        let token_env = option_string(provider_id, map, "api_token_env")?
-            .unwrap_or_else(|| "VERTEX_AI_TOKEN".to_string());
-        let token = std::env::var(&token_env)
-            .map_err(|_| RuntimeError::Config(format!("{} env var not set", token_env)))?;
+        let credentials_path = match option_string(provider_id, map, "credentials_path")? {
+            Some(path) => Some(PathBuf::from(path)),
+            None if option_bool(provider_id, map, "use_adc")?.unwrap_or(false) => {
+                Some(GoogleCredentials::adc_path()?)
+            }
+            None => None,
+        };
+
+        let auth = if let Some(path) = credentials_path {
+            VertexAiAuth::Refreshing(provider.token_source(&path)?)
+        } else {
+            let token_env = option_string(provider_id, map, "api_token_env")?
+                .unwrap_or_else(|| "VERTEX_AI_TOKEN".to_string());
+            let token = std::env::var(&token_env)
+                .map_err(|_| RuntimeError::Config(format!("{} env var not set", token_env)))?;
+            VertexAiAuth::Static(token)
+        };
 
+        let credentials_project = match &auth {
+            VertexAiAuth::Refreshing(source) => {
+                source.credentials().project_id().map(str::to_string)
+            }
+            VertexAiAuth::Static(_) => None,
+        };
         let project_id = if let Some(project_id) = option_string(provider_id, map, "project_id")? {
             project_id
+        } else if let Ok(project_id) = std::env::var("VERTEX_AI_PROJECT") {
+            project_id
         } else {
-            std::env::var("VERTEX_AI_PROJECT").map_err(|_| {
+            credentials_project.ok_or_else(|| {
                 RuntimeError::Config(
                     "project_id option not set and VERTEX_AI_PROJECT env var not set".to_string(),
                 )
@@ -113,26 +220,161 @@ impl VertexAiResolvedOptions {
             option_string(provider_id, map, "location")?.unwrap_or_else(|| "us-central1".into());
         let publisher =
             option_string(provider_id, map, "publisher")?.unwrap_or_else(|| "google".into());
+        let base_url = resolve_base_url(
+            &spec.options,
+            &format!("https://{}-aiplatform.googleapis.com", location),
+        );
         let embedding_dimensions = option_u32(provider_id, map, "embedding_dimensions")?;
 
         Ok(Self {
-            token,
+            auth,
             project_id,
             location,
             publisher,
+            base_url,
             embedding_dimensions,
         })
     }
 }
 
+/// Typed options for `remote/vertexai` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VertexAIOptions {
+    /// Environment variable holding a static bearer token (default `VERTEX_AI_TOKEN`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_token_env: Option<String>,
+    /// API root to send requests to instead of the regional endpoint, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Service-account key or authorized-user JSON file to mint refreshing tokens from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials_path: Option<String>,
+    /// Mint refreshing tokens from Application Default Credentials.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_adc: Option<bool>,
+    /// Google Cloud project; falls back to `VERTEX_AI_PROJECT`, then the credentials file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Vertex AI region (default `us-central1`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Model publisher (default `google`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    /// Embedding dimension reported by model handles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
+    /// How over-long embedding inputs are handled: `end` (the default) or
+    /// `none`. Vertex AI cannot truncate from the start.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+    /// Harm-category blocking thresholds sent as `safetySettings` (generate only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_settings: Option<Vec<SafetySetting>>,
+    /// Response MIME type, e.g. `"text/plain"` or `"application/json"` (generate only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+}
+
+impl VertexAIOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_token_env`](Self::api_token_env).
+    pub fn with_api_token_env(mut self, api_token_env: impl Into<String>) -> Self {
+        self.api_token_env = Some(api_token_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`credentials_path`](Self::credentials_path).
+    pub fn with_credentials_path(mut self, credentials_path: impl Into<String>) -> Self {
+        self.credentials_path = Some(credentials_path.into());
+        self
+    }
+
+    /// Set [`use_adc`](Self::use_adc).
+    pub fn with_use_adc(mut self, use_adc: bool) -> Self {
+        self.use_adc = Some(use_adc);
+        self
+    }
+
+    /// Set [`project_id`](Self::project_id).
+    pub fn with_project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Set [`location`](Self::location).
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Set [`publisher`](Self::publisher).
+    pub fn with_publisher(mut self, publisher: impl Into<String>) -> Self {
+        self.publisher = Some(publisher.into());
+        self
+    }
+
+    /// Set [`embedding_dimensions`](Self::embedding_dimensions).
+    pub fn with_embedding_dimensions(mut self, embedding_dimensions: u32) -> Self {
+        self.embedding_dimensions = Some(embedding_dimensions);
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
+    /// Set [`safety_settings`](Self::safety_settings).
+    pub fn with_safety_settings(
+        mut self,
+        safety_settings: impl IntoIterator<Item = SafetySetting>,
+    ) -> Self {
+        self.safety_settings = Some(safety_settings.into_iter().collect());
+        self
+    }
+
+    /// Set [`response_mime_type`](Self::response_mime_type).
+    pub fn with_response_mime_type(mut self, response_mime_type: impl Into<String>) -> Self {
+        self.response_mime_type = Some(response_mime_type.into());
+        self
+    }
+}
+
+impl From<&VertexAIOptions> for serde_json::Value {
+    fn from(options: &VertexAIOptions) -> Self {
+        serde_json::to_value(options).expect("VertexAIOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Google Vertex AI](https://cloud.google.com/vertex-ai/docs)
 /// prediction and generation endpoints for embedding and text generation.
 ///
-/// Requires the `VERTEX_AI_TOKEN` environment variable (or a custom env var
-/// via `api_token_env`) and either the `project_id` option or the
-/// `VERTEX_AI_PROJECT` env var.
+/// Authenticates with either a static bearer token from `VERTEX_AI_TOKEN`
+/// (or a custom env var via `api_token_env`), or with refreshing OAuth tokens
+/// minted from a service-account key (`credentials_path`) or Application
+/// Default Credentials (`use_adc`). Also requires the `project_id` option, the
+/// `VERTEX_AI_PROJECT` env var, or a project recorded in the credentials file.
+/// The `base_url` option replaces the regional endpoint, e.g. to route an
+/// alias through a proxy.
 pub struct RemoteVertexAIProvider {
     base: RemoteProviderBase,
+    /// Token sources keyed by credentials path, so aliases sharing a key file
+    /// share one cached token.
+    token_sources: Mutex<HashMap<PathBuf, Arc<GoogleTokenSource>>>,
 }
 
 impl RemoteVertexAIProvider {
@@ -140,6 +382,29 @@ impl RemoteVertexAIProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+            token_sources: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn token_source(&self, path: &Path) -> Result<Arc<GoogleTokenSource>> {
+        let mut sources = self.token_sources.lock().unwrap();
+        if let Some(source) = sources.get(path) {
+            return Ok(source.clone());
+        }
+        let credentials = GoogleCredentials::from_file(path)?;
+        let source = Arc::new(GoogleTokenSource::new(
+            self.base.client.clone(),
+            credentials,
+        ));
+        sources.insert(path.to_path_buf(), source.clone());
+        Ok(source)
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -160,6 +425,7 @@ impl Default for RemoteVertexAIProvider {
     fn default() -> Self {
         Self {
             base: RemoteProviderBase::new(),
+            token_sources: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -178,7 +444,7 @@ impl ModelProvider for RemoteVertexAIProvider {
 
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
-        let resolved = VertexAiResolvedOptions::from_spec(spec)?;
+        let resolved = VertexAiResolvedOptions::from_spec(spec, self)?;
 
         match spec.task {
             ModelTask::Embed => {
@@ -188,6 +454,7 @@ impl ModelProvider for RemoteVertexAIProvider {
                     model_id: spec.model_id.clone(),
                     options: resolved.clone(),
                     dimensions: resolved.embedding_dimensions.unwrap_or(768),
+                    truncation: Truncation::from_options(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -198,6 +465,10 @@ impl ModelProvider for RemoteVertexAIProvider {
                     cb,
                     model_id: spec.model_id.clone(),
                     options: resolved,
+                    settings: GoogleGenerateSettings::from_options(
+                        "remote/vertexai",
+                        &spec.options,
+                    )?,
                 };
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -212,6 +483,39 @@ impl ModelProvider for RemoteVertexAIProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Validates the format of `VERTEX_AI_TOKEN` when it is set.
+    /// Project and region are per alias, so the token is not sent.
+    async fn warmup(&self) -> Result<()> {
+        default_credential("remote/vertexai", "VERTEX_AI_TOKEN").map(|_| ())
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/vertexai.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_token_env",
+            "base_url",
+            "credentials_path",
+            "embedding_dimensions",
+            "location",
+            "project_id",
+            "publisher",
+            "response_mime_type",
+            "safety_settings",
+            "truncation",
+            "use_adc",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
 }
 
 /// Embedding model backed by the Vertex AI prediction API.
@@ -221,13 +525,29 @@ pub struct VertexAiEmbeddingModel {
     model_id: String,
     options: VertexAiResolvedOptions,
     dimensions: u32,
+    /// Sent as `parameters.autoTruncate`; the server default (truncate)
+    /// applies when unset.
+    truncation: Option<Truncation>,
+}
+
+/// Build the JSON body for a Vertex AI embedding `:predict` request.
+fn build_vertex_embed_payload(
+    texts: &[String],
+    truncation: Option<Truncation>,
+) -> serde_json::Value {
+    let instances: Vec<_> = texts.iter().map(|t| json!({ "content": t })).collect();
+    let mut body = json!({ "instances": instances });
+    if let Some(truncation) = truncation {
+        body["parameters"] = json!({ "autoTruncate": truncation == Truncation::End });
+    }
+    body
 }
 
 impl VertexAiEmbeddingModel {
     fn endpoint_url(&self) -> String {
         format!(
-            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/{}/models/{}:predict",
-            self.options.location,
+            "{}/v1/projects/{}/locations/{}/publishers/{}/models/{}:predict",
+            self.options.base_url,
             self.options.project_id,
             self.options.location,
             self.options.publisher,
@@ -239,30 +559,35 @@ impl VertexAiEmbeddingModel {
 #[async_trait]
 impl EmbeddingModel for VertexAiEmbeddingModel {
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
 
         self.cb
             .call(move || async move {
-                let instances: Vec<_> = texts.iter().map(|t| json!({ "content": t })).collect();
-                let response = self
-                    .client
-                    .post(self.endpoint_url())
-                    .header("Authorization", format!("Bearer {}", self.options.token))
-                    .json(&json!({ "instances": instances }))
-                    .send()
-                    .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                let response = post_authorized(
+                    &self.client,
+                    &self.options.auth,
+                    &self.endpoint_url(),
+                    &build_vertex_embed_payload(&texts, self.truncation),
+                )
+                .await?;
 
-                let body: serde_json::Value = check_http_status("Vertex AI", response)?
+                let body: serde_json::Value = check_http_status("remote/vertexai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/vertexai", e))?;
 
                 let predictions = body
                     .get("predictions")
                     .and_then(|v| v.as_array())
                     .ok_or_else(|| {
-                        RuntimeError::ApiError("Invalid response: missing predictions".to_string())
+                        RuntimeError::api_error(
+                            "remote/vertexai",
+                            "Invalid response: missing predictions",
+                        )
                     })?;
 
                 let mut result = Vec::new();
@@ -277,8 +602,9 @@ impl EmbeddingModel for VertexAiEmbeddingModel {
                         });
 
                     let values = values_opt.ok_or_else(|| {
-                        RuntimeError::ApiError(
-                            "Invalid embedding format in Vertex AI response".to_string(),
+                        RuntimeError::api_error(
+                            "remote/vertexai",
+                            "Invalid embedding format in Vertex AI response",
                         )
                     })?;
 
@@ -309,13 +635,14 @@ pub struct VertexAiGeneratorModel {
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
     options: VertexAiResolvedOptions,
+    settings: GoogleGenerateSettings,
 }
 
 impl VertexAiGeneratorModel {
     fn endpoint_url(&self) -> String {
         format!(
-            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/{}/models/{}:generateContent",
-            self.options.location,
+            "{}/v1/projects/{}/locations/{}/publishers/{}/models/{}:generateContent",
+            self.options.base_url,
             self.options.project_id,
             self.options.location,
             self.options.publisher,
@@ -335,54 +662,38 @@ impl GeneratorModel for VertexAiGeneratorModel {
 
         self.cb
             .call(move || async move {
-                let payload = build_google_generate_payload(&messages, &options);
-                let response = self
-                    .client
-                    .post(self.endpoint_url())
-                    .header("Authorization", format!("Bearer {}", self.options.token))
-                    .json(&payload)
-                    .send()
-                    .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                let payload = build_google_generate_payload(&messages, &options, &self.settings);
+                let response = post_authorized(
+                    &self.client,
+                    &self.options.auth,
+                    &self.endpoint_url(),
+                    &payload,
+                )
+                .await?;
 
-                let body: serde_json::Value = check_http_status("Vertex AI", response)?
+                let body: serde_json::Value = check_http_status("remote/vertexai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/vertexai", e))?;
 
-                let candidates = body
-                    .get("candidates")
-                    .and_then(|v| v.as_array())
-                    .ok_or_else(|| RuntimeError::ApiError("No candidates returned".to_string()))?;
-
-                let first_candidate = candidates
-                    .first()
-                    .ok_or_else(|| RuntimeError::ApiError("Empty candidates".to_string()))?;
-
-                let content_parts = first_candidate
-                    .get("content")
-                    .and_then(|c| c.get("parts"))
-                    .and_then(|p| p.as_array())
-                    .ok_or_else(|| RuntimeError::ApiError("Invalid content format".to_string()))?;
-
-                let text = content_parts
-                    .first()
-                    .and_then(|p| p.get("text"))
-                    .and_then(|t| t.as_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                let usage = body.get("usageMetadata").map(|u| TokenUsage {
-                    prompt_tokens: u["promptTokenCount"].as_u64().unwrap_or(0) as usize,
-                    completion_tokens: u["candidatesTokenCount"].as_u64().unwrap_or(0) as usize,
-                    total_tokens: u["totalTokenCount"].as_u64().unwrap_or(0) as usize,
-                });
+                let mut texts = parse_google_candidate_texts("remote/vertexai", &body)?.into_iter();
+                let finish_reason = body
+                    .pointer("/candidates/0/finishReason")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string);
+                let reasoning = parse_google_reasoning(&body);
 
                 Ok(GenerationResult {
-                    text,
-                    usage,
+                    text: texts.next().unwrap_or_default(),
+                    usage: parse_google_usage(&body),
                     images: vec![],
                     audio: None,
+                    alternatives: texts.collect(),
+                    finish_reason,
+                    raw: options.include_raw.then_some(body),
+                    logprobs: None,
+                    reasoning,
                 })
             })
             .await
@@ -415,6 +726,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options,
         }
@@ -520,6 +832,104 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn credentials_path_supplies_refreshing_auth_and_project() {
+        let _lock = ENV_LOCK.lock().await;
+        // SAFETY: protected by ENV_LOCK
+        unsafe {
+            std::env::remove_var("VERTEX_AI_TOKEN");
+            std::env::remove_var("VERTEX_AI_PROJECT");
+        }
+
+        let dir = std::env::temp_dir().join(format!("uni-xervo-vertex-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sa.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "type": "service_account",
+                "project_id": "sa-project",
+                "private_key": "unused",
+                "client_email": "svc@sa-project.iam.gserviceaccount.com"
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let provider = RemoteVertexAIProvider::new();
+        let s = spec(
+            "embed/a",
+            ModelTask::Embed,
+            "text-embedding-005",
+            serde_json::json!({ "credentials_path": path.to_str().unwrap() }),
+        );
+        let resolved = VertexAiResolvedOptions::from_spec(&s, &provider).unwrap();
+        assert!(matches!(resolved.auth, VertexAiAuth::Refreshing(_)));
+        assert_eq!(resolved.project_id, "sa-project");
+
+        // A second alias with the same key file shares the token source.
+        let _ = VertexAiResolvedOptions::from_spec(&s, &provider).unwrap();
+        assert_eq!(provider.token_sources.lock().unwrap().len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn base_url_defaults_to_regional_endpoint() {
+        let _lock = ENV_LOCK.lock().await;
+        // SAFETY: protected by ENV_LOCK
+        unsafe {
+            std::env::set_var("VERTEX_AI_TOKEN", "test-token");
+        }
+
+        let provider = RemoteVertexAIProvider::new();
+        let regional = spec(
+            "embed/a",
+            ModelTask::Embed,
+            "text-embedding-005",
+            serde_json::json!({ "project_id": "p", "location": "europe-west4" }),
+        );
+        let resolved = VertexAiResolvedOptions::from_spec(&regional, &provider).unwrap();
+        assert_eq!(
+            resolved.base_url,
+            "https://europe-west4-aiplatform.googleapis.com"
+        );
+
+        let proxied = spec(
+            "embed/b",
+            ModelTask::Embed,
+            "text-embedding-005",
+            serde_json::json!({ "project_id": "p", "base_url": "https://proxy.internal/vertex/" }),
+        );
+        let resolved = VertexAiResolvedOptions::from_spec(&proxied, &provider).unwrap();
+        assert_eq!(resolved.base_url, "https://proxy.internal/vertex");
+
+        // SAFETY: protected by ENV_LOCK
+        unsafe {
+            std::env::remove_var("VERTEX_AI_TOKEN");
+        }
+    }
+
+    #[tokio::test]
+    async fn static_token_is_not_refreshable() {
+        let auth = VertexAiAuth::Static("token".to_string());
+        assert_eq!(auth.token().await.unwrap(), "token");
+        assert!(!auth.invalidate().await);
+    }
+
+    #[test]
+    fn embed_payload_sets_auto_truncate_only_when_configured() {
+        let texts = vec!["a".to_string()];
+        let payload = build_vertex_embed_payload(&texts, None);
+        assert_eq!(payload["instances"], json!([{ "content": "a" }]));
+        assert!(payload.get("parameters").is_none());
+
+        let payload = build_vertex_embed_payload(&texts, Some(Truncation::None));
+        assert_eq!(payload["parameters"]["autoTruncate"], false);
+        let payload = build_vertex_embed_payload(&texts, Some(Truncation::End));
+        assert_eq!(payload["parameters"]["autoTruncate"], true);
+    }
+
     #[test]
     fn generation_payload_alternates_roles() {
         use crate::traits::Message;
@@ -528,7 +938,11 @@ mod tests {
             Message::assistant("assistant answer"),
             Message::user("user follow-up"),
         ];
-        let payload = build_google_generate_payload(&messages, &GenerationOptions::default());
+        let payload = build_google_generate_payload(
+            &messages,
+            &GenerationOptions::default(),
+            &GoogleGenerateSettings::default(),
+        );
         let contents = payload["contents"].as_array().unwrap();
 
         assert_eq!(contents[0]["role"], "user");
@@ -548,6 +962,7 @@ mod tests {
                 top_p: Some(0.9),
                 ..Default::default()
             },
+            &GoogleGenerateSettings::default(),
         );
 
         assert_eq!(payload["generationConfig"]["maxOutputTokens"], 64);
@@ -556,4 +971,31 @@ mod tests {
         assert!((temperature - 0.7).abs() < 1e-6);
         assert!((top_p - 0.9).abs() < 1e-6);
     }
+
+    #[test]
+    fn generation_without_candidates_or_blocked_is_an_error() {
+        let blocked_prompt = json!({
+            "promptFeedback": { "blockReason": "BLOCKLIST" },
+            "usageMetadata": { "promptTokenCount": 4, "totalTokenCount": 4 }
+        });
+        let err = parse_google_candidate_texts("remote/vertexai", &blocked_prompt).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::ContentFiltered { provider, reason }
+                if provider == "remote/vertexai" && reason == "BLOCKLIST"
+        ));
+
+        let blocked_response = json!({
+            "candidates": [{ "finishReason": "SPII", "content": { "role": "model" } }]
+        });
+        let err = parse_google_candidate_texts("remote/vertexai", &blocked_response).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::ContentFiltered { reason, .. } if reason == "SPII"
+        ));
+
+        let err = parse_google_candidate_texts("remote/vertexai", &json!({ "candidates": [] }))
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::ApiError { .. }));
+    }
 }
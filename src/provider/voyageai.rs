@@ -1,20 +1,104 @@
 use crate::api::{ModelAliasSpec, ModelTask};
+use crate::batching::{SUB_BATCH_CONCURRENCY, embed_in_sub_batches};
 use crate::error::{Result, RuntimeError};
-use crate::provider::remote_common::{RemoteProviderBase, check_http_status, resolve_api_key};
+use crate::provider::remote_common::{
+    RemoteProviderBase, RequestIdExt, Truncation, check_http_status, probe_request,
+    resolve_api_key, resolve_base_url, split_embed_batches, verify_credentials,
+};
 use crate::traits::{
     EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
-    RerankerModel, ScoredDoc,
+    RerankOptions, RerankerModel, ScoredDoc, TokenUsage,
 };
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
+/// API root used when the `base_url` option is not set.
+const DEFAULT_BASE_URL: &str = "https://api.voyageai.com";
+
+/// Typed options for `remote/voyageai` aliases.
+///
+/// Converts into the JSON object expected in [`ModelAliasSpec::options`];
+/// unset fields are omitted so the provider defaults apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoyageAIOptions {
+    /// Environment variable holding the API key (default `VOYAGE_API_KEY`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// API root to send requests to instead of `https://api.voyageai.com`, e.g. a proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Embedding input type: `"query"` or `"document"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+    /// How over-long inputs are handled: `end` (the default) or `none`.
+    /// Voyage AI cannot truncate from the start.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<Truncation>,
+    /// Vector length for models that support several: 256, 512, 1024, or 2048.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dimension: Option<u32>,
+    /// Vector element type: `float`, `int8`, `uint8`, `binary`, or `ubinary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dtype: Option<String>,
+}
+
+impl VoyageAIOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`api_key_env`](Self::api_key_env).
+    pub fn with_api_key_env(mut self, api_key_env: impl Into<String>) -> Self {
+        self.api_key_env = Some(api_key_env.into());
+        self
+    }
+
+    /// Set [`base_url`](Self::base_url).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set [`input_type`](Self::input_type).
+    pub fn with_input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.input_type = Some(input_type.into());
+        self
+    }
+
+    /// Set [`truncation`](Self::truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
+    /// Set [`output_dimension`](Self::output_dimension).
+    pub fn with_output_dimension(mut self, output_dimension: u32) -> Self {
+        self.output_dimension = Some(output_dimension);
+        self
+    }
+
+    /// Set [`output_dtype`](Self::output_dtype).
+    pub fn with_output_dtype(mut self, output_dtype: impl Into<String>) -> Self {
+        self.output_dtype = Some(output_dtype.into());
+        self
+    }
+}
+
+impl From<&VoyageAIOptions> for serde_json::Value {
+    fn from(options: &VoyageAIOptions) -> Self {
+        serde_json::to_value(options).expect("VoyageAIOptions serializes to a JSON object")
+    }
+}
+
 /// Remote provider that calls the [Voyage AI API](https://docs.voyageai.com/reference/embeddings-api)
 /// for embedding and reranking. Does not support text generation.
 ///
 /// Requires the `VOYAGE_API_KEY` environment variable (or a custom env var
-/// name via the `api_key_env` option).
+/// name via the `api_key_env` option). The `base_url` option replaces the API
+/// root, e.g. to route an alias through a proxy.
 pub struct RemoteVoyageAIProvider {
     base: RemoteProviderBase,
 }
@@ -32,6 +116,23 @@ impl RemoteVoyageAIProvider {
         Self::default()
     }
 
+    /// Create a provider that sends requests through `client` instead of a
+    /// default-configured one.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            base: RemoteProviderBase::with_client(client),
+        }
+    }
+
+    /// The health probe request, authenticated with `api_key`.
+    fn health_request(&self, api_key: &str) -> reqwest::RequestBuilder {
+        self.base
+            .client
+            .post(format!("{DEFAULT_BASE_URL}/v1/embeddings"))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&json!({ "model": "voyage-3.5-lite", "input": ["ping"] }))
+    }
+
     #[cfg(test)]
     fn insert_test_breaker(&self, key: crate::api::ModelRuntimeKey, age: std::time::Duration) {
         self.base.insert_test_breaker(key, age);
@@ -63,6 +164,7 @@ impl ModelProvider for RemoteVoyageAIProvider {
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
         let cb = self.base.circuit_breaker_for(spec);
         let api_key = resolve_api_key(&spec.options, "api_key_env", "VOYAGE_API_KEY")?;
+        let base_url = resolve_base_url(&spec.options, DEFAULT_BASE_URL);
 
         match spec.task {
             ModelTask::Embed => {
@@ -70,7 +172,9 @@ impl ModelProvider for RemoteVoyageAIProvider {
                     client: self.base.client.clone(),
                     cb: cb.clone(),
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
+                    settings: VoyageEmbedSettings::from_options(&spec.options),
                 };
                 let handle: Arc<dyn EmbeddingModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
@@ -80,6 +184,7 @@ impl ModelProvider for RemoteVoyageAIProvider {
                     client: self.base.client.clone(),
                     cb,
                     model_id: spec.model_id.clone(),
+                    base_url,
                     api_key,
                 };
                 let handle: Arc<dyn RerankerModel> = Arc::new(model);
@@ -95,38 +200,158 @@ impl ModelProvider for RemoteVoyageAIProvider {
     async fn health(&self) -> ProviderHealth {
         ProviderHealth::Healthy
     }
+
+    /// Voyage AI has no free endpoint, so this embeds one short string with
+    /// `voyage-3.5-lite` (a few billed tokens per probe).
+    /// Uses the default `VOYAGE_API_KEY` env var.
+    async fn probe_health(&self) -> ProviderHealth {
+        self.base
+            .cached_probe(|| async {
+                let api_key = match resolve_api_key(
+                    &serde_json::Value::Null,
+                    "api_key_env",
+                    "VOYAGE_API_KEY",
+                ) {
+                    Ok(key) => key,
+                    Err(e) => return ProviderHealth::Unhealthy(e.to_string()),
+                };
+                probe_request(self.health_request(&api_key)).await
+            })
+            .await
+    }
+
+    /// Checks `VOYAGE_API_KEY`, when set, with the health probe request so
+    /// eager warmup fails fast on a rejected key.
+    async fn warmup(&self) -> Result<()> {
+        verify_credentials("remote/voyageai", "VOYAGE_API_KEY", |key| {
+            self.health_request(key)
+        })
+        .await
+    }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(include_str!(
+            "../../schemas/provider-options/voyageai.schema.json"
+        ))
+        .ok()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        Some(&[
+            "api_key_env",
+            "base_url",
+            "input_type",
+            "output_dimension",
+            "output_dtype",
+            "truncation",
+        ])
+    }
+
+    fn set_max_concurrency(&self, permits: usize) -> Result<()> {
+        self.base.set_max_concurrency(permits)
+    }
+}
+
+/// Embedding request knobs taken from the alias options.
+#[derive(Debug, Clone, PartialEq)]
+struct VoyageEmbedSettings {
+    /// `query` or `document`; omitted from the request when unset.
+    input_type: Option<String>,
+    /// Whether Voyage truncates over-long inputs instead of rejecting them.
+    truncation: bool,
+    /// Requested vector length for models that support several.
+    output_dimension: Option<u32>,
+    /// `float` (default), `int8`, `uint8`, `binary`, or `ubinary`.
+    output_dtype: Option<String>,
+}
+
+impl VoyageEmbedSettings {
+    fn from_options(options: &serde_json::Value) -> Self {
+        let string = |key: &str| {
+            options
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        Self {
+            input_type: string("input_type"),
+            truncation: Truncation::from_options(options) != Some(Truncation::None),
+            output_dimension: options
+                .get("output_dimension")
+                .and_then(|v| v.as_u64())
+                .map(|d| d as u32),
+            output_dtype: string("output_dtype"),
+        }
+    }
+}
+
+/// Build the JSON body for Voyage's `/v1/embeddings` endpoint.
+fn build_voyage_embed_payload(
+    model_id: &str,
+    texts: &[String],
+    settings: &VoyageEmbedSettings,
+) -> serde_json::Value {
+    let mut body = json!({
+        "input": texts,
+        "model": model_id,
+        "truncation": settings.truncation,
+    });
+    if let Some(input_type) = &settings.input_type {
+        body["input_type"] = json!(input_type);
+    }
+    if let Some(output_dimension) = settings.output_dimension {
+        body["output_dimension"] = json!(output_dimension);
+    }
+    if let Some(output_dtype) = &settings.output_dtype {
+        body["output_dtype"] = json!(output_dtype);
+    }
+    body
+}
+
+/// Most texts Voyage's `/v1/embeddings` accepts in one request.
+const VOYAGE_MAX_TEXTS: usize = 1000;
+
+/// Total input tokens Voyage accepts in one embedding request for `model_id`.
+fn voyage_token_limit(model_id: &str) -> usize {
+    match model_id {
+        "voyage-3.5-lite" | "voyage-3-lite" => 1_000_000,
+        "voyage-3.5" | "voyage-3" | "voyage-2" => 320_000,
+        _ => 120_000,
+    }
 }
 
 struct VoyageAIEmbeddingModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.voyageai.com`.
+    base_url: String,
     api_key: String,
+    settings: VoyageEmbedSettings,
 }
 
-#[async_trait]
-impl EmbeddingModel for VoyageAIEmbeddingModel {
-    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+impl VoyageAIEmbeddingModel {
+    async fn embed_batch(&self, texts: &[&str]) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
         let texts: Vec<String> = texts.iter().map(|s| s.to_string()).collect();
+        let payload = build_voyage_embed_payload(&self.model_id, &texts, &self.settings);
 
         self.cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.voyageai.com/v1/embeddings")
+                    .post(format!("{}/v1/embeddings", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
-                    .json(&json!({
-                        "input": texts,
-                        "model": self.model_id
-                    }))
+                    .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/voyageai", e))?;
 
-                let body: serde_json::Value = check_http_status("Voyage AI", response)?
+                let body: serde_json::Value = check_http_status("remote/voyageai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/voyageai", e))?;
 
                 let mut embeddings = Vec::new();
                 if let Some(data) = body.get("data").and_then(|d| d.as_array()) {
@@ -140,15 +365,62 @@ impl EmbeddingModel for VoyageAIEmbeddingModel {
                         }
                     }
                 }
-                Ok(embeddings)
+
+                let usage = body
+                    .pointer("/usage/total_tokens")
+                    .and_then(|t| t.as_u64())
+                    .map(|t| TokenUsage {
+                        prompt_tokens: t as usize,
+                        completion_tokens: 0,
+                        total_tokens: t as usize,
+                        ..Default::default()
+                    });
+
+                Ok((embeddings, usage))
             })
             .await
     }
+}
 
+#[async_trait]
+impl EmbeddingModel for VoyageAIEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    /// Splits `texts` into requests within Voyage's text-count and per-model
+    /// token limits, sent concurrently, and sums the reported usage across
+    /// them. Vectors come back in input order.
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        let batches = split_embed_batches(
+            &texts,
+            VOYAGE_MAX_TEXTS,
+            Some(voyage_token_limit(&self.model_id)),
+        );
+        embed_in_sub_batches(batches, SUB_BATCH_CONCURRENCY, |batch| async move {
+            self.embed_batch(&batch).await
+        })
+        .await
+    }
+
+    /// `output_dimension` if set, else the model default. Bit-packed
+    /// `binary`/`ubinary` output holds eight dimensions per value.
     fn dimensions(&self) -> u32 {
-        match self.model_id.as_str() {
-            "voyage-large-2" => 1536,
-            _ => 1024,
+        let dimensions = self
+            .settings
+            .output_dimension
+            .unwrap_or(match self.model_id.as_str() {
+                "voyage-large-2" => 1536,
+                _ => 1024,
+            });
+        match self.settings.output_dtype.as_deref() {
+            Some("binary" | "ubinary") => dimensions / 8,
+            _ => dimensions,
         }
     }
 
@@ -161,37 +433,76 @@ struct VoyageAIRerankerModel {
     client: Client,
     cb: crate::reliability::CircuitBreakerWrapper,
     model_id: String,
+    /// API root without a trailing slash, e.g. `https://api.voyageai.com`.
+    base_url: String,
     api_key: String,
 }
 
+/// Build the Voyage rerank request body, mapping `top_n` to `top_k`.
+fn build_voyage_rerank_payload(
+    model_id: &str,
+    query: &str,
+    docs: &[String],
+    options: &RerankOptions,
+) -> serde_json::Value {
+    let mut body = json!({
+        "query": query,
+        "documents": docs,
+        "model": model_id,
+    });
+    if let Some(top_n) = options.top_n {
+        body["top_k"] = json!(top_n);
+    }
+    if options.return_documents {
+        body["return_documents"] = json!(true);
+    }
+    body
+}
+
 #[async_trait]
 impl RerankerModel for VoyageAIRerankerModel {
     async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
-        let query = query.to_string();
-        let docs: Vec<String> = docs.iter().map(|s| s.to_string()).collect();
+        self.rerank_with_options(query, docs, &RerankOptions::default())
+            .await
+    }
 
-        self.cb
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let payload = build_voyage_rerank_payload(
+            &self.model_id,
+            query,
+            &docs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            options,
+        );
+
+        let results = self
+            .cb
             .call(move || async move {
                 let response = self
                     .client
-                    .post("https://api.voyageai.com/v1/reranking")
+                    .post(format!("{}/v1/reranking", self.base_url))
                     .header("Authorization", format!("Bearer {}", self.api_key))
-                    .json(&json!({
-                        "query": query,
-                        "documents": docs,
-                        "model": self.model_id,
-                    }))
+                    .json(&payload)
+                    .request_id_header()
                     .send()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/voyageai", e))?;
 
-                let body: serde_json::Value = check_http_status("Voyage AI", response)?
+                let body: serde_json::Value = check_http_status("remote/voyageai", response)
+                    .await?
                     .json()
                     .await
-                    .map_err(|e| RuntimeError::ApiError(e.to_string()))?;
+                    .map_err(|e| RuntimeError::api_error("remote/voyageai", e))?;
 
                 let data = body.get("data").and_then(|d| d.as_array()).ok_or_else(|| {
-                    RuntimeError::ApiError("Invalid rerank response format".to_string())
+                    RuntimeError::api_error("remote/voyageai", "Invalid rerank response format")
                 })?;
 
                 let mut results = Vec::new();
@@ -201,15 +512,16 @@ impl RerankerModel for VoyageAIRerankerModel {
                         .get("relevance_score")
                         .and_then(|s| s.as_f64())
                         .unwrap_or(0.0) as f32;
-                    results.push(ScoredDoc {
-                        index,
-                        score,
-                        text: None,
-                    });
+                    let text = item
+                        .get("document")
+                        .and_then(|d| d.as_str())
+                        .map(str::to_string);
+                    results.push(ScoredDoc { index, score, text });
                 }
                 Ok(results)
             })
-            .await
+            .await?;
+        Ok(options.apply(results, docs))
     }
 }
 
@@ -234,6 +546,7 @@ mod tests {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }
@@ -314,4 +627,89 @@ mod tests {
 
         unsafe { std::env::remove_var("VOYAGE_API_KEY") };
     }
+
+    #[test]
+    fn rerank_payload_maps_top_n_and_return_documents() {
+        let docs = vec!["a".to_string(), "b".to_string()];
+        let payload = build_voyage_rerank_payload(
+            "rerank-2",
+            "q",
+            &docs,
+            &RerankOptions {
+                top_n: Some(1),
+                return_documents: true,
+                sort: true,
+            },
+        );
+        assert_eq!(payload["top_k"], 1);
+        assert_eq!(payload["return_documents"], true);
+
+        let payload = build_voyage_rerank_payload("rerank-2", "q", &docs, &Default::default());
+        assert!(payload.get("top_k").is_none());
+        assert!(payload.get("return_documents").is_none());
+    }
+
+    #[test]
+    fn embed_payload_defaults_to_truncation_without_input_type() {
+        let settings = VoyageEmbedSettings::from_options(&serde_json::Value::Null);
+        let payload = build_voyage_embed_payload("voyage-3", &["a".to_string()], &settings);
+        assert_eq!(payload["truncation"], true);
+        assert!(payload.get("input_type").is_none());
+        assert!(payload.get("output_dimension").is_none());
+        assert!(payload.get("output_dtype").is_none());
+    }
+
+    #[test]
+    fn embed_payload_carries_configured_options() {
+        let settings = VoyageEmbedSettings::from_options(&json!({
+            "input_type": "query",
+            "truncation": false,
+            "output_dimension": 512,
+            "output_dtype": "int8"
+        }));
+        let payload = build_voyage_embed_payload("voyage-3-large", &["a".to_string()], &settings);
+        assert_eq!(payload["input_type"], "query");
+        assert_eq!(payload["truncation"], false);
+        assert_eq!(payload["output_dimension"], 512);
+        assert_eq!(payload["output_dtype"], "int8");
+    }
+
+    #[test]
+    fn embed_payload_maps_truncation_strategy() {
+        let truncation = |value: serde_json::Value| {
+            VoyageEmbedSettings::from_options(&json!({ "truncation": value })).truncation
+        };
+        assert!(!truncation(json!("none")));
+        assert!(truncation(json!("end")));
+        assert!(truncation(json!(true)));
+    }
+
+    #[test]
+    fn dimensions_follow_output_dimension_and_dtype() {
+        let model = |options: serde_json::Value| VoyageAIEmbeddingModel {
+            client: Client::new(),
+            cb: crate::reliability::CircuitBreakerWrapper::new(Default::default()),
+            model_id: "voyage-3-large".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: "test-key".to_string(),
+            settings: VoyageEmbedSettings::from_options(&options),
+        };
+        assert_eq!(model(serde_json::Value::Null).dimensions(), 1024);
+        assert_eq!(
+            model(json!({ "output_dimension": 2048 })).dimensions(),
+            2048
+        );
+        assert_eq!(
+            model(json!({ "output_dimension": 512, "output_dtype": "binary" })).dimensions(),
+            64
+        );
+    }
+
+    #[test]
+    fn token_limit_follows_model_family() {
+        assert_eq!(voyage_token_limit("voyage-3.5-lite"), 1_000_000);
+        assert_eq!(voyage_token_limit("voyage-3.5"), 320_000);
+        assert_eq!(voyage_token_limit("voyage-3-large"), 120_000);
+        assert_eq!(voyage_token_limit("voyage-code-3"), 120_000);
+    }
 }
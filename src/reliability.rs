@@ -1,14 +1,20 @@
 //! Reliability primitives: circuit breaker, instrumented model wrappers with
 //! timeout and retry support, and metrics emission.
 
-use crate::error::{Result, RuntimeError};
+use crate::context::current_request_id;
+use crate::error::{Result, RuntimeError, TimeoutPhase};
 use crate::traits::{
-    EmbeddingModel, GenerationOptions, GenerationResult, GeneratorModel, Message, RerankerModel,
-    ScoredDoc,
+    ClassifierModel, EmbeddingModel, EmbeddingStream, GenerationOptions, GenerationResult,
+    GenerationStream, GeneratorModel, Message, QuantizedEmbedding, RateLimitSnapshot,
+    RerankOptions, RerankerModel, ScoredDoc, ScoredLabel, TokenUsage,
 };
+use crate::usage::UsageTracker;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
 /// Internal circuit breaker state machine.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,7 +24,18 @@ enum State {
     HalfOpen,
 }
 
+impl State {
+    fn as_str(self) -> &'static str {
+        match self {
+            State::Closed => "closed",
+            State::Open => "open",
+            State::HalfOpen => "half_open",
+        }
+    }
+}
+
 /// Tunable parameters for the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CircuitBreakerConfig {
     /// Number of consecutive failures before the breaker opens.
     pub failure_threshold: u32,
@@ -41,6 +58,47 @@ struct Inner {
     last_failure: Option<Instant>,
     config: CircuitBreakerConfig,
     half_open_probe_in_flight: bool,
+    /// When the breaker last left `Closed`; cleared once it closes again.
+    opened_at: Option<Instant>,
+    provider: String,
+    model: String,
+}
+
+impl Inner {
+    /// Move to `to`, emitting `circuit_breaker.transitions` and, when the
+    /// breaker closes, `circuit_breaker.open_duration_seconds`.
+    fn transition(&mut self, to: State) {
+        let from = self.state;
+        if from == to {
+            return;
+        }
+        self.state = to;
+        crate::telemetry::counter!(
+            "circuit_breaker.transitions",
+            "provider" => self.provider.clone(),
+            "model" => self.model.clone(),
+            "from" => from.as_str(),
+            "to" => to.as_str()
+        )
+        .increment(1);
+
+        match to {
+            State::Open => {
+                self.opened_at.get_or_insert_with(Instant::now);
+            }
+            State::Closed => {
+                if let Some(opened_at) = self.opened_at.take() {
+                    crate::telemetry::histogram!(
+                        "circuit_breaker.open_duration_seconds",
+                        "provider" => self.provider.clone(),
+                        "model" => self.model.clone()
+                    )
+                    .record(opened_at.elapsed().as_secs_f64());
+                }
+            }
+            State::HalfOpen => {}
+        }
+    }
 }
 
 /// Thread-safe circuit breaker that tracks failures and short-circuits calls
@@ -49,9 +107,25 @@ struct Inner {
 /// State transitions: **Closed** -> (failures >= threshold) -> **Open** ->
 /// (wait period elapsed) -> **HalfOpen** -> (probe succeeds) -> **Closed**
 /// (or probe fails -> back to **Open**).
+///
+/// [`call`](Self::call) runs the wrapped future in place rather than spawning
+/// it, so dropping the returned future drops the in-flight request (for
+/// remote providers, aborting the HTTP call). A cancelled call counts as
+/// neither success nor failure.
+///
+/// Every state change increments `circuit_breaker.transitions` (labels
+/// `provider`, `model`, `from`, `to`), and closing a breaker records
+/// `circuit_breaker.open_duration_seconds`: the time since it first opened,
+/// including any failed half-open probes. Label breakers with
+/// [`with_labels`](Self::with_labels); unlabelled breakers report `unknown`.
+///
+/// A breaker may also carry a shared concurrency limit (see
+/// [`with_concurrency_limit`](Self::with_concurrency_limit)); calls admitted
+/// by the breaker then wait for a permit before running.
 #[derive(Clone)]
 pub struct CircuitBreakerWrapper {
     inner: Arc<Mutex<Inner>>,
+    permits: Option<Arc<Semaphore>>,
 }
 
 impl CircuitBreakerWrapper {
@@ -64,8 +138,43 @@ impl CircuitBreakerWrapper {
                 last_failure: None,
                 config,
                 half_open_probe_in_flight: false,
+                opened_at: None,
+                provider: "unknown".to_string(),
+                model: "unknown".to_string(),
             })),
+            permits: None,
+        }
+    }
+
+    /// Set the `provider` and `model` labels attached to this breaker's
+    /// metrics.
+    pub fn with_labels(self, provider: impl Into<String>, model: impl Into<String>) -> Self {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.provider = provider.into();
+            inner.model = model.into();
         }
+        self
+    }
+
+    /// Bound concurrent calls with `permits`, which may be shared by several
+    /// breakers (e.g. every model of one provider). The permit is acquired
+    /// after the breaker admits the call, so an open breaker still fails
+    /// fast, and is released as soon as the wrapped future completes or is
+    /// dropped.
+    pub fn with_concurrency_limit(mut self, permits: Arc<Semaphore>) -> Self {
+        self.permits = Some(permits);
+        self
+    }
+
+    /// Whether the breaker is open and still inside its wait period, i.e. a
+    /// [`call`](Self::call) made now would be rejected without running.
+    pub fn is_open(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.state == State::Open
+            && inner.last_failure.is_some_and(|last| {
+                last.elapsed() < Duration::from_secs(inner.config.open_wait_seconds)
+            })
     }
 
     /// Execute `f` through the circuit breaker.
@@ -73,6 +182,8 @@ impl CircuitBreakerWrapper {
     /// Returns [`RuntimeError::Unavailable`] immediately when the breaker is
     /// open.  In the half-open state only a single probe call is allowed;
     /// concurrent callers receive `Unavailable` until the probe completes.
+    /// With a concurrency limit attached, admitted calls wait for a permit
+    /// before `f` runs.
     pub async fn call<F, Fut, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce() -> Fut,
@@ -87,7 +198,7 @@ impl CircuitBreakerWrapper {
                 State::Open => {
                     if let Some(last) = inner.last_failure {
                         if last.elapsed() >= Duration::from_secs(inner.config.open_wait_seconds) {
-                            inner.state = State::HalfOpen;
+                            inner.transition(State::HalfOpen);
                         } else {
                             return Err(RuntimeError::Unavailable);
                         }
@@ -107,344 +218,1158 @@ impl CircuitBreakerWrapper {
         }
 
         // 2. Execute
+        let mut probe = ProbeGuard {
+            inner: &self.inner,
+            armed: is_probe_call,
+        };
+        let _permit = match &self.permits {
+            Some(permits) => Some(
+                permits
+                    .acquire()
+                    .await
+                    .map_err(|_| RuntimeError::Unavailable)?,
+            ),
+            None => None,
+        };
         let result = f().await;
+        probe.armed = false;
 
-        // 3. Update state
+        // 3. Update state. A filtered response still shows the provider is
+        // serving requests.
         let mut inner = self.inner.lock().unwrap();
-        match result {
-            Ok(val) => {
-                if is_probe_call {
-                    inner.state = State::Closed;
-                    inner.failures = 0;
-                    inner.half_open_probe_in_flight = false;
-                } else if inner.state == State::Closed {
-                    inner.failures = 0;
-                }
-                Ok(val)
+        if matches!(&result, Ok(_) | Err(RuntimeError::ContentFiltered { .. })) {
+            if is_probe_call {
+                inner.transition(State::Closed);
+                inner.failures = 0;
+                inner.half_open_probe_in_flight = false;
+            } else if inner.state == State::Closed {
+                inner.failures = 0;
             }
-            Err(e) => {
-                if is_probe_call {
-                    inner.half_open_probe_in_flight = false;
-                }
-                inner.failures += 1;
-                inner.last_failure = Some(Instant::now());
-
-                if is_probe_call
-                    || (inner.state == State::Closed
-                        && inner.failures >= inner.config.failure_threshold)
-                {
-                    inner.state = State::Open;
-                }
-                Err(e)
+        } else {
+            if is_probe_call {
+                inner.half_open_probe_in_flight = false;
+            }
+            inner.failures += 1;
+            inner.last_failure = Some(Instant::now());
+
+            if is_probe_call
+                || (inner.state == State::Closed
+                    && inner.failures >= inner.config.failure_threshold)
+            {
+                inner.transition(State::Open);
             }
         }
+        result
     }
 }
 
-/// Wrapper around an [`EmbeddingModel`] that adds per-call timeout enforcement,
-/// exponential-backoff retries for transient errors, and metrics emission
-/// (`model_inference.duration_seconds`, `model_inference.total`).
-pub struct InstrumentedEmbeddingModel {
-    pub inner: Arc<dyn EmbeddingModel>,
-    pub alias: String,
-    pub provider_id: String,
-    pub timeout: Option<Duration>,
-    pub retry: Option<crate::api::RetryConfig>,
+/// Releases the half-open probe slot if the probe is cancelled before it
+/// finishes, so the next call can probe instead of the breaker staying
+/// unavailable forever.
+struct ProbeGuard<'a> {
+    inner: &'a Mutex<Inner>,
+    armed: bool,
 }
 
-#[async_trait]
-impl EmbeddingModel for InstrumentedEmbeddingModel {
-    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
-        let start = Instant::now();
-        let mut attempts = 0;
-        let max_attempts = self.retry.as_ref().map(|r| r.max_attempts).unwrap_or(1);
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed
+            && let Ok(mut inner) = self.inner.lock()
+        {
+            inner.half_open_probe_in_flight = false;
+        }
+    }
+}
 
-        let res = loop {
-            attempts += 1;
-            let fut = self.inner.embed(texts.clone());
+/// What to do with an input longer than [`InputLimit::max_chars`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlongPolicy {
+    /// Fail the call with [`RuntimeError::Config`] naming the offending input.
+    #[default]
+    Error,
+    /// Cut the input down to `max_chars` characters and carry on.
+    Truncate,
+}
 
-            let res = if let Some(timeout) = self.timeout {
-                match tokio::time::timeout(timeout, fut).await {
-                    Ok(r) => r,
-                    Err(_) => Err(RuntimeError::Timeout),
-                }
-            } else {
-                fut.await
-            };
+/// Per-alias cap on input length, read from the `max_input_chars` and
+/// `on_overlong` runtime option keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimit {
+    /// Maximum number of characters (Unicode scalar values) per input.
+    pub max_chars: usize,
+    pub policy: OverlongPolicy,
+}
 
-            match res {
-                Ok(val) => break Ok(val),
-                Err(e) if e.is_retryable() && attempts < max_attempts => {
-                    let backoff = self.retry.as_ref().unwrap().get_backoff(attempts);
-                    tracing::warn!(
-                        alias = %self.alias,
-                        attempt = attempts,
-                        backoff_ms = backoff.as_millis(),
-                        error = %e,
-                        "Retrying embedding call"
-                    );
-                    tokio::time::sleep(backoff).await;
-                    continue;
-                }
-                Err(e) => break Err(e),
-            }
+impl InputLimit {
+    /// Build a limit from a spec's `options`, or `None` when `max_input_chars`
+    /// is not set. Values are assumed to have passed options validation.
+    pub fn from_options(options: &serde_json::Value) -> Option<Self> {
+        let max_chars = options.get("max_input_chars")?.as_u64()? as usize;
+        let policy = match options.get("on_overlong").and_then(|v| v.as_str()) {
+            Some("truncate") => OverlongPolicy::Truncate,
+            _ => OverlongPolicy::Error,
         };
+        Some(Self { max_chars, policy })
+    }
 
-        let duration = start.elapsed();
-        let status = if res.is_ok() { "success" } else { "failure" };
+    /// Check or truncate a single input. `label` names it in the error, e.g.
+    /// `"Input 3"` or `"Query"`.
+    fn apply_one<'a>(&self, alias: &str, label: &str, text: &'a str) -> Result<&'a str> {
+        let Some((cut, _)) = text.char_indices().nth(self.max_chars) else {
+            return Ok(text);
+        };
+        match self.policy {
+            OverlongPolicy::Truncate => Ok(&text[..cut]),
+            OverlongPolicy::Error => Err(RuntimeError::Config(format!(
+                "{} for alias '{}' has {} characters, more than max_input_chars ({})",
+                label,
+                alias,
+                text.chars().count(),
+                self.max_chars
+            ))),
+        }
+    }
 
-        metrics::histogram!(
-            "model_inference.duration_seconds",
-            "alias" => self.alias.clone(),
-            "task" => "embed",
-            "provider" => self.provider_id.clone()
-        )
-        .record(duration.as_secs_f64());
+    /// Check or truncate every input in `texts`, failing on the first one over
+    /// the limit under [`OverlongPolicy::Error`].
+    fn apply<'a>(&self, alias: &str, texts: &[&'a str]) -> Result<Vec<&'a str>> {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| self.apply_one(alias, &format!("Input {i}"), text))
+            .collect()
+    }
+}
 
-        metrics::counter!(
-            "model_inference.total",
-            "alias" => self.alias.clone(),
-            "task" => "embed",
-            "provider" => self.provider_id.clone(),
-            "status" => status
-        )
-        .increment(1);
+/// Count of instrumented calls currently running, shared by every handle a
+/// runtime hands out so [`ModelRuntime::shutdown`](crate::runtime::ModelRuntime::shutdown)
+/// can wait for them to finish.
+#[derive(Debug, Default)]
+pub struct InflightCalls {
+    count: AtomicUsize,
+    idle: tokio::sync::Notify,
+}
 
-        res
+impl InflightCalls {
+    /// Count a call as running until the returned guard is dropped.
+    pub fn start(self: &Arc<Self>) -> InflightGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        InflightGuard(self.clone())
     }
 
-    fn dimensions(&self) -> u32 {
-        self.inner.dimensions()
+    /// Number of calls currently running.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
     }
 
-    fn model_id(&self) -> &str {
-        self.inner.model_id()
+    /// Wait until no calls are running.
+    pub async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            // Register before checking so a guard dropped in between still
+            // wakes us.
+            notified.as_mut().enable();
+            if self.count() == 0 {
+                return;
+            }
+            notified.await;
+        }
     }
+}
 
-    async fn warmup(&self) -> Result<()> {
-        self.inner.warmup().await
+/// Marks one call as running in [`InflightCalls`]; see [`InflightCalls::start`].
+#[derive(Debug)]
+pub struct InflightGuard(Arc<InflightCalls>);
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
     }
 }
 
-/// Wrapper around a [`GeneratorModel`] that adds timeout, retry, and metrics.
-///
-/// See [`InstrumentedEmbeddingModel`] for details on the instrumentation behavior.
-pub struct InstrumentedGeneratorModel {
-    pub inner: Arc<dyn GeneratorModel>,
-    pub alias: String,
-    pub provider_id: String,
-    pub timeout: Option<Duration>,
-    pub retry: Option<crate::api::RetryConfig>,
+/// What an `Instrumented*` wrapper applies to every call it forwards: the
+/// per-attempt timeout, the retry policy, and where the outcome is counted.
+struct CallPolicy<'a> {
+    task: &'static str,
+    alias: &'a str,
+    provider_id: &'a str,
+    timeout: Option<Duration>,
+    retry: Option<&'a crate::api::RetryConfig>,
+    usage: Option<&'a Arc<UsageTracker>>,
+    inflight: Option<&'a Arc<InflightCalls>>,
 }
 
-#[async_trait]
-impl GeneratorModel for InstrumentedGeneratorModel {
-    async fn generate(
-        &self,
-        messages: &[Message],
-        options: GenerationOptions,
-    ) -> Result<GenerationResult> {
-        let start = Instant::now();
+impl CallPolicy<'_> {
+    /// Run `call` until it succeeds or the retry policy gives up, bounding
+    /// each attempt by the timeout. `start` anchors the retry budget.
+    async fn retry<T, F, Fut>(&self, start: Instant, call: F) -> Result<T>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+    {
         let mut attempts = 0;
-        let max_attempts = self.retry.as_ref().map(|r| r.max_attempts).unwrap_or(1);
-
-        let res = loop {
+        loop {
             attempts += 1;
-            let fut = self.inner.generate(messages, options.clone());
+            let fut = call();
 
             let res = if let Some(timeout) = self.timeout {
                 match tokio::time::timeout(timeout, fut).await {
                     Ok(r) => r,
-                    Err(_) => Err(RuntimeError::Timeout),
+                    Err(_) => Err(RuntimeError::timeout(TimeoutPhase::Inference, timeout)),
                 }
             } else {
                 fut.await
             };
 
-            match res {
-                Ok(val) => break Ok(val),
-                Err(e) if e.is_retryable() && attempts < max_attempts => {
-                    let backoff = self.retry.as_ref().unwrap().get_backoff(attempts);
-                    tracing::warn!(
-                        alias = %self.alias,
-                        attempt = attempts,
-                        backoff_ms = backoff.as_millis(),
-                        error = %e,
-                        "Retrying generation call"
-                    );
-                    tokio::time::sleep(backoff).await;
-                    continue;
-                }
-                Err(e) => break Err(e),
-            }
-        };
+            let e = match res {
+                Ok(val) => return Ok(val),
+                Err(e) => e,
+            };
+            let Some(backoff) = self
+                .retry
+                .and_then(|r| r.next_backoff(attempts, start.elapsed(), &e))
+            else {
+                return Err(e);
+            };
+            tracing::warn!(
+                alias = %self.alias,
+                task = self.task,
+                attempt = attempts,
+                backoff_ms = backoff.as_millis(),
+                error = %e,
+                "Retrying model call"
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    /// A recorder for a call that started at `start`, owned so a stream's
+    /// relay task can hold it until the stream ends.
+    fn recorder(&self, start: Instant) -> CallRecorder {
+        CallRecorder {
+            task: self.task,
+            alias: self.alias.to_string(),
+            provider_id: self.provider_id.to_string(),
+            usage: self.usage.cloned(),
+            start,
+        }
+    }
+}
 
-        let duration = start.elapsed();
-        let status = if res.is_ok() { "success" } else { "failure" };
+/// Emits `model_inference.duration_seconds` and `model_inference.total` for
+/// one finished call, and records its usage when it succeeded.
+struct CallRecorder {
+    task: &'static str,
+    alias: String,
+    provider_id: String,
+    usage: Option<Arc<UsageTracker>>,
+    start: Instant,
+}
 
-        metrics::histogram!(
+impl CallRecorder {
+    fn record(&self, ok: bool, usage: Option<&TokenUsage>) {
+        crate::telemetry::histogram!(
             "model_inference.duration_seconds",
             "alias" => self.alias.clone(),
-            "task" => "generate",
+            "task" => self.task,
             "provider" => self.provider_id.clone()
         )
-        .record(duration.as_secs_f64());
+        .record(self.start.elapsed().as_secs_f64());
 
-        metrics::counter!(
+        crate::telemetry::counter!(
             "model_inference.total",
             "alias" => self.alias.clone(),
-            "task" => "generate",
+            "task" => self.task,
             "provider" => self.provider_id.clone(),
-            "status" => status
+            "status" => if ok { "success" } else { "failure" }
         )
         .increment(1);
 
-        res
+        if let (Some(tracker), true) = (&self.usage, ok) {
+            tracker.record(&self.alias, usage);
+        }
     }
+}
 
-    async fn warmup(&self) -> Result<()> {
-        self.inner.warmup().await
-    }
+/// Run one unary call under `policy`: count it as in flight, apply timeout
+/// and retry, then record metrics and, on success, the usage `usage_of`
+/// reads from the result.
+async fn run_instrumented<T, F, Fut>(
+    policy: &CallPolicy<'_>,
+    call: F,
+    usage_of: fn(&T) -> Option<&TokenUsage>,
+) -> Result<T>
+where
+    F: Fn() -> Fut + Send,
+    Fut: std::future::Future<Output = Result<T>> + Send,
+{
+    let _inflight = policy.inflight.map(InflightCalls::start);
+    let start = Instant::now();
+    let res = policy.retry(start, call).await;
+    policy
+        .recorder(start)
+        .record(res.is_ok(), res.as_ref().ok().and_then(usage_of));
+    res
 }
 
-/// Wrapper around a [`RerankerModel`] that adds timeout, retry, and metrics.
+/// Wrapper around an [`EmbeddingModel`] that adds per-call timeout enforcement,
+/// exponential-backoff retries for transient errors, and metrics emission
+/// (`model_inference.duration_seconds`, `model_inference.total`).
 ///
-/// See [`InstrumentedEmbeddingModel`] for details on the instrumentation behavior.
-pub struct InstrumentedRerankerModel {
-    pub inner: Arc<dyn RerankerModel>,
+/// When `usage` is set, each successful call is recorded against `alias` in
+/// the [`UsageTracker`]. When `inflight` is set, each call is counted in it
+/// until it returns.
+///
+/// When `expected_dimensions` is set, a response containing a vector of any
+/// other length fails with [`RuntimeError::Config`] instead of being returned.
+///
+/// When `input_limit` is set, over-long texts are rejected or truncated before
+/// they reach the inner model; see [`InputLimit`].
+///
+/// When `observed_dimensions` is set, the first float embedding length is
+/// recorded in it and a later response of another length fails with
+/// [`RuntimeError::InferenceError`]; see [`ObservedDimensions`].
+pub struct InstrumentedEmbeddingModel {
+    pub inner: Arc<dyn EmbeddingModel>,
     pub alias: String,
     pub provider_id: String,
     pub timeout: Option<Duration>,
     pub retry: Option<crate::api::RetryConfig>,
+    pub usage: Option<Arc<UsageTracker>>,
+    pub inflight: Option<Arc<InflightCalls>>,
+    pub expected_dimensions: Option<u32>,
+    pub input_limit: Option<InputLimit>,
+    pub observed_dimensions: Option<Arc<ObservedDimensions>>,
 }
 
-#[async_trait]
-impl RerankerModel for InstrumentedRerankerModel {
-    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
-        let start = Instant::now();
-        let mut attempts = 0;
-        let max_attempts = self.retry.as_ref().map(|r| r.max_attempts).unwrap_or(1);
+/// Embedding length first returned by a model, shared by every handle for
+/// the same [`ModelRuntimeKey`](crate::api::ModelRuntimeKey).
+///
+/// Catches provider-side model swaps the key cannot see, such as a remote
+/// deployment redeployed with a different embedding size, before vectors of
+/// mixed sizes reach an index.
+#[derive(Debug, Default)]
+pub struct ObservedDimensions(AtomicUsize);
+
+impl ObservedDimensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        let res = loop {
-            attempts += 1;
-            let fut = self.inner.rerank(query, docs);
+    /// The first recorded length, if any call has completed.
+    pub fn get(&self) -> Option<usize> {
+        match self.0.load(Ordering::Acquire) {
+            0 => None,
+            dimensions => Some(dimensions),
+        }
+    }
 
-            let res = if let Some(timeout) = self.timeout {
-                match tokio::time::timeout(timeout, fut).await {
-                    Ok(r) => r,
-                    Err(_) => Err(RuntimeError::Timeout),
-                }
-            } else {
-                fut.await
-            };
+    /// Record `dimensions`, or return the earlier length if it differs.
+    pub fn observe(&self, dimensions: usize) -> Option<usize> {
+        match self
+            .0
+            .compare_exchange(0, dimensions, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => None,
+            Err(first) if first == dimensions => None,
+            Err(first) => Some(first),
+        }
+    }
+}
 
-            match res {
-                Ok(val) => break Ok(val),
-                Err(e) if e.is_retryable() && attempts < max_attempts => {
-                    let backoff = self.retry.as_ref().unwrap().get_backoff(attempts);
-                    tracing::warn!(
-                        alias = %self.alias,
-                        attempt = attempts,
-                        backoff_ms = backoff.as_millis(),
-                        error = %e,
-                        "Retrying rerank call"
-                    );
-                    tokio::time::sleep(backoff).await;
-                    continue;
-                }
-                Err(e) => break Err(e),
-            }
-        };
+/// Output of an embedding call that [`InstrumentedEmbeddingModel`] checks
+/// against `expected_dimensions` and records usage for.
+trait EmbedOutput: Send {
+    /// Dimensions of the first vector that does not have `expected`, if any.
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize>;
 
-        let duration = start.elapsed();
-        let status = if res.is_ok() { "success" } else { "failure" };
+    /// Length of the float vectors, checked against [`ObservedDimensions`].
+    /// Compact representations and per-call overrides are not tracked.
+    fn float_dimensions(&self) -> Option<usize> {
+        None
+    }
 
-        metrics::histogram!(
-            "model_inference.duration_seconds",
-            "alias" => self.alias.clone(),
-            "task" => "rerank",
-            "provider" => self.provider_id.clone()
-        )
-        .record(duration.as_secs_f64());
+    fn usage(&self) -> Option<&TokenUsage> {
+        None
+    }
+}
 
-        metrics::counter!(
-            "model_inference.total",
-            "alias" => self.alias.clone(),
-            "task" => "rerank",
-            "provider" => self.provider_id.clone(),
-            "status" => status
-        )
-        .increment(1);
+impl EmbedOutput for (Vec<Vec<f32>>, Option<TokenUsage>) {
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize> {
+        self.0.iter().map(Vec::len).find(|&d| d != expected)
+    }
 
-        res
+    fn float_dimensions(&self) -> Option<usize> {
+        self.0.first().map(Vec::len)
     }
 
-    async fn warmup(&self) -> Result<()> {
-        self.inner.warmup().await
+    fn usage(&self) -> Option<&TokenUsage> {
+        self.1.as_ref()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::atomic::{AtomicU32, Ordering};
-
-    #[tokio::test]
-    async fn test_circuit_breaker_transitions() {
-        let config = CircuitBreakerConfig {
-            failure_threshold: 2,
-            open_wait_seconds: 1,
-        };
-        let cb = CircuitBreakerWrapper::new(config);
-        let counter = Arc::new(AtomicU32::new(0));
+impl EmbedOutput for Vec<Vec<i8>> {
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize> {
+        self.iter().map(Vec::len).find(|&d| d != expected)
+    }
+}
 
-        // 1. Success calls - state remains Closed
-        let res = cb.call(|| async { Ok::<_, RuntimeError>(()) }).await;
-        assert!(res.is_ok());
+/// One streamed vector from [`embed_stream`](EmbeddingModel::embed_stream).
+impl EmbedOutput for Vec<f32> {
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize> {
+        (self.len() != expected).then_some(self.len())
+    }
 
-        // 2. Failures - state transitions to Open
-        let res = cb
-            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
-            .await;
-        assert!(res.is_err()); // Fail 1
+    fn float_dimensions(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
 
-        let res = cb
-            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
-            .await;
-        assert!(res.is_err()); // Fail 2 -> Open
+/// Float vectors from [`embed_with`](EmbeddingModel::embed_with), whose
+/// overrides may legitimately change the length.
+struct OverriddenEmbeddings(Vec<Vec<f32>>);
 
-        // 3. Open state - calls rejected immediately
-        let res = cb
-            .call(|| async {
-                counter.fetch_add(1, Ordering::SeqCst);
-                Ok(())
-            })
-            .await;
-        assert!(res.is_err());
-        assert_eq!(res.err().unwrap().to_string(), "Unavailable");
-        assert_eq!(counter.load(Ordering::SeqCst), 0); // Should not have run
+impl EmbedOutput for OverriddenEmbeddings {
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize> {
+        self.0.iter().map(Vec::len).find(|&d| d != expected)
+    }
+}
 
-        // 4. Wait for HalfOpen
-        tokio::time::sleep(Duration::from_millis(1100)).await;
+/// Bit-packed binary vectors, eight dimensions per byte.
+impl EmbedOutput for Vec<Vec<u8>> {
+    fn mismatched_dimensions(&self, expected: usize) -> Option<usize> {
+        self.iter().map(|v| v.len() * 8).find(|&d| d != expected)
+    }
+}
 
-        // 5. HalfOpen - allow one call
-        // If it fails, go back to Open
-        let res = cb
-            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
-            .await;
-        assert!(res.is_err());
+/// The length checks [`InstrumentedEmbeddingModel`] applies to embedding
+/// output, borrowed so a stream's relay task can run them too.
+struct DimensionCheck<'a> {
+    alias: &'a str,
+    model_id: &'a str,
+    expected: Option<u32>,
+    observed: Option<&'a ObservedDimensions>,
+}
 
-        // Should be Open again
-        let res = cb.call(|| async { Ok(()) }).await;
-        assert!(res.is_err());
-        assert_eq!(res.err().unwrap().to_string(), "Unavailable");
+impl DimensionCheck<'_> {
+    fn check(&self, output: &impl EmbedOutput) -> Result<()> {
+        if let Some(expected) = self.expected {
+            if let Some(actual) = output.mismatched_dimensions(expected as usize) {
+                return Err(RuntimeError::Config(format!(
+                    "Alias '{}' expected {}-dimensional embeddings but model '{}' returned {}",
+                    self.alias, expected, self.model_id, actual
+                )));
+            }
+        }
+        let (Some(observed), Some(actual)) = (self.observed, output.float_dimensions()) else {
+            return Ok(());
+        };
+        match observed.observe(actual) {
+            Some(first) => {
+                tracing::error!(
+                    alias = %self.alias,
+                    model_id = %self.model_id,
+                    first,
+                    actual,
+                    "Embedding dimensions changed since the first call"
+                );
+                Err(RuntimeError::InferenceError(format!(
+                    "Alias '{}': model '{}' returned {}-dimensional embeddings after earlier calls \
+                     returned {}; the upstream model may have changed",
+                    self.alias, self.model_id, actual, first
+                )))
+            }
+            None => Ok(()),
+        }
+    }
+}
 
-        // 6. Wait again for HalfOpen
-        tokio::time::sleep(Duration::from_millis(1100)).await;
+impl InstrumentedEmbeddingModel {
+    fn limit_inputs<'a>(&self, texts: Vec<&'a str>) -> Result<Vec<&'a str>> {
+        match &self.input_limit {
+            Some(limit) => limit.apply(&self.alias, &texts),
+            None => Ok(texts),
+        }
+    }
 
-        // 7. Success - transition to Closed
-        let res = cb.call(|| async { Ok(()) }).await;
-        assert!(res.is_ok());
+    fn policy(&self) -> CallPolicy<'_> {
+        CallPolicy {
+            task: "embed",
+            alias: &self.alias,
+            provider_id: &self.provider_id,
+            timeout: self.timeout,
+            retry: self.retry.as_ref(),
+            usage: self.usage.as_ref(),
+            inflight: self.inflight.as_ref(),
+        }
+    }
+
+    fn check_dimensions(&self, output: &impl EmbedOutput) -> Result<()> {
+        DimensionCheck {
+            alias: &self.alias,
+            model_id: self.inner.model_id(),
+            expected: self.expected_dimensions,
+            observed: self.observed_dimensions.as_deref(),
+        }
+        .check(output)
+    }
+
+    /// Run `call` with timeout, retry, dimension checking, metrics, and usage
+    /// recording. Shared by [`embed_with_usage`](EmbeddingModel::embed_with_usage),
+    /// [`embed_with`](EmbeddingModel::embed_with), and the compact
+    /// representations.
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "embed",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn instrumented<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        T: EmbedOutput,
+        F: Fn() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+    {
+        run_instrumented(
+            &self.policy(),
+            move || {
+                let fut = call();
+                async move {
+                    fut.await
+                        .and_then(|val| self.check_dimensions(&val).map(|()| val))
+                }
+            },
+            EmbedOutput::usage,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl EmbeddingModel for InstrumentedEmbeddingModel {
+    async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+        self.embed_with_usage(texts)
+            .await
+            .map(|(embeddings, _)| embeddings)
+    }
+
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        let texts = self.limit_inputs(texts)?;
+        self.instrumented(|| self.inner.embed_with_usage(texts.clone()))
+            .await
+    }
+
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        let texts = self.limit_inputs(texts)?;
+        self.instrumented(|| async {
+            let vectors = self
+                .inner
+                .embed_with(texts.clone(), overrides.clone())
+                .await?;
+            Ok(OverriddenEmbeddings(vectors))
+        })
+        .await
+        .map(|OverriddenEmbeddings(embeddings)| embeddings)
+    }
+
+    /// Quantizes the output of [`embed`](EmbeddingModel::embed), so the call
+    /// gets the same timeout, retry, and metrics.
+    async fn embed_quantized(&self, texts: Vec<&str>) -> Result<Vec<QuantizedEmbedding>> {
+        let vectors = self.embed(texts).await?;
+        Ok(vectors
+            .iter()
+            .map(Vec::as_slice)
+            .map(QuantizedEmbedding::quantize)
+            .collect())
+    }
+
+    async fn embed_int8(&self, texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+        let texts = self.limit_inputs(texts)?;
+        self.instrumented(|| self.inner.embed_int8(texts.clone()))
+            .await
+    }
+
+    async fn embed_binary(&self, texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+        let texts = self.limit_inputs(texts)?;
+        self.instrumented(|| self.inner.embed_binary(texts.clone()))
+            .await
+    }
+
+    /// Token sequences bypass `input_limit`, which counts characters.
+    async fn embed_tokens(&self, batches: Vec<Vec<u32>>) -> Result<Vec<Vec<f32>>> {
+        self.instrumented(|| async {
+            let vectors = self.inner.embed_tokens(batches.clone()).await?;
+            Ok((vectors, None))
+        })
+        .await
+        .map(|(embeddings, _)| embeddings)
+    }
+
+    /// The timeout and retries cover starting the stream; each vector is
+    /// then checked against `expected_dimensions` as it arrives, and the call
+    /// is recorded once the stream ends.
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "embed",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        let texts = self.limit_inputs(texts)?;
+        let policy = self.policy();
+        let inflight = policy.inflight.map(InflightCalls::start);
+        let start = Instant::now();
+        let recorder = policy.recorder(start);
+
+        let res = policy
+            .retry(start, || self.inner.embed_stream(texts.clone()))
+            .await;
+        let mut upstream = match res {
+            Ok(stream) => stream,
+            Err(e) => {
+                recorder.record(false, None);
+                return Err(e);
+            }
+        };
+
+        let alias = self.alias.clone();
+        let model_id = self.inner.model_id().to_string();
+        let expected = self.expected_dimensions;
+        let observed = self.observed_dimensions.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(upstream.max_capacity());
+        tokio::spawn(async move {
+            // The stream counts as in flight until it is fully relayed.
+            let _inflight = inflight;
+            let check = DimensionCheck {
+                alias: &alias,
+                model_id: &model_id,
+                expected,
+                observed: observed.as_deref(),
+            };
+            let mut ok = true;
+            while let Some(item) = upstream.recv().await {
+                let item =
+                    item.and_then(|(index, vector)| check.check(&vector).map(|()| (index, vector)));
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    // Either the caller stopped listening or the stream
+                    // failed; nothing after this point is delivered.
+                    ok = false;
+                    break;
+                }
+            }
+            recorder.record(ok, None);
+        });
+        Ok(rx)
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.inner.rate_limits()
+    }
+}
+
+/// Wrapper around a [`GeneratorModel`] that adds timeout, retry, and metrics.
+///
+/// See [`InstrumentedEmbeddingModel`] for details on the instrumentation behavior.
+pub struct InstrumentedGeneratorModel {
+    pub inner: Arc<dyn GeneratorModel>,
+    pub alias: String,
+    pub provider_id: String,
+    pub timeout: Option<Duration>,
+    pub retry: Option<crate::api::RetryConfig>,
+    pub usage: Option<Arc<UsageTracker>>,
+    pub inflight: Option<Arc<InflightCalls>>,
+}
+
+impl InstrumentedGeneratorModel {
+    fn policy(&self) -> CallPolicy<'_> {
+        CallPolicy {
+            task: "generate",
+            alias: &self.alias,
+            provider_id: &self.provider_id,
+            timeout: self.timeout,
+            retry: self.retry.as_ref(),
+            usage: self.usage.as_ref(),
+            inflight: self.inflight.as_ref(),
+        }
+    }
+
+    /// Give a call that may be retried its own idempotency key, unless the
+    /// caller supplied one, so every attempt of the call carries the same key.
+    fn with_idempotency_key(&self, mut options: GenerationOptions) -> GenerationOptions {
+        if self.retry.is_some() && options.idempotency_key.is_none() {
+            options.idempotency_key = Some(new_idempotency_key());
+        }
+        options
+    }
+}
+
+/// A random key for one logical generation call.
+fn new_idempotency_key() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    // Each `RandomState` is seeded from process-wide random keys plus a
+    // per-instance counter, giving distinct unpredictable values per call.
+    let half = || {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        hasher.finish()
+    };
+    format!("uni-xervo-{:016x}{:016x}", half(), half())
+}
+
+#[async_trait]
+impl GeneratorModel for InstrumentedGeneratorModel {
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "generate",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn generate(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationResult> {
+        let options = self.with_idempotency_key(options);
+        run_instrumented(
+            &self.policy(),
+            || self.inner.generate(messages, options.clone()),
+            |result| result.usage.as_ref(),
+        )
+        .await
+    }
+
+    /// Timeout and retry apply to opening the stream only; once chunks flow
+    /// they are relayed as they arrive. Metrics and usage are recorded when
+    /// the stream finishes, with the duration covering the whole stream, and
+    /// the call stays in `inflight` until then.
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "generate",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        if options.n.is_some_and(|n| n > 1) {
+            return Err(RuntimeError::Config(format!(
+                "Alias '{}': streaming generation does not support n > 1",
+                self.alias
+            )));
+        }
+        let policy = self.policy();
+        let inflight = policy.inflight.map(InflightCalls::start);
+        let options = self.with_idempotency_key(options);
+        let start = Instant::now();
+        let recorder = policy.recorder(start);
+
+        let res = policy
+            .retry(start, || {
+                self.inner.generate_stream(messages, options.clone())
+            })
+            .await;
+        let mut upstream = match res {
+            Ok(stream) => stream,
+            Err(e) => {
+                recorder.record(false, None);
+                return Err(e);
+            }
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(upstream.max_capacity());
+        tokio::spawn(async move {
+            // The stream counts as in flight until it is fully relayed.
+            let _inflight = inflight;
+            let mut ok = true;
+            let mut usage = None;
+            while let Some(item) = upstream.recv().await {
+                match &item {
+                    Ok(chunk) => usage = chunk.usage.clone().or(usage),
+                    Err(_) => ok = false,
+                }
+                if tx.send(item).await.is_err() {
+                    // The caller stopped listening; the generation was abandoned.
+                    ok = false;
+                    break;
+                }
+            }
+            recorder.record(ok, usage.as_ref());
+        });
+        Ok(rx)
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.inner.rate_limits()
+    }
+}
+
+/// Wrapper around a [`RerankerModel`] that adds timeout, retry, and metrics.
+///
+/// See [`InstrumentedEmbeddingModel`] for details on the instrumentation behavior.
+/// `input_limit` applies to the query and to every document.
+///
+/// Results follow the [`ScoredDoc::text`] contract regardless of the
+/// provider: with `return_documents` every result carries the caller's
+/// original, untruncated document, backfilled from the input slice when the
+/// provider does not echo it; otherwise `text` is `None`.
+///
+/// With `dedup_documents` set, identical documents are sent upstream once and
+/// their score is reported at every original index.
+pub struct InstrumentedRerankerModel {
+    pub inner: Arc<dyn RerankerModel>,
+    pub alias: String,
+    pub provider_id: String,
+    pub timeout: Option<Duration>,
+    pub retry: Option<crate::api::RetryConfig>,
+    pub usage: Option<Arc<UsageTracker>>,
+    pub inflight: Option<Arc<InflightCalls>>,
+    pub input_limit: Option<InputLimit>,
+    pub dedup_documents: bool,
+}
+
+/// Documents of one rerank call with duplicates collapsed, remembering every
+/// position each unique document held in the caller's input.
+struct DedupedDocs<'a> {
+    unique: Vec<&'a str>,
+    /// Original indices of `unique[i]`, ascending.
+    positions: Vec<Vec<usize>>,
+}
+
+impl<'a> DedupedDocs<'a> {
+    /// Collapse `docs`, or return `None` when there is nothing to collapse.
+    fn new(docs: &[&'a str]) -> Option<Self> {
+        let mut seen: std::collections::HashMap<&str, usize> = Default::default();
+        let mut deduped = Self {
+            unique: Vec::new(),
+            positions: Vec::new(),
+        };
+        for (index, &doc) in docs.iter().enumerate() {
+            let slot = *seen.entry(doc).or_insert_with(|| {
+                deduped.unique.push(doc);
+                deduped.positions.push(Vec::new());
+                deduped.unique.len() - 1
+            });
+            deduped.positions[slot].push(index);
+        }
+        (deduped.unique.len() < docs.len()).then_some(deduped)
+    }
+
+    /// Map results indexed into `unique` back to every original index,
+    /// keeping the upstream order, then cut to `top_n` again.
+    fn expand(&self, results: Vec<ScoredDoc>, top_n: Option<usize>) -> Vec<ScoredDoc> {
+        let mut expanded: Vec<ScoredDoc> = results
+            .into_iter()
+            .flat_map(|doc| {
+                self.positions
+                    .get(doc.index)
+                    .into_iter()
+                    .flatten()
+                    .map(move |&index| ScoredDoc {
+                        index,
+                        ..doc.clone()
+                    })
+            })
+            .collect();
+        if let Some(top_n) = top_n {
+            expanded.truncate(top_n);
+        }
+        expanded
+    }
+}
+
+impl InstrumentedRerankerModel {
+    /// Apply `input_limit` to the query and documents, returning the (possibly
+    /// truncated) inputs to forward to the inner model.
+    fn limit_inputs<'a>(
+        &self,
+        query: &'a str,
+        docs: &[&'a str],
+    ) -> Result<(&'a str, Vec<&'a str>)> {
+        match &self.input_limit {
+            Some(limit) => Ok((
+                limit.apply_one(&self.alias, "Query", query)?,
+                limit.apply(&self.alias, docs)?,
+            )),
+            None => Ok((query, docs.to_vec())),
+        }
+    }
+
+    fn policy(&self) -> CallPolicy<'_> {
+        CallPolicy {
+            task: "rerank",
+            alias: &self.alias,
+            provider_id: &self.provider_id,
+            timeout: self.timeout,
+            retry: self.retry.as_ref(),
+            usage: self.usage.as_ref(),
+            inflight: self.inflight.as_ref(),
+        }
+    }
+
+    /// Collapse duplicate documents when `dedup_documents` is set.
+    fn dedup<'a>(&self, docs: &[&'a str]) -> Option<DedupedDocs<'a>> {
+        if self.dedup_documents {
+            DedupedDocs::new(docs)
+        } else {
+            None
+        }
+    }
+
+    /// Run `call` with timeout, retry, metrics, and usage recording. Shared by
+    /// [`rerank`](RerankerModel::rerank) and
+    /// [`rerank_with_options`](RerankerModel::rerank_with_options).
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "rerank",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn instrumented<F, Fut>(&self, call: F) -> Result<Vec<ScoredDoc>>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<Vec<ScoredDoc>>> + Send,
+    {
+        run_instrumented(&self.policy(), call, |_| None).await
+    }
+}
+
+#[async_trait]
+impl RerankerModel for InstrumentedRerankerModel {
+    async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+        let (query, limited) = self.limit_inputs(query, docs)?;
+        let deduped = self.dedup(&limited);
+        let inputs = deduped.as_ref().map_or(&limited, |d| &d.unique);
+        let mut results = self
+            .instrumented(|| self.inner.rerank(query, inputs))
+            .await?;
+        if let Some(deduped) = &deduped {
+            results = deduped.expand(results, None);
+        }
+        for doc in &mut results {
+            doc.text = None;
+        }
+        Ok(results)
+    }
+
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        let (query, limited) = self.limit_inputs(query, docs)?;
+        let deduped = self.dedup(&limited);
+        let inputs = deduped.as_ref().map_or(&limited, |d| &d.unique);
+        let mut results = self
+            .instrumented(|| self.inner.rerank_with_options(query, inputs, options))
+            .await?;
+        if let Some(deduped) = &deduped {
+            results = if options.sort {
+                deduped.expand(results, options.top_n)
+            } else {
+                // Expanded results follow the unique documents; restore
+                // corpus order.
+                options.apply(deduped.expand(results, None), docs)
+            };
+        }
+        for doc in &mut results {
+            doc.text = if options.return_documents {
+                docs.get(doc.index).map(|t| t.to_string())
+            } else {
+                None
+            };
+        }
+        Ok(results)
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.inner.rate_limits()
+    }
+}
+
+/// Wrapper around a [`ClassifierModel`] that adds timeout, retry, and metrics.
+///
+/// See [`InstrumentedEmbeddingModel`] for details on the instrumentation behavior.
+/// `input_limit` applies to the classified text.
+pub struct InstrumentedClassifierModel {
+    pub inner: Arc<dyn ClassifierModel>,
+    pub alias: String,
+    pub provider_id: String,
+    pub timeout: Option<Duration>,
+    pub retry: Option<crate::api::RetryConfig>,
+    pub usage: Option<Arc<UsageTracker>>,
+    pub inflight: Option<Arc<InflightCalls>>,
+    pub input_limit: Option<InputLimit>,
+}
+
+impl InstrumentedClassifierModel {
+    fn policy(&self) -> CallPolicy<'_> {
+        CallPolicy {
+            task: "classify",
+            alias: &self.alias,
+            provider_id: &self.provider_id,
+            timeout: self.timeout,
+            retry: self.retry.as_ref(),
+            usage: self.usage.as_ref(),
+            inflight: self.inflight.as_ref(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClassifierModel for InstrumentedClassifierModel {
+    #[tracing::instrument(
+        name = "model_call",
+        skip_all,
+        fields(
+            task = "classify",
+            alias = %self.alias,
+            provider = %self.provider_id,
+            request_id = current_request_id().as_deref(),
+        )
+    )]
+    async fn classify(&self, text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        let text = match &self.input_limit {
+            Some(limit) => limit.apply_one(&self.alias, "Text", text)?,
+            None => text,
+        };
+        run_instrumented(
+            &self.policy(),
+            || self.inner.classify(text, labels),
+            |_| None,
+        )
+        .await
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        self.inner.rate_limits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_circuit_breaker_transitions() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_wait_seconds: 1,
+        };
+        let cb = CircuitBreakerWrapper::new(config);
+        let counter = Arc::new(AtomicU32::new(0));
+
+        // 1. Success calls - state remains Closed
+        let res = cb.call(|| async { Ok::<_, RuntimeError>(()) }).await;
+        assert!(res.is_ok());
+
+        // 2. Failures - state transitions to Open
+        let res = cb
+            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
+            .await;
+        assert!(res.is_err()); // Fail 1
+
+        let res = cb
+            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
+            .await;
+        assert!(res.is_err()); // Fail 2 -> Open
+
+        // 3. Open state - calls rejected immediately
+        let res = cb
+            .call(|| async {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().to_string(), "Unavailable");
+        assert_eq!(counter.load(Ordering::SeqCst), 0); // Should not have run
+
+        // 4. Wait for HalfOpen
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // 5. HalfOpen - allow one call
+        // If it fails, go back to Open
+        let res = cb
+            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
+            .await;
+        assert!(res.is_err());
+
+        // Should be Open again
+        let res = cb.call(|| async { Ok(()) }).await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().to_string(), "Unavailable");
+
+        // 6. Wait again for HalfOpen
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // 7. Success - transition to Closed
+        let res = cb.call(|| async { Ok(()) }).await;
+        assert!(res.is_ok());
 
         // Should be closed now, next call works
         let res = cb.call(|| async { Ok(()) }).await;
@@ -499,4 +1424,994 @@ mod tests {
         let res = cb.call(|| async { Ok::<_, RuntimeError>(()) }).await;
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn cancelled_half_open_probe_releases_probe_slot() {
+        let cb = CircuitBreakerWrapper::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            open_wait_seconds: 0,
+        });
+        let _ = cb
+            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
+            .await;
+
+        // The probe is dropped mid-flight by the timeout.
+        let probe = tokio::time::timeout(
+            Duration::from_millis(20),
+            cb.call(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok::<_, RuntimeError>(())
+            }),
+        )
+        .await;
+        assert!(probe.is_err());
+
+        // The next call may probe and closes the breaker.
+        assert!(
+            cb.call(|| async { Ok::<_, RuntimeError>(()) })
+                .await
+                .is_ok()
+        );
+        assert!(
+            cb.call(|| async { Ok::<_, RuntimeError>(()) })
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn content_filtered_errors_do_not_open_breaker() {
+        let cb = CircuitBreakerWrapper::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            open_wait_seconds: 60,
+        });
+        for _ in 0..3 {
+            let res = cb
+                .call(|| async {
+                    Err::<(), _>(RuntimeError::content_filtered("remote/gemini", "SAFETY"))
+                })
+                .await;
+            assert!(matches!(res, Err(RuntimeError::ContentFiltered { .. })));
+        }
+        assert!(!cb.is_open());
+    }
+
+    #[tokio::test]
+    async fn shared_concurrency_limit_spans_breakers() {
+        let permits = Arc::new(Semaphore::new(1));
+        let a = CircuitBreakerWrapper::new(CircuitBreakerConfig::default())
+            .with_concurrency_limit(permits.clone());
+        let b = CircuitBreakerWrapper::new(CircuitBreakerConfig::default())
+            .with_concurrency_limit(permits.clone());
+        let in_flight = Arc::new(AtomicU32::new(0));
+        let peak = Arc::new(AtomicU32::new(0));
+
+        let run = |cb: CircuitBreakerWrapper| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                cb.call(|| async {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, RuntimeError>(())
+                })
+                .await
+            }
+        };
+        let (ra, rb) = tokio::join!(run(a.clone()), run(b.clone()));
+        assert!(ra.is_ok() && rb.is_ok());
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert_eq!(permits.available_permits(), 1);
+
+        // An open breaker rejects without waiting for a permit.
+        let held = permits.clone().acquire_owned().await.unwrap();
+        let open = CircuitBreakerWrapper::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            open_wait_seconds: 60,
+        });
+        let _ = open
+            .call(|| async { Err::<(), _>(RuntimeError::InferenceError("fail".into())) })
+            .await;
+        let open = open.with_concurrency_limit(permits.clone());
+        let rejected = tokio::time::timeout(
+            Duration::from_millis(100),
+            open.call(|| async { Ok::<_, RuntimeError>(()) }),
+        )
+        .await;
+        assert!(matches!(rejected, Ok(Err(RuntimeError::Unavailable))));
+        drop(held);
+    }
+
+    /// Embedding model standing in for a slow remote call: records whether
+    /// the request ran to completion or was dropped part-way.
+    struct SlowRemoteModel {
+        cb: CircuitBreakerWrapper,
+        started: Arc<std::sync::atomic::AtomicBool>,
+        completed: Arc<std::sync::atomic::AtomicBool>,
+        aborted: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    /// Sets its flag when dropped, i.e. when the request future is dropped.
+    struct SetOnDrop(Arc<std::sync::atomic::AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for SlowRemoteModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            self.cb
+                .call(|| async {
+                    self.started
+                        .store(true, std::sync::atomic::Ordering::SeqCst);
+                    let in_flight = SetOnDrop(self.aborted.clone());
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    std::mem::forget(in_flight);
+                    self.completed
+                        .store(true, std::sync::atomic::Ordering::SeqCst);
+                    Ok(texts.iter().map(|_| vec![0.0]).collect())
+                })
+                .await
+        }
+
+        fn dimensions(&self) -> u32 {
+            1
+        }
+
+        fn model_id(&self) -> &str {
+            "slow"
+        }
+    }
+
+    #[tokio::test]
+    async fn dropping_instrumented_embed_aborts_inner_request() {
+        let started = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let completed = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let aborted = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let model = Arc::new(InstrumentedEmbeddingModel {
+            inner: Arc::new(SlowRemoteModel {
+                cb: CircuitBreakerWrapper::new(CircuitBreakerConfig::default()),
+                started: started.clone(),
+                completed: completed.clone(),
+                aborted: aborted.clone(),
+            }),
+            alias: "embed/slow".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: None,
+            observed_dimensions: None,
+        });
+
+        // Simulate a client disconnect: the caller's task is aborted while
+        // the request is in flight.
+        let caller = {
+            let model = model.clone();
+            tokio::spawn(async move { model.embed(vec!["a"]).await })
+        };
+        while !started.load(std::sync::atomic::Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+        caller.abort();
+        assert!(caller.await.unwrap_err().is_cancelled());
+
+        assert!(aborted.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(!completed.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    struct UsageReportingModel;
+
+    #[async_trait]
+    impl EmbeddingModel for UsageReportingModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0]).collect())
+        }
+
+        async fn embed_with_usage(
+            &self,
+            texts: Vec<&str>,
+        ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+            let n = texts.len();
+            Ok((
+                self.embed(texts).await?,
+                Some(TokenUsage {
+                    prompt_tokens: n,
+                    completion_tokens: 0,
+                    total_tokens: n,
+                    ..Default::default()
+                }),
+            ))
+        }
+
+        fn dimensions(&self) -> u32 {
+            1
+        }
+
+        fn model_id(&self) -> &str {
+            "usage"
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_forwards_and_records_usage() {
+        let tracker = Arc::new(UsageTracker::new());
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(UsageReportingModel),
+            alias: "embed/usage".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: Some(tracker.clone()),
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: None,
+            observed_dimensions: None,
+        };
+
+        let (vectors, usage) = model.embed_with_usage(vec!["a", "b"]).await.unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(usage.unwrap().prompt_tokens, 2);
+
+        model.embed(vec!["c"]).await.unwrap();
+
+        let report = tracker.report();
+        assert_eq!(report["embed/usage"].calls, 2);
+        assert_eq!(report["embed/usage"].prompt_tokens, 3);
+    }
+
+    /// Records the request ID visible to the inner model on each call.
+    #[derive(Default)]
+    struct RequestIdProbe {
+        seen: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for RequestIdProbe {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            self.seen
+                .lock()
+                .unwrap()
+                .push(crate::context::current_request_id());
+            Ok(texts.iter().map(|_| vec![0.0]).collect())
+        }
+
+        fn dimensions(&self) -> u32 {
+            1
+        }
+
+        fn model_id(&self) -> &str {
+            "probe"
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_propagates_request_id() {
+        let probe = Arc::new(RequestIdProbe::default());
+        let model = InstrumentedEmbeddingModel {
+            inner: probe.clone(),
+            alias: "embed/probe".to_string(),
+            provider_id: "test".to_string(),
+            timeout: Some(Duration::from_secs(5)),
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: None,
+            observed_dimensions: None,
+        };
+
+        crate::context::with_request_id("req-1", model.embed(vec!["a"]))
+            .await
+            .unwrap();
+        model.embed(vec!["b"]).await.unwrap();
+
+        assert_eq!(
+            *probe.seen.lock().unwrap(),
+            vec![Some("req-1".to_string()), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_rejects_unexpected_dimensions() {
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(UsageReportingModel),
+            alias: "embed/usage".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: Some(768),
+            input_limit: None,
+            observed_dimensions: None,
+        };
+
+        let err = model.embed(vec!["a"]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        let msg = err.to_string();
+        assert!(msg.contains("768") && msg.contains("returned 1"), "{msg}");
+
+        let matching = InstrumentedEmbeddingModel {
+            expected_dimensions: Some(1),
+            ..model
+        };
+        assert_eq!(matching.embed(vec!["a"]).await.unwrap().len(), 1);
+    }
+
+    /// Returns 4-dimensional vectors until `swapped` is set, then 8.
+    #[derive(Default)]
+    struct SwappableModel {
+        swapped: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for SwappableModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            let dims = if self.swapped.load(Ordering::SeqCst) {
+                8
+            } else {
+                4
+            };
+            Ok(texts.iter().map(|_| vec![0.0; dims]).collect())
+        }
+
+        fn dimensions(&self) -> u32 {
+            4
+        }
+
+        fn model_id(&self) -> &str {
+            "swappable"
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_rejects_dimension_drift() {
+        let inner = Arc::new(SwappableModel::default());
+        let observed = Arc::new(ObservedDimensions::new());
+        let handle = |alias: &str| InstrumentedEmbeddingModel {
+            inner: inner.clone(),
+            alias: alias.to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: None,
+            observed_dimensions: Some(observed.clone()),
+        };
+        let first = handle("embed/a");
+        let second = handle("embed/b");
+
+        first.embed(vec!["a"]).await.unwrap();
+        assert_eq!(observed.get(), Some(4));
+
+        inner.swapped.store(true, Ordering::SeqCst);
+        let err = second.embed(vec!["a"]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::InferenceError(_)));
+        let msg = err.to_string();
+        assert!(
+            msg.contains("embed/b") && msg.contains("returned 4"),
+            "{msg}"
+        );
+
+        // Per-call overrides may change the length and are not tracked.
+        assert_eq!(
+            first
+                .embed_with(vec!["a"], serde_json::Value::Null)
+                .await
+                .unwrap()[0]
+                .len(),
+            8
+        );
+        assert_eq!(observed.get(), Some(4));
+    }
+
+    /// Returns 16-dimensional int8 and binary embeddings.
+    struct CompactModel;
+
+    #[async_trait]
+    impl EmbeddingModel for CompactModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0; 16]).collect())
+        }
+
+        async fn embed_int8(&self, texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+            Ok(texts.iter().map(|_| vec![1; 16]).collect())
+        }
+
+        async fn embed_binary(&self, texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+            Ok(texts.iter().map(|_| vec![0b1010_1010; 2]).collect())
+        }
+
+        fn dimensions(&self) -> u32 {
+            16
+        }
+
+        fn model_id(&self) -> &str {
+            "compact"
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_checks_compact_dimensions() {
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(CompactModel),
+            alias: "embed/compact".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: Some(16),
+            input_limit: None,
+            observed_dimensions: None,
+        };
+        assert_eq!(model.embed_int8(vec!["a"]).await.unwrap()[0].len(), 16);
+        // Two packed bytes hold all 16 dimensions.
+        assert_eq!(model.embed_binary(vec!["a"]).await.unwrap()[0].len(), 2);
+
+        let mismatched = InstrumentedEmbeddingModel {
+            expected_dimensions: Some(32),
+            ..model
+        };
+        let err = mismatched.embed_binary(vec!["a"]).await.unwrap_err();
+        assert!(err.to_string().contains("returned 16"), "{err}");
+    }
+
+    /// Embeds each text as a single value: its length in characters.
+    struct CharCountModel;
+
+    #[async_trait]
+    impl EmbeddingModel for CharCountModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| vec![t.chars().count() as f32])
+                .collect())
+        }
+
+        fn dimensions(&self) -> u32 {
+            1
+        }
+
+        fn model_id(&self) -> &str {
+            "chars"
+        }
+    }
+
+    #[test]
+    fn input_limit_from_options() {
+        assert_eq!(InputLimit::from_options(&serde_json::json!({})), None);
+        assert_eq!(
+            InputLimit::from_options(&serde_json::json!({"max_input_chars": 8})),
+            Some(InputLimit {
+                max_chars: 8,
+                policy: OverlongPolicy::Error,
+            })
+        );
+        assert_eq!(
+            InputLimit::from_options(
+                &serde_json::json!({"max_input_chars": 8, "on_overlong": "truncate"})
+            ),
+            Some(InputLimit {
+                max_chars: 8,
+                policy: OverlongPolicy::Truncate,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_rejects_overlong_input() {
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(CharCountModel),
+            alias: "embed/chars".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: Some(InputLimit {
+                max_chars: 4,
+                policy: OverlongPolicy::Error,
+            }),
+            observed_dimensions: None,
+        };
+
+        assert_eq!(
+            model.embed(vec!["abcd", "ab"]).await.unwrap(),
+            vec![vec![4.0], vec![2.0]]
+        );
+
+        let err = model.embed(vec!["ok", "too long"]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        let msg = err.to_string();
+        assert!(
+            msg.contains("Input 1") && msg.contains("embed/chars") && msg.contains("(4)"),
+            "{msg}"
+        );
+    }
+
+    #[tokio::test]
+    async fn instrumented_embedding_truncates_overlong_input() {
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(CharCountModel),
+            alias: "embed/chars".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: Some(InputLimit {
+                max_chars: 3,
+                policy: OverlongPolicy::Truncate,
+            }),
+            observed_dimensions: None,
+        };
+
+        // Multi-byte characters are cut on a character boundary.
+        let vectors = model
+            .embed_with(vec!["héllo wörld", "ab"], serde_json::Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(vectors, vec![vec![3.0], vec![2.0]]);
+    }
+
+    #[tokio::test]
+    async fn instrumented_embed_with_forwards_overrides() {
+        let tracker = Arc::new(UsageTracker::new());
+        let model = InstrumentedEmbeddingModel {
+            inner: Arc::new(UsageReportingModel),
+            alias: "embed/usage".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: Some(tracker.clone()),
+            inflight: None,
+            expected_dimensions: None,
+            input_limit: None,
+            observed_dimensions: None,
+        };
+
+        // No overrides falls back to `embed` under the default impl.
+        let vectors = model
+            .embed_with(vec!["a"], serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(vectors.len(), 1);
+
+        let err = model
+            .embed_with(vec!["a"], serde_json::json!({ "input_type": "query" }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::Config(_)));
+        assert_eq!(tracker.report()["embed/usage"].calls, 1);
+    }
+
+    struct EchoGenerator;
+
+    #[async_trait]
+    impl GeneratorModel for EchoGenerator {
+        async fn generate(
+            &self,
+            messages: &[Message],
+            _options: GenerationOptions,
+        ) -> Result<GenerationResult> {
+            Ok(GenerationResult {
+                text: messages.last().map(|m| m.text()).unwrap_or_default(),
+                usage: Some(TokenUsage {
+                    prompt_tokens: 3,
+                    completion_tokens: 2,
+                    total_tokens: 5,
+                    ..Default::default()
+                }),
+                images: vec![],
+                audio: None,
+                alternatives: vec![],
+                finish_reason: None,
+                raw: None,
+                logprobs: None,
+                reasoning: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_stream_records_usage_when_finished() {
+        let tracker = Arc::new(UsageTracker::new());
+        let model = InstrumentedGeneratorModel {
+            inner: Arc::new(EchoGenerator),
+            alias: "gen/echo".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: Some(tracker.clone()),
+            inflight: None,
+        };
+
+        let mut stream = model
+            .generate_stream(&[Message::user("hello")], GenerationOptions::default())
+            .await
+            .unwrap();
+        let mut text = String::new();
+        while let Some(chunk) = stream.recv().await {
+            text.push_str(&chunk.unwrap().text);
+        }
+
+        assert_eq!(text, "hello");
+        let report = tracker.report();
+        assert_eq!(report["gen/echo"].calls, 1);
+        assert_eq!(report["gen/echo"].prompt_tokens, 3);
+    }
+
+    /// Records each attempt's idempotency key and fails every other attempt
+    /// with a timeout, starting with the first.
+    #[derive(Default)]
+    struct KeyRecorder {
+        keys: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl GeneratorModel for KeyRecorder {
+        async fn generate(
+            &self,
+            _messages: &[Message],
+            options: GenerationOptions,
+        ) -> Result<GenerationResult> {
+            let mut keys = self.keys.lock().unwrap();
+            keys.push(options.idempotency_key);
+            if keys.len() % 2 == 1 {
+                return Err(RuntimeError::timeout(
+                    TimeoutPhase::Inference,
+                    Duration::from_secs(1),
+                ));
+            }
+            Ok(GenerationResult {
+                text: String::new(),
+                usage: None,
+                images: vec![],
+                audio: None,
+                alternatives: vec![],
+                finish_reason: None,
+                raw: None,
+                logprobs: None,
+                reasoning: None,
+            })
+        }
+    }
+
+    fn key_recording_model(
+        inner: Arc<KeyRecorder>,
+        retry: Option<crate::api::RetryConfig>,
+    ) -> InstrumentedGeneratorModel {
+        InstrumentedGeneratorModel {
+            inner,
+            alias: "gen/keys".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry,
+            usage: None,
+            inflight: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_is_shared_by_retries_and_fresh_per_call() {
+        let inner = Arc::new(KeyRecorder::default());
+        let model = key_recording_model(
+            inner.clone(),
+            Some(crate::api::RetryConfig {
+                max_attempts: 2,
+                initial_backoff_ms: 1,
+                retry_on: None,
+                max_total_duration_ms: None,
+            }),
+        );
+
+        for _ in 0..2 {
+            model
+                .generate(&[Message::user("hi")], GenerationOptions::default())
+                .await
+                .unwrap();
+        }
+
+        let keys = inner.keys.lock().unwrap();
+        assert_eq!(keys.len(), 4);
+        assert!(keys[0].is_some());
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[2], keys[3]);
+        assert_ne!(keys[0], keys[2]);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_from_caller_is_kept() {
+        let inner = Arc::new(KeyRecorder::default());
+        let model = key_recording_model(
+            inner.clone(),
+            Some(crate::api::RetryConfig {
+                max_attempts: 2,
+                initial_backoff_ms: 1,
+                retry_on: None,
+                max_total_duration_ms: None,
+            }),
+        );
+
+        let options = GenerationOptions {
+            idempotency_key: Some("order-42".to_string()),
+            ..Default::default()
+        };
+        model
+            .generate(&[Message::user("hi")], options)
+            .await
+            .unwrap();
+
+        let keys = inner.keys.lock().unwrap();
+        assert_eq!(*keys, vec![Some("order-42".to_string()); 2]);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_not_generated_without_retry() {
+        let inner = Arc::new(KeyRecorder::default());
+        let model = key_recording_model(inner.clone(), None);
+
+        let _ = model
+            .generate(&[Message::user("hi")], GenerationOptions::default())
+            .await;
+
+        assert_eq!(*inner.keys.lock().unwrap(), vec![None]);
+    }
+
+    /// Scores documents by length, in input order, without text.
+    struct LengthReranker;
+
+    #[async_trait]
+    impl RerankerModel for LengthReranker {
+        async fn rerank(&self, _query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+            Ok(docs
+                .iter()
+                .enumerate()
+                .map(|(index, d)| ScoredDoc {
+                    index,
+                    score: d.len() as f32,
+                    text: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_rerank_with_options_applies_top_n_and_text() {
+        let model = InstrumentedRerankerModel {
+            inner: Arc::new(LengthReranker),
+            alias: "rerank/len".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            input_limit: None,
+            dedup_documents: false,
+        };
+        let docs = ["bb", "a", "dddd", "ccc"];
+
+        let all = model.rerank("q", &docs).await.unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all.iter().all(|d| d.text.is_none()));
+
+        let top = model
+            .rerank_with_options(
+                "q",
+                &docs,
+                &RerankOptions {
+                    top_n: Some(2),
+                    return_documents: true,
+                    sort: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].index, 2);
+        assert_eq!(top[0].text.as_deref(), Some("dddd"));
+        assert_eq!(top[1].index, 3);
+        assert_eq!(top[1].text.as_deref(), Some("ccc"));
+    }
+
+    /// [`LengthReranker`] that records every document it is asked to score.
+    #[derive(Default)]
+    struct RecordingReranker {
+        seen: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl RerankerModel for RecordingReranker {
+        async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>> {
+            {
+                let mut seen = self.seen.lock().unwrap();
+                seen.extend(docs.iter().map(|d| d.to_string()));
+            }
+            let mut results = LengthReranker.rerank(query, docs).await?;
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+            Ok(results)
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_rerank_dedups_documents_and_remaps_indices() {
+        let inner = Arc::new(RecordingReranker::default());
+        let model = InstrumentedRerankerModel {
+            inner: inner.clone(),
+            alias: "rerank/len".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            input_limit: None,
+            dedup_documents: true,
+        };
+        let docs = ["ccc", "a", "ccc", "bb", "a", "ccc"];
+
+        let ranked = model.rerank("q", &docs).await.unwrap();
+        // Each unique document is scored once, in first-seen order.
+        assert_eq!(*inner.seen.lock().unwrap(), vec!["ccc", "a", "bb"]);
+        let indices: Vec<usize> = ranked.iter().map(|d| d.index).collect();
+        assert_eq!(indices, vec![0, 2, 5, 3, 1, 4]);
+        for doc in &ranked {
+            assert_eq!(doc.score, docs[doc.index].len() as f32);
+        }
+
+        inner.seen.lock().unwrap().clear();
+        let top = model
+            .rerank_with_options(
+                "q",
+                &docs,
+                &RerankOptions {
+                    top_n: Some(4),
+                    return_documents: true,
+                    sort: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(inner.seen.lock().unwrap().len(), 3);
+        let top: Vec<(usize, Option<&str>)> =
+            top.iter().map(|d| (d.index, d.text.as_deref())).collect();
+        assert_eq!(
+            top,
+            vec![
+                (0, Some("ccc")),
+                (2, Some("ccc")),
+                (5, Some("ccc")),
+                (3, Some("bb"))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rerank_unsorted_returns_corpus_order() {
+        let docs = ["bb", "a", "dddd", "ccc"];
+        let ranked = RecordingReranker::default()
+            .rerank_with_options("q", &docs, &RerankOptions::default())
+            .await
+            .unwrap();
+        let indices: Vec<usize> = ranked.iter().map(|d| d.index).collect();
+        assert_eq!(indices, vec![2, 3, 0, 1]);
+
+        let unsorted = RerankOptions {
+            sort: false,
+            ..Default::default()
+        };
+        let aligned = RecordingReranker::default()
+            .rerank_with_options("q", &docs, &unsorted)
+            .await
+            .unwrap();
+        let scores: Vec<(usize, f32)> = aligned.iter().map(|d| (d.index, d.score)).collect();
+        assert_eq!(scores, vec![(0, 2.0), (1, 1.0), (2, 4.0), (3, 3.0)]);
+
+        // top_n keeps the best documents, still in corpus order, including
+        // when duplicates are collapsed.
+        let model = InstrumentedRerankerModel {
+            inner: Arc::new(RecordingReranker::default()),
+            alias: "rerank/len".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            input_limit: None,
+            dedup_documents: true,
+        };
+        let docs = ["ccc", "a", "dddd", "bb", "ccc"];
+        let top = model
+            .rerank_with_options(
+                "q",
+                &docs,
+                &RerankOptions {
+                    top_n: Some(3),
+                    sort: false,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let indices: Vec<usize> = top.iter().map(|d| d.index).collect();
+        assert_eq!(indices, vec![0, 2, 4]);
+    }
+
+    #[tokio::test]
+    async fn instrumented_rerank_without_dedup_sends_every_document() {
+        let inner = Arc::new(RecordingReranker::default());
+        let model = InstrumentedRerankerModel {
+            inner: inner.clone(),
+            alias: "rerank/len".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            input_limit: None,
+            dedup_documents: false,
+        };
+
+        model.rerank("q", &["a", "a"]).await.unwrap();
+        assert_eq!(inner.seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn instrumented_rerank_truncation_keeps_original_text() {
+        let model = InstrumentedRerankerModel {
+            inner: Arc::new(LengthReranker),
+            alias: "rerank/len".to_string(),
+            provider_id: "test".to_string(),
+            timeout: None,
+            retry: None,
+            usage: None,
+            inflight: None,
+            input_limit: Some(InputLimit {
+                max_chars: 2,
+                policy: OverlongPolicy::Truncate,
+            }),
+            dedup_documents: false,
+        };
+
+        let ranked = model
+            .rerank_with_options(
+                "a long query",
+                &["dddd", "a"],
+                &RerankOptions {
+                    top_n: None,
+                    return_documents: true,
+                    sort: true,
+                },
+            )
+            .await
+            .unwrap();
+        // "dddd" was scored as "dd", but is returned in full.
+        assert_eq!(ranked[0].index, 0);
+        assert_eq!(ranked[0].score, 2.0);
+        assert_eq!(ranked[0].text.as_deref(), Some("dddd"));
+
+        let strict = InstrumentedRerankerModel {
+            input_limit: Some(InputLimit {
+                max_chars: 2,
+                policy: OverlongPolicy::Error,
+            }),
+            ..model
+        };
+        let err = strict.rerank("q", &["a", "bbb"]).await.unwrap_err();
+        assert!(err.to_string().contains("Input 1"), "{err}");
+        let err = strict.rerank("long query", &["a"]).await.unwrap_err();
+        assert!(err.to_string().contains("Query"), "{err}");
+    }
 }
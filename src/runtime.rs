@@ -1,22 +1,37 @@
 //! The core runtime that manages providers, catalogs, and loaded model instances.
 
-use crate::api::{ModelAliasSpec, ModelRuntimeKey};
-use crate::error::{Result, RuntimeError};
-use crate::options_validation::validate_provider_options;
+use crate::api::{ModelAliasSpec, ModelRuntimeKey, ModelTask, MultiBackendSpec};
+use crate::balancing::{
+    WeightedClassifierModel, WeightedEmbeddingModel, WeightedGeneratorModel, WeightedRerankerModel,
+    WeightedSelector,
+};
+use crate::batching::{MicroBatchConfig, MicroBatchingEmbeddingModel};
+use crate::error::{Result, RuntimeError, TimeoutPhase};
+use crate::options_validation::{RUNTIME_OPTION_KEYS, validate_spec_options};
 use crate::reliability::{
-    InstrumentedEmbeddingModel, InstrumentedGeneratorModel, InstrumentedRerankerModel,
+    InflightCalls, InputLimit, InstrumentedClassifierModel, InstrumentedEmbeddingModel,
+    InstrumentedGeneratorModel, InstrumentedRerankerModel, ObservedDimensions,
 };
 use crate::traits::{
-    EmbeddingModel, GeneratorModel, LoadedModelHandle, ModelProvider, RerankerModel,
+    ClassifierModel, DownloadProgress, DownloadProgressFn, EmbeddingModel, GeneratorModel,
+    LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth, RateLimitSnapshot,
+    RerankerModel,
 };
+use crate::usage::{AggregatedUsage, CostPer1k, UsageTracker};
 use std::any::Any;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::{Mutex, RwLock, mpsc};
 
 /// Default load timeout applied when [`ModelAliasSpec::load_timeout`] is `None`.
 const DEFAULT_LOAD_TIMEOUT_SECS: u64 = 600;
 
+/// Default time [`ModelRuntime::shutdown`] waits for in-flight calls when
+/// [`ModelRuntimeBuilder::shutdown_grace_period`] is not set.
+const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 30;
+
 /// The central runtime that owns registered providers and a catalog of model
 /// aliases.
 ///
@@ -31,7 +46,101 @@ const DEFAULT_LOAD_TIMEOUT_SECS: u64 = 600;
 pub struct ModelRuntime {
     providers: HashMap<String, Box<dyn ModelProvider>>,
     registry: Arc<ModelRegistry>,
-    catalog: RwLock<HashMap<String, ModelAliasSpec>>,
+    catalog: RwLock<Catalog>,
+    /// Whether one alias may be defined once per provider; see
+    /// [`ModelRuntimeBuilder::provider_scoped_aliases`].
+    provider_scoped_aliases: bool,
+    micro_batch: Option<MicroBatchConfig>,
+    /// One shared batcher per loaded embedding model so that concurrent
+    /// callers (across aliases resolving to the same model) coalesce.
+    batchers: Mutex<HashMap<ModelRuntimeKey, Arc<dyn EmbeddingModel>>>,
+    /// First embedding length per loaded model, shared by every handle for
+    /// that model so a later response of another length is caught.
+    observed_dimensions: std::sync::Mutex<HashMap<ModelRuntimeKey, Arc<ObservedDimensions>>>,
+    usage: Arc<UsageTracker>,
+    /// Balanced aliases, keyed by alias. Fixed at build time.
+    multi_backends: HashMap<String, MultiBackend>,
+    /// Whether handles are wrapped in the `Instrumented*` models.
+    instrumentation: bool,
+    /// Background warmup tasks spawned by `build()`, drained by
+    /// `wait_for_warmup()`.
+    background_warmups: Mutex<Vec<tokio::task::JoinHandle<()>>>,
+    /// Required aliases whose background warmup failed and that have not
+    /// loaded since.
+    failed_warmups: std::sync::Mutex<std::collections::BTreeSet<String>>,
+    /// Calls running through instrumented handles, drained by `shutdown()`.
+    inflight: Arc<InflightCalls>,
+    /// Set by `shutdown()`; new handles are refused from then on.
+    draining: AtomicBool,
+    shutdown_grace_period: std::time::Duration,
+    /// Per-provider default options, merged beneath each alias's options
+    /// before `provider.load`.
+    provider_defaults: HashMap<String, serde_json::Value>,
+}
+
+/// A balanced alias and the rotation state shared by all of its handles.
+struct MultiBackend {
+    spec: MultiBackendSpec,
+    selector: Arc<WeightedSelector>,
+}
+
+/// Catalog specs grouped by alias. An alias has exactly one spec unless the
+/// runtime allows provider-scoped aliases, in which case it has at most one
+/// per provider.
+#[derive(Default)]
+struct Catalog {
+    by_alias: HashMap<String, Vec<ModelAliasSpec>>,
+}
+
+impl Catalog {
+    fn new(specs: impl IntoIterator<Item = ModelAliasSpec>) -> Self {
+        let mut catalog = Self::default();
+        for spec in specs {
+            catalog.insert(spec);
+        }
+        catalog
+    }
+
+    fn insert(&mut self, spec: ModelAliasSpec) {
+        self.by_alias
+            .entry(spec.alias.clone())
+            .or_default()
+            .push(spec);
+    }
+
+    fn contains(&self, alias: &str) -> bool {
+        self.by_alias.contains_key(alias)
+    }
+
+    /// The spec for `alias`, failing if it is missing or defined for more
+    /// than one provider.
+    fn lookup(&self, alias: &str) -> Result<&ModelAliasSpec> {
+        match self.by_alias.get(alias).map(Vec::as_slice) {
+            None | Some([]) => Err(RuntimeError::Config(format!("Alias '{}' not found", alias))),
+            Some([spec]) => Ok(spec),
+            Some(specs) => Err(RuntimeError::Config(format!(
+                "Alias '{}' is defined for several providers ({}); name one with embedding_from",
+                alias,
+                specs
+                    .iter()
+                    .map(|spec| spec.provider_id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// The spec for `alias` as defined for `provider_id`.
+    fn get_from(&self, alias: &str, provider_id: &str) -> Option<&ModelAliasSpec> {
+        self.by_alias
+            .get(alias)?
+            .iter()
+            .find(|spec| spec.provider_id == provider_id)
+    }
+
+    fn specs(&self) -> impl Iterator<Item = &ModelAliasSpec> {
+        self.by_alias.values().flatten()
+    }
 }
 
 /// Internal registry that caches loaded model instances and coordinates
@@ -40,7 +149,144 @@ pub struct ModelRuntime {
 pub struct ModelRegistry {
     instances: RwLock<HashMap<ModelRuntimeKey, LoadedModelHandle>>,
     /// Per-key mutexes to prevent concurrent loads of the same model.
-    loader_locks: Mutex<HashMap<ModelRuntimeKey, Arc<Mutex<()>>>>,
+    loader_locks: LoaderLocks,
+}
+
+/// Number of independently locked shards in [`LoaderLocks`].
+const LOADER_LOCK_SHARDS: usize = 16;
+
+/// Per-key loader mutexes, spread over shards so first loads of different
+/// models rarely touch the same map lock.
+///
+/// Shard locks are synchronous and only held to look up or remove an entry,
+/// never across an `await`. An entry is removed by the last
+/// [`LoaderLockEntry`] for its key, so the map holds only keys with a load
+/// in progress or waiters queued behind one.
+struct LoaderLocks {
+    shards: Box<[std::sync::Mutex<LoaderLockMap>]>,
+}
+
+type LoaderLockMap = HashMap<ModelRuntimeKey, Arc<Mutex<()>>>;
+
+impl Default for LoaderLocks {
+    fn default() -> Self {
+        Self {
+            shards: (0..LOADER_LOCK_SHARDS)
+                .map(|_| Default::default())
+                .collect(),
+        }
+    }
+}
+
+impl LoaderLocks {
+    fn shard(&self, key: &ModelRuntimeKey) -> &std::sync::Mutex<LoaderLockMap> {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// Register interest in loading `key`; lock the returned entry to load.
+    fn entry(&self, key: &ModelRuntimeKey) -> LoaderLockEntry<'_> {
+        let lock = self
+            .shard(key)
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        LoaderLockEntry {
+            locks: self,
+            key: key.clone(),
+            lock,
+        }
+    }
+
+    /// Whether no key has a load in progress.
+    #[cfg(test)]
+    fn is_empty(&self) -> bool {
+        self.shards
+            .iter()
+            .all(|shard| shard.lock().unwrap().is_empty())
+    }
+}
+
+/// A caller's reference to one key's loader mutex. Dropping the last
+/// reference, whether after loading or while still waiting, removes the key
+/// from [`LoaderLocks`].
+struct LoaderLockEntry<'a> {
+    locks: &'a LoaderLocks,
+    key: ModelRuntimeKey,
+    lock: Arc<Mutex<()>>,
+}
+
+impl LoaderLockEntry<'_> {
+    async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    /// Lock without waiting, or `None` while another caller is loading.
+    fn try_lock(&self) -> Option<tokio::sync::MutexGuard<'_, ()>> {
+        self.lock.try_lock().ok()
+    }
+}
+
+/// A caller blocked behind another caller's load of the same model, counted
+/// in the `model_load.waiters` gauge (labels `alias`, `provider`) until it
+/// proceeds or is cancelled.
+struct LoadWaiter {
+    alias: String,
+    provider: String,
+    since: std::time::Instant,
+}
+
+impl LoadWaiter {
+    fn start(alias: &str, provider: &str) -> Self {
+        crate::telemetry::gauge!(
+            "model_load.waiters",
+            "alias" => alias.to_string(),
+            "provider" => provider.to_string()
+        )
+        .increment(1.0);
+        tracing::info!(alias = %alias, provider = %provider, "Waiting for in-progress model load");
+        Self {
+            alias: alias.to_string(),
+            provider: provider.to_string(),
+            since: std::time::Instant::now(),
+        }
+    }
+}
+
+impl Drop for LoadWaiter {
+    fn drop(&mut self) {
+        crate::telemetry::gauge!(
+            "model_load.waiters",
+            "alias" => self.alias.clone(),
+            "provider" => self.provider.clone()
+        )
+        .decrement(1.0);
+        tracing::debug!(
+            alias = %self.alias,
+            provider = %self.provider,
+            waited_ms = self.since.elapsed().as_millis(),
+            "Stopped waiting for model load"
+        );
+    }
+}
+
+impl Drop for LoaderLockEntry<'_> {
+    fn drop(&mut self) {
+        let mut shard = self.locks.shard(&self.key).lock().unwrap();
+        // New entries clone under the shard lock, so a count of two (the map
+        // and this entry) means nobody else is loading or waiting.
+        if Arc::strong_count(&self.lock) == 2
+            && shard
+                .get(&self.key)
+                .is_some_and(|lock| Arc::ptr_eq(lock, &self.lock))
+        {
+            shard.remove(&self.key);
+        }
+    }
 }
 
 impl ModelRuntime {
@@ -53,37 +299,388 @@ impl ModelRuntime {
     /// Register a new model alias at runtime.
     pub async fn register(&self, spec: ModelAliasSpec) -> Result<()> {
         spec.validate()?;
-        if !self.providers.contains_key(&spec.provider_id) {
+        let Some(provider) = self.providers.get(&spec.provider_id) else {
             return Err(RuntimeError::Config(format!(
                 "Unknown provider '{}' for alias '{}'",
                 spec.provider_id, spec.alias
             )));
-        }
-        validate_provider_options(&spec.provider_id, spec.task, &spec.options)?;
+        };
+        validate_spec_options(
+            provider.as_ref(),
+            &with_provider_defaults(&spec, &self.provider_defaults),
+        )?;
         let mut catalog = self.catalog.write().await;
-        if catalog.contains_key(&spec.alias) {
+        let taken = if self.provider_scoped_aliases {
+            catalog.get_from(&spec.alias, &spec.provider_id).is_some()
+        } else {
+            catalog.contains(&spec.alias)
+        };
+        if taken || self.multi_backends.contains_key(&spec.alias) {
             return Err(RuntimeError::Config(format!(
                 "Alias '{}' already exists",
                 spec.alias
             )));
         }
-        catalog.insert(spec.alias.clone(), spec);
+        if self.provider_scoped_aliases
+            && catalog.contains(&spec.alias)
+            && (spec.task != ModelTask::Embed
+                || catalog
+                    .specs()
+                    .any(|s| s.alias == spec.alias && s.task != ModelTask::Embed))
+        {
+            return Err(scoped_alias_task_error(&spec.alias));
+        }
+        catalog.insert(spec);
         Ok(())
     }
 
-    /// Check if an alias exists in the catalog.
+    /// Replace the catalog with the specs in a JSON file (array of model
+    /// alias specs). See [`reload_catalog`](Self::reload_catalog).
+    pub async fn reload_catalog_from_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<CatalogReload> {
+        self.reload_catalog(crate::api::catalog_from_file(path)?)
+            .await
+    }
+
+    /// Replace the catalog with `specs`, e.g. after the catalog file changed.
+    ///
+    /// The new catalog is validated as a whole, like
+    /// [`ModelRuntimeBuilder::build`] does, and balanced aliases must still
+    /// resolve against it; on any error nothing changes. Otherwise aliases
+    /// missing from `specs` are removed, new ones added, and changed ones
+    /// replaced. A cached model is unloaded once no alias resolves to its
+    /// [`ModelRuntimeKey`] any more; models whose key is unchanged stay
+    /// loaded. Added aliases load lazily on first access or via
+    /// [`warmup`](Self::warmup); their `warmup` policy is not applied.
+    ///
+    /// Handles already handed out keep working until dropped. Once
+    /// [`shutdown`](Self::shutdown) has been called, reloads fail with
+    /// [`RuntimeError::ShuttingDown`].
+    pub async fn reload_catalog(&self, specs: Vec<ModelAliasSpec>) -> Result<CatalogReload> {
+        self.check_accepting("catalog reload")?;
+        check_catalog_issues(validate_catalog(
+            &self.providers,
+            &self.provider_defaults,
+            &specs,
+            self.multi_backends.values().map(|multi| &multi.spec),
+            self.provider_scoped_aliases,
+        ))?;
+
+        let mut catalog = self.catalog.write().await;
+        let mut changes = CatalogReload::default();
+        let new_catalog = Catalog::new(specs);
+        for (alias, specs) in &new_catalog.by_alias {
+            match catalog.by_alias.get(alias) {
+                None => changes.added.push(alias.clone()),
+                Some(old) if old.len() != specs.len() || specs.iter().any(|s| !old.contains(s)) => {
+                    changes.updated.push(alias.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = catalog
+            .by_alias
+            .keys()
+            .filter(|alias| !new_catalog.contains(alias))
+            .cloned()
+            .collect();
+        changes.added.sort();
+        changes.updated.sort();
+        changes.removed.sort();
+
+        let live: std::collections::HashSet<ModelRuntimeKey> = new_catalog
+            .specs()
+            .map(|spec| self.runtime_key(spec))
+            .collect();
+        let stale: std::collections::HashSet<ModelRuntimeKey> = catalog
+            .specs()
+            .map(|spec| self.runtime_key(spec))
+            .filter(|key| !live.contains(key))
+            .collect();
+        *catalog = new_catalog;
+        drop(catalog);
+
+        if !stale.is_empty() {
+            let mut batchers = self.batchers.lock().await;
+            let mut instances = self.registry.instances.write().await;
+            let mut observed = self.observed_dimensions.lock().unwrap();
+            for key in &stale {
+                batchers.remove(key);
+                instances.remove(key);
+                observed.remove(key);
+            }
+        }
+        let mut failed = self.failed_warmups.lock().unwrap();
+        for alias in changes.removed.iter().chain(&changes.updated) {
+            failed.remove(alias);
+        }
+        drop(failed);
+
+        tracing::info!(
+            added = changes.added.len(),
+            updated = changes.updated.len(),
+            removed = changes.removed.len(),
+            unloaded = stale.len(),
+            "Catalog reloaded"
+        );
+        Ok(changes)
+    }
+
+    /// Check if an alias exists in the catalog or names a balanced alias.
     pub async fn contains_alias(&self, alias: &str) -> bool {
+        if self.multi_backends.contains_key(alias) {
+            return true;
+        }
         let catalog = self.catalog.read().await;
-        catalog.contains_key(alias)
+        catalog.contains(alias)
+    }
+
+    /// Whether the model behind `alias` is already loaded, so resolving a
+    /// handle for it will not trigger a load. Never loads anything itself.
+    ///
+    /// A balanced alias counts as loaded once every backend is. Unknown
+    /// aliases return `false`.
+    pub async fn is_loaded(&self, alias: &str) -> bool {
+        let keys: Vec<ModelRuntimeKey> = {
+            let catalog = self.catalog.read().await;
+            let aliases: Vec<&str> = match self.multi_backends.get(alias) {
+                Some(multi) => multi
+                    .spec
+                    .backends
+                    .iter()
+                    .map(|b| b.alias.as_str())
+                    .collect(),
+                None => vec![alias],
+            };
+            let Some(keys) = aliases
+                .into_iter()
+                .map(|alias| {
+                    catalog
+                        .lookup(alias)
+                        .ok()
+                        .map(|spec| self.runtime_key(spec))
+                })
+                .collect::<Option<Vec<_>>>()
+            else {
+                return false;
+            };
+            keys
+        };
+        let instances = self.registry.instances.read().await;
+        keys.iter().all(|key| instances.contains_key(key))
+    }
+
+    /// The latest rate-limit headers the provider returned for `alias`, for
+    /// throttling locally before requests start failing with 429s.
+    ///
+    /// Returns `None` when the alias is unknown, balanced across backends,
+    /// not loaded yet, or served by a provider that does not report rate
+    /// limits, and before its first response. Never loads anything itself.
+    pub async fn rate_limits(&self, alias: &str) -> Option<RateLimitSnapshot> {
+        let (task, key) = {
+            let catalog = self.catalog.read().await;
+            let spec = catalog.lookup(alias).ok()?;
+            (spec.task, self.runtime_key(spec))
+        };
+        let instances = self.registry.instances.read().await;
+        let handle = instances.get(&key)?;
+        match task {
+            ModelTask::Embed => handle
+                .downcast_ref::<Arc<dyn EmbeddingModel>>()?
+                .rate_limits(),
+            ModelTask::Rerank => handle
+                .downcast_ref::<Arc<dyn RerankerModel>>()?
+                .rate_limits(),
+            ModelTask::Generate => handle
+                .downcast_ref::<Arc<dyn GeneratorModel>>()?
+                .rate_limits(),
+            ModelTask::Classify => handle
+                .downcast_ref::<Arc<dyn ClassifierModel>>()?
+                .rate_limits(),
+        }
+    }
+
+    /// Per-alias token usage and call counts accumulated since the runtime
+    /// was built.
+    ///
+    /// Only calls made through handles returned by this runtime are counted.
+    /// `estimated_cost` is filled in for aliases present in the table passed
+    /// to [`ModelRuntimeBuilder::cost_per_1k`].
+    pub fn usage_report(&self) -> HashMap<String, AggregatedUsage> {
+        self.usage.report()
+    }
+
+    /// Health of every registered provider, keyed by provider ID.
+    ///
+    /// With `probe` set, each provider's
+    /// [`probe_health`](ModelProvider::probe_health) is used instead of its
+    /// passive [`health`](ModelProvider::health). Probes of built-in remote
+    /// providers send a request to the provider's API (cached for 30 seconds).
+    pub async fn health(&self, probe: bool) -> HashMap<String, ProviderHealth> {
+        let mut report = HashMap::with_capacity(self.providers.len());
+        for (id, provider) in &self.providers {
+            let health = if probe {
+                provider.probe_health().await
+            } else {
+                provider.health().await
+            };
+            report.insert(id.clone(), health);
+        }
+        report
+    }
+
+    /// Capabilities of every registered provider, keyed by provider ID.
+    pub fn provider_capabilities(&self) -> HashMap<String, ProviderCapabilities> {
+        self.providers
+            .iter()
+            .map(|(id, provider)| (id.clone(), provider.capabilities()))
+            .collect()
+    }
+
+    /// Whether every `required` alias scheduled for background warmup has
+    /// loaded (or is still loading).
+    ///
+    /// Turns `false` when a required alias fails its background warmup, and
+    /// back to `true` once every such alias has loaded, e.g. on first access
+    /// or via [`warmup`](Self::warmup). Failures of optional aliases do not
+    /// affect readiness.
+    pub fn is_ready(&self) -> bool {
+        self.failed_warmups.lock().unwrap().is_empty()
+    }
+
+    /// Required aliases whose background warmup failed and that have not
+    /// loaded since, in alias order.
+    pub fn failed_warmups(&self) -> Vec<String> {
+        self.failed_warmups
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Wait until every background warmup scheduled by
+    /// [`ModelRuntimeBuilder::build`] has finished.
+    ///
+    /// Returns [`RuntimeError::Load`] naming the required aliases that failed
+    /// (see [`is_ready`](Self::is_ready)); optional aliases that failed are
+    /// only logged.
+    pub async fn wait_for_warmup(&self) -> Result<()> {
+        // Hold the lock while draining so concurrent callers also wait.
+        let mut tasks = self.background_warmups.lock().await;
+        for task in tasks.drain(..) {
+            if let Err(e) = task.await {
+                tracing::error!(error = %e, "Background warmup task panicked");
+            }
+        }
+        drop(tasks);
+
+        let failed = self.failed_warmups();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(RuntimeError::Load(format!(
+                "Background warmup failed for required alias(es): {}",
+                failed.join(", ")
+            )))
+        }
+    }
+
+    /// Stop handing out models, let in-flight calls finish, and unload every
+    /// model.
+    ///
+    /// From the first call on, [`embedding`](Self::embedding),
+    /// [`reranker`](Self::reranker), [`generator`](Self::generator), and the
+    /// other handle accessors, as well as [`warmup`](Self::warmup), the
+    /// `prefetch` methods, and [`reload_catalog`](Self::reload_catalog), fail
+    /// with [`RuntimeError::ShuttingDown`]. The
+    /// returned future then waits, up
+    /// to [`ModelRuntimeBuilder::shutdown_grace_period`] (30 seconds by
+    /// default), for calls already running through instrumented handles,
+    /// including open generation streams, and finally clears the registry.
+    /// Background warmups still running are aborted, and loads still running
+    /// fail with [`RuntimeError::ShuttingDown`] once they finish.
+    /// Remote providers' HTTP connections close once the last handle to
+    /// their models is dropped.
+    ///
+    /// Calls through handles of a runtime built with
+    /// [`instrumentation(false)`](ModelRuntimeBuilder::instrumentation) are
+    /// not tracked and are not waited for.
+    pub async fn shutdown(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+        for task in self.background_warmups.lock().await.drain(..) {
+            task.abort();
+        }
+        let running = self.inflight.count();
+        tracing::info!(inflight = running, "Runtime shutting down");
+
+        let grace = self.shutdown_grace_period;
+        if tokio::time::timeout(grace, self.inflight.wait_idle())
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                inflight = self.inflight.count(),
+                grace_secs = grace.as_secs_f64(),
+                "Shutdown grace period elapsed with calls still running"
+            );
+        }
+
+        self.batchers.lock().await.clear();
+        self.observed_dimensions.lock().unwrap().clear();
+        self.registry.instances.write().await.clear();
+        tracing::info!("Runtime shut down");
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Refuse new handles, loads, and catalog reloads once the runtime is
+    /// draining. `request` is the alias requested, or what else was refused.
+    fn check_accepting(&self, request: &str) -> Result<()> {
+        if self.is_shutting_down() {
+            tracing::warn!(request = %request, "Runtime is shutting down; refusing request");
+            return Err(RuntimeError::ShuttingDown);
+        }
+        Ok(())
+    }
+
+    /// The key of the instance serving `spec`, hashing only the options its
+    /// provider declares load-relevant.
+    fn runtime_key(&self, spec: &ModelAliasSpec) -> ModelRuntimeKey {
+        match self
+            .providers
+            .get(&spec.provider_id)
+            .and_then(|provider| provider.load_option_keys())
+        {
+            Some(keys) => ModelRuntimeKey::with_load_options(spec, keys),
+            None => ModelRuntimeKey::new(spec),
+        }
     }
 
-    /// Look up a spec by alias, returning an error if not found.
+    /// Look up a spec by alias, returning an error if not found or defined
+    /// for several providers.
     async fn lookup_spec(&self, alias: &str) -> Result<ModelAliasSpec> {
+        let catalog = self.catalog.read().await;
+        catalog.lookup(alias).cloned()
+    }
+
+    /// Look up the spec `provider_id` defines for `alias`.
+    async fn lookup_spec_from(&self, alias: &str, provider_id: &str) -> Result<ModelAliasSpec> {
         let catalog = self.catalog.read().await;
         catalog
-            .get(alias)
+            .get_from(alias, provider_id)
             .cloned()
-            .ok_or_else(|| RuntimeError::Config(format!("Alias '{}' not found", alias)))
+            .ok_or_else(|| {
+                RuntimeError::Config(format!(
+                    "Alias '{}' not found for provider '{}'",
+                    alias, provider_id
+                ))
+            })
     }
 
     /// Pre-load and cache every model in the catalog.
@@ -94,9 +691,10 @@ impl ModelRuntime {
     pub async fn prefetch_all(&self) -> Result<()> {
         let specs: Vec<ModelAliasSpec> = {
             let catalog = self.catalog.read().await;
-            catalog.values().cloned().collect()
+            catalog.specs().cloned().collect()
         };
         for spec in specs {
+            self.check_accepting(&spec.alias)?;
             tracing::info!(alias = %spec.alias, "Prefetching model");
             self.resolve_and_load_internal(&spec).await?;
         }
@@ -109,6 +707,7 @@ impl ModelRuntime {
     /// or if any model fails to load. Models already loaded are skipped.
     pub async fn prefetch(&self, aliases: &[&str]) -> Result<()> {
         for alias in aliases {
+            self.check_accepting(alias)?;
             let spec = self.lookup_spec(alias).await?;
             tracing::info!(alias = %alias, "Prefetching model");
             self.resolve_and_load_internal(&spec).await?;
@@ -116,40 +715,195 @@ impl ModelRuntime {
         Ok(())
     }
 
+    /// Pre-load and cache specific aliases, attempting every one even when
+    /// some fail.
+    ///
+    /// Returns the outcome per alias: `Ok(())` once the model is loaded (or
+    /// was already), or the error from looking it up or loading it. Use this
+    /// for best-effort startup warming where optional models may be
+    /// unavailable; [`prefetch`](Self::prefetch) stops at the first failure
+    /// instead.
+    pub async fn prefetch_report(&self, aliases: &[&str]) -> HashMap<String, Result<()>> {
+        let mut report = HashMap::with_capacity(aliases.len());
+        for alias in aliases {
+            tracing::info!(alias = %alias, "Prefetching model");
+            let outcome = match self.check_accepting(alias) {
+                Ok(()) => match self.lookup_spec(alias).await {
+                    Ok(spec) => self.resolve_and_load_internal(&spec).await.map(|_| ()),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &outcome {
+                tracing::warn!(alias = %alias, error = %e, "Prefetch failed");
+            }
+            report.insert(alias.to_string(), outcome);
+        }
+        report
+    }
+
+    /// Load one alias if it is not resident yet, without returning a handle.
+    ///
+    /// Returns once the model is loaded and cached, e.g. before a
+    /// latency-sensitive section of a request handler. For a balanced alias
+    /// every member is loaded. Returns an error if the alias is not in the
+    /// catalog or fails to load.
+    pub async fn warmup(&self, alias: &str) -> Result<()> {
+        self.check_accepting(alias)?;
+        let aliases: Vec<&str> = match self.multi_backends.get(alias) {
+            Some(multi) => multi
+                .spec
+                .backends
+                .iter()
+                .map(|b| b.alias.as_str())
+                .collect(),
+            None => vec![alias],
+        };
+        for alias in aliases {
+            let spec = self.lookup_spec(alias).await?;
+            self.resolve_and_load_internal(&spec).await?;
+        }
+        Ok(())
+    }
+
     /// Resolve, load (if necessary), and return an instrumented [`EmbeddingModel`]
     /// handle for the given alias.
+    ///
+    /// When micro-batching is enabled via
+    /// [`ModelRuntimeBuilder::micro_batch`], the returned handle shares a
+    /// batcher with every other handle for the same underlying model.
+    ///
+    /// For a balanced alias registered with
+    /// [`ModelRuntimeBuilder::multi_backend`], every member is resolved and
+    /// the returned handle picks one per call; see [`crate::balancing`].
     pub async fn embedding(&self, alias: &str) -> Result<Arc<dyn EmbeddingModel>> {
+        self.check_accepting(alias)?;
+        let Some(multi) = self.multi_backends.get(alias) else {
+            return self.single_embedding(alias).await;
+        };
+        let mut models = Vec::with_capacity(multi.spec.backends.len());
+        for backend in &multi.spec.backends {
+            models.push(self.single_embedding(&backend.alias).await?);
+        }
+        Ok(Arc::new(WeightedEmbeddingModel::new(
+            multi.selector.clone(),
+            models,
+        )?))
+    }
+
+    async fn single_embedding(&self, alias: &str) -> Result<Arc<dyn EmbeddingModel>> {
         let spec = self.lookup_spec(alias).await?;
+        self.embedding_for_spec(spec).await
+    }
+
+    /// Resolve, load (if necessary), and return an instrumented
+    /// [`EmbeddingModel`] handle for `alias` as defined for `provider_id`.
+    ///
+    /// Catalogs built with
+    /// [`ModelRuntimeBuilder::provider_scoped_aliases`] may define an alias
+    /// once per provider; this is how such an alias is reached, since
+    /// [`embedding`](Self::embedding) rejects it as ambiguous. Any other
+    /// catalog alias resolves too, as long as `provider_id` matches its
+    /// provider. Balanced aliases are not tied to a provider and are not
+    /// found.
+    pub async fn embedding_from(
+        &self,
+        alias: &str,
+        provider_id: &str,
+    ) -> Result<Arc<dyn EmbeddingModel>> {
+        self.check_accepting(alias)?;
+        let spec = self.lookup_spec_from(alias, provider_id).await?;
+        self.embedding_for_spec(spec).await
+    }
+
+    async fn embedding_for_spec(&self, spec: ModelAliasSpec) -> Result<Arc<dyn EmbeddingModel>> {
         let handle = self.resolve_and_load_internal(&spec).await?;
         if let Some(model) = handle.downcast_ref::<Arc<dyn EmbeddingModel>>() {
+            let inner = match self.micro_batch {
+                Some(config) => {
+                    let mut batchers = self.batchers.lock().await;
+                    batchers
+                        .entry(self.runtime_key(&spec))
+                        .or_insert_with(|| {
+                            Arc::new(MicroBatchingEmbeddingModel::new(model.clone(), config))
+                        })
+                        .clone()
+                }
+                None => model.clone(),
+            };
+            if !self.instrumentation {
+                return Ok(inner);
+            }
             let instrumented = InstrumentedEmbeddingModel {
-                inner: model.clone(),
-                alias: alias.to_string(),
+                inner,
+                alias: spec.alias.clone(),
                 provider_id: spec.provider_id.clone(),
                 timeout: spec.timeout.map(std::time::Duration::from_secs),
                 retry: spec.retry.clone(),
+                usage: Some(self.usage.clone()),
+                inflight: Some(self.inflight.clone()),
+                expected_dimensions: spec
+                    .options
+                    .get("expected_dimensions")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as u32),
+                input_limit: InputLimit::from_options(&spec.options),
+                observed_dimensions: Some(
+                    self.observed_dimensions
+                        .lock()
+                        .unwrap()
+                        .entry(self.runtime_key(&spec))
+                        .or_default()
+                        .clone(),
+                ),
             };
             return Ok(Arc::new(instrumented));
         }
 
         Err(RuntimeError::CapabilityMismatch(format!(
             "Model for alias '{}' does not implement EmbeddingModel",
-            alias
+            spec.alias
         )))
     }
 
     /// Resolve, load (if necessary), and return an instrumented [`RerankerModel`]
-    /// handle for the given alias.
+    /// handle for the given alias, which may be a balanced alias.
     pub async fn reranker(&self, alias: &str) -> Result<Arc<dyn RerankerModel>> {
+        self.check_accepting(alias)?;
+        let Some(multi) = self.multi_backends.get(alias) else {
+            return self.single_reranker(alias).await;
+        };
+        let mut models = Vec::with_capacity(multi.spec.backends.len());
+        for backend in &multi.spec.backends {
+            models.push(self.single_reranker(&backend.alias).await?);
+        }
+        Ok(Arc::new(WeightedRerankerModel::new(
+            multi.selector.clone(),
+            models,
+        )))
+    }
+
+    async fn single_reranker(&self, alias: &str) -> Result<Arc<dyn RerankerModel>> {
         let spec = self.lookup_spec(alias).await?;
         let handle = self.resolve_and_load_internal(&spec).await?;
         if let Some(model) = handle.downcast_ref::<Arc<dyn RerankerModel>>() {
+            if !self.instrumentation {
+                return Ok(model.clone());
+            }
             let instrumented = InstrumentedRerankerModel {
                 inner: model.clone(),
                 alias: alias.to_string(),
                 provider_id: spec.provider_id.clone(),
                 timeout: spec.timeout.map(std::time::Duration::from_secs),
                 retry: spec.retry.clone(),
+                usage: Some(self.usage.clone()),
+                inflight: Some(self.inflight.clone()),
+                input_limit: InputLimit::from_options(&spec.options),
+                dedup_documents: spec
+                    .options
+                    .get("dedup_documents")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
             };
             return Ok(Arc::new(instrumented));
         }
@@ -160,17 +914,37 @@ impl ModelRuntime {
     }
 
     /// Resolve, load (if necessary), and return an instrumented [`GeneratorModel`]
-    /// handle for the given alias.
+    /// handle for the given alias, which may be a balanced alias.
     pub async fn generator(&self, alias: &str) -> Result<Arc<dyn GeneratorModel>> {
+        self.check_accepting(alias)?;
+        let Some(multi) = self.multi_backends.get(alias) else {
+            return self.single_generator(alias).await;
+        };
+        let mut models = Vec::with_capacity(multi.spec.backends.len());
+        for backend in &multi.spec.backends {
+            models.push(self.single_generator(&backend.alias).await?);
+        }
+        Ok(Arc::new(WeightedGeneratorModel::new(
+            multi.selector.clone(),
+            models,
+        )))
+    }
+
+    async fn single_generator(&self, alias: &str) -> Result<Arc<dyn GeneratorModel>> {
         let spec = self.lookup_spec(alias).await?;
         let handle = self.resolve_and_load_internal(&spec).await?;
         if let Some(model) = handle.downcast_ref::<Arc<dyn GeneratorModel>>() {
+            if !self.instrumentation {
+                return Ok(model.clone());
+            }
             let instrumented = InstrumentedGeneratorModel {
                 inner: model.clone(),
                 alias: alias.to_string(),
                 provider_id: spec.provider_id.clone(),
                 timeout: spec.timeout.map(std::time::Duration::from_secs),
                 retry: spec.retry.clone(),
+                usage: Some(self.usage.clone()),
+                inflight: Some(self.inflight.clone()),
             };
             return Ok(Arc::new(instrumented));
         }
@@ -180,12 +954,59 @@ impl ModelRuntime {
         )))
     }
 
+    /// Resolve, load (if necessary), and return an instrumented [`ClassifierModel`]
+    /// handle for the given alias, which may be a balanced alias.
+    pub async fn classifier(&self, alias: &str) -> Result<Arc<dyn ClassifierModel>> {
+        self.check_accepting(alias)?;
+        let Some(multi) = self.multi_backends.get(alias) else {
+            return self.single_classifier(alias).await;
+        };
+        let mut models = Vec::with_capacity(multi.spec.backends.len());
+        for backend in &multi.spec.backends {
+            models.push(self.single_classifier(&backend.alias).await?);
+        }
+        Ok(Arc::new(WeightedClassifierModel::new(
+            multi.selector.clone(),
+            models,
+        )))
+    }
+
+    async fn single_classifier(&self, alias: &str) -> Result<Arc<dyn ClassifierModel>> {
+        let spec = self.lookup_spec(alias).await?;
+        let handle = self.resolve_and_load_internal(&spec).await?;
+        if let Some(model) = handle.downcast_ref::<Arc<dyn ClassifierModel>>() {
+            if !self.instrumentation {
+                return Ok(model.clone());
+            }
+            let instrumented = InstrumentedClassifierModel {
+                inner: model.clone(),
+                alias: alias.to_string(),
+                provider_id: spec.provider_id.clone(),
+                timeout: spec.timeout.map(std::time::Duration::from_secs),
+                retry: spec.retry.clone(),
+                usage: Some(self.usage.clone()),
+                inflight: Some(self.inflight.clone()),
+                input_limit: InputLimit::from_options(&spec.options),
+            };
+            return Ok(Arc::new(instrumented));
+        }
+        Err(RuntimeError::CapabilityMismatch(format!(
+            "Model for alias '{}' does not implement ClassifierModel",
+            alias
+        )))
+    }
+
     #[tracing::instrument(skip(self, spec), fields(provider, model))]
     async fn resolve_and_load_internal(
         &self,
         spec: &ModelAliasSpec,
     ) -> Result<Arc<dyn Any + Send + Sync>> {
-        let key = ModelRuntimeKey::new(spec);
+        let provider = self.providers.get(&spec.provider_id).ok_or_else(|| {
+            RuntimeError::ProviderNotFound(format!("Provider '{}' not found", spec.provider_id))
+        })?;
+        check_capability(provider.as_ref(), spec)?;
+
+        let key = self.runtime_key(spec);
 
         // Fast path: already loaded
         {
@@ -195,104 +1016,374 @@ impl ModelRuntime {
             }
         }
 
-        // Slow path: coordinate loading
-        let lock = {
-            let mut locks = self.registry.loader_locks.lock().await;
-            locks
-                .entry(key.clone())
-                .or_insert_with(|| Arc::new(Mutex::new(())))
-                .clone()
+        // Slow path: coordinate loading. The entry removes itself from the
+        // lock map when the last caller for this key is done.
+        let entry = self.registry.loader_locks.entry(&key);
+        let _guard = match entry.try_lock() {
+            Some(guard) => guard,
+            None => {
+                let _waiter = LoadWaiter::start(&spec.alias, &spec.provider_id);
+                entry.lock().await
+            }
         };
 
-        // Acquire loader lock for this key
-        let _guard = lock.lock().await;
-
         // Double-check after acquiring the loader lock
         {
             let registry = self.registry.instances.read().await;
             if let Some(handle) = registry.get(&key) {
-                let result = Ok(handle.clone());
-                let mut locks = self.registry.loader_locks.lock().await;
-                locks.remove(&key);
-                return result;
+                self.failed_warmups.lock().unwrap().remove(&spec.alias);
+                return Ok(handle.clone());
             }
         }
 
         let load_timeout =
             std::time::Duration::from_secs(spec.load_timeout.unwrap_or(DEFAULT_LOAD_TIMEOUT_SECS));
+        let warmup_timeout = spec
+            .warmup_timeout
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(load_timeout);
 
-        let result = match tokio::time::timeout(load_timeout, async {
-            let provider = self.providers.get(&spec.provider_id).ok_or_else(|| {
-                RuntimeError::ProviderNotFound(format!("Provider '{}' not found", spec.provider_id))
-            })?;
+        let result = async {
+            let handle = match tokio::time::timeout(load_timeout, async {
+                tracing::info!(alias = %spec.alias, provider = %spec.provider_id, "Loading model instance");
+                let start = std::time::Instant::now();
+                let handle_result = provider
+                    .load(&with_provider_defaults(spec, &self.provider_defaults))
+                    .await;
+                let duration = start.elapsed().as_secs_f64();
 
-            tracing::info!(alias = %spec.alias, provider = %spec.provider_id, "Loading model instance");
-            let start = std::time::Instant::now();
-            let handle_result = provider.load(spec).await;
-            let duration = start.elapsed().as_secs_f64();
+                crate::telemetry::histogram!("model_load.duration_seconds").record(duration);
 
-            metrics::histogram!("model_load.duration_seconds").record(duration);
-
-            let handle = match handle_result {
-                Ok(h) => {
-                    metrics::counter!("model_load.total", "status" => "success").increment(1);
-                    h
+                match handle_result {
+                    Ok(h) => {
+                        crate::telemetry::counter!("model_load.total", "status" => "success").increment(1);
+                        Ok(h)
+                    }
+                    Err(e) => {
+                        crate::telemetry::counter!("model_load.total", "status" => "failure").increment(1);
+                        tracing::error!(alias = %spec.alias, error = %e, "Model load failed");
+                        Err(e)
+                    }
                 }
-                Err(e) => {
-                    metrics::counter!("model_load.total", "status" => "failure").increment(1);
-                    tracing::error!(alias = %spec.alias, error = %e, "Model load failed");
-                    return Err(e);
+            })
+            .await
+            {
+                Ok(res) => res?,
+                Err(_) => {
+                    crate::telemetry::counter!("model_load.total", "status" => "failure").increment(1);
+                    tracing::error!(
+                        alias = %spec.alias,
+                        provider = %spec.provider_id,
+                        timeout_secs = load_timeout.as_secs(),
+                        "Model load timed out"
+                    );
+                    return Err(RuntimeError::timeout(TimeoutPhase::Load, load_timeout));
                 }
             };
 
-            // Model warmup
-            if let Some(model) = handle.downcast_ref::<Arc<dyn EmbeddingModel>>() {
-                model.warmup().await?;
-            } else if let Some(model) = handle.downcast_ref::<Arc<dyn RerankerModel>>() {
-                model.warmup().await?;
-            } else if let Some(model) = handle.downcast_ref::<Arc<dyn GeneratorModel>>() {
-                model.warmup().await?;
-            }
+            // Model warmup, bounded separately so a slow first forward pass
+            // does not eat into the download budget.
+            tokio::time::timeout(warmup_timeout, warmup_handle(&handle))
+                .await
+                .map_err(|_| {
+                    tracing::error!(
+                        alias = %spec.alias,
+                        provider = %spec.provider_id,
+                        timeout_secs = warmup_timeout.as_secs(),
+                        "Model warmup timed out"
+                    );
+                    RuntimeError::timeout(TimeoutPhase::Warmup, warmup_timeout)
+                })??;
 
             {
+                // `shutdown` marks the runtime draining before clearing the
+                // registry, so checking under the lock keeps a load that
+                // finishes during shutdown from outliving it.
                 let mut registry = self.registry.instances.write().await;
+                if self.is_shutting_down() {
+                    tracing::info!(alias = %spec.alias, "Runtime shut down during load; dropping model");
+                    return Err(RuntimeError::ShuttingDown);
+                }
                 registry.insert(key.clone(), handle.clone());
             }
 
-            Ok(handle)
-        })
-        .await
-        {
-            Ok(res) => res,
-            Err(_) => {
-                metrics::counter!("model_load.total", "status" => "failure").increment(1);
-                tracing::error!(
-                    alias = %spec.alias,
-                    provider = %spec.provider_id,
-                    timeout_secs = load_timeout.as_secs(),
-                    "Model load timed out"
-                );
-                Err(RuntimeError::Timeout)
-            }
-        };
+            Ok::<_, RuntimeError>(handle)
+        }
+        .await;
 
-        // Bound loader lock map growth by removing this key once the load path completes.
-        // Existing waiters hold cloned lock Arcs, so this is safe.
-        {
-            let mut locks = self.registry.loader_locks.lock().await;
-            locks.remove(&key);
+        if result.is_ok() {
+            self.failed_warmups.lock().unwrap().remove(&spec.alias);
         }
 
         result
     }
 }
 
-/// Builder for constructing a [`ModelRuntime`] with registered providers,
-/// a model catalog, and a warmup policy.
+/// Call the model's own `warmup` hook on a freshly loaded handle.
+async fn warmup_handle(handle: &LoadedModelHandle) -> Result<()> {
+    if let Some(model) = handle.downcast_ref::<Arc<dyn EmbeddingModel>>() {
+        model.warmup().await
+    } else if let Some(model) = handle.downcast_ref::<Arc<dyn RerankerModel>>() {
+        model.warmup().await
+    } else if let Some(model) = handle.downcast_ref::<Arc<dyn GeneratorModel>>() {
+        model.warmup().await
+    } else if let Some(model) = handle.downcast_ref::<Arc<dyn ClassifierModel>>() {
+        model.warmup().await
+    } else {
+        Ok(())
+    }
+}
+
+/// Fail with [`RuntimeError::CapabilityMismatch`] unless `provider` advertises
+/// `spec.task` in its capabilities.
+fn check_capability(provider: &dyn ModelProvider, spec: &ModelAliasSpec) -> Result<()> {
+    if provider.capabilities().supported_tasks.contains(&spec.task) {
+        return Ok(());
+    }
+    Err(RuntimeError::CapabilityMismatch(format!(
+        "Provider '{}' does not support task {:?}",
+        spec.provider_id, spec.task
+    )))
+}
+
+/// `spec` with its provider's default options merged beneath its own, as
+/// the provider sees it at load time.
+fn with_provider_defaults<'a>(
+    spec: &'a ModelAliasSpec,
+    provider_defaults: &HashMap<String, serde_json::Value>,
+) -> Cow<'a, ModelAliasSpec> {
+    match provider_defaults.get(&spec.provider_id) {
+        Some(defaults) => Cow::Owned(ModelAliasSpec {
+            options: merge_options(defaults, &spec.options),
+            ..spec.clone()
+        }),
+        None => Cow::Borrowed(spec),
+    }
+}
+
+/// Deep-merge `options` over `defaults`: objects are merged key by key and
+/// any other `options` value replaces the default. A null `options` keeps
+/// the defaults.
+fn merge_options(defaults: &serde_json::Value, options: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match (defaults, options) {
+        (Value::Object(defaults), Value::Object(options)) => {
+            let mut merged = defaults.clone();
+            for (key, value) in options {
+                let value = match merged.get(key) {
+                    Some(default) => merge_options(default, value),
+                    None => value.clone(),
+                };
+                merged.insert(key.clone(), value);
+            }
+            Value::Object(merged)
+        }
+        (defaults, Value::Null) => defaults.clone(),
+        (_, options) => options.clone(),
+    }
+}
+
+/// Check every spec in `catalog` and every balanced alias in
+/// `multi_backends`; see [`ModelRuntimeBuilder::validate`].
 ///
-/// ```rust,no_run
-/// # use uni_xervo::runtime::ModelRuntime;
-/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// With `provider_scoped_aliases`, a catalog alias may repeat across
+/// providers as long as every definition is an embed alias.
+fn validate_catalog<'a>(
+    providers: &HashMap<String, Box<dyn ModelProvider>>,
+    provider_defaults: &HashMap<String, serde_json::Value>,
+    catalog: &[ModelAliasSpec],
+    multi_backends: impl IntoIterator<Item = &'a MultiBackendSpec>,
+    provider_scoped_aliases: bool,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    // (alias, provider scope); the scope is empty unless aliases are
+    // provider-scoped.
+    let mut seen = std::collections::HashSet::new();
+    let definitions = |alias: &str| catalog.iter().filter(|s| s.alias == alias).count();
+    for spec in catalog {
+        let mut report = |error| {
+            issues.push(ValidationIssue {
+                alias: spec.alias.clone(),
+                error,
+            })
+        };
+        if let Err(e) = spec.validate() {
+            report(e);
+        }
+        let scope = if provider_scoped_aliases {
+            spec.provider_id.as_str()
+        } else {
+            ""
+        };
+        if !seen.insert((spec.alias.as_str(), scope)) {
+            report(RuntimeError::Config(
+                "Duplicate alias in catalog".to_string(),
+            ));
+        } else if provider_scoped_aliases
+            && definitions(&spec.alias) > 1
+            && spec.task != ModelTask::Embed
+        {
+            report(scoped_alias_task_error(&spec.alias));
+        }
+        let Some(provider) = providers.get(&spec.provider_id) else {
+            report(RuntimeError::Config(format!(
+                "Unknown provider '{}' for alias '{}'",
+                spec.provider_id, spec.alias
+            )));
+            continue;
+        };
+        if let Err(e) = validate_spec_options(
+            provider.as_ref(),
+            &with_provider_defaults(spec, provider_defaults),
+        ) {
+            report(e);
+        }
+        if let Err(e) = check_capability(provider.as_ref(), spec) {
+            report(e);
+        }
+    }
+
+    for multi in multi_backends {
+        let mut report = |error| {
+            issues.push(ValidationIssue {
+                alias: multi.alias.clone(),
+                error,
+            })
+        };
+        if !multi.alias.contains('/') {
+            report(RuntimeError::Config(format!(
+                "Alias '{}' must be in 'task/name' format",
+                multi.alias
+            )));
+        }
+        if seen.iter().any(|(alias, _)| *alias == multi.alias) {
+            report(RuntimeError::Config(
+                "Duplicate alias in catalog".to_string(),
+            ));
+        }
+        seen.insert((multi.alias.as_str(), ""));
+        if multi.backends.is_empty() {
+            report(RuntimeError::Config(format!(
+                "Balanced alias '{}' has no backends",
+                multi.alias
+            )));
+        }
+        if multi
+            .circuit_breaker
+            .is_some_and(|breaker| breaker.failure_threshold == 0)
+        {
+            report(RuntimeError::Config(format!(
+                "Balanced alias '{}' must have a circuit breaker failure_threshold greater than 0",
+                multi.alias
+            )));
+        }
+        let mut members = std::collections::HashSet::new();
+        for backend in &multi.backends {
+            if backend.weight == 0 {
+                report(RuntimeError::Config(format!(
+                    "Backend '{}' of '{}' must have a weight greater than 0",
+                    backend.alias, multi.alias
+                )));
+            }
+            if !members.insert(backend.alias.as_str()) {
+                report(RuntimeError::Config(format!(
+                    "Backend '{}' is listed more than once in '{}'",
+                    backend.alias, multi.alias
+                )));
+            }
+            match catalog.iter().find(|s| s.alias == backend.alias) {
+                None => report(RuntimeError::Config(format!(
+                    "Backend alias '{}' of '{}' is not in the catalog",
+                    backend.alias, multi.alias
+                ))),
+                Some(_) if definitions(&backend.alias) > 1 => {
+                    report(RuntimeError::Config(format!(
+                        "Backend alias '{}' of '{}' is defined for several providers",
+                        backend.alias, multi.alias
+                    )))
+                }
+                Some(spec) if spec.task != multi.task => report(RuntimeError::Config(format!(
+                    "Backend alias '{}' has task {:?} but '{}' has task {:?}",
+                    backend.alias, spec.task, multi.alias, multi.task
+                ))),
+                Some(_) => {}
+            }
+        }
+    }
+    issues
+}
+
+/// An alias defined for several providers that is not an embed alias in
+/// every definition.
+fn scoped_alias_task_error(alias: &str) -> RuntimeError {
+    RuntimeError::Config(format!(
+        "Alias '{}' is defined for several providers, which is only supported for embed aliases",
+        alias
+    ))
+}
+
+/// Fail with the first issue that blocks using the catalog. Capability
+/// mismatches are only logged; they surface when the alias is first accessed.
+fn check_catalog_issues(issues: Vec<ValidationIssue>) -> Result<()> {
+    for issue in issues {
+        if matches!(issue.error, RuntimeError::CapabilityMismatch(_)) {
+            tracing::warn!(alias = %issue.alias, error = %issue.error, "Catalog entry will fail to load");
+            continue;
+        }
+        return Err(issue.error);
+    }
+    Ok(())
+}
+
+/// Aliases changed by [`ModelRuntime::reload_catalog`], each list sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogReload {
+    /// Aliases that were not in the previous catalog.
+    pub added: Vec<String>,
+    /// Aliases whose spec changed.
+    pub updated: Vec<String>,
+    /// Aliases that are no longer in the catalog.
+    pub removed: Vec<String>,
+}
+
+/// A problem found in one catalog entry by [`ModelRuntimeBuilder::validate`].
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    /// Alias of the offending spec, as written in the catalog.
+    pub alias: String,
+    /// The error [`build`](ModelRuntimeBuilder::build) or the first model
+    /// access would report for this spec.
+    pub error: RuntimeError,
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "alias '{}': {}", self.alias, self.error)
+    }
+}
+
+/// Progress of a background model warmup, sent to the sink registered with
+/// [`ModelRuntimeBuilder::warmup_events`].
+#[derive(Debug, Clone)]
+pub enum WarmupEvent {
+    /// The alias started loading.
+    Started { alias: String },
+    /// The alias loaded and is cached; `duration` covers the whole load.
+    Succeeded {
+        alias: String,
+        duration: std::time::Duration,
+    },
+    /// The alias failed to load. It will be retried on first access. For a
+    /// `required` alias, [`ModelRuntime::is_ready`] is `false` until then.
+    Failed { alias: String, error: RuntimeError },
+}
+
+/// Builder for constructing a [`ModelRuntime`] with registered providers,
+/// a model catalog, and a warmup policy.
+///
+/// ```rust,no_run
+/// # use uni_xervo::runtime::ModelRuntime;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let runtime = ModelRuntime::builder()
 ///     // .register_provider(...)
 ///     // .catalog(...)
@@ -305,38 +1396,129 @@ impl ModelRuntime {
 pub struct ModelRuntimeBuilder {
     providers: HashMap<String, Box<dyn ModelProvider>>,
     catalog: Vec<ModelAliasSpec>,
+    multi_backends: Vec<MultiBackendSpec>,
     warmup_policy: crate::api::WarmupPolicy,
+    micro_batch: Option<MicroBatchConfig>,
+    cost_per_1k: HashMap<String, CostPer1k>,
+    warmup_events: Option<mpsc::Sender<WarmupEvent>>,
+    download_progress: Option<DownloadProgressFn>,
+    instrumentation_disabled: bool,
+    shutdown_grace_period: Option<std::time::Duration>,
+    provider_defaults: HashMap<String, serde_json::Value>,
+    provider_concurrency: HashMap<String, usize>,
+    /// Provider IDs registered more than once, reported by `build()`.
+    replaced_providers: Vec<String>,
+    provider_scoped_aliases: bool,
 }
 
 impl ModelRuntimeBuilder {
     /// Register a provider. The provider's
     /// [`provider_id`](crate::traits::ModelProvider::provider_id) is used as
     /// the lookup key; registering a second provider with the same ID
-    /// replaces the first.
+    /// replaces the first, and [`build`](Self::build) logs a warning. Use
+    /// [`register_provider_checked`](Self::register_provider_checked) to
+    /// reject duplicates instead.
     pub fn register_provider<P: ModelProvider + 'static>(mut self, provider: P) -> Self {
-        self.providers
-            .insert(provider.provider_id().to_string(), Box::new(provider));
+        self.insert_provider(Box::new(provider));
         self
     }
 
-    /// Set the model catalog from a pre-built vector of specs.
+    /// Register a provider, failing with [`RuntimeError::Config`] if one
+    /// with the same `provider_id` is already registered.
+    pub fn register_provider_checked<P: ModelProvider + 'static>(
+        mut self,
+        provider: P,
+    ) -> Result<Self> {
+        if self.providers.contains_key(provider.provider_id()) {
+            return Err(RuntimeError::Config(format!(
+                "Provider '{}' is already registered",
+                provider.provider_id()
+            )));
+        }
+        self.insert_provider(Box::new(provider));
+        Ok(self)
+    }
+
+    /// Register several providers at once, e.g. a list assembled from the
+    /// enabled features and available API keys.
+    ///
+    /// Providers are registered in iteration order with the same semantics as
+    /// [`register_provider`](Self::register_provider): a later provider with
+    /// a duplicate `provider_id` replaces the earlier one.
+    pub fn register_providers(
+        mut self,
+        providers: impl IntoIterator<Item = Box<dyn ModelProvider>>,
+    ) -> Self {
+        for provider in providers {
+            self.insert_provider(provider);
+        }
+        self
+    }
+
+    fn insert_provider(&mut self, provider: Box<dyn ModelProvider>) {
+        let id = provider.provider_id().to_string();
+        if self.providers.insert(id.clone(), provider).is_some() {
+            self.replaced_providers.push(id);
+        }
+    }
+
+    /// Set the model catalog from a pre-built vector of specs, replacing any
+    /// specs set or added earlier. Use [`add_catalog`](Self::add_catalog) to
+    /// append instead.
     pub fn catalog(mut self, catalog: Vec<ModelAliasSpec>) -> Self {
         self.catalog = catalog;
         self
     }
 
-    /// Load catalog from a JSON string (array of model alias specs).
+    /// Load catalog from a JSON string (array of model alias specs),
+    /// replacing the current catalog.
     pub fn catalog_from_str(mut self, s: &str) -> Result<Self> {
         self.catalog = crate::api::catalog_from_str(s)?;
         Ok(self)
     }
 
-    /// Load catalog from a JSON file (array of model alias specs).
+    /// Load catalog from a JSON file (array of model alias specs), replacing
+    /// the current catalog.
     pub fn catalog_from_file(mut self, path: impl AsRef<std::path::Path>) -> Result<Self> {
         self.catalog = crate::api::catalog_from_file(path)?;
         Ok(self)
     }
 
+    /// Append specs to the catalog, e.g. per-environment additions layered
+    /// on a shared base catalog.
+    ///
+    /// Aliases are not checked here; a spec whose alias is already in the
+    /// catalog makes [`validate`](Self::validate) and [`build`](Self::build)
+    /// report a duplicate alias.
+    pub fn add_catalog(mut self, specs: Vec<ModelAliasSpec>) -> Self {
+        self.catalog.extend(specs);
+        self
+    }
+
+    /// Append a single spec to the catalog. See
+    /// [`add_catalog`](Self::add_catalog).
+    pub fn add_spec(mut self, spec: ModelAliasSpec) -> Self {
+        self.catalog.push(spec);
+        self
+    }
+
+    /// Append specs parsed from a JSON string (array of model alias specs).
+    pub fn add_catalog_from_str(self, s: &str) -> Result<Self> {
+        Ok(self.add_catalog(crate::api::catalog_from_str(s)?))
+    }
+
+    /// Append specs loaded from a JSON file (array of model alias specs).
+    pub fn add_catalog_from_file(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Ok(self.add_catalog(crate::api::catalog_from_file(path)?))
+    }
+
+    /// Add a balanced alias that spreads calls across catalog aliases by
+    /// weight. See [`crate::balancing`] for how members are picked.
+    pub fn multi_backend(mut self, spec: MultiBackendSpec) -> Self {
+        self.multi_backends.push(spec);
+        self
+    }
+
     /// Set the global warmup policy applied to providers during
     /// [`build`](Self::build).
     pub fn warmup_policy(mut self, policy: crate::api::WarmupPolicy) -> Self {
@@ -344,37 +1526,209 @@ impl ModelRuntimeBuilder {
         self
     }
 
-    /// Validate the catalog, execute the warmup policy, and return the
-    /// constructed [`ModelRuntime`].
+    /// Report the progress of aliases with
+    /// [`WarmupPolicy::Background`](crate::api::WarmupPolicy::Background) to
+    /// `sink`, e.g. to flip a readiness probe or alert on failures.
     ///
-    /// Returns an error if any spec references an unknown provider, contains
-    /// invalid options, or if a required eager warmup fails.
-    pub async fn build(self) -> Result<Arc<ModelRuntime>> {
-        let mut catalog_map = HashMap::new();
-        for spec in self.catalog {
-            spec.validate()?;
-            if !self.providers.contains_key(&spec.provider_id) {
+    /// Events are sent in addition to the usual log lines. A full sink delays
+    /// the sending warmup task; a closed sink is ignored.
+    pub fn warmup_events(mut self, sink: mpsc::Sender<WarmupEvent>) -> Self {
+        self.warmup_events = Some(sink);
+        self
+    }
+
+    /// Report weight download progress from local providers to `callback`,
+    /// e.g. to render a progress bar during a cold start.
+    ///
+    /// The callback is handed to every registered provider through
+    /// [`ModelProvider::set_download_progress`]. Candle reports byte-level
+    /// progress for each file it fetches from the HuggingFace Hub; files
+    /// already in the cache produce no events. FastEmbed and mistral.rs
+    /// download through libraries without a progress hook and report nothing.
+    pub fn on_download_progress(
+        mut self,
+        callback: impl Fn(&DownloadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.download_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Wrap model handles in the instrumentation layer (default `true`).
+    ///
+    /// With `false`, [`embedding`](ModelRuntime::embedding),
+    /// [`reranker`](ModelRuntime::reranker), and
+    /// [`generator`](ModelRuntime::generator) return the provider's model
+    /// directly, avoiding per-call allocations and metrics. That also drops
+    /// everything the wrappers provide: per-alias `timeout` and `retry`,
+    /// inference metrics and tracing spans, usage tracking, dimension drift
+    /// detection, and the `expected_dimensions` and `max_input_chars` checks.
+    /// Micro-batching and
+    /// balanced aliases still apply.
+    pub fn instrumentation(mut self, enabled: bool) -> Self {
+        self.instrumentation_disabled = !enabled;
+        self
+    }
+
+    /// Allow the same alias once per provider instead of once per catalog
+    /// (default `false`), e.g. when merging tenant catalogs that share alias
+    /// names but use different backends.
+    ///
+    /// An alias defined for several providers must be an embed alias in
+    /// every definition and is resolved with
+    /// [`ModelRuntime::embedding_from`]; [`ModelRuntime::embedding`] and the
+    /// other alias lookups reject it as ambiguous. Aliases defined once
+    /// behave as usual. Usage and readiness are still reported per alias
+    /// name.
+    pub fn provider_scoped_aliases(mut self, enabled: bool) -> Self {
+        self.provider_scoped_aliases = enabled;
+        self
+    }
+
+    /// Enable micro-batching for every embedding alias.
+    ///
+    /// Concurrent `embed` calls against the same model are coalesced into a
+    /// single upstream batch, flushed once it holds `max_batch` texts or
+    /// `max_delay` after its first text arrived. If the upstream call fails,
+    /// every caller in that batch receives the same error; see
+    /// [`crate::batching`] for details.
+    pub fn micro_batch(mut self, max_batch: usize, max_delay: std::time::Duration) -> Self {
+        self.micro_batch = Some(MicroBatchConfig {
+            max_batch,
+            max_delay,
+        });
+        self
+    }
+
+    /// Set how long [`ModelRuntime::shutdown`] waits for in-flight calls
+    /// before unloading models anyway. Defaults to 30 seconds.
+    pub fn shutdown_grace_period(mut self, grace: std::time::Duration) -> Self {
+        self.shutdown_grace_period = Some(grace);
+        self
+    }
+
+    /// Set per-alias token prices used to estimate spend in
+    /// [`ModelRuntime::usage_report`].
+    pub fn cost_per_1k(mut self, table: HashMap<String, CostPer1k>) -> Self {
+        self.cost_per_1k = table;
+        self
+    }
+
+    /// Set default options for every alias served by `provider_id`, e.g. a
+    /// Vertex AI `project_id` and `location` shared by all Vertex aliases.
+    ///
+    /// `options` must be a JSON object. At load time it is deep-merged
+    /// beneath each alias's `options`: nested objects are merged key by key
+    /// and the alias wins on conflicts. Catalog validation checks the merged
+    /// options. Runtime-level keys such as `expected_dimensions` are rejected
+    /// here; set them per alias. Calling this again for the same provider
+    /// replaces its defaults.
+    pub fn provider_defaults(
+        mut self,
+        provider_id: impl Into<String>,
+        options: serde_json::Value,
+    ) -> Self {
+        self.provider_defaults.insert(provider_id.into(), options);
+        self
+    }
+
+    /// Cap concurrent requests to `provider_id` at `permits`, shared by every
+    /// alias it serves, e.g. so all `remote/openai` aliases stay within one
+    /// account's quota together.
+    ///
+    /// Requests beyond the limit wait for a permit instead of failing; a
+    /// request holds its permit only while the HTTP call is in flight, not
+    /// during retry backoff, and an open circuit breaker still rejects
+    /// immediately. Built-in remote providers support this;
+    /// [`build`](Self::build) fails for providers that do not, for an
+    /// unregistered provider, or for a limit of 0. Calling this again for the
+    /// same provider replaces its limit.
+    pub fn provider_concurrency(mut self, provider_id: impl Into<String>, permits: usize) -> Self {
+        self.provider_concurrency
+            .insert(provider_id.into(), permits);
+        self
+    }
+
+    /// Check the builder configuration and every catalog entry without
+    /// constructing a runtime, loading models, or touching the network.
+    ///
+    /// Each spec is checked with [`ModelAliasSpec::validate`], against the
+    /// registered providers, with the provider's option validation and
+    /// [`options_schema`](crate::traits::ModelProvider::options_schema), for
+    /// duplicate aliases, and for a task the provider's
+    /// [`capabilities`](crate::traits::ModelProvider::capabilities) include.
+    /// All problems are collected rather than stopping at the first; an
+    /// empty list means the catalog is valid. Intended for CI checks of
+    /// catalog files.
+    ///
+    /// Returns `Err` only for builder-level misconfiguration that is not tied
+    /// to a catalog entry, such as a zero micro-batch size or
+    /// [`provider_defaults`](Self::provider_defaults) for an unregistered
+    /// provider.
+    pub fn validate(&self) -> Result<Vec<ValidationIssue>> {
+        if let Some(config) = &self.micro_batch
+            && config.max_batch == 0
+        {
+            return Err(RuntimeError::Config(
+                "micro_batch max_batch must be greater than 0".to_string(),
+            ));
+        }
+        for (provider_id, defaults) in &self.provider_defaults {
+            if !self.providers.contains_key(provider_id) {
                 return Err(RuntimeError::Config(format!(
-                    "Unknown provider '{}' for alias '{}'",
-                    spec.provider_id, spec.alias
+                    "Default options set for unknown provider '{}'",
+                    provider_id
                 )));
             }
-            validate_provider_options(&spec.provider_id, spec.task, &spec.options)?;
-            if catalog_map.insert(spec.alias.clone(), spec).is_some() {
-                return Err(RuntimeError::Config(
-                    "Duplicate alias in catalog".to_string(),
-                ));
+            let Some(map) = defaults.as_object() else {
+                return Err(RuntimeError::Config(format!(
+                    "Default options for provider '{}' must be a JSON object",
+                    provider_id
+                )));
+            };
+            if let Some(key) = RUNTIME_OPTION_KEYS.iter().find(|k| map.contains_key(**k)) {
+                return Err(RuntimeError::Config(format!(
+                    "Option '{}' cannot be a provider default for '{}'; set it per alias",
+                    key, provider_id
+                )));
+            }
+        }
+        for (provider_id, permits) in &self.provider_concurrency {
+            if !self.providers.contains_key(provider_id) {
+                return Err(RuntimeError::Config(format!(
+                    "Concurrency limit set for unknown provider '{}'",
+                    provider_id
+                )));
+            }
+            if *permits == 0 {
+                return Err(RuntimeError::Config(format!(
+                    "Concurrency limit for provider '{}' must be greater than 0",
+                    provider_id
+                )));
             }
         }
 
-        let runtime = Arc::new(ModelRuntime {
-            providers: self.providers,
-            registry: Arc::new(ModelRegistry::default()),
-            catalog: RwLock::new(catalog_map),
-        });
+        Ok(validate_catalog(
+            &self.providers,
+            &self.provider_defaults,
+            &self.catalog,
+            &self.multi_backends,
+            self.provider_scoped_aliases,
+        ))
+    }
+
+    /// Validate the catalog, execute the warmup policy, and return the
+    /// constructed [`ModelRuntime`].
+    ///
+    /// Returns the first issue found by [`validate`](Self::validate), or an
+    /// error if a required eager warmup fails. Capability mismatches are only
+    /// logged here; they surface as errors when the alias is first accessed.
+    pub async fn build(self) -> Result<Arc<ModelRuntime>> {
+        let warmup_policy = self.warmup_policy;
+        let warmup_events = self.warmup_events.clone();
+        let runtime = self.construct()?;
 
         // Provider Warmup Phase
-        match self.warmup_policy {
+        match warmup_policy {
             crate::api::WarmupPolicy::Eager => {
                 for (id, provider) in &runtime.providers {
                     tracing::info!(provider = %id, "Eagerly warming up provider");
@@ -408,7 +1762,7 @@ impl ModelRuntimeBuilder {
 
         let specs: Vec<ModelAliasSpec> = {
             let catalog = runtime.catalog.read().await;
-            catalog.values().cloned().collect()
+            catalog.specs().cloned().collect()
         };
 
         for spec in specs {
@@ -431,10 +1785,33 @@ impl ModelRuntimeBuilder {
                     tracing::info!(alias = %spec.alias, "Scheduling background warmup");
                     let rt = runtime.clone();
                     let spec_clone = spec.clone();
+                    let events = warmup_events.clone();
                     // Spawn background task
                     warmup_tasks.push(tokio::spawn(async move {
-                        if let Err(e) = rt.resolve_and_load_internal(&spec_clone).await {
-                            tracing::error!(alias = %spec_clone.alias, error = %e, "Background warmup failed");
+                        let alias = spec_clone.alias.clone();
+                        if let Some(events) = &events {
+                            let _ = events
+                                .send(WarmupEvent::Started {
+                                    alias: alias.clone(),
+                                })
+                                .await;
+                        }
+                        let start = std::time::Instant::now();
+                        let event = match rt.resolve_and_load_internal(&spec_clone).await {
+                            Ok(_) => WarmupEvent::Succeeded {
+                                alias,
+                                duration: start.elapsed(),
+                            },
+                            Err(e) => {
+                                tracing::error!(alias = %alias, error = %e, "Background warmup failed");
+                                if spec_clone.required {
+                                    rt.failed_warmups.lock().unwrap().insert(alias.clone());
+                                }
+                                WarmupEvent::Failed { alias, error: e }
+                            }
+                        };
+                        if let Some(events) = &events {
+                            let _ = events.send(event).await;
                         }
                     }));
                 }
@@ -444,11 +1821,97 @@ impl ModelRuntimeBuilder {
             }
         }
 
-        // We don't await background tasks here, they run detached.
+        // Background tasks run detached; wait_for_warmup() awaits them.
         // Eager tasks are already awaited.
+        *runtime.background_warmups.lock().await = warmup_tasks;
 
         Ok(runtime)
     }
+
+    /// Validate the catalog and return the constructed [`ModelRuntime`]
+    /// without awaiting anything, for catalogs where every alias loads
+    /// lazily. Suits sync contexts such as CLI setup or lazily initialized
+    /// globals; models still load on first access, which needs a tokio
+    /// runtime.
+    ///
+    /// Fails with [`RuntimeError::Config`] if the builder's
+    /// [`warmup_policy`](Self::warmup_policy) or any catalog alias requests
+    /// eager or background warmup; use [`build`](Self::build) for those.
+    /// Otherwise validates exactly as `build` does.
+    pub fn build_lazy(self) -> Result<Arc<ModelRuntime>> {
+        if self.warmup_policy != crate::api::WarmupPolicy::Lazy {
+            return Err(RuntimeError::Config(format!(
+                "build_lazy() requires a lazy provider warmup policy, got {}; use build()",
+                self.warmup_policy
+            )));
+        }
+        if let Some(spec) = self
+            .catalog
+            .iter()
+            .find(|spec| spec.warmup != crate::api::WarmupPolicy::Lazy)
+        {
+            return Err(RuntimeError::Config(format!(
+                "build_lazy() requires lazy warmup, but alias '{}' uses {} warmup; use build()",
+                spec.alias, spec.warmup
+            )));
+        }
+        self.construct()
+    }
+
+    /// The synchronous part of [`build`](Self::build): validate, configure
+    /// providers, and assemble the runtime without warming anything up.
+    fn construct(self) -> Result<Arc<ModelRuntime>> {
+        check_catalog_issues(self.validate()?)?;
+        for id in &self.replaced_providers {
+            tracing::warn!(provider = %id, "Provider registered more than once; the last registration replaced the earlier one");
+        }
+
+        let multi_backends = self
+            .multi_backends
+            .into_iter()
+            .map(|spec| {
+                let selector = Arc::new(WeightedSelector::new(&spec, |member| {
+                    self.catalog
+                        .iter()
+                        .find(|s| s.alias == member)
+                        .map(|s| s.provider_id.as_str())
+                }));
+                (spec.alias.clone(), MultiBackend { spec, selector })
+            })
+            .collect();
+
+        if let Some(callback) = &self.download_progress {
+            for provider in self.providers.values() {
+                provider.set_download_progress(callback.clone());
+            }
+        }
+        for (provider_id, permits) in &self.provider_concurrency {
+            if let Some(provider) = self.providers.get(provider_id) {
+                provider.set_max_concurrency(*permits)?;
+            }
+        }
+
+        Ok(Arc::new(ModelRuntime {
+            providers: self.providers,
+            registry: Arc::new(ModelRegistry::default()),
+            catalog: RwLock::new(Catalog::new(self.catalog)),
+            provider_scoped_aliases: self.provider_scoped_aliases,
+            micro_batch: self.micro_batch,
+            batchers: Mutex::new(HashMap::new()),
+            observed_dimensions: std::sync::Mutex::new(HashMap::new()),
+            usage: Arc::new(UsageTracker::with_cost_per_1k(self.cost_per_1k)),
+            multi_backends,
+            instrumentation: !self.instrumentation_disabled,
+            background_warmups: Mutex::new(Vec::new()),
+            failed_warmups: std::sync::Mutex::new(Default::default()),
+            inflight: Arc::new(InflightCalls::default()),
+            draining: AtomicBool::new(false),
+            shutdown_grace_period: self
+                .shutdown_grace_period
+                .unwrap_or(std::time::Duration::from_secs(DEFAULT_SHUTDOWN_GRACE_SECS)),
+            provider_defaults: self.provider_defaults,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -469,9 +1932,8 @@ mod tests {
 
         let _ = runtime.embedding("embed/test").await.unwrap();
 
-        let locks = runtime.registry.loader_locks.lock().await;
         assert!(
-            locks.is_empty(),
+            runtime.registry.loader_locks.is_empty(),
             "loader lock map should be empty after load"
         );
     }
@@ -490,9 +1952,8 @@ mod tests {
         let err = runtime.embedding("embed/test").await;
         assert!(err.is_err());
 
-        let locks = runtime.registry.loader_locks.lock().await;
         assert!(
-            locks.is_empty(),
+            runtime.registry.loader_locks.is_empty(),
             "loader lock map should be empty after failure"
         );
     }
@@ -511,12 +1972,392 @@ mod tests {
             .unwrap();
 
         let err = runtime.embedding("embed/test").await;
-        assert!(matches!(err, Err(RuntimeError::Timeout)));
+        assert!(matches!(
+            err,
+            Err(RuntimeError::Timeout {
+                phase: TimeoutPhase::Load,
+                ..
+            })
+        ));
 
-        let locks = runtime.registry.loader_locks.lock().await;
         assert!(
-            locks.is_empty(),
+            runtime.registry.loader_locks.is_empty(),
             "loader lock map should be empty after load timeout"
         );
     }
+
+    #[tokio::test]
+    async fn distinct_models_load_concurrently() {
+        let specs: Vec<_> = (0..32)
+            .map(|i| {
+                make_spec(
+                    &format!("embed/m{i}"),
+                    ModelTask::Embed,
+                    "mock/embed",
+                    &format!("model-{i}"),
+                )
+            })
+            .collect();
+        let aliases: Vec<String> = specs.iter().map(|s| s.alias.clone()).collect();
+        let runtime = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only().with_load_delay(200))
+            .catalog(specs)
+            .build()
+            .await
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let results =
+            futures_util::future::join_all(aliases.iter().map(|alias| runtime.embedding(alias)))
+                .await;
+        assert!(results.iter().all(Result::is_ok));
+        // Serialized loads would take 32 x 200ms.
+        assert!(
+            start.elapsed() < std::time::Duration::from_millis(2_000),
+            "loads of distinct models were serialized: {:?}",
+            start.elapsed()
+        );
+        assert!(runtime.registry.loader_locks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn loader_lock_entry_removed_when_waiter_is_cancelled() {
+        let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+        let runtime = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only().with_load_delay(300))
+            .catalog(vec![spec])
+            .build()
+            .await
+            .unwrap();
+
+        let loader = runtime.embedding("embed/test");
+        let waiter = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            runtime.embedding("embed/test"),
+        );
+        let (loaded, waited) = tokio::join!(loader, waiter);
+        assert!(loaded.is_ok());
+        assert!(waited.is_err(), "waiter should have been cancelled");
+        assert!(runtime.registry.loader_locks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn micro_batched_handles_share_one_batcher() {
+        let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+        let runtime = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![spec])
+            .micro_batch(8, std::time::Duration::from_millis(10))
+            .build()
+            .await
+            .unwrap();
+
+        let a = runtime.embedding("embed/test").await.unwrap();
+        let b = runtime.embedding("embed/test").await.unwrap();
+        let (ra, rb) = tokio::join!(a.embed(vec!["one"]), b.embed(vec!["two", "three"]));
+        assert_eq!(ra.unwrap().len(), 1);
+        assert_eq!(rb.unwrap().len(), 2);
+
+        assert_eq!(runtime.batchers.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn micro_batch_rejects_zero_batch_size() {
+        let result = ModelRuntime::builder()
+            .micro_batch(0, std::time::Duration::from_millis(5))
+            .build()
+            .await;
+        assert!(matches!(result, Err(RuntimeError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn capability_mismatch_fails_before_load() {
+        struct EmbedOnly;
+
+        #[async_trait::async_trait]
+        impl ModelProvider for EmbedOnly {
+            fn provider_id(&self) -> &'static str {
+                "test/embed-only"
+            }
+            fn capabilities(&self) -> crate::traits::ProviderCapabilities {
+                crate::traits::ProviderCapabilities {
+                    supported_tasks: vec![ModelTask::Embed],
+                }
+            }
+            async fn load(&self, _: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+                unreachable!("load must not be called for an unsupported task")
+            }
+            async fn health(&self) -> crate::traits::ProviderHealth {
+                crate::traits::ProviderHealth::Healthy
+            }
+        }
+
+        let spec = make_spec("generate/x", ModelTask::Generate, "test/embed-only", "m");
+        let runtime = ModelRuntime::builder()
+            .register_provider(EmbedOnly)
+            .catalog(vec![spec])
+            .build()
+            .await
+            .unwrap();
+
+        let err = runtime.generator("generate/x").await.err().unwrap();
+        assert!(matches!(err, RuntimeError::CapabilityMismatch(_)));
+        assert!(runtime.registry.loader_locks.is_empty());
+    }
+
+    #[test]
+    fn validate_collects_every_issue() {
+        let mut bad_options = make_spec("embed/opts", ModelTask::Embed, "mock/embed", "m");
+        bad_options.options = serde_json::json!({ "expected_dimensions": "big" });
+        let builder = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![
+                make_spec("embed/ok", ModelTask::Embed, "mock/embed", "m"),
+                make_spec("no-slash", ModelTask::Embed, "mock/embed", "m"),
+                make_spec("embed/typo", ModelTask::Embed, "mock/embd", "m"),
+                bad_options,
+                make_spec("generate/wrong", ModelTask::Generate, "mock/embed", "m"),
+                make_spec("embed/ok", ModelTask::Embed, "mock/embed", "m"),
+            ]);
+
+        let issues = builder.validate().unwrap();
+        let aliases: Vec<&str> = issues.iter().map(|i| i.alias.as_str()).collect();
+        assert_eq!(
+            aliases,
+            vec![
+                "no-slash",
+                "embed/typo",
+                "embed/opts",
+                "generate/wrong",
+                "embed/ok"
+            ]
+        );
+        assert!(
+            issues[1]
+                .to_string()
+                .contains("Unknown provider 'mock/embd'")
+        );
+        assert!(matches!(
+            issues[3].error,
+            RuntimeError::CapabilityMismatch(_)
+        ));
+        assert!(issues[4].error.to_string().contains("Duplicate alias"));
+    }
+
+    #[test]
+    fn merge_options_lets_alias_win() {
+        let defaults = serde_json::json!({
+            "project_id": "proj",
+            "location": "us-central1",
+            "safety": { "harassment": "block_none", "hate": "block_none" }
+        });
+        let options = serde_json::json!({
+            "location": "europe-west4",
+            "safety": { "hate": "block_high" }
+        });
+        assert_eq!(
+            merge_options(&defaults, &options),
+            serde_json::json!({
+                "project_id": "proj",
+                "location": "europe-west4",
+                "safety": { "harassment": "block_none", "hate": "block_high" }
+            })
+        );
+        assert_eq!(merge_options(&defaults, &serde_json::Value::Null), defaults);
+    }
+
+    #[test]
+    fn validate_rejects_bad_provider_defaults() {
+        let builder = || {
+            ModelRuntime::builder()
+                .register_provider(MockProvider::embed_only())
+                .catalog(vec![make_spec(
+                    "embed/ok",
+                    ModelTask::Embed,
+                    "mock/embed",
+                    "m",
+                )])
+        };
+
+        let unknown = builder().provider_defaults("mock/embd", serde_json::json!({}));
+        assert!(
+            unknown
+                .validate()
+                .unwrap_err()
+                .to_string()
+                .contains("mock/embd")
+        );
+
+        let not_object = builder().provider_defaults("mock/embed", serde_json::json!("x"));
+        assert!(not_object.validate().is_err());
+
+        let runtime_key = builder().provider_defaults(
+            "mock/embed",
+            serde_json::json!({ "expected_dimensions": 384 }),
+        );
+        assert!(
+            runtime_key
+                .validate()
+                .unwrap_err()
+                .to_string()
+                .contains("expected_dimensions")
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_concurrency_is_validated() {
+        let builder = || {
+            ModelRuntime::builder()
+                .register_provider(MockProvider::embed_only())
+                .catalog(vec![make_spec(
+                    "embed/ok",
+                    ModelTask::Embed,
+                    "mock/embed",
+                    "m",
+                )])
+        };
+
+        let unknown = builder().provider_concurrency("mock/embd", 4);
+        assert!(
+            unknown
+                .validate()
+                .unwrap_err()
+                .to_string()
+                .contains("mock/embd")
+        );
+
+        let zero = builder().provider_concurrency("mock/embed", 0);
+        assert!(zero.validate().is_err());
+
+        // The mock provider keeps the default hook, which refuses the limit.
+        let unsupported = builder().provider_concurrency("mock/embed", 4);
+        assert!(unsupported.validate().is_ok());
+        assert!(matches!(
+            unsupported.build().await,
+            Err(RuntimeError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn validate_accepts_valid_catalog() {
+        let builder = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![make_spec(
+                "embed/ok",
+                ModelTask::Embed,
+                "mock/embed",
+                "m",
+            )]);
+        assert!(builder.validate().unwrap().is_empty());
+    }
+
+    fn balanced(weights: &[(&str, u32)]) -> MultiBackendSpec {
+        MultiBackendSpec {
+            alias: "embed/balanced".to_string(),
+            task: ModelTask::Embed,
+            backends: weights
+                .iter()
+                .map(|(alias, weight)| crate::api::WeightedBackend {
+                    alias: alias.to_string(),
+                    weight: *weight,
+                })
+                .collect(),
+            circuit_breaker: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn balanced_alias_splits_calls_by_weight() {
+        let runtime = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![
+                make_spec("embed/a", ModelTask::Embed, "mock/embed", "model-a"),
+                make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b"),
+            ])
+            .multi_backend(balanced(&[("embed/a", 2), ("embed/b", 1)]))
+            .build()
+            .await
+            .unwrap();
+
+        assert!(runtime.contains_alias("embed/balanced").await);
+        for _ in 0..6 {
+            // Fresh handles share the runtime's rotation state.
+            let model = runtime.embedding("embed/balanced").await.unwrap();
+            model.embed(vec!["hi"]).await.unwrap();
+        }
+
+        let report = runtime.usage_report();
+        assert_eq!(report["embed/a"].calls, 4);
+        assert_eq!(report["embed/b"].calls, 2);
+    }
+
+    #[test]
+    fn validate_reports_bad_balanced_alias() {
+        let builder = ModelRuntime::builder()
+            .register_provider(MockProvider::new(
+                "mock/both",
+                vec![ModelTask::Embed, ModelTask::Generate],
+            ))
+            .catalog(vec![
+                make_spec("embed/a", ModelTask::Embed, "mock/both", "m"),
+                make_spec("generate/g", ModelTask::Generate, "mock/both", "m"),
+            ])
+            .multi_backend(balanced(&[
+                ("embed/a", 0),
+                ("embed/missing", 1),
+                ("generate/g", 1),
+            ]));
+
+        let issues = builder.validate().unwrap();
+        let messages: Vec<String> = issues.iter().map(|i| i.error.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages[0].contains("weight greater than 0"));
+        assert!(messages[1].contains("not in the catalog"));
+        assert!(messages[2].contains("has task Generate"));
+        assert!(issues.iter().all(|i| i.alias == "embed/balanced"));
+    }
+
+    #[test]
+    fn validate_rejects_zero_breaker_threshold_on_balanced_alias() {
+        let builder = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![make_spec(
+                "embed/a",
+                ModelTask::Embed,
+                "mock/embed",
+                "m",
+            )])
+            .multi_backend(MultiBackendSpec {
+                circuit_breaker: Some(crate::reliability::CircuitBreakerConfig {
+                    failure_threshold: 0,
+                    open_wait_seconds: 10,
+                }),
+                ..balanced(&[("embed/a", 1)])
+            });
+
+        let issues = builder.validate().unwrap();
+        assert_eq!(issues.len(), 1);
+        assert!(
+            issues[0]
+                .error
+                .to_string()
+                .contains("failure_threshold greater than 0")
+        );
+    }
+
+    #[tokio::test]
+    async fn usage_report_accumulates_per_alias() {
+        let runtime = crate::mock::runtime_with_generator().await.unwrap();
+        let model = runtime.generator("generate/test").await.unwrap();
+        let messages = [crate::traits::Message::user("hello there")];
+        model.generate(&messages, Default::default()).await.unwrap();
+        model.generate(&messages, Default::default()).await.unwrap();
+
+        let report = runtime.usage_report();
+        let usage = &report["generate/test"];
+        assert_eq!(usage.calls, 2);
+        assert_eq!(usage.prompt_tokens, 4);
+        assert!(usage.total_tokens >= usage.prompt_tokens);
+        assert_eq!(usage.estimated_cost, None);
+    }
 }
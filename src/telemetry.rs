@@ -0,0 +1,89 @@
+//! Thin shim over the `metrics` crate.
+//!
+//! [`counter!`], [`gauge!`], and [`histogram!`] forward to their `metrics`
+//! counterparts when the `metrics` feature is enabled. Without it they expand to a no-op
+//! handle: label values are type-checked but never evaluated, so neither the
+//! dependency nor the per-call label allocations remain.
+
+#[cfg(feature = "metrics")]
+macro_rules! counter {
+    ($($args:tt)*) => {
+        ::metrics::counter!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! counter {
+    ($name:expr $(, $key:expr => $value:expr)* $(,)?) => {{
+        let _ = || ($name, $(($key, $value)),*);
+        $crate::telemetry::Noop
+    }};
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! gauge {
+    ($($args:tt)*) => {
+        ::metrics::gauge!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! gauge {
+    ($name:expr $(, $key:expr => $value:expr)* $(,)?) => {{
+        let _ = || ($name, $(($key, $value)),*);
+        $crate::telemetry::NoopGauge
+    }};
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! histogram {
+    ($($args:tt)*) => {
+        ::metrics::histogram!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+macro_rules! histogram {
+    ($name:expr $(, $key:expr => $value:expr)* $(,)?) => {{
+        let _ = || ($name, $(($key, $value)),*);
+        $crate::telemetry::Noop
+    }};
+}
+
+pub(crate) use counter;
+pub(crate) use gauge;
+pub(crate) use histogram;
+
+/// Stand-in for `metrics::Counter` and `metrics::Histogram` when the
+/// `metrics` feature is disabled.
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct Noop;
+
+#[cfg(not(feature = "metrics"))]
+impl Noop {
+    pub(crate) fn increment(&self, _value: u64) {}
+
+    pub(crate) fn record(&self, _value: f64) {}
+}
+
+/// Stand-in for `metrics::Gauge` when the `metrics` feature is disabled.
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct NoopGauge;
+
+#[cfg(not(feature = "metrics"))]
+impl NoopGauge {
+    pub(crate) fn increment(&self, _value: f64) {}
+
+    pub(crate) fn decrement(&self, _value: f64) {}
+}
+
+#[cfg(all(test, not(feature = "metrics")))]
+mod tests {
+    #[test]
+    fn disabled_macros_skip_label_evaluation() {
+        let label = || -> String { panic!("label evaluated") };
+        super::counter!("test.total", "label" => label()).increment(1);
+        super::gauge!("test.waiters", "label" => label()).increment(1.0);
+        super::histogram!("test.duration_seconds", "label" => label()).record(1.0);
+    }
+}
@@ -0,0 +1,333 @@
+//! Splitting long documents into overlapping chunks before embedding.
+//!
+//! Most embedding models truncate their input (often at 512 tokens), so text
+//! past the limit silently does not contribute to the vector. The chunkers here
+//! split a document into overlapping windows that each fit the model, and
+//! [`embed_chunked`] / [`embed_chunked_mean`] embed those windows in one batch.
+//!
+//! ```rust,no_run
+//! # async fn example(model: &dyn uni_xervo::traits::EmbeddingModel) -> uni_xervo::error::Result<()> {
+//! use uni_xervo::text::{chunk_by_chars, embed_chunked_mean};
+//!
+//! let docs = ["a very long document ...", "another one ..."];
+//! let vectors = embed_chunked_mean(model, &docs, |doc| chunk_by_chars(doc, 1000, 200)).await?;
+//! assert_eq!(vectors.len(), docs.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, RuntimeError};
+use crate::traits::EmbeddingModel;
+
+/// A tokenizer that can report where each token sits in the source text.
+///
+/// Implemented for [`tokenizers::Tokenizer`] when the `provider-candle`
+/// feature is enabled. Implement it for other tokenizers to use
+/// [`chunk_by_tokens`] with them.
+pub trait ChunkTokenizer {
+    /// Byte ranges `(start, end)` of each token in `text`, in order. Special
+    /// tokens that do not correspond to any text must be omitted.
+    fn token_offsets(&self, text: &str) -> Result<Vec<(usize, usize)>>;
+}
+
+#[cfg(feature = "provider-candle")]
+impl ChunkTokenizer for tokenizers::Tokenizer {
+    /// Encodes without special tokens. Truncation configured on the tokenizer
+    /// still applies, so use an instance with truncation disabled.
+    fn token_offsets(&self, text: &str) -> Result<Vec<(usize, usize)>> {
+        let encoding = self
+            .encode(text, false)
+            .map_err(|e| RuntimeError::InferenceError(format!("Tokenization failed: {}", e)))?;
+        Ok(encoding.get_offsets().to_vec())
+    }
+}
+
+fn check_window(max: usize, overlap: usize) -> Result<()> {
+    if max == 0 {
+        return Err(RuntimeError::Config(
+            "Chunk size must be greater than 0".to_string(),
+        ));
+    }
+    if overlap >= max {
+        return Err(RuntimeError::Config(format!(
+            "Chunk overlap ({}) must be smaller than chunk size ({})",
+            overlap, max
+        )));
+    }
+    Ok(())
+}
+
+/// Start and end indices of each window of `size` items over `len` items,
+/// advancing by `size - overlap`. The last window ends at `len`.
+fn windows(len: usize, size: usize, overlap: usize) -> Vec<(usize, usize)> {
+    let stride = size - overlap;
+    let mut out = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let end = (start + size).min(len);
+        out.push((start, end));
+        if end == len {
+            break;
+        }
+        start += stride;
+    }
+    out
+}
+
+/// Split `text` into chunks of at most `max_tokens` tokens, with consecutive
+/// chunks sharing `overlap` tokens.
+///
+/// Chunks are slices of the original text, from the start of their first token
+/// to the end of their last, so whitespace and formatting are preserved.
+/// Returns no chunks for text with no tokens.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Config`] if `max_tokens` is 0 or `overlap` is not
+/// smaller than `max_tokens`, and propagates tokenizer errors.
+pub fn chunk_by_tokens<T: ChunkTokenizer + ?Sized>(
+    text: &str,
+    max_tokens: usize,
+    overlap: usize,
+    tokenizer: &T,
+) -> Result<Vec<String>> {
+    check_window(max_tokens, overlap)?;
+    let offsets = tokenizer.token_offsets(text)?;
+    Ok(windows(offsets.len(), max_tokens, overlap)
+        .into_iter()
+        .map(|(first, last)| {
+            let start = offsets[first].0;
+            let end = offsets[last - 1].1;
+            text[start..end].to_string()
+        })
+        .collect())
+}
+
+/// Split `text` into chunks of at most `max_chars` characters, with
+/// consecutive chunks sharing `overlap` characters.
+///
+/// A fallback for when the model's tokenizer is not available. Pick
+/// `max_chars` conservatively: English text averages roughly four characters
+/// per token. Returns no chunks for empty text.
+///
+/// # Errors
+///
+/// Returns [`RuntimeError::Config`] if `max_chars` is 0 or `overlap` is not
+/// smaller than `max_chars`.
+pub fn chunk_by_chars(text: &str, max_chars: usize, overlap: usize) -> Result<Vec<String>> {
+    check_window(max_chars, overlap)?;
+    let boundaries: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let char_count = boundaries.len() - 1;
+    Ok(windows(char_count, max_chars, overlap)
+        .into_iter()
+        .map(|(start, end)| text[boundaries[start]..boundaries[end]].to_string())
+        .collect())
+}
+
+/// Chunk each document with `chunker` and embed all chunks in a single
+/// `embed` call.
+///
+/// Returns one entry per document holding that document's chunk vectors, in
+/// chunk order. A document that yields no chunks gets an empty entry.
+pub async fn embed_chunked<F>(
+    model: &dyn EmbeddingModel,
+    docs: &[&str],
+    chunker: F,
+) -> Result<Vec<Vec<Vec<f32>>>>
+where
+    F: Fn(&str) -> Result<Vec<String>>,
+{
+    let chunked: Vec<Vec<String>> = docs.iter().map(|d| chunker(d)).collect::<Result<_>>()?;
+    let all: Vec<&str> = chunked.iter().flatten().map(String::as_str).collect();
+
+    let mut vectors = if all.is_empty() {
+        Vec::new().into_iter()
+    } else {
+        let expected = all.len();
+        let vectors = model.embed(all).await?;
+        if vectors.len() != expected {
+            return Err(RuntimeError::InferenceError(format!(
+                "Expected {} chunk embeddings, model returned {}",
+                expected,
+                vectors.len()
+            )));
+        }
+        vectors.into_iter()
+    };
+
+    Ok(chunked
+        .iter()
+        .map(|chunks| vectors.by_ref().take(chunks.len()).collect())
+        .collect())
+}
+
+/// Like [`embed_chunked`], but mean-pools each document's chunk vectors into
+/// one vector per document.
+///
+/// The pooled vector is rescaled to unit length, so cosine and dot-product
+/// similarity behave as they do for single-chunk embeddings. A document that
+/// yields no chunks gets a zero vector of [`EmbeddingModel::dimensions`]
+/// length.
+pub async fn embed_chunked_mean<F>(
+    model: &dyn EmbeddingModel,
+    docs: &[&str],
+    chunker: F,
+) -> Result<Vec<Vec<f32>>>
+where
+    F: Fn(&str) -> Result<Vec<String>>,
+{
+    let per_doc = embed_chunked(model, docs, chunker).await?;
+    let zero = || vec![0.0; model.dimensions() as usize];
+    Ok(per_doc
+        .into_iter()
+        .map(|chunks| mean_pool(&chunks).unwrap_or_else(zero))
+        .collect())
+}
+
+/// Unit-length mean of `vectors`, or `None` if there are none.
+fn mean_pool(vectors: &[Vec<f32>]) -> Option<Vec<f32>> {
+    let first = vectors.first()?;
+    let mut mean = vec![0.0f32; first.len()];
+    for v in vectors {
+        for (acc, x) in mean.iter_mut().zip(v) {
+            *acc += x;
+        }
+    }
+    let norm = mean.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in &mut mean {
+            *x /= norm;
+        }
+    }
+    Some(mean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Treats each whitespace-separated word as one token.
+    struct WordTokenizer;
+
+    impl ChunkTokenizer for WordTokenizer {
+        fn token_offsets(&self, text: &str) -> Result<Vec<(usize, usize)>> {
+            let mut offsets = Vec::new();
+            let mut start = None;
+            for (i, c) in text.char_indices() {
+                match (c.is_whitespace(), start) {
+                    (false, None) => start = Some(i),
+                    (true, Some(s)) => {
+                        offsets.push((s, i));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(s) = start {
+                offsets.push((s, text.len()));
+            }
+            Ok(offsets)
+        }
+    }
+
+    #[test]
+    fn token_chunks_overlap_and_cover_text() {
+        let chunks = chunk_by_tokens("a b c d e f g", 3, 1, &WordTokenizer).unwrap();
+        assert_eq!(chunks, vec!["a b c", "c d e", "e f g"]);
+    }
+
+    #[test]
+    fn token_chunks_short_text_is_single_chunk() {
+        let chunks = chunk_by_tokens("  hello   world ", 10, 2, &WordTokenizer).unwrap();
+        assert_eq!(chunks, vec!["hello   world"]);
+        assert!(
+            chunk_by_tokens("   ", 10, 2, &WordTokenizer)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn char_chunks_respect_char_boundaries() {
+        let chunks = chunk_by_chars("héllo wörld", 4, 1).unwrap();
+        assert_eq!(chunks, vec!["héll", "lo w", "wörl", "ld"]);
+        assert!(chunk_by_chars("", 4, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_overlap_not_smaller_than_window() {
+        assert!(matches!(
+            chunk_by_chars("abc", 2, 2),
+            Err(RuntimeError::Config(_))
+        ));
+        assert!(matches!(
+            chunk_by_tokens("abc", 0, 0, &WordTokenizer),
+            Err(RuntimeError::Config(_))
+        ));
+    }
+
+    /// Embeds each text as `[len, 1]` and counts calls.
+    struct LenModel {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for LenModel {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 1.0]).collect())
+        }
+
+        fn dimensions(&self) -> u32 {
+            2
+        }
+
+        fn model_id(&self) -> &str {
+            "len"
+        }
+    }
+
+    #[tokio::test]
+    async fn embed_chunked_groups_vectors_per_document_in_one_call() {
+        let model = LenModel {
+            calls: AtomicU32::new(0),
+        };
+        let docs = ["abcdef", "", "xy"];
+        let vectors = embed_chunked(&model, &docs, |d| chunk_by_chars(d, 4, 0))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vectors,
+            vec![
+                vec![vec![4.0, 1.0], vec![2.0, 1.0]],
+                vec![],
+                vec![vec![2.0, 1.0]]
+            ]
+        );
+        assert_eq!(model.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn embed_chunked_mean_pools_to_unit_vectors() {
+        let model = LenModel {
+            calls: AtomicU32::new(0),
+        };
+        let docs = ["abcdef", ""];
+        let vectors = embed_chunked_mean(&model, &docs, |d| chunk_by_chars(d, 4, 0))
+            .await
+            .unwrap();
+
+        // mean of [4,1] and [2,1] is [3,1], normalized.
+        let norm = 10f32.sqrt();
+        assert!((vectors[0][0] - 3.0 / norm).abs() < 1e-6);
+        assert!((vectors[0][1] - 1.0 / norm).abs() < 1e-6);
+        assert_eq!(vectors[1], vec![0.0, 0.0]);
+    }
+}
@@ -23,6 +23,30 @@ pub enum ProviderHealth {
     Unhealthy(String),
 }
 
+/// Progress of one model file download, reported to a
+/// [`DownloadProgressFn`].
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    /// Provider performing the download, e.g. `"local/candle"`.
+    pub provider_id: &'static str,
+    /// Repository or model the file belongs to, e.g. `"BAAI/bge-small-en-v1.5"`.
+    pub model_id: String,
+    /// File name within the repository, e.g. `"model.safetensors"`.
+    pub file: String,
+    /// Bytes downloaded so far.
+    pub downloaded: u64,
+    /// Total file size in bytes, when the server reports it.
+    pub total: Option<u64>,
+}
+
+/// Callback receiving [`DownloadProgress`] updates; see
+/// [`ModelRuntimeBuilder::on_download_progress`](crate::runtime::ModelRuntimeBuilder::on_download_progress).
+///
+/// It is called from the downloading task, potentially many times per
+/// second, so it should return quickly (e.g. update a progress bar or send
+/// on a channel).
+pub type DownloadProgressFn = std::sync::Arc<dyn Fn(&DownloadProgress) + Send + Sync>;
+
 /// A pluggable backend that knows how to load models for one or more
 /// [`ModelTask`] types.
 ///
@@ -46,15 +70,82 @@ pub trait ModelProvider: Send + Sync {
     async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle>;
 
     /// Report the current health of this provider.
+    ///
+    /// This is a cheap, local check; it does not contact any remote service.
     async fn health(&self) -> ProviderHealth;
 
+    /// Actively check that the provider can serve requests, e.g. by calling a
+    /// remote API's models-list endpoint with the default credentials.
+    ///
+    /// Unlike [`health`](Self::health) this may send a network request (and,
+    /// for some APIs, consume a few tokens), so callers opt in explicitly.
+    /// Built-in remote providers cache the result for 30 seconds. The default
+    /// implementation returns [`health`](Self::health).
+    async fn probe_health(&self) -> ProviderHealth {
+        self.health().await
+    }
+
     /// Optional one-time warmup hook called during runtime startup.
     ///
     /// Use this for provider-wide initialization such as setting up API clients
-    /// or pre-caching shared resources. The default implementation is a no-op.
+    /// or pre-caching shared resources. Remote providers use it to validate
+    /// their default credentials. The default implementation is a no-op.
     async fn warmup(&self) -> Result<()> {
         Ok(())
     }
+
+    /// JSON Schema for the `options` object of specs using this provider.
+    ///
+    /// When present, the runtime validates every spec's options against it
+    /// during [`build`](crate::runtime::ModelRuntimeBuilder::build) and
+    /// [`register`](crate::runtime::ModelRuntime::register). Runtime-level
+    /// keys such as `expected_dimensions` and `max_input_chars` are removed
+    /// first, so the schema only needs to describe the provider's own options. Supported keywords:
+    /// `type`, `enum`, `minimum`, `maximum`, `properties`, `required`,
+    /// `additionalProperties`, `dependentRequired`, and `items`; others are
+    /// ignored. The default returns `None` and accepts any options.
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// The option keys that change the instance [`load`](Self::load)
+    /// returns, such as a device or weight precision.
+    ///
+    /// The runtime shares one loaded instance between aliases with the same
+    /// model and equal values for these keys (see
+    /// [`ModelRuntimeKey::with_load_options`](crate::api::ModelRuntimeKey::with_load_options)).
+    /// The instance is loaded with the options of whichever alias loads it
+    /// first, so only list every key the instance depends on. The default,
+    /// `None`, treats every option as load-relevant.
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Install a callback for weight download progress, called by
+    /// [`ModelRuntimeBuilder::build`](crate::runtime::ModelRuntimeBuilder::build)
+    /// when [`on_download_progress`](crate::runtime::ModelRuntimeBuilder::on_download_progress)
+    /// is set. Applies to models loaded afterwards.
+    ///
+    /// The default ignores the callback, for providers that download nothing
+    /// or whose download library offers no progress hook.
+    fn set_download_progress(&self, _callback: DownloadProgressFn) {}
+
+    /// Cap concurrent requests across every model this provider serves at
+    /// `permits`, called by
+    /// [`ModelRuntimeBuilder::build`](crate::runtime::ModelRuntimeBuilder::build)
+    /// for each [`provider_concurrency`](crate::runtime::ModelRuntimeBuilder::provider_concurrency)
+    /// entry. Applies to models loaded afterwards.
+    ///
+    /// Built-in remote providers implement this with one semaphore shared by
+    /// all their models. The default returns
+    /// [`RuntimeError::Config`](crate::error::RuntimeError::Config), since a
+    /// silently ignored limit would not protect the upstream quota.
+    fn set_max_concurrency(&self, _permits: usize) -> Result<()> {
+        Err(crate::error::RuntimeError::Config(format!(
+            "Provider '{}' does not support a concurrency limit",
+            self.provider_id()
+        )))
+    }
 }
 
 /// A type-erased, reference-counted handle to a loaded model instance.
@@ -64,15 +155,149 @@ pub trait ModelProvider: Send + Sync {
 /// The runtime later downcasts the handle back to the expected trait object.
 pub type LoadedModelHandle = std::sync::Arc<dyn Any + Send + Sync>;
 
+/// Receiver side of a streamed embedding, as returned by
+/// [`EmbeddingModel::embed_stream`].
+///
+/// Yields `(index, vector)` pairs, where `index` is the text's position in
+/// the input, and closes once every text is embedded. An error arrives as an
+/// `Err` item, after which the stream closes. Dropping the receiver stops
+/// the remaining work.
+pub type EmbeddingStream = tokio::sync::mpsc::Receiver<Result<(usize, Vec<f32>)>>;
+
 /// A model that produces dense vector embeddings from text.
 #[async_trait]
 pub trait EmbeddingModel: Send + Sync + Any {
     /// Embed a batch of text strings into dense vectors.
     ///
-    /// Returns one `Vec<f32>` per input text, each with [`dimensions()`](EmbeddingModel::dimensions)
-    /// elements.
+    /// Returns one `Vec<f32>` per input text, in input order, each with
+    /// [`dimensions()`](EmbeddingModel::dimensions) elements. An empty batch
+    /// returns an empty result without calling the backend.
     async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>>;
 
+    /// Embed a single text, e.g. a search query, and return its vector.
+    ///
+    /// The default calls [`embed`](EmbeddingModel::embed) and fails with
+    /// [`RuntimeError::InferenceError`](crate::error::RuntimeError::InferenceError)
+    /// if the backend returns no vectors.
+    async fn embed_one(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed(vec![text])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                crate::error::RuntimeError::InferenceError("empty embedding response".to_string())
+            })
+    }
+
+    /// Embed a batch of texts and also return the token usage reported by
+    /// the provider, if any.
+    ///
+    /// For embeddings, usage is reported as prompt tokens with zero
+    /// completion tokens. The default calls [`embed`](EmbeddingModel::embed)
+    /// and returns `None` for usage.
+    async fn embed_with_usage(
+        &self,
+        texts: Vec<&str>,
+    ) -> Result<(Vec<Vec<f32>>, Option<TokenUsage>)> {
+        Ok((self.embed(texts).await?, None))
+    }
+
+    /// Embed a batch of texts with request-scoped option overrides merged
+    /// onto the alias's options for this call only, e.g.
+    /// `{"input_type": "search_query"}` for Cohere.
+    ///
+    /// Only options a provider documents as overridable are accepted; others
+    /// fail with [`RuntimeError::Config`](crate::error::RuntimeError::Config).
+    /// The default accepts `null` or `{}` (no overrides) and calls
+    /// [`embed`](EmbeddingModel::embed), and rejects anything else.
+    async fn embed_with(
+        &self,
+        texts: Vec<&str>,
+        overrides: serde_json::Value,
+    ) -> Result<Vec<Vec<f32>>> {
+        if !has_overrides(&overrides) {
+            return self.embed(texts).await;
+        }
+        Err(crate::error::RuntimeError::Config(format!(
+            "Model '{}' does not support per-call option overrides",
+            self.model_id()
+        )))
+    }
+
+    /// Embed a batch of texts as int8 vectors with a per-vector scale, for
+    /// storage at a quarter of the size of `f32` vectors.
+    ///
+    /// Handles returned by [`ModelRuntime`](crate::runtime::ModelRuntime)
+    /// implement this by quantizing [`embed`](EmbeddingModel::embed) output
+    /// with [`QuantizedEmbedding::quantize`]. The default fails with
+    /// [`RuntimeError::CapabilityMismatch`](crate::error::RuntimeError::CapabilityMismatch).
+    async fn embed_quantized(&self, _texts: Vec<&str>) -> Result<Vec<QuantizedEmbedding>> {
+        Err(crate::error::RuntimeError::CapabilityMismatch(format!(
+            "Model '{}' does not support quantized embeddings",
+            self.model_id()
+        )))
+    }
+
+    /// Embed a batch of texts as int8 vectors computed by the provider, one
+    /// value per dimension.
+    ///
+    /// Unlike [`embed_quantized`](EmbeddingModel::embed_quantized), the
+    /// values use the provider's own calibration and carry no scale. The
+    /// default fails with
+    /// [`RuntimeError::CapabilityMismatch`](crate::error::RuntimeError::CapabilityMismatch).
+    async fn embed_int8(&self, _texts: Vec<&str>) -> Result<Vec<Vec<i8>>> {
+        Err(crate::error::RuntimeError::CapabilityMismatch(format!(
+            "Model '{}' does not support int8 embeddings",
+            self.model_id()
+        )))
+    }
+
+    /// Embed a batch of texts as bit-packed binary vectors computed by the
+    /// provider: eight dimensions per byte, most significant bit first, so
+    /// each vector has [`dimensions()`](EmbeddingModel::dimensions) / 8
+    /// bytes. Compare them by Hamming distance.
+    ///
+    /// The default fails with
+    /// [`RuntimeError::CapabilityMismatch`](crate::error::RuntimeError::CapabilityMismatch).
+    async fn embed_binary(&self, _texts: Vec<&str>) -> Result<Vec<Vec<u8>>> {
+        Err(crate::error::RuntimeError::CapabilityMismatch(format!(
+            "Model '{}' does not support binary embeddings",
+            self.model_id()
+        )))
+    }
+
+    /// Embed pre-tokenized inputs, one vector per token sequence, skipping
+    /// the model's tokenizer.
+    ///
+    /// Each sequence holds token IDs from this model's tokenizer, with
+    /// special tokens (e.g. `[CLS]`/`[SEP]`) already added, so texts
+    /// tokenized once can be embedded repeatedly. Implemented by
+    /// `local/candle`. The default fails with
+    /// [`RuntimeError::CapabilityMismatch`](crate::error::RuntimeError::CapabilityMismatch).
+    async fn embed_tokens(&self, _batches: Vec<Vec<u32>>) -> Result<Vec<Vec<f32>>> {
+        Err(crate::error::RuntimeError::CapabilityMismatch(format!(
+            "Model '{}' does not support pre-tokenized input",
+            self.model_id()
+        )))
+    }
+
+    /// Embed a large batch of texts incrementally, yielding vectors as each
+    /// sub-batch finishes so they can be consumed (e.g. written to an index)
+    /// without holding the whole result in memory.
+    ///
+    /// Returns once the request is accepted; vectors then arrive on the
+    /// returned [`EmbeddingStream`] in input order. `local/candle` and
+    /// `local/mistralrs` stream one configured batch at a time. The default
+    /// calls [`embed`](EmbeddingModel::embed) and yields the whole result.
+    async fn embed_stream(&self, texts: Vec<&str>) -> Result<EmbeddingStream> {
+        let vectors = self.embed(texts).await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(vectors.len().max(1));
+        for item in vectors.into_iter().enumerate() {
+            let _ = tx.try_send(Ok(item));
+        }
+        Ok(rx)
+    }
+
     /// The dimensionality of the embedding vectors produced by this model.
     fn dimensions(&self) -> u32;
 
@@ -84,6 +309,64 @@ pub trait EmbeddingModel: Send + Sync + Any {
     async fn warmup(&self) -> Result<()> {
         Ok(())
     }
+
+    /// The provider's rate-limit state as of the latest response, for
+    /// throttling before the provider starts returning 429s.
+    ///
+    /// Providers that report quota headers (OpenAI, Azure OpenAI, Anthropic,
+    /// Cohere) return a snapshot once a response has been received. The
+    /// default returns `None`.
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
+}
+
+/// Whether `overrides` passed to [`EmbeddingModel::embed_with`] contains
+/// anything other than `null` or an empty object.
+pub(crate) fn has_overrides(overrides: &serde_json::Value) -> bool {
+    match overrides {
+        serde_json::Value::Null => false,
+        serde_json::Value::Object(map) => !map.is_empty(),
+        _ => true,
+    }
+}
+
+/// An embedding quantized to int8 with symmetric per-vector scaling.
+///
+/// Each component is stored as `round(x / scale)`, where `scale` is the
+/// vector's largest absolute component divided by 127, so `values[i] * scale`
+/// approximates the original component. Cosine similarity between quantized
+/// vectors closely tracks the `f32` similarity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedEmbedding {
+    /// Quantized components, in `-127..=127`.
+    pub values: Vec<i8>,
+    /// Multiplier that maps `values` back to the original range.
+    pub scale: f32,
+}
+
+impl QuantizedEmbedding {
+    /// Quantize an `f32` vector. An all-zero vector gets a `scale` of 0.
+    pub fn quantize(vector: &[f32]) -> Self {
+        let max_abs = vector.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+        if max_abs == 0.0 {
+            return Self {
+                values: vec![0; vector.len()],
+                scale: 0.0,
+            };
+        }
+        let scale = max_abs / 127.0;
+        let values = vector
+            .iter()
+            .map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+            .collect();
+        Self { values, scale }
+    }
+
+    /// Reconstruct an approximate `f32` vector.
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.values.iter().map(|&v| v as f32 * self.scale).collect()
+    }
 }
 
 /// A single scored document returned by a [`RerankerModel`].
@@ -94,21 +377,130 @@ pub struct ScoredDoc {
     pub index: usize,
     /// Relevance score assigned by the reranker (higher is more relevant).
     pub score: f32,
-    /// The document text, if the provider returns it. May be `None`.
+    /// The document text. Instrumented handles from
+    /// [`ModelRuntime`](crate::runtime::ModelRuntime) set it to the
+    /// caller's original document exactly when
+    /// [`RerankOptions::return_documents`] is requested, whether or not the
+    /// provider echoes documents, and leave it `None` otherwise (including
+    /// for [`RerankerModel::rerank`]).
     pub text: Option<String>,
 }
 
+/// Optional parameters for [`RerankerModel::rerank_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RerankOptions {
+    /// Return only the `top_n` highest-scoring documents. `None` returns all.
+    pub top_n: Option<usize>,
+    /// Fill [`ScoredDoc::text`] with the document text.
+    pub return_documents: bool,
+    /// Order results by descending score (the default). When `false`,
+    /// results follow the input order of `docs` instead, so scores line up
+    /// with the corpus for merging with other signals; `top_n` still keeps
+    /// the highest-scoring documents.
+    pub sort: bool,
+}
+
+impl Default for RerankOptions {
+    fn default() -> Self {
+        Self {
+            top_n: None,
+            return_documents: false,
+            sort: true,
+        }
+    }
+}
+
+impl RerankOptions {
+    /// Apply these options to results produced without them: sort by
+    /// descending score, truncate to `top_n`, restore input order when
+    /// `sort` is `false`, and fill in `text` from `docs` when
+    /// `return_documents` is set or clear it when not.
+    ///
+    /// This is the fallback used by the default
+    /// [`rerank_with_options`](RerankerModel::rerank_with_options); providers
+    /// whose API handles the options natively can still call it to fill in
+    /// anything the API omits.
+    pub fn apply(&self, mut results: Vec<ScoredDoc>, docs: &[&str]) -> Vec<ScoredDoc> {
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(top_n) = self.top_n {
+            results.truncate(top_n);
+        }
+        if !self.sort {
+            results.sort_by_key(|doc| doc.index);
+        }
+        for doc in &mut results {
+            if !self.return_documents {
+                doc.text = None;
+            } else if doc.text.is_none() {
+                doc.text = docs.get(doc.index).map(|t| t.to_string());
+            }
+        }
+        results
+    }
+}
+
 /// A model that re-scores documents against a query for relevance ranking.
 #[async_trait]
 pub trait RerankerModel: Send + Sync {
     /// Rerank `docs` by relevance to `query`, returning scored results
-    /// (typically sorted by descending score).
+    /// (typically sorted by descending score). An empty `docs` returns an
+    /// empty result without calling the backend.
     async fn rerank(&self, query: &str, docs: &[&str]) -> Result<Vec<ScoredDoc>>;
 
+    /// Rerank with [`RerankOptions`] controlling how many results are
+    /// returned, in which order, and whether they carry the document text.
+    ///
+    /// The default calls [`rerank`](RerankerModel::rerank) and applies the
+    /// options locally via [`RerankOptions::apply`].
+    async fn rerank_with_options(
+        &self,
+        query: &str,
+        docs: &[&str],
+        options: &RerankOptions,
+    ) -> Result<Vec<ScoredDoc>> {
+        let results = self.rerank(query, docs).await?;
+        Ok(options.apply(results, docs))
+    }
+
+    /// Optional warmup hook. The default is a no-op.
+    async fn warmup(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// The provider's rate-limit state as of the latest response. See
+    /// [`EmbeddingModel::rate_limits`]. The default returns `None`.
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
+}
+
+/// A candidate label scored by a [`ClassifierModel`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredLabel {
+    /// The label, as passed to [`ClassifierModel::classify`].
+    pub label: String,
+    /// Confidence that the text belongs to this label, from 0 to 1.
+    pub score: f32,
+}
+
+/// A model that assigns a text to one of a set of caller-supplied labels.
+#[async_trait]
+pub trait ClassifierModel: Send + Sync {
+    /// Score `text` against every label in `labels`, returning one
+    /// [`ScoredLabel`] per label sorted by descending score. An empty
+    /// `labels` returns an empty result without calling the backend.
+    async fn classify(&self, text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>>;
+
     /// Optional warmup hook. The default is a no-op.
     async fn warmup(&self) -> Result<()> {
         Ok(())
     }
+
+    /// The provider's rate-limit state as of the latest response. See
+    /// [`EmbeddingModel::rate_limits`]. The default returns `None`.
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -204,10 +596,89 @@ pub struct GenerationOptions {
     pub temperature: Option<f32>,
     /// Nucleus sampling threshold.
     pub top_p: Option<f32>,
+    /// Sample only from the `top_k` most likely tokens. Honored by
+    /// Anthropic; ignored by other providers.
+    pub top_k: Option<u32>,
     /// Desired image width (for diffusion models; ignored by text/vision).
     pub width: Option<u32>,
     /// Desired image height (for diffusion models; ignored by text/vision).
     pub height: Option<u32>,
+    /// Number of completions to generate. `None` or `1` produces one; extra
+    /// completions are returned in [`GenerationResult::alternatives`].
+    pub n: Option<u32>,
+    /// Sequences that end generation when produced. The stop sequence itself
+    /// is not included in the output.
+    pub stop: Option<Vec<String>>,
+    /// Additive bias per token ID, applied to logits before sampling.
+    /// Token IDs are tokenizer-specific; providers without logit bias
+    /// support ignore it.
+    pub logit_bias: Option<std::collections::HashMap<u32, f32>>,
+    /// Key that lets a provider recognize repeated submissions of the same
+    /// request, so a retry does not generate (and bill) twice. Sent as the
+    /// `Idempotency-Key` header by OpenAI; ignored by other providers. When
+    /// unset and the alias has a retry policy, the runtime generates a fresh
+    /// key per call and reuses it across that call's retries.
+    pub idempotency_key: Option<String>,
+    /// Return the provider's parsed response body in
+    /// [`GenerationResult::raw`]. Off by default to avoid keeping large
+    /// payloads around.
+    pub include_raw: bool,
+    /// Return the log probability of each generated token in
+    /// [`GenerationResult::logprobs`]. Honored by OpenAI, Azure OpenAI, and
+    /// mistral.rs text models; ignored elsewhere.
+    pub logprobs: bool,
+    /// Also return this many most likely alternatives per token position.
+    /// Implies `logprobs`.
+    pub top_logprobs: Option<u32>,
+    /// How much reasoning a reasoning model should do before answering.
+    /// Sent as `reasoning_effort` by OpenAI, Azure OpenAI, and NIM; Anthropic,
+    /// Gemini, and Vertex AI use it as a thinking budget (see
+    /// [`ReasoningEffort::budget_tokens`]) when `thinking_budget` is unset.
+    /// Ignored elsewhere.
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Token budget for extended thinking. Enables Anthropic extended
+    /// thinking and sets the Gemini and Vertex AI `thinkingBudget`; ignored
+    /// elsewhere. Anthropic requires at least 1024 and, when `max_tokens` is
+    /// unset, gets a `max_tokens` of the budget plus 1024.
+    pub thinking_budget: Option<u32>,
+}
+
+impl GenerationOptions {
+    /// The thinking budget to request: `thinking_budget`, or the budget for
+    /// `reasoning_effort` when only that is set.
+    pub fn thinking_budget_tokens(&self) -> Option<u32> {
+        self.thinking_budget
+            .or_else(|| self.reasoning_effort.map(ReasoningEffort::budget_tokens))
+    }
+}
+
+/// Requested reasoning depth; see [`GenerationOptions::reasoning_effort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    /// The wire value: `"low"`, `"medium"`, or `"high"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Thinking budget used by providers that take a token count: 1024,
+    /// 4096, or 16384 tokens.
+    pub fn budget_tokens(self) -> u32 {
+        match self {
+            Self::Low => 1024,
+            Self::Medium => 4096,
+            Self::High => 16384,
+        }
+    }
 }
 
 /// An image produced by a generation call (e.g. from a diffusion model).
@@ -241,10 +712,83 @@ pub struct GenerationResult {
     pub images: Vec<GeneratedImage>,
     /// Generated audio (present for speech models).
     pub audio: Option<AudioOutput>,
+    /// Completions after the first when [`GenerationOptions::n`] is greater
+    /// than 1; `text` holds the first. Empty otherwise.
+    pub alternatives: Vec<String>,
+    /// Why generation of `text` ended, as reported by the provider (e.g.
+    /// `"stop"`, `"length"`, `"end_turn"`). Values are provider-specific.
+    pub finish_reason: Option<String>,
+    /// The provider's response body, for fields uni-xervo does not model
+    /// (citations, safety ratings, ...). Set by remote providers when
+    /// [`GenerationOptions::include_raw`] is on; always `None` for local
+    /// providers and streaming.
+    pub raw: Option<serde_json::Value>,
+    /// Per-token log probabilities of `text`, when
+    /// [`GenerationOptions::logprobs`] was requested and the provider
+    /// supports it.
+    pub logprobs: Option<Vec<TokenLogprob>>,
+    /// Reasoning ("thinking") the model returned separately from its answer,
+    /// e.g. Anthropic thinking blocks, Gemini thought parts, or the
+    /// `reasoning_content` of OpenAI-compatible servers. `text` holds only
+    /// the final answer. `None` when the provider returned no reasoning.
+    pub reasoning: Option<String>,
 }
 
-/// Token counts for a generation request.
-#[derive(Debug, Clone)]
+/// Log probability of one generated token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenLogprob {
+    /// The token text.
+    pub token: String,
+    /// Natural-log probability of the token.
+    pub logprob: f32,
+    /// The most likely tokens at this position (including, usually, the
+    /// chosen one) when [`GenerationOptions::top_logprobs`] was set. Entries
+    /// have an empty `top_logprobs` of their own.
+    pub top_logprobs: Vec<TokenLogprob>,
+}
+
+impl GenerationResult {
+    /// All text completions: `text` followed by
+    /// [`alternatives`](Self::alternatives).
+    pub fn completions(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.text.as_str()).chain(self.alternatives.iter().map(String::as_str))
+    }
+}
+
+/// Produce [`GenerationOptions::n`] completions by calling
+/// [`generate`](GeneratorModel::generate) once per completion with `n` unset.
+///
+/// For providers without native multi-completion support. Calls run one after
+/// another; usage is summed across them, and images, audio, `raw`, and
+/// `logprobs` come from the first call.
+pub async fn generate_each<G: GeneratorModel + ?Sized>(
+    model: &G,
+    messages: &[Message],
+    options: GenerationOptions,
+) -> Result<GenerationResult> {
+    let n = options.n.unwrap_or(1).max(1);
+    let options = GenerationOptions { n: None, ..options };
+    let mut merged = model.generate(messages, options.clone()).await?;
+    for _ in 1..n {
+        let next = model.generate(messages, options.clone()).await?;
+        merged.usage = match (merged.usage.take(), next.usage) {
+            (Some(mut total), Some(usage)) => {
+                total += &usage;
+                Some(total)
+            }
+            (total, usage) => total.or(usage),
+        };
+        merged.alternatives.push(next.text);
+    }
+    Ok(merged)
+}
+
+/// How many [`generate`](GeneratorModel::generate) calls the default
+/// [`GeneratorModel::generate_batch`] keeps in flight at once.
+pub const GENERATE_BATCH_CONCURRENCY: usize = 16;
+
+/// Token counts for a generation or embedding request.
+#[derive(Debug, Clone, Default)]
 pub struct TokenUsage {
     /// Number of tokens in the prompt / input.
     pub prompt_tokens: usize,
@@ -252,8 +796,60 @@ pub struct TokenUsage {
     pub completion_tokens: usize,
     /// Sum of prompt and completion tokens.
     pub total_tokens: usize,
+    /// Prompt tokens served from the provider's prompt cache. Included in
+    /// `prompt_tokens`; zero for providers without prompt caching.
+    pub cache_read_tokens: usize,
+    /// Prompt tokens written to the provider's prompt cache. Included in
+    /// `prompt_tokens`; zero for providers without prompt caching.
+    pub cache_creation_tokens: usize,
+}
+
+impl std::ops::AddAssign<&TokenUsage> for TokenUsage {
+    fn add_assign(&mut self, other: &TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+    }
+}
+
+/// Remaining quota reported by a provider's rate-limit response headers.
+///
+/// Fields the provider did not report are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitSnapshot {
+    /// Requests left in the current window.
+    pub remaining_requests: Option<u64>,
+    /// Tokens left in the current window.
+    pub remaining_tokens: Option<u64>,
+    /// When the request quota is fully replenished.
+    pub requests_reset_at: Option<std::time::SystemTime>,
+    /// When the token quota is fully replenished.
+    pub tokens_reset_at: Option<std::time::SystemTime>,
+    /// When the response carrying these headers was received.
+    pub observed_at: std::time::SystemTime,
+}
+
+/// An incremental piece of a streamed generation.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationChunk {
+    /// Text generated since the previous chunk. May be empty, e.g. on a final
+    /// chunk that only carries usage.
+    pub text: String,
+    /// Token usage for the whole generation, set on the final chunk when the
+    /// provider reports it.
+    pub usage: Option<TokenUsage>,
 }
 
+/// Receiver side of a streamed generation, as returned by
+/// [`GeneratorModel::generate_stream`].
+///
+/// Yields chunks in order and closes once generation finishes. A provider
+/// error mid-stream arrives as an `Err` item, after which the stream closes.
+/// Dropping the receiver abandons the request.
+pub type GenerationStream = tokio::sync::mpsc::Receiver<Result<GenerationChunk>>;
+
 /// A model that generates text, images, or audio from a conversational
 /// message history.
 ///
@@ -269,8 +865,71 @@ pub trait GeneratorModel: Send + Sync {
         options: GenerationOptions,
     ) -> Result<GenerationResult>;
 
+    /// Generate a text response incrementally.
+    ///
+    /// Returns once the provider has accepted the request; chunks then arrive
+    /// on the returned [`GenerationStream`]. The default calls
+    /// [`generate`](GeneratorModel::generate) and yields the whole response as
+    /// a single chunk.
+    async fn generate_stream(
+        &self,
+        messages: &[Message],
+        options: GenerationOptions,
+    ) -> Result<GenerationStream> {
+        let result = self.generate(messages, options).await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        let _ = tx.try_send(Ok(GenerationChunk {
+            text: result.text,
+            usage: result.usage,
+        }));
+        Ok(rx)
+    }
+
+    /// Generate one response per conversation in `prompts`, returning
+    /// results in the same order, for bulk offline jobs.
+    ///
+    /// The default issues a [`generate`](GeneratorModel::generate) call per
+    /// conversation, keeping up to [`GENERATE_BATCH_CONCURRENCY`] in flight.
+    /// Providers with a lower concurrency limit (see
+    /// [`provider_concurrency`](crate::runtime::ModelRuntimeBuilder::provider_concurrency))
+    /// queue the excess, and mistral.rs schedules the concurrent requests
+    /// into shared batches. A caller-supplied
+    /// [`idempotency_key`](GenerationOptions::idempotency_key) is suffixed
+    /// with `-{index}` so each conversation is a distinct request. Fails with
+    /// the first error in input order once every call has finished.
+    async fn generate_batch(
+        &self,
+        prompts: &[Vec<Message>],
+        options: GenerationOptions,
+    ) -> Result<Vec<GenerationResult>> {
+        use futures_util::StreamExt;
+
+        // The boxed futures do nothing until polled, so building them up
+        // front still leaves `buffered` in control of how many run at once.
+        let calls: Vec<_> = prompts
+            .iter()
+            .enumerate()
+            .map(|(i, messages)| {
+                let mut options = options.clone();
+                options.idempotency_key = options.idempotency_key.map(|key| format!("{key}-{i}"));
+                self.generate(messages, options)
+            })
+            .collect();
+        let results: Vec<Result<GenerationResult>> = futures_util::stream::iter(calls)
+            .buffered(GENERATE_BATCH_CONCURRENCY)
+            .collect()
+            .await;
+        results.into_iter().collect()
+    }
+
     /// Optional warmup hook. The default is a no-op.
     async fn warmup(&self) -> Result<()> {
         Ok(())
     }
+
+    /// The provider's rate-limit state as of the latest response. See
+    /// [`EmbeddingModel::rate_limits`]. The default returns `None`.
+    fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
 }
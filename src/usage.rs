@@ -0,0 +1,152 @@
+//! In-process accumulation of token usage per alias.
+//!
+//! The `metrics` counters emitted by the instrumented wrappers are exported to
+//! whatever recorder the host installs and cannot be read back in-process.
+//! [`UsageTracker`] keeps running totals that can be queried at any time via
+//! [`ModelRuntime::usage_report`](crate::runtime::ModelRuntime::usage_report),
+//! e.g. for billing.
+
+use crate::traits::TokenUsage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Price per 1,000 tokens, used to estimate spend for an alias.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostPer1k {
+    /// Cost per 1,000 prompt (input) tokens.
+    pub prompt: f64,
+    /// Cost per 1,000 completion (output) tokens.
+    pub completion: f64,
+}
+
+/// Running totals for a single alias.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AggregatedUsage {
+    /// Number of successful calls, whether or not the provider reported usage.
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// Estimated spend, present only when a [`CostPer1k`] entry exists for
+    /// the alias.
+    pub estimated_cost: Option<f64>,
+}
+
+/// Thread-safe accumulator of per-alias token usage.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    per_alias: Mutex<HashMap<String, AggregatedUsage>>,
+    cost_per_1k: HashMap<String, CostPer1k>,
+}
+
+impl UsageTracker {
+    /// Create an empty tracker with no cost table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty tracker that estimates spend using `cost_per_1k`,
+    /// keyed by alias.
+    pub fn with_cost_per_1k(cost_per_1k: HashMap<String, CostPer1k>) -> Self {
+        Self {
+            per_alias: Mutex::new(HashMap::new()),
+            cost_per_1k,
+        }
+    }
+
+    /// Record one successful call for `alias`, adding `usage` if the
+    /// provider reported it.
+    pub fn record(&self, alias: &str, usage: Option<&TokenUsage>) {
+        let mut per_alias = self.per_alias.lock().unwrap();
+        let entry = per_alias.entry(alias.to_string()).or_default();
+        entry.calls += 1;
+        if let Some(usage) = usage {
+            entry.prompt_tokens += usage.prompt_tokens as u64;
+            entry.completion_tokens += usage.completion_tokens as u64;
+            entry.total_tokens += usage.total_tokens as u64;
+            entry.cache_read_tokens += usage.cache_read_tokens as u64;
+            entry.cache_creation_tokens += usage.cache_creation_tokens as u64;
+        }
+    }
+
+    /// Snapshot of the totals accumulated so far, with estimated cost filled
+    /// in for aliases that have a cost entry.
+    pub fn report(&self) -> HashMap<String, AggregatedUsage> {
+        let per_alias = self.per_alias.lock().unwrap();
+        per_alias
+            .iter()
+            .map(|(alias, usage)| {
+                let mut usage = usage.clone();
+                usage.estimated_cost = self.cost_per_1k.get(alias).map(|cost| {
+                    (usage.prompt_tokens as f64 * cost.prompt
+                        + usage.completion_tokens as f64 * cost.completion)
+                        / 1000.0
+                });
+                (alias.clone(), usage)
+            })
+            .collect()
+    }
+
+    /// Clear all accumulated totals. The cost table is kept.
+    pub fn reset(&self) {
+        self.per_alias.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(prompt: usize, completion: usize) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accumulates_per_alias() {
+        let tracker = UsageTracker::new();
+        tracker.record("chat", Some(&usage(10, 5)));
+        tracker.record("chat", Some(&usage(1, 2)));
+        tracker.record("embed", None);
+
+        let report = tracker.report();
+        let chat = &report["chat"];
+        assert_eq!(chat.calls, 2);
+        assert_eq!(chat.prompt_tokens, 11);
+        assert_eq!(chat.completion_tokens, 7);
+        assert_eq!(chat.total_tokens, 18);
+        assert_eq!(chat.estimated_cost, None);
+        assert_eq!(report["embed"].calls, 1);
+        assert_eq!(report["embed"].total_tokens, 0);
+    }
+
+    #[test]
+    fn estimates_cost_from_table() {
+        let tracker = UsageTracker::with_cost_per_1k(HashMap::from([(
+            "chat".to_string(),
+            CostPer1k {
+                prompt: 0.5,
+                completion: 1.5,
+            },
+        )]));
+        tracker.record("chat", Some(&usage(2000, 1000)));
+
+        let cost = tracker.report()["chat"].estimated_cost.unwrap();
+        assert!((cost - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reset_clears_totals() {
+        let tracker = UsageTracker::new();
+        tracker.record("chat", Some(&usage(1, 1)));
+        tracker.reset();
+        assert!(tracker.report().is_empty());
+    }
+}
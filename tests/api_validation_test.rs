@@ -1,6 +1,13 @@
 //! Tests for ModelAliasSpec validation and ModelRuntimeKey behavior
 
-use uni_xervo::api::{ModelAliasSpec, ModelRuntimeKey, ModelTask, RetryConfig, WarmupPolicy};
+use uni_xervo::api::{
+    ModelAliasSpec, ModelRuntimeKey, ModelTask, RetryConfig, RetryableKind, WarmupPolicy,
+};
+use uni_xervo::error::{RuntimeError, TimeoutPhase};
+
+fn timeout_error() -> RuntimeError {
+    RuntimeError::timeout(TimeoutPhase::Inference, std::time::Duration::from_secs(30))
+}
 
 #[test]
 fn test_alias_validation_empty() {
@@ -14,6 +21,7 @@ fn test_alias_validation_empty() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -35,6 +43,7 @@ fn test_alias_validation_no_slash() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -61,6 +70,7 @@ fn test_alias_validation_valid() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -80,6 +90,7 @@ fn test_alias_validation_timeout_must_be_positive() {
         required: false,
         timeout: Some(0),
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -101,6 +112,7 @@ fn test_alias_validation_load_timeout_must_be_positive() {
         required: false,
         timeout: None,
         load_timeout: Some(0),
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -110,6 +122,28 @@ fn test_alias_validation_load_timeout_must_be_positive() {
     assert!(err.unwrap_err().to_string().contains("Load timeout"));
 }
 
+#[test]
+fn test_alias_validation_warmup_timeout_must_be_positive() {
+    let spec = ModelAliasSpec {
+        alias: "embed/test".to_string(),
+        task: ModelTask::Embed,
+        provider_id: "test".to_string(),
+        model_id: "model".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: Some(0),
+        retry: None,
+        options: serde_json::Value::Null,
+    };
+
+    let err = spec.validate();
+    assert!(err.is_err());
+    assert!(err.unwrap_err().to_string().contains("Warmup timeout"));
+}
+
 #[test]
 fn test_runtime_key_determinism() {
     let spec1 = ModelAliasSpec {
@@ -122,6 +156,7 @@ fn test_runtime_key_determinism() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"key": "value"}),
     };
@@ -136,6 +171,7 @@ fn test_runtime_key_determinism() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"key": "value"}),
     };
@@ -159,6 +195,7 @@ fn test_runtime_key_option_order_independence() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"a": "1", "b": "2"}),
     };
@@ -173,6 +210,7 @@ fn test_runtime_key_option_order_independence() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"b": "2", "a": "1"}), // Different order
     };
@@ -196,6 +234,7 @@ fn test_runtime_key_different_tasks() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -210,6 +249,7 @@ fn test_runtime_key_different_tasks() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -232,6 +272,7 @@ fn test_runtime_key_different_revisions() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -246,6 +287,7 @@ fn test_runtime_key_different_revisions() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -268,6 +310,7 @@ fn test_serde_roundtrip() {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"cache_dir": "/tmp"}),
     };
@@ -333,12 +376,117 @@ fn test_retry_config_backoff() {
     let config = RetryConfig {
         max_attempts: 3,
         initial_backoff_ms: 100,
+        retry_on: None,
+        max_total_duration_ms: None,
     };
     assert_eq!(config.get_backoff(1).as_millis(), 100);
     assert_eq!(config.get_backoff(2).as_millis(), 200);
     assert_eq!(config.get_backoff(3).as_millis(), 400);
 }
 
+#[test]
+fn test_retry_config_should_retry_defaults_to_is_retryable() {
+    let config = RetryConfig::default();
+    assert!(config.should_retry(&RuntimeError::RateLimited));
+    assert!(config.should_retry(&timeout_error()));
+    assert!(config.should_retry(&RuntimeError::Unavailable));
+    assert!(!config.should_retry(&RuntimeError::Unauthorized));
+    assert!(config.should_retry(&RuntimeError::api_status_error(
+        "remote/test",
+        502,
+        "bad gateway"
+    )));
+    assert!(config.should_retry(&RuntimeError::api_status_error(
+        "remote/test",
+        429,
+        "slow down"
+    )));
+    assert!(!config.should_retry(&RuntimeError::api_status_error(
+        "remote/test",
+        400,
+        "bad request"
+    )));
+}
+
+#[test]
+fn test_retry_config_retry_on_replaces_default_kinds() {
+    let config = RetryConfig {
+        retry_on: Some(vec![RetryableKind::Timeout, RetryableKind::Api5xx]),
+        ..Default::default()
+    };
+    assert!(config.should_retry(&timeout_error()));
+    assert!(!config.should_retry(&RuntimeError::RateLimited));
+    assert!(!config.should_retry(&RuntimeError::Unavailable));
+    assert!(config.should_retry(&RuntimeError::api_status_error(
+        "remote/test",
+        503,
+        "overloaded"
+    )));
+    assert!(!config.should_retry(&RuntimeError::api_status_error(
+        "remote/test",
+        400,
+        "bad request"
+    )));
+}
+
+#[test]
+fn test_retry_config_next_backoff_respects_total_duration() {
+    use std::time::Duration;
+
+    let config = RetryConfig {
+        max_attempts: 5,
+        initial_backoff_ms: 100,
+        retry_on: None,
+        max_total_duration_ms: Some(1000),
+    };
+    let err = timeout_error();
+    assert_eq!(
+        config.next_backoff(1, Duration::from_millis(500), &err),
+        Some(Duration::from_millis(100))
+    );
+    // 850ms elapsed + 200ms backoff would pass the 1s budget.
+    assert_eq!(
+        config.next_backoff(2, Duration::from_millis(850), &err),
+        None
+    );
+    assert_eq!(config.next_backoff(5, Duration::ZERO, &err), None);
+    assert_eq!(
+        config.next_backoff(1, Duration::ZERO, &RuntimeError::Unauthorized),
+        None
+    );
+
+    let unbounded = RetryConfig {
+        max_total_duration_ms: None,
+        ..config
+    };
+    assert_eq!(
+        unbounded.next_backoff(2, Duration::from_secs(60), &err),
+        Some(Duration::from_millis(200))
+    );
+}
+
+#[test]
+fn test_retry_config_retry_on_serde() {
+    let config: RetryConfig = serde_json::from_value(serde_json::json!({
+        "max_attempts": 3,
+        "initial_backoff_ms": 100,
+        "retry_on": ["timeout", "rate_limited", "unavailable", "api_5xx"]
+    }))
+    .unwrap();
+    assert_eq!(
+        config.retry_on,
+        Some(vec![
+            RetryableKind::Timeout,
+            RetryableKind::RateLimited,
+            RetryableKind::Unavailable,
+            RetryableKind::Api5xx,
+        ])
+    );
+
+    let json = serde_json::to_value(RetryConfig::default()).unwrap();
+    assert!(json.get("retry_on").is_none());
+}
+
 #[test]
 fn test_warmup_policy_display() {
     assert_eq!(WarmupPolicy::Eager.to_string(), "eager");
@@ -358,6 +506,7 @@ fn test_runtime_key_different_options() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"key": "value1"}),
     };
@@ -372,6 +521,7 @@ fn test_runtime_key_different_options() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({"key": "value2"}),
     };
@@ -394,6 +544,7 @@ fn test_runtime_key_non_object_options_distinct() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Null,
     };
@@ -424,6 +575,7 @@ fn test_runtime_key_nested_object_order_independence() {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({
             "outer": {
@@ -453,3 +605,39 @@ fn test_runtime_key_nested_object_order_independence() {
     let key3 = ModelRuntimeKey::new(&spec1);
     assert_ne!(key2, key3);
 }
+
+#[test]
+fn test_runtime_key_load_options_ignore_other_keys() {
+    let spec = |options: serde_json::Value| ModelAliasSpec {
+        alias: "embed/test".to_string(),
+        task: ModelTask::Embed,
+        provider_id: "test".to_string(),
+        model_id: "model".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    };
+    let cpu_query = spec(serde_json::json!({"device": "cpu", "prefix": "query: "}));
+    let cpu_passage = spec(serde_json::json!({"device": "cpu", "prefix": "passage: "}));
+    let gpu_query = spec(serde_json::json!({"device": "cuda:0", "prefix": "query: "}));
+
+    // Without an allowlist every option is part of the key
+    assert_ne!(
+        ModelRuntimeKey::new(&cpu_query),
+        ModelRuntimeKey::new(&cpu_passage)
+    );
+
+    let key = |spec: &ModelAliasSpec| ModelRuntimeKey::with_load_options(spec, &["device"]);
+    assert_eq!(key(&cpu_query), key(&cpu_passage));
+    assert_ne!(key(&cpu_query), key(&gpu_query));
+    // An absent listed key matches an empty options object
+    assert_eq!(
+        key(&spec(serde_json::json!({"prefix": "query: "}))),
+        key(&spec(serde_json::json!({})))
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "metrics")]
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use uni_xervo::error::RuntimeError;
+use uni_xervo::reliability::{CircuitBreakerConfig, CircuitBreakerWrapper};
+
+#[tokio::test]
+async fn test_circuit_breaker_emits_transition_metrics() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::set_global_recorder(recorder).unwrap();
+
+    let cb = CircuitBreakerWrapper::new(CircuitBreakerConfig {
+        failure_threshold: 1,
+        open_wait_seconds: 0,
+    })
+    .with_labels("remote/test", "model-a");
+
+    // Closed -> Open on the first failure, then Open -> HalfOpen -> Closed on
+    // the next (successful) probe, since the wait period is zero.
+    let _ = cb
+        .call(|| async { Err::<(), _>(RuntimeError::Unavailable) })
+        .await;
+    cb.call(|| async { Ok::<_, RuntimeError>(()) })
+        .await
+        .unwrap();
+
+    let snapshot = snapshotter.snapshot().into_vec();
+    let label = |key: &metrics::Key, name: &str| {
+        key.labels()
+            .find(|l| l.key() == name)
+            .map(|l| l.value().to_string())
+    };
+
+    let mut transitions: Vec<(String, String)> = snapshot
+        .iter()
+        .filter(|(ckey, _, _, _)| ckey.key().name() == "circuit_breaker.transitions")
+        .map(|(ckey, _, _, value)| {
+            let key = ckey.key();
+            assert_eq!(label(key, "provider").as_deref(), Some("remote/test"));
+            assert_eq!(label(key, "model").as_deref(), Some("model-a"));
+            assert_eq!(*value, DebugValue::Counter(1));
+            (label(key, "from").unwrap(), label(key, "to").unwrap())
+        })
+        .collect();
+    transitions.sort();
+    assert_eq!(
+        transitions,
+        vec![
+            ("closed".to_string(), "open".to_string()),
+            ("half_open".to_string(), "closed".to_string()),
+            ("open".to_string(), "half_open".to_string()),
+        ]
+    );
+
+    let open_durations = snapshot
+        .iter()
+        .find(|(ckey, _, _, _)| ckey.key().name() == "circuit_breaker.open_duration_seconds")
+        .map(|(_, _, _, value)| value);
+    match open_durations {
+        Some(DebugValue::Histogram(values)) => assert_eq!(values.len(), 1),
+        other => panic!("Expected one open-duration sample, got {:?}", other),
+    }
+}
@@ -0,0 +1,93 @@
+//! Tests for classifier model operations
+
+mod common;
+use common::mock_support::{
+    MockClassifierModel, MockProvider, make_spec, runtime_with_classifier, runtime_with_embed,
+};
+use uni_xervo::api::ModelTask;
+use uni_xervo::runtime::ModelRuntime;
+use uni_xervo::traits::{ClassifierModel, ModelProvider};
+
+#[tokio::test]
+async fn test_classify_scores_every_label_descending() {
+    let model = MockClassifierModel::new();
+    let scored = model
+        .classify(
+            "the match went to penalties",
+            &["sports", "politics", "cooking"],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(scored.len(), 3);
+    assert_eq!(scored[0].label, "sports");
+    assert!(scored.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[tokio::test]
+async fn test_classify_empty_labels() {
+    let model = MockClassifierModel::new();
+    assert!(model.classify("text", &[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failure_propagation() {
+    let model = MockClassifierModel::new().with_failure(true);
+    let err = model.classify("text", &["a"]).await.unwrap_err();
+    assert!(err.to_string().contains("Mock classifier failure"), "{err}");
+}
+
+#[tokio::test]
+async fn test_end_to_end_via_runtime() {
+    let runtime = runtime_with_classifier().await.unwrap();
+    let model = runtime.classifier("classify/test").await.unwrap();
+
+    let scored = model.classify("text", &["yes", "no"]).await.unwrap();
+    assert_eq!(scored.len(), 2);
+    assert_eq!(scored[0].label, "yes");
+    assert!(scored[0].score > scored[1].score);
+}
+
+#[tokio::test]
+async fn test_classifier_on_embedding_alias_is_rejected() {
+    let runtime = runtime_with_embed().await.unwrap();
+    let err = runtime.classifier("embed/test").await.err().unwrap();
+    assert!(
+        err.to_string()
+            .contains("does not implement ClassifierModel"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_classify_task_mismatch_error() {
+    let provider = MockProvider::embed_only();
+    let spec = make_spec(
+        "classify/test",
+        ModelTask::Classify,
+        "mock/embed",
+        "test-model",
+    );
+
+    let err = provider.load(&spec).await.err().unwrap();
+    assert!(err.to_string().contains("does not support task"), "{err}");
+}
+
+#[tokio::test]
+async fn test_classify_alias_loads_through_builder() {
+    let spec = make_spec(
+        "classify/test",
+        ModelTask::Classify,
+        "mock/classify",
+        "test-model",
+    );
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::classify_only())
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.classifier("classify/test").await.is_ok());
+    assert!(runtime.embedding("classify/test").await.is_err());
+}
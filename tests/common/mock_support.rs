@@ -7,9 +7,10 @@ use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
 use uni_xervo::error::{Result, RuntimeError};
 use uni_xervo::runtime::ModelRuntime;
 use uni_xervo::traits::{
-    AudioOutput, ContentBlock, EmbeddingModel, GeneratedImage, GenerationOptions, GenerationResult,
-    GeneratorModel, LoadedModelHandle, Message, ModelProvider, ProviderCapabilities,
-    ProviderHealth, RerankerModel, ScoredDoc, TokenUsage,
+    AudioOutput, ClassifierModel, ContentBlock, EmbeddingModel, GeneratedImage, GenerationOptions,
+    GenerationResult, GeneratorModel, LoadedModelHandle, Message, ModelProvider,
+    ProviderCapabilities, ProviderHealth, RerankerModel, ScoredDoc, ScoredLabel, TokenUsage,
+    generate_each,
 };
 
 pub struct MockEmbeddingModel {
@@ -20,6 +21,7 @@ pub struct MockEmbeddingModel {
     embed_delay_ms: u64,
     call_count: AtomicU32,
     warmup_count: Arc<AtomicU32>,
+    warmup_delay_ms: u64,
 }
 
 impl MockEmbeddingModel {
@@ -32,6 +34,7 @@ impl MockEmbeddingModel {
             embed_delay_ms: 0,
             call_count: AtomicU32::new(0),
             warmup_count: Arc::new(AtomicU32::new(0)),
+            warmup_delay_ms: 0,
         }
     }
 
@@ -50,6 +53,11 @@ impl MockEmbeddingModel {
         self
     }
 
+    pub fn with_warmup_delay(mut self, delay_ms: u64) -> Self {
+        self.warmup_delay_ms = delay_ms;
+        self
+    }
+
     pub fn with_failure(mut self, fail: bool) -> Self {
         self.fail_on_embed = fail;
         self
@@ -97,6 +105,9 @@ impl EmbeddingModel for MockEmbeddingModel {
     }
 
     async fn warmup(&self) -> Result<()> {
+        if self.warmup_delay_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(self.warmup_delay_ms)).await;
+        }
         self.warmup_count.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
@@ -162,6 +173,59 @@ impl RerankerModel for MockRerankerModel {
     }
 }
 
+/// Mock classifier: earlier labels score higher, and scores sum to 1.
+pub struct MockClassifierModel {
+    fail_on_classify: bool,
+    call_count: AtomicU32,
+}
+
+impl MockClassifierModel {
+    pub fn new() -> Self {
+        Self {
+            fail_on_classify: false,
+            call_count: AtomicU32::new(0),
+        }
+    }
+
+    pub fn with_failure(mut self, fail: bool) -> Self {
+        self.fail_on_classify = fail;
+        self
+    }
+
+    pub fn call_count(&self) -> u32 {
+        self.call_count.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for MockClassifierModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ClassifierModel for MockClassifierModel {
+    async fn classify(&self, _text: &str, labels: &[&str]) -> Result<Vec<ScoredLabel>> {
+        self.call_count.fetch_add(1, Ordering::SeqCst);
+
+        if self.fail_on_classify {
+            return Err(RuntimeError::InferenceError(
+                "Mock classifier failure".to_string(),
+            ));
+        }
+
+        let total: usize = (1..=labels.len()).sum();
+        Ok(labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| ScoredLabel {
+                label: label.to_string(),
+                score: (labels.len() - i) as f32 / total as f32,
+            })
+            .collect())
+    }
+}
+
 pub struct MockGeneratorModel {
     response_text: String,
     response_images: Vec<GeneratedImage>,
@@ -208,8 +272,11 @@ impl GeneratorModel for MockGeneratorModel {
     async fn generate(
         &self,
         messages: &[Message],
-        _options: GenerationOptions,
+        options: GenerationOptions,
     ) -> Result<GenerationResult> {
+        if options.n.is_some_and(|n| n > 1) {
+            return generate_each(self, messages, options).await;
+        }
         self.call_count.fetch_add(1, Ordering::SeqCst);
 
         if self.fail_on_generate {
@@ -236,9 +303,15 @@ impl GeneratorModel for MockGeneratorModel {
                 completion_tokens: self.response_text.split_whitespace().count(),
                 total_tokens: all_text.split_whitespace().count()
                     + self.response_text.split_whitespace().count(),
+                ..Default::default()
             }),
             images: self.response_images.clone(),
             audio: self.response_audio.clone(),
+            alternatives: vec![],
+            finish_reason: None,
+            raw: None,
+            logprobs: None,
+            reasoning: None,
         })
     }
 
@@ -252,13 +325,17 @@ pub struct MockProvider {
     provider_id: &'static str,
     supported_tasks: Vec<ModelTask>,
     health: ProviderHealth,
+    probe_health: Option<ProviderHealth>,
     load_count: AtomicU32,
     warmup_count: AtomicU32,
     load_delay_ms: u64,
     model_delay_ms: u64,
+    model_warmup_delay_ms: u64,
     model_fail_count: u32,
     fail_on_load: bool,
     model_warmup_tracker: Option<Arc<AtomicU32>>,
+    options_schema: Option<serde_json::Value>,
+    load_option_keys: Option<&'static [&'static str]>,
 }
 
 impl MockProvider {
@@ -267,13 +344,17 @@ impl MockProvider {
             provider_id,
             supported_tasks,
             health: ProviderHealth::Healthy,
+            probe_health: None,
             load_count: AtomicU32::new(0),
             warmup_count: AtomicU32::new(0),
             load_delay_ms: 0,
             model_delay_ms: 0,
+            model_warmup_delay_ms: 0,
             model_fail_count: 0,
             fail_on_load: false,
             model_warmup_tracker: None,
+            options_schema: None,
+            load_option_keys: None,
         }
     }
 
@@ -287,11 +368,26 @@ impl MockProvider {
         self
     }
 
+    pub fn with_model_warmup_delay(mut self, delay_ms: u64) -> Self {
+        self.model_warmup_delay_ms = delay_ms;
+        self
+    }
+
     pub fn with_model_warmup_tracker(mut self, tracker: Arc<AtomicU32>) -> Self {
         self.model_warmup_tracker = Some(tracker);
         self
     }
 
+    pub fn with_options_schema(mut self, schema: serde_json::Value) -> Self {
+        self.options_schema = Some(schema);
+        self
+    }
+
+    pub fn with_load_option_keys(mut self, keys: &'static [&'static str]) -> Self {
+        self.load_option_keys = Some(keys);
+        self
+    }
+
     pub fn embed_only() -> Self {
         Self::new("mock/embed", vec![ModelTask::Embed])
     }
@@ -304,6 +400,10 @@ impl MockProvider {
         Self::new("mock/rerank", vec![ModelTask::Rerank])
     }
 
+    pub fn classify_only() -> Self {
+        Self::new("mock/classify", vec![ModelTask::Classify])
+    }
+
     pub fn failing() -> Self {
         let mut provider = Self::new("mock/failing", vec![ModelTask::Embed]);
         provider.fail_on_load = true;
@@ -315,6 +415,12 @@ impl MockProvider {
         self
     }
 
+    /// Health reported by `probe_health`; defaults to the passive health.
+    pub fn with_probe_health(mut self, health: ProviderHealth) -> Self {
+        self.probe_health = Some(health);
+        self
+    }
+
     pub fn with_load_delay(mut self, delay_ms: u64) -> Self {
         self.load_delay_ms = delay_ms;
         self
@@ -368,6 +474,9 @@ impl ModelProvider for MockProvider {
                 if self.model_fail_count > 0 {
                     model = model.with_fail_count(self.model_fail_count);
                 }
+                if self.model_warmup_delay_ms > 0 {
+                    model = model.with_warmup_delay(self.model_warmup_delay_ms);
+                }
                 if let Some(tracker) = &self.model_warmup_tracker {
                     model = model.with_warmup_tracker(tracker.clone());
                 }
@@ -384,6 +493,10 @@ impl ModelProvider for MockProvider {
                 let handle: Arc<dyn GeneratorModel> = Arc::new(model);
                 Ok(Arc::new(handle) as LoadedModelHandle)
             }
+            ModelTask::Classify => {
+                let handle: Arc<dyn ClassifierModel> = Arc::new(MockClassifierModel::new());
+                Ok(Arc::new(handle) as LoadedModelHandle)
+            }
         }
     }
 
@@ -391,10 +504,24 @@ impl ModelProvider for MockProvider {
         self.health.clone()
     }
 
+    async fn probe_health(&self) -> ProviderHealth {
+        self.probe_health
+            .clone()
+            .unwrap_or_else(|| self.health.clone())
+    }
+
     async fn warmup(&self) -> Result<()> {
         self.warmup_count.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
+
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        self.options_schema.clone()
+    }
+
+    fn load_option_keys(&self) -> Option<&'static [&'static str]> {
+        self.load_option_keys
+    }
 }
 
 pub fn make_spec(
@@ -413,6 +540,7 @@ pub fn make_spec(
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::Value::Object(serde_json::Map::new()),
     }
@@ -460,3 +588,19 @@ pub async fn runtime_with_reranker() -> Result<Arc<ModelRuntime>> {
         .build()
         .await
 }
+
+pub async fn runtime_with_classifier() -> Result<Arc<ModelRuntime>> {
+    let provider = MockProvider::classify_only();
+    let spec = make_spec(
+        "classify/test",
+        ModelTask::Classify,
+        "mock/classify",
+        "test-model",
+    );
+
+    ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+}
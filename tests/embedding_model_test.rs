@@ -2,7 +2,7 @@
 
 mod common;
 use common::mock_support::{MockEmbeddingModel, runtime_with_embed};
-use uni_xervo::traits::EmbeddingModel;
+use uni_xervo::traits::{EmbeddingModel, QuantizedEmbedding};
 
 #[tokio::test]
 async fn test_single_text_embed() {
@@ -103,3 +103,147 @@ async fn test_end_to_end_via_runtime() {
     assert_eq!(embeddings.len(), 2);
     assert_eq!(embeddings[0].len(), 384);
 }
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    dot / (norm(a) * norm(b))
+}
+
+/// Deterministic pseudo-random vector with components in `[-1, 1)`.
+fn pseudo_random_vector(seed: u64, len: usize) -> Vec<f32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 40) as f32 / (1u64 << 24) as f32) * 2.0 - 1.0
+        })
+        .collect()
+}
+
+#[test]
+fn test_quantization_preserves_cosine_similarity() {
+    let vectors: Vec<Vec<f32>> = (0..8).map(|i| pseudo_random_vector(i, 384)).collect();
+    let restored: Vec<Vec<f32>> = vectors
+        .iter()
+        .map(|v| QuantizedEmbedding::quantize(v).dequantize())
+        .collect();
+
+    for i in 0..vectors.len() {
+        for j in 0..vectors.len() {
+            let exact = cosine(&vectors[i], &vectors[j]);
+            let approx = cosine(&restored[i], &restored[j]);
+            assert!(
+                (exact - approx).abs() < 0.01,
+                "pair ({i}, {j}): f32 {exact} vs int8 {approx}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_quantization_scales_per_vector() {
+    let quantized = QuantizedEmbedding::quantize(&[0.5, -0.3, 0.0]);
+    assert_eq!(quantized.values, vec![127, -76, 0]);
+    assert!((quantized.scale - 0.5 / 127.0).abs() < f32::EPSILON);
+
+    let zero = QuantizedEmbedding::quantize(&[0.0, 0.0]);
+    assert_eq!(zero.scale, 0.0);
+    assert_eq!(zero.dequantize(), vec![0.0, 0.0]);
+}
+
+#[tokio::test]
+async fn test_embed_quantized_unsupported_by_default() {
+    let model = MockEmbeddingModel::new(384, "test-model".to_string());
+    let err = model.embed_quantized(vec!["test"]).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("does not support quantized embeddings")
+    );
+}
+
+#[tokio::test]
+async fn test_embed_quantized_via_runtime() {
+    let runtime = runtime_with_embed().await.unwrap();
+    let model = runtime.embedding("embed/test").await.unwrap();
+
+    let quantized = model.embed_quantized(vec!["hello", "world"]).await.unwrap();
+    assert_eq!(quantized.len(), 2);
+    assert_eq!(quantized[0].values.len(), 384);
+    assert!(quantized[0].values.iter().all(|&v| v == 127));
+}
+
+#[tokio::test]
+async fn test_embed_one_returns_single_vector() {
+    let runtime = runtime_with_embed().await.unwrap();
+    let model = runtime.embedding("embed/test").await.unwrap();
+
+    let vector = model.embed_one("query").await.unwrap();
+    assert_eq!(vector.len(), 384);
+}
+
+#[tokio::test]
+async fn test_embed_one_rejects_empty_response() {
+    use async_trait::async_trait;
+    use uni_xervo::error::{Result, RuntimeError};
+
+    /// Returns no vectors, as a misbehaving backend might.
+    struct Empty;
+
+    #[async_trait]
+    impl EmbeddingModel for Empty {
+        async fn embed(&self, _texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            Ok(Vec::new())
+        }
+        fn dimensions(&self) -> u32 {
+            384
+        }
+        fn model_id(&self) -> &str {
+            "empty"
+        }
+    }
+
+    let err = Empty.embed_one("query").await.unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::InferenceError(ref msg) if msg == "empty embedding response")
+    );
+}
+
+#[tokio::test]
+async fn test_embed_stream_yields_indexed_vectors_in_order() {
+    let model = MockEmbeddingModel::new(4, "test-model".to_string());
+    let mut stream = model.embed_stream(vec!["a", "b", "c"]).await.unwrap();
+
+    let mut indices = Vec::new();
+    while let Some(item) = stream.recv().await {
+        let (index, vector) = item.unwrap();
+        assert_eq!(vector.len(), 4);
+        indices.push(index);
+    }
+    assert_eq!(indices, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn test_embed_stream_via_runtime_checks_expected_dimensions() {
+    use common::mock_support::{MockProvider, make_spec};
+    use uni_xervo::api::ModelTask;
+    use uni_xervo::runtime::ModelRuntime;
+
+    let mut spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+    spec.options = serde_json::json!({"expected_dimensions": 768});
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+    let model = runtime.embedding("embed/test").await.unwrap();
+
+    let mut stream = model.embed_stream(vec!["a", "b"]).await.unwrap();
+    let err = stream.recv().await.unwrap().unwrap_err();
+    assert!(err.to_string().contains("expected 768-dimensional"));
+    // Nothing is delivered after the failure
+    assert!(stream.recv().await.is_none());
+}
@@ -0,0 +1,386 @@
+//! Empty embedding batches and empty rerank document lists succeed without
+//! contacting the provider.
+//!
+//! Every alias uses a fake credential, so any request that did reach the API
+//! would fail the test.
+
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+use uni_xervo::runtime::ModelRuntime;
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+use uni_xervo::traits::ModelProvider;
+#[cfg(any(
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+use uni_xervo::traits::RerankOptions;
+
+/// Env var holding the fake credential each alias reads via `api_key_env`.
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+const KEY_ENV: &str = "UNI_XERVO_EMPTY_INPUT_TEST_KEY";
+
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+fn spec(
+    task: ModelTask,
+    provider_id: &str,
+    model_id: &str,
+    options: serde_json::Value,
+) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "test/empty".to_string(),
+        task,
+        provider_id: provider_id.to_string(),
+        model_id: model_id.to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+async fn runtime_for<P: ModelProvider + 'static>(
+    provider: P,
+    spec: ModelAliasSpec,
+) -> std::sync::Arc<ModelRuntime> {
+    // SAFETY: every test sets the same value, so concurrent writes agree.
+    unsafe { std::env::set_var(KEY_ENV, "test-key") };
+    ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap()
+}
+
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-azure-openai",
+    feature = "provider-gemini",
+    feature = "provider-vertexai",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+async fn assert_empty_embed_is_noop<P: ModelProvider + 'static>(provider: P, spec: ModelAliasSpec) {
+    let provider_id = spec.provider_id.clone();
+    let runtime = runtime_for(provider, spec).await;
+    let model = runtime.embedding("test/empty").await.unwrap();
+
+    let embeddings = model.embed(vec![]).await;
+    assert!(embeddings.unwrap().is_empty(), "{provider_id}");
+    let (embeddings, _) = model.embed_with_usage(vec![]).await.unwrap();
+    assert!(embeddings.is_empty(), "{provider_id}");
+}
+
+#[cfg(any(
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina",
+    feature = "provider-nim"
+))]
+async fn assert_empty_rerank_is_noop<P: ModelProvider + 'static>(
+    provider: P,
+    spec: ModelAliasSpec,
+) {
+    let provider_id = spec.provider_id.clone();
+    let runtime = runtime_for(provider, spec).await;
+    let model = runtime.reranker("test/empty").await.unwrap();
+
+    assert!(
+        model.rerank("query", &[]).await.unwrap().is_empty(),
+        "{provider_id}"
+    );
+    let options = RerankOptions {
+        top_n: Some(3),
+        return_documents: true,
+        sort: true,
+    };
+    assert!(
+        model
+            .rerank_with_options("query", &[], &options)
+            .await
+            .unwrap()
+            .is_empty(),
+        "{provider_id}"
+    );
+}
+
+#[cfg(any(
+    feature = "provider-openai",
+    feature = "provider-gemini",
+    feature = "provider-mistral",
+    feature = "provider-cohere",
+    feature = "provider-voyageai",
+    feature = "provider-jina"
+))]
+fn key_options() -> serde_json::Value {
+    serde_json::json!({ "api_key_env": KEY_ENV })
+}
+
+#[cfg(feature = "provider-openai")]
+#[tokio::test]
+async fn openai_empty_inputs() {
+    use uni_xervo::provider::RemoteOpenAIProvider;
+    assert_empty_embed_is_noop(
+        RemoteOpenAIProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/openai",
+            "text-embedding-3-small",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-azure-openai")]
+#[tokio::test]
+async fn azure_openai_empty_inputs() {
+    use uni_xervo::provider::RemoteAzureOpenAIProvider;
+    assert_empty_embed_is_noop(
+        RemoteAzureOpenAIProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/azure-openai",
+            "text-embedding-3-small",
+            serde_json::json!({ "api_key_env": KEY_ENV, "resource_name": "test-resource" }),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-gemini")]
+#[tokio::test]
+async fn gemini_empty_inputs() {
+    use uni_xervo::provider::RemoteGeminiProvider;
+    assert_empty_embed_is_noop(
+        RemoteGeminiProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/gemini",
+            "text-embedding-004",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-vertexai")]
+#[tokio::test]
+async fn vertexai_empty_inputs() {
+    use uni_xervo::provider::RemoteVertexAIProvider;
+    assert_empty_embed_is_noop(
+        RemoteVertexAIProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/vertexai",
+            "text-embedding-005",
+            serde_json::json!({ "api_token_env": KEY_ENV, "project_id": "test-project" }),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-mistral")]
+#[tokio::test]
+async fn mistral_empty_inputs() {
+    use uni_xervo::provider::RemoteMistralProvider;
+    assert_empty_embed_is_noop(
+        RemoteMistralProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/mistral",
+            "mistral-embed",
+            key_options(),
+        ),
+    )
+    .await;
+    assert_empty_rerank_is_noop(
+        RemoteMistralProvider::new(),
+        spec(
+            ModelTask::Rerank,
+            "remote/mistral",
+            "mistral-rerank-latest",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-cohere")]
+#[tokio::test]
+async fn cohere_empty_inputs() {
+    use uni_xervo::provider::RemoteCohereProvider;
+    assert_empty_embed_is_noop(
+        RemoteCohereProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/cohere",
+            "embed-english-v3.0",
+            key_options(),
+        ),
+    )
+    .await;
+    assert_empty_rerank_is_noop(
+        RemoteCohereProvider::new(),
+        spec(
+            ModelTask::Rerank,
+            "remote/cohere",
+            "rerank-english-v3.0",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-voyageai")]
+#[tokio::test]
+async fn voyageai_empty_inputs() {
+    use uni_xervo::provider::RemoteVoyageAIProvider;
+    assert_empty_embed_is_noop(
+        RemoteVoyageAIProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/voyageai",
+            "voyage-3",
+            key_options(),
+        ),
+    )
+    .await;
+    assert_empty_rerank_is_noop(
+        RemoteVoyageAIProvider::new(),
+        spec(
+            ModelTask::Rerank,
+            "remote/voyageai",
+            "rerank-2",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-jina")]
+#[tokio::test]
+async fn jina_empty_inputs() {
+    use uni_xervo::provider::RemoteJinaProvider;
+    assert_empty_embed_is_noop(
+        RemoteJinaProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/jina",
+            "jina-embeddings-v3",
+            key_options(),
+        ),
+    )
+    .await;
+    assert_empty_rerank_is_noop(
+        RemoteJinaProvider::new(),
+        spec(
+            ModelTask::Rerank,
+            "remote/jina",
+            "jina-reranker-v2-base-multilingual",
+            key_options(),
+        ),
+    )
+    .await;
+}
+
+#[cfg(feature = "provider-nim")]
+#[tokio::test]
+async fn nim_empty_inputs() {
+    use uni_xervo::provider::RemoteNimProvider;
+    // Nothing listens on this port, so any request would fail.
+    let options = serde_json::json!({ "api_key_env": KEY_ENV, "base_url": "http://127.0.0.1:9" });
+    assert_empty_embed_is_noop(
+        RemoteNimProvider::new(),
+        spec(
+            ModelTask::Embed,
+            "remote/nim",
+            "nvidia/nv-embedqa-e5-v5",
+            options.clone(),
+        ),
+    )
+    .await;
+    assert_empty_rerank_is_noop(
+        RemoteNimProvider::new(),
+        spec(
+            ModelTask::Rerank,
+            "remote/nim",
+            "nvidia/nv-rerankqa-mistral-4b-v3",
+            options,
+        ),
+    )
+    .await;
+}
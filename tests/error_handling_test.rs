@@ -1,7 +1,8 @@
 //! Tests for error variant coverage and propagation
 
+use std::time::Duration;
 use uni_xervo::api::ModelTask;
-use uni_xervo::error::RuntimeError;
+use uni_xervo::error::{RuntimeError, TimeoutPhase};
 mod common;
 use common::mock_support::{MockProvider, make_spec};
 use uni_xervo::runtime::ModelRuntime;
@@ -32,8 +33,17 @@ fn test_error_display_load() {
 
 #[test]
 fn test_error_display_api_error() {
-    let err = RuntimeError::ApiError("upstream failed".to_string());
-    assert_eq!(err.to_string(), "API error: upstream failed");
+    let err = RuntimeError::api_error("remote/openai", "upstream failed");
+    assert_eq!(err.to_string(), "API error: remote/openai: upstream failed");
+    assert_eq!(err.provider(), Some("remote/openai"));
+    assert_eq!(err.status(), None);
+
+    let err = RuntimeError::api_status_error("remote/cohere", 400, "bad input");
+    assert_eq!(
+        err.to_string(),
+        "API error: remote/cohere (HTTP 400): bad input"
+    );
+    assert_eq!(err.status(), Some(400));
 }
 
 #[test]
@@ -56,8 +66,11 @@ fn test_error_display_unauthorized() {
 
 #[test]
 fn test_error_display_timeout() {
-    let err = RuntimeError::Timeout;
-    assert_eq!(err.to_string(), "Timeout");
+    let err = RuntimeError::timeout(TimeoutPhase::Inference, Duration::from_secs(30));
+    assert_eq!(err.to_string(), "inference timed out after 30s");
+
+    let err = RuntimeError::timeout(TimeoutPhase::Load, Duration::from_millis(1500));
+    assert_eq!(err.to_string(), "load timed out after 1500ms");
 }
 
 #[test]
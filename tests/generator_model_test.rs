@@ -3,8 +3,8 @@
 mod common;
 use common::mock_support::{MockGeneratorModel, runtime_with_generator};
 use uni_xervo::traits::{
-    AudioOutput, ContentBlock, GeneratedImage, GenerationOptions, GeneratorModel, ImageInput,
-    Message,
+    AudioOutput, ContentBlock, GENERATE_BATCH_CONCURRENCY, GeneratedImage, GenerationOptions,
+    GenerationResult, GeneratorModel, ImageInput, Message,
 };
 
 #[tokio::test]
@@ -340,3 +340,207 @@ async fn test_text_generation_still_works_with_roles() {
     // "You are a helpful assistant. What is 2+2? Let me think... Please answer."
     assert!(usage.prompt_tokens > 0);
 }
+
+#[tokio::test]
+async fn test_generate_each_returns_n_completions_with_summed_usage() {
+    let response = "Mock response";
+    let prompt = "Tell me a story";
+    let model = MockGeneratorModel::new(response.to_string());
+    let result = model
+        .generate(
+            &[Message::user(prompt)],
+            GenerationOptions {
+                n: Some(3),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(model.call_count(), 3);
+    assert_eq!(result.text, "Mock response");
+    assert_eq!(result.alternatives.len(), 2);
+    assert_eq!(result.completions().count(), 3);
+    assert!(result.completions().all(|text| text == "Mock response"));
+
+    // The mock counts one token per whitespace-separated word, per call.
+    let prompt_words = prompt.split_whitespace().count();
+    let response_words = response.split_whitespace().count();
+    let usage = result.usage.unwrap();
+    assert_eq!(usage.prompt_tokens, prompt_words * 3);
+    assert_eq!(usage.completion_tokens, response_words * 3);
+    assert_eq!(usage.total_tokens, (prompt_words + response_words) * 3);
+}
+
+#[tokio::test]
+async fn test_single_completion_has_no_alternatives() {
+    let model = MockGeneratorModel::new("Response".to_string());
+    let result = model
+        .generate(
+            &[Message::user("Hi")],
+            GenerationOptions {
+                n: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(model.call_count(), 1);
+    assert!(result.alternatives.is_empty());
+    assert_eq!(result.completions().collect::<Vec<_>>(), vec!["Response"]);
+}
+
+#[tokio::test]
+async fn test_streaming_rejects_multiple_completions() {
+    let runtime = runtime_with_generator().await.unwrap();
+    let model = runtime.generator("generate/test").await.unwrap();
+
+    let err = model
+        .generate_stream(
+            &[Message::user("Hello")],
+            GenerationOptions {
+                n: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("does not support n > 1"), "{err}");
+}
+
+#[tokio::test]
+async fn test_generate_batch_returns_results_in_input_order() {
+    let model = MockGeneratorModel::new("Mock response".to_string());
+    let prompts = vec![
+        vec![Message::user("one")],
+        vec![Message::user("two words")],
+        vec![Message::user("three words here")],
+    ];
+
+    let results = model
+        .generate_batch(&prompts, GenerationOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(model.call_count(), 3);
+    let prompt_tokens: Vec<usize> = results
+        .iter()
+        .map(|r| r.usage.as_ref().unwrap().prompt_tokens)
+        .collect();
+    assert_eq!(prompt_tokens, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_generate_batch_propagates_failure() {
+    let model = MockGeneratorModel::new("Response".to_string()).with_failure(true);
+    let prompts = vec![vec![Message::user("a")], vec![Message::user("b")]];
+
+    let err = model
+        .generate_batch(&prompts, GenerationOptions::default())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Mock generator failure"));
+}
+
+/// Records the idempotency key of every call and the peak number of calls
+/// in flight at once.
+#[derive(Default)]
+struct RecordingGenerator {
+    keys: std::sync::Mutex<Vec<Option<String>>>,
+    in_flight: std::sync::atomic::AtomicUsize,
+    peak: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl GeneratorModel for RecordingGenerator {
+    async fn generate(
+        &self,
+        _messages: &[Message],
+        options: GenerationOptions,
+    ) -> uni_xervo::error::Result<GenerationResult> {
+        use std::sync::atomic::Ordering;
+
+        self.keys.lock().unwrap().push(options.idempotency_key);
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(GenerationResult {
+            text: String::new(),
+            usage: None,
+            images: vec![],
+            audio: None,
+            alternatives: vec![],
+            finish_reason: None,
+            raw: None,
+            logprobs: None,
+            reasoning: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_generate_batch_bounds_concurrency() {
+    let model = RecordingGenerator::default();
+    let prompts = vec![vec![Message::user("hi")]; GENERATE_BATCH_CONCURRENCY * 3];
+
+    let results = model
+        .generate_batch(&prompts, GenerationOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), prompts.len());
+    let peak = model.peak.load(std::sync::atomic::Ordering::SeqCst);
+    assert!(
+        peak > 1 && peak <= GENERATE_BATCH_CONCURRENCY,
+        "peak {peak}"
+    );
+}
+
+#[tokio::test]
+async fn test_generate_batch_derives_per_prompt_idempotency_keys() {
+    let model = RecordingGenerator::default();
+    let prompts = vec![vec![Message::user("a")], vec![Message::user("b")]];
+
+    model
+        .generate_batch(
+            &prompts,
+            GenerationOptions {
+                idempotency_key: Some("job-7".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let mut keys = model.keys.lock().unwrap().clone();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec![Some("job-7-0".to_string()), Some("job-7-1".to_string())]
+    );
+
+    let model = RecordingGenerator::default();
+    model
+        .generate_batch(&prompts, GenerationOptions::default())
+        .await
+        .unwrap();
+    assert!(model.keys.lock().unwrap().iter().all(Option::is_none));
+}
+
+#[tokio::test]
+async fn test_generate_batch_via_runtime() {
+    let runtime = runtime_with_generator().await.unwrap();
+    let model = runtime.generator("generate/test").await.unwrap();
+
+    let results = model
+        .generate_batch(
+            &[vec![Message::user("Hello")], vec![Message::user("World")]],
+            GenerationOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.text == "Mock response"));
+}
@@ -18,6 +18,7 @@ async fn test_runtime_candle_embed() -> anyhow::Result<()> {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({}),
     }];
@@ -56,6 +57,7 @@ async fn test_warmup_policies() -> anyhow::Result<()> {
         required: true,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options: serde_json::json!({}),
     }];
@@ -0,0 +1,61 @@
+#![cfg(feature = "metrics")]
+
+mod common;
+use common::mock_support::{MockProvider, make_spec};
+use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+use std::time::Duration;
+use uni_xervo::api::ModelTask;
+use uni_xervo::runtime::ModelRuntime;
+
+fn waiters(snapshotter: &Snapshotter) -> Option<f64> {
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .find(|(ckey, _, _, _)| {
+            let key = ckey.key();
+            key.name() == "model_load.waiters"
+                && key
+                    .labels()
+                    .any(|l| l.key() == "alias" && l.value() == "embed/cold")
+        })
+        .map(|(_, _, _, value)| match value {
+            DebugValue::Gauge(value) => value.into_inner(),
+            other => panic!("Expected a gauge, got {:?}", other),
+        })
+}
+
+#[tokio::test]
+async fn test_callers_blocked_on_a_cold_load_are_counted() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::set_global_recorder(recorder).unwrap();
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only().with_load_delay(300))
+        .catalog(vec![make_spec(
+            "embed/cold",
+            ModelTask::Embed,
+            "mock/embed",
+            "cold-model",
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let runtime = runtime.clone();
+            tokio::spawn(async move { runtime.embedding("embed/cold").await.map(|_| ()) })
+        })
+        .collect();
+
+    // One caller loads; the other three wait behind it.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(waiters(&snapshotter), Some(3.0));
+
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    assert!(matches!(waiters(&snapshotter), None | Some(0.0)));
+}
@@ -0,0 +1,206 @@
+//! Tests for validating spec options against a provider's published schema.
+
+use serde_json::json;
+use uni_xervo::api::{ModelAliasSpec, ModelTask};
+mod common;
+use common::mock_support::{MockProvider, make_spec};
+use uni_xervo::runtime::ModelRuntime;
+use uni_xervo::traits::ModelProvider;
+
+fn schema_provider() -> MockProvider {
+    MockProvider::embed_only().with_options_schema(json!({
+        "type": "object",
+        "additionalProperties": false,
+        "required": ["region"],
+        "properties": {
+            "region": { "type": "string", "enum": ["us", "eu"] },
+            "batch_size": { "type": "integer", "minimum": 1, "maximum": 256 },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "proxy": { "type": "string" },
+            "proxy_token": { "type": "string" }
+        },
+        "dependentRequired": { "proxy_token": ["proxy"] }
+    }))
+}
+
+fn spec(options: serde_json::Value) -> ModelAliasSpec {
+    let mut spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+    spec.options = options;
+    spec
+}
+
+async fn build_error(options: serde_json::Value) -> String {
+    ModelRuntime::builder()
+        .register_provider(schema_provider())
+        .catalog(vec![spec(options)])
+        .build()
+        .await
+        .err()
+        .expect("build should fail")
+        .to_string()
+}
+
+#[tokio::test]
+async fn build_accepts_options_matching_schema() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(schema_provider())
+        .catalog(vec![spec(json!({
+            "region": "eu",
+            "batch_size": 32,
+            "tags": ["a", "b"],
+            "expected_dimensions": 384
+        }))])
+        .build()
+        .await;
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn build_rejects_options_violating_schema() {
+    assert!(
+        build_error(json!({ "region": "us", "colour": "red" }))
+            .await
+            .contains("Unknown option 'colour'")
+    );
+    assert!(
+        build_error(json!({}))
+            .await
+            .contains("Missing required option 'region'")
+    );
+    assert!(
+        build_error(json!({ "region": "ap" }))
+            .await
+            .contains("must be one of: us, eu")
+    );
+    assert!(
+        build_error(json!({ "region": "us", "batch_size": "8" }))
+            .await
+            .contains("must be an integer")
+    );
+    assert!(
+        build_error(json!({ "region": "us", "batch_size": 512 }))
+            .await
+            .contains("must be at most 256")
+    );
+    assert!(
+        build_error(json!({ "region": "us", "tags": ["a", 1] }))
+            .await
+            .contains("Option 'tags[1]'")
+    );
+    assert!(
+        build_error(json!({ "region": "us", "proxy_token": "t" }))
+            .await
+            .contains("requires 'proxy'")
+    );
+}
+
+#[tokio::test]
+async fn register_validates_against_schema() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(schema_provider())
+        .build()
+        .await
+        .unwrap();
+
+    let err = runtime
+        .register(spec(json!({ "region": 1 })))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("must be a string"));
+
+    runtime
+        .register(spec(json!({ "region": "us" })))
+        .await
+        .unwrap();
+    assert!(runtime.contains_alias("embed/test").await);
+}
+
+#[tokio::test]
+async fn provider_without_schema_accepts_any_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![spec(json!({ "anything": [1, 2, 3] }))])
+        .build()
+        .await;
+    assert!(runtime.is_ok());
+}
+
+#[test]
+fn builtin_schemas_are_valid_json_objects() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas/provider-options");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let schema: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(schema["type"], "object", "{}", path.display());
+        assert_eq!(schema["additionalProperties"], false, "{}", path.display());
+    }
+}
+
+/// Built-in providers treat every option of their own as load-relevant, so
+/// only runtime-level options may differ between aliases sharing a model.
+fn assert_load_keys_match_schema(provider: &dyn ModelProvider) {
+    const RUNTIME_KEYS: &[&str] = &[
+        "expected_dimensions",
+        "max_input_chars",
+        "on_overlong",
+        "dedup_documents",
+    ];
+    let id = provider.provider_id();
+    let schema = provider.options_schema().expect(id);
+    let mut expected: Vec<&str> = schema["properties"]
+        .as_object()
+        .expect(id)
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !RUNTIME_KEYS.contains(key))
+        .collect();
+    expected.sort_unstable();
+    let mut actual = provider.load_option_keys().expect(id).to_vec();
+    actual.sort_unstable();
+    assert_eq!(actual, expected, "{id}");
+}
+
+#[test]
+fn builtin_load_option_keys_cover_every_provider_option() {
+    #[allow(unused_mut)]
+    let mut providers: Vec<Box<dyn ModelProvider>> = Vec::new();
+    #[cfg(feature = "provider-candle")]
+    providers.push(Box::new(uni_xervo::provider::LocalCandleProvider::new()));
+    #[cfg(feature = "provider-fastembed")]
+    providers.push(Box::new(uni_xervo::provider::LocalFastEmbedProvider::new()));
+    #[cfg(feature = "provider-mistralrs")]
+    providers.push(Box::new(uni_xervo::provider::LocalMistralRsProvider::new()));
+    #[cfg(feature = "provider-onnx")]
+    providers.push(Box::new(uni_xervo::provider::LocalOnnxProvider::new()));
+    #[cfg(feature = "provider-openai")]
+    providers.push(Box::new(uni_xervo::provider::RemoteOpenAIProvider::new()));
+    #[cfg(feature = "provider-gemini")]
+    providers.push(Box::new(uni_xervo::provider::RemoteGeminiProvider::new()));
+    #[cfg(feature = "provider-vertexai")]
+    providers.push(Box::new(uni_xervo::provider::RemoteVertexAIProvider::new()));
+    #[cfg(feature = "provider-mistral")]
+    providers.push(Box::new(uni_xervo::provider::RemoteMistralProvider::new()));
+    #[cfg(feature = "provider-anthropic")]
+    providers.push(Box::new(uni_xervo::provider::RemoteAnthropicProvider::new()));
+    #[cfg(feature = "provider-voyageai")]
+    providers.push(Box::new(uni_xervo::provider::RemoteVoyageAIProvider::new()));
+    #[cfg(feature = "provider-cohere")]
+    providers.push(Box::new(uni_xervo::provider::RemoteCohereProvider::new()));
+    #[cfg(feature = "provider-azure-openai")]
+    providers.push(Box::new(
+        uni_xervo::provider::RemoteAzureOpenAIProvider::new(),
+    ));
+    #[cfg(feature = "provider-jina")]
+    providers.push(Box::new(uni_xervo::provider::RemoteJinaProvider::new()));
+    #[cfg(feature = "provider-nim")]
+    providers.push(Box::new(uni_xervo::provider::RemoteNimProvider::new()));
+    #[cfg(feature = "provider-replicate")]
+    providers.push(Box::new(uni_xervo::provider::RemoteReplicateProvider::new()));
+    #[cfg(feature = "testing")]
+    providers.push(Box::new(uni_xervo::provider::NullEmbeddingProvider::new()));
+
+    for provider in &providers {
+        assert_load_keys_match_schema(provider.as_ref());
+    }
+}
@@ -15,6 +15,7 @@ fn anthropic_spec(options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -100,3 +101,96 @@ async fn builder_accepts_null_anthropic_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_rejects_non_bool_anthropic_cache_flag() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(
+            serde_json::json!({"cache_system_prompt": "yes"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must be a boolean")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_anthropic_cache_flags() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(serde_json::json!({
+            "cache_system_prompt": true,
+            "cache_last_message": false
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_accepts_anthropic_base_url() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(serde_json::json!({
+            "base_url": "https://llm-proxy.internal/anthropic",
+            "anthropic_version": "2023-06-01"
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_anthropic_base_url_without_scheme() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(
+            serde_json::json!({"base_url": "llm-proxy.internal"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must start with http:// or https://")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_anthropic_user_id() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(
+            serde_json::json!({"user_id": "5f0c6a1e"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_non_string_anthropic_user_id() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAnthropicProvider::new())
+        .catalog(vec![anthropic_spec(serde_json::json!({"user_id": 42}))])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("user_id"), "{err}");
+}
@@ -15,6 +15,7 @@ fn azure_spec(options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -89,6 +90,20 @@ async fn builder_accepts_valid_azure_options() {
     assert!(runtime.is_ok());
 }
 
+#[tokio::test]
+async fn builder_accepts_azure_base_url_without_resource_name() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAzureOpenAIProvider::new())
+        .catalog(vec![azure_spec(serde_json::json!({
+            "base_url": "https://llm-proxy.internal/azure",
+            "api_version": "2024-10-21"
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
 #[tokio::test]
 async fn builder_accepts_null_azure_options() {
     let runtime = ModelRuntime::builder()
@@ -99,3 +114,33 @@ async fn builder_accepts_null_azure_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_accepts_azure_base64_encoding_format() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAzureOpenAIProvider::new())
+        .catalog(vec![azure_spec(serde_json::json!({
+            "resource_name": "my-resource",
+            "encoding_format": "base64"
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_azure_encoding_format() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteAzureOpenAIProvider::new())
+        .catalog(vec![azure_spec(serde_json::json!({
+            "resource_name": "my-resource",
+            "encoding_format": "int8"
+        }))])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("encoding_format"));
+    assert!(err.contains("float, base64"));
+}
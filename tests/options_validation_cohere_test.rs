@@ -15,6 +15,7 @@ fn cohere_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -113,3 +114,79 @@ async fn builder_accepts_null_cohere_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_accepts_cohere_embedding_type() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteCohereProvider::new())
+        .catalog(vec![cohere_spec(
+            ModelTask::Embed,
+            serde_json::json!({"embedding_type": "int8"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_cohere_embedding_type() {
+    for (task, options, expected) in [
+        (
+            ModelTask::Embed,
+            serde_json::json!({"embedding_type": "uint4"}),
+            "must be one of: float, int8, binary, ubinary",
+        ),
+        (
+            ModelTask::Rerank,
+            serde_json::json!({"embedding_type": "int8"}),
+            "only valid for embed tasks",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteCohereProvider::new())
+            .catalog(vec![cohere_spec(task, options)])
+            .build()
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_validates_cohere_truncation() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteCohereProvider::new())
+        .catalog(vec![cohere_spec(
+            ModelTask::Embed,
+            serde_json::json!({"truncation": "start"}),
+        )])
+        .build()
+        .await;
+    assert!(runtime.is_ok());
+
+    for (task, options, expected) in [
+        (
+            ModelTask::Embed,
+            serde_json::json!({"truncation": "middle"}),
+            "must be one of: none, start, end",
+        ),
+        (
+            ModelTask::Rerank,
+            serde_json::json!({"truncation": "end"}),
+            "only valid for embed tasks",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteCohereProvider::new())
+            .catalog(vec![cohere_spec(task, options)])
+            .build()
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+}
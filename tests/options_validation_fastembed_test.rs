@@ -0,0 +1,120 @@
+#![cfg(feature = "provider-fastembed")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::LocalFastEmbedProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn fastembed_spec(options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "embed/default".to_string(),
+        task: ModelTask::Embed,
+        provider_id: "local/fastembed".to_string(),
+        model_id: "BGESmallENV15".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+async fn build_with(options: serde_json::Value) -> uni_xervo::error::Result<()> {
+    ModelRuntime::builder()
+        .register_provider(LocalFastEmbedProvider::new())
+        .catalog(vec![fastembed_spec(options)])
+        .build()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn builder_accepts_model_path_with_dimensions() {
+    let result = build_with(serde_json::json!({
+        "model_path": "/models/custom",
+        "dimensions": 384,
+        "pooling": "cls"
+    }))
+    .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_model_path_without_dimensions() {
+    let err = build_with(serde_json::json!({"model_path": "/models/custom"}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'dimensions'"));
+}
+
+#[tokio::test]
+async fn builder_rejects_dimensions_without_model_path() {
+    let err = build_with(serde_json::json!({"dimensions": 384}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("only valid with 'model_path'"));
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_pooling() {
+    let err = build_with(serde_json::json!({
+        "model_path": "/models/custom",
+        "dimensions": 384,
+        "pooling": "max"
+    }))
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("must be one of"));
+}
+
+#[test]
+fn supported_models_lists_builtin_names() {
+    let models = LocalFastEmbedProvider::supported_models();
+    assert!(models.contains(&"BGESmallENV15"));
+    assert!(models.contains(&"all-MiniLM-L6-v2"));
+}
+
+fn fastembed_rerank_spec(options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "rerank/default".to_string(),
+        task: ModelTask::Rerank,
+        model_id: "bge-reranker-base".to_string(),
+        ..fastembed_spec(options)
+    }
+}
+
+#[tokio::test]
+async fn builder_accepts_rerank_alias_with_cache_dir() {
+    let result = ModelRuntime::builder()
+        .register_provider(LocalFastEmbedProvider::new())
+        .catalog(vec![fastembed_rerank_spec(
+            serde_json::json!({"cache_dir": "/tmp/rerankers"}),
+        )])
+        .build()
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_model_path_for_rerank_alias() {
+    let err = ModelRuntime::builder()
+        .register_provider(LocalFastEmbedProvider::new())
+        .catalog(vec![fastembed_rerank_spec(serde_json::json!({
+            "model_path": "/models/custom",
+            "dimensions": 384
+        }))])
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("not supported for rerank models"));
+}
+
+#[test]
+fn supported_rerank_models_lists_builtin_names() {
+    let models = LocalFastEmbedProvider::supported_rerank_models();
+    assert!(models.contains(&"bge-reranker-base"));
+    assert!(models.contains(&"jina-reranker-v1-turbo-en"));
+}
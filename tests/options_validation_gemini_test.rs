@@ -0,0 +1,117 @@
+#![cfg(feature = "provider-gemini")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::RemoteGeminiProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn gemini_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "embed/gemini".to_string(),
+        task,
+        provider_id: "remote/gemini".to_string(),
+        model_id: "text-embedding-004".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+async fn build_error(spec: ModelAliasSpec) -> String {
+    ModelRuntime::builder()
+        .register_provider(RemoteGeminiProvider::new())
+        .catalog(vec![spec])
+        .build()
+        .await
+        .err()
+        .expect("build should fail")
+        .to_string()
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_gemini_task_type() {
+    let err = build_error(gemini_spec(
+        ModelTask::Embed,
+        serde_json::json!({"task_type": "RETRIEVAL"}),
+    ))
+    .await;
+    assert!(err.contains("must be one of"));
+}
+
+#[tokio::test]
+async fn builder_rejects_gemini_task_type_for_generate() {
+    let err = build_error(gemini_spec(
+        ModelTask::Generate,
+        serde_json::json!({"task_type": "RETRIEVAL_QUERY"}),
+    ))
+    .await;
+    assert!(err.contains("only valid for embed tasks"));
+}
+
+#[tokio::test]
+async fn builder_rejects_gemini_title_without_document_task_type() {
+    let err = build_error(gemini_spec(
+        ModelTask::Embed,
+        serde_json::json!({"task_type": "RETRIEVAL_QUERY", "title": "Doc"}),
+    ))
+    .await;
+    assert!(err.contains("requires task_type RETRIEVAL_DOCUMENT"));
+}
+
+#[tokio::test]
+async fn builder_accepts_valid_gemini_embed_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteGeminiProvider::new())
+        .catalog(vec![gemini_spec(
+            ModelTask::Embed,
+            serde_json::json!({
+                "api_key_env": "MY_GEMINI_KEY",
+                "task_type": "RETRIEVAL_DOCUMENT",
+                "title": "Quarterly report"
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_accepts_gemini_safety_settings_for_generate() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteGeminiProvider::new())
+        .catalog(vec![gemini_spec(
+            ModelTask::Generate,
+            serde_json::json!({
+                "safety_settings": [
+                    {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_ONLY_HIGH"}
+                ],
+                "response_mime_type": "application/json"
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_malformed_gemini_safety_settings() {
+    let err = build_error(gemini_spec(
+        ModelTask::Generate,
+        serde_json::json!({"safety_settings": [{"category": "HARM_CATEGORY_HARASSMENT"}]}),
+    ))
+    .await;
+    assert!(err.contains("safety_settings"));
+
+    let err = build_error(gemini_spec(
+        ModelTask::Embed,
+        serde_json::json!({"response_mime_type": "application/json"}),
+    ))
+    .await;
+    assert!(err.contains("only valid for generate tasks"));
+}
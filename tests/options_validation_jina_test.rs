@@ -0,0 +1,124 @@
+#![cfg(feature = "provider-jina")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::RemoteJinaProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn jina_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "test/default".to_string(),
+        task,
+        provider_id: "remote/jina".to_string(),
+        model_id: "jina-embeddings-v3".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_jina_option_key() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteJinaProvider::new())
+        .catalog(vec![jina_spec(
+            ModelTask::Embed,
+            serde_json::json!({"unknown": true}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Unknown option")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_valid_jina_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteJinaProvider::new())
+        .catalog(vec![jina_spec(
+            ModelTask::Embed,
+            serde_json::json!({
+                "api_key_env": "MY_JINA_KEY",
+                "task": "retrieval.passage",
+                "dimensions": 256
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_accepts_null_jina_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteJinaProvider::new())
+        .catalog(vec![jina_spec(ModelTask::Rerank, serde_json::Value::Null)])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_jina_embedding_options() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"task": "retrieval"}),
+            "must be one of: retrieval.query, retrieval.passage",
+        ),
+        (serde_json::json!({"task": 1}), "must be a string"),
+        (
+            serde_json::json!({"dimensions": 0}),
+            "must be greater than 0",
+        ),
+        (
+            serde_json::json!({"dimensions": "256"}),
+            "must be a positive integer",
+        ),
+        (
+            serde_json::json!({"truncation": "start"}),
+            "must be one of: none, end",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteJinaProvider::new())
+            .catalog(vec![jina_spec(ModelTask::Embed, options)])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_jina_embed_options_for_rerank() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteJinaProvider::new())
+        .catalog(vec![jina_spec(
+            ModelTask::Rerank,
+            serde_json::json!({"dimensions": 512}),
+        )])
+        .build()
+        .await;
+
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("only valid for embed tasks")
+    );
+}
@@ -15,6 +15,7 @@ fn mistral_spec(options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -79,3 +80,58 @@ async fn builder_accepts_null_mistral_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_accepts_mistral_base_url() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteMistralProvider::new())
+        .catalog(vec![mistral_spec(serde_json::json!({
+            "base_url": "https://llm-proxy.internal/mistral/"
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_mistral_base_url_without_scheme() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteMistralProvider::new())
+        .catalog(vec![mistral_spec(
+            serde_json::json!({"base_url": "llm-proxy.internal"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must start with http:// or https://")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_only_mistral_truncation_none() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteMistralProvider::new())
+        .catalog(vec![mistral_spec(
+            serde_json::json!({"truncation": "none"}),
+        )])
+        .build()
+        .await;
+    assert!(runtime.is_ok());
+
+    let err = ModelRuntime::builder()
+        .register_provider(RemoteMistralProvider::new())
+        .catalog(vec![mistral_spec(serde_json::json!({"truncation": "end"}))])
+        .build()
+        .await
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("must be one of: none"), "{err}");
+}
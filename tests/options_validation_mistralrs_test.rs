@@ -1,7 +1,7 @@
 #![cfg(feature = "provider-mistralrs")]
 
 use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
-use uni_xervo::provider::LocalMistralRsProvider;
+use uni_xervo::provider::{LocalMistralRsProvider, MistralRsOptions};
 use uni_xervo::runtime::ModelRuntime;
 
 fn mistralrs_spec(options: serde_json::Value) -> ModelAliasSpec {
@@ -19,6 +19,7 @@ fn mistralrs_spec_with_task(task: ModelTask, options: serde_json::Value) -> Mode
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -319,3 +320,94 @@ async fn builder_rejects_non_bool_force_cpu_for_diffusion_pipeline() {
             .contains("must be a boolean")
     );
 }
+
+#[tokio::test]
+async fn builder_rejects_hf_endpoint_without_scheme() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalMistralRsProvider::new())
+        .catalog(vec![mistralrs_spec(
+            serde_json::json!({"hf_endpoint": "hf-mirror.com"}),
+        )])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("hf_endpoint"));
+}
+
+#[tokio::test]
+async fn builder_accepts_paged_attention_tuning() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalMistralRsProvider::new())
+        .catalog(vec![mistralrs_spec_with_task(
+            ModelTask::Generate,
+            serde_json::json!({
+                "paged_attention": true,
+                "paged_attn_block_size": 32,
+                "kv_cache_mem_mb": 4096
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_paged_attention_tuning_without_paged_attention() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalMistralRsProvider::new())
+        .catalog(vec![mistralrs_spec_with_task(
+            ModelTask::Generate,
+            serde_json::json!({"kv_cache_mem_mb": 4096}),
+        )])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("requires 'paged_attention'"));
+}
+
+#[tokio::test]
+async fn builder_rejects_zero_paged_attn_block_size() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalMistralRsProvider::new())
+        .catalog(vec![mistralrs_spec_with_task(
+            ModelTask::Generate,
+            serde_json::json!({"paged_attention": true, "paged_attn_block_size": 0}),
+        )])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("paged_attn_block_size"));
+}
+
+#[tokio::test]
+async fn builder_accepts_typed_options() {
+    let options = MistralRsOptions::new()
+        .with_paged_attention(true)
+        .with_paged_attn_block_size(32)
+        .with_dtype("bf16")
+        .with_gguf_files(["model-q4.gguf"]);
+    assert_eq!(
+        serde_json::Value::from(&options),
+        serde_json::json!({
+            "paged_attention": true,
+            "paged_attn_block_size": 32,
+            "dtype": "bf16",
+            "gguf_files": ["model-q4.gguf"],
+        })
+    );
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalMistralRsProvider::new())
+        .catalog(vec![mistralrs_spec_with_task(
+            ModelTask::Generate,
+            (&options).into(),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "provider-nim")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::RemoteNimProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn nim_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "test/default".to_string(),
+        task,
+        provider_id: "remote/nim".to_string(),
+        model_id: "nvidia/nv-embedqa-e5-v5".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_nim_option_key() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteNimProvider::new())
+        .catalog(vec![nim_spec(
+            ModelTask::Embed,
+            serde_json::json!({"unknown": true}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Unknown option")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_valid_nim_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteNimProvider::new())
+        .catalog(vec![nim_spec(
+            ModelTask::Embed,
+            serde_json::json!({
+                "base_url": "http://gpu-node-1:8000",
+                "api_key_env": "MY_NIM_KEY",
+                "input_type": "passage",
+                "truncate": "END",
+                "embedding_dimensions": 1024
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_accepts_null_nim_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteNimProvider::new())
+        .catalog(vec![nim_spec(ModelTask::Generate, serde_json::Value::Null)])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_nim_options() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"input_type": "document"}),
+            "must be one of: query, passage",
+        ),
+        (
+            serde_json::json!({"truncate": "end"}),
+            "must be one of: NONE, START, END",
+        ),
+        (
+            serde_json::json!({"truncation": "END"}),
+            "must be one of: none, start, end",
+        ),
+        (
+            serde_json::json!({"truncate": "END", "truncation": "end"}),
+            "mutually exclusive",
+        ),
+        (
+            serde_json::json!({"base_url": "gpu-node-1:8000"}),
+            "must start with http:// or https://",
+        ),
+        (serde_json::json!({"base_url": 8000}), "must be a string"),
+        (
+            serde_json::json!({"embedding_dimensions": 0}),
+            "must be greater than 0",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteNimProvider::new())
+            .catalog(vec![nim_spec(ModelTask::Embed, options)])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_scopes_nim_options_to_their_tasks() {
+    for (task, options, expected) in [
+        (
+            ModelTask::Rerank,
+            serde_json::json!({"input_type": "query"}),
+            "only valid for embed tasks",
+        ),
+        (
+            ModelTask::Generate,
+            serde_json::json!({"truncate": "END"}),
+            "only valid for embed and rerank tasks",
+        ),
+        (
+            ModelTask::Generate,
+            serde_json::json!({"truncation": "end"}),
+            "only valid for embed and rerank tasks",
+        ),
+        (
+            ModelTask::Generate,
+            serde_json::json!({"embedding_dimensions": 1024}),
+            "only valid for embed tasks",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteNimProvider::new())
+            .catalog(vec![nim_spec(task, options)])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
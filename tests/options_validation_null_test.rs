@@ -0,0 +1,72 @@
+#![cfg(feature = "testing")]
+
+use serde_json::json;
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::{NullEmbeddingOptions, NullEmbeddingProvider};
+use uni_xervo::runtime::ModelRuntime;
+
+fn null_spec(options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "embed/null".to_string(),
+        task: ModelTask::Embed,
+        provider_id: "test/null".to_string(),
+        model_id: "null".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+async fn build_with(options: serde_json::Value) -> uni_xervo::error::Result<()> {
+    ModelRuntime::builder()
+        .register_provider(NullEmbeddingProvider::new())
+        .catalog(vec![null_spec(options)])
+        .build()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn null_accepts_dimensions_and_fill() {
+    assert!(
+        build_with(json!({ "dimensions": 16, "fill": "hash" }))
+            .await
+            .is_ok()
+    );
+    assert!(build_with(serde_json::Value::Null).await.is_ok());
+}
+
+#[tokio::test]
+async fn null_rejects_bad_options() {
+    assert!(build_with(json!({ "dimensions": 0 })).await.is_err());
+    assert!(build_with(json!({ "fill": "random" })).await.is_err());
+    assert!(build_with(json!({ "model_path": "/tmp" })).await.is_err());
+}
+
+#[tokio::test]
+async fn null_embeds_through_runtime() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(NullEmbeddingProvider::new())
+        .catalog(vec![null_spec(json!({ "dimensions": 4 }))])
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/null").await.unwrap();
+    assert_eq!(model.embed_one("hello").await.unwrap(), vec![0.0; 4]);
+}
+
+#[tokio::test]
+async fn null_accepts_typed_options() {
+    let options = NullEmbeddingOptions::new().with_dimensions(8);
+    assert_eq!(
+        serde_json::Value::from(&options),
+        json!({ "dimensions": 8 })
+    );
+    assert!(build_with((&options).into()).await.is_ok());
+}
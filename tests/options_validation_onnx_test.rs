@@ -0,0 +1,97 @@
+#![cfg(feature = "provider-onnx")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::LocalOnnxProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn onnx_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "test/default".to_string(),
+        task,
+        provider_id: "local/onnx".to_string(),
+        model_id: "/models/bge-reranker-base".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+async fn build_with(task: ModelTask, options: serde_json::Value) -> uni_xervo::error::Result<()> {
+    ModelRuntime::builder()
+        .register_provider(LocalOnnxProvider::new())
+        .catalog(vec![onnx_spec(task, options)])
+        .build()
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn builder_accepts_valid_onnx_options() {
+    let embed = build_with(
+        ModelTask::Embed,
+        serde_json::json!({
+            "model_path": "/models/bge-small",
+            "device": "cuda:1",
+            "max_length": 256,
+            "pooling": "cls",
+            "embedding_dimensions": 384
+        }),
+    )
+    .await;
+    assert!(embed.is_ok());
+
+    let rerank = build_with(ModelTask::Rerank, serde_json::json!({"device": "CPU"})).await;
+    assert!(rerank.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_onnx_options() {
+    for (task, options, expected) in [
+        (
+            ModelTask::Embed,
+            serde_json::json!({"cache_dir": "/tmp"}),
+            "Unknown option",
+        ),
+        (
+            ModelTask::Embed,
+            serde_json::json!({"device": "metal"}),
+            "must be one of: cpu, cuda, cuda:N",
+        ),
+        (
+            ModelTask::Embed,
+            serde_json::json!({"pooling": "max"}),
+            "must be one of: mean, cls",
+        ),
+        (
+            ModelTask::Embed,
+            serde_json::json!({"max_length": 0}),
+            "must be greater than 0",
+        ),
+        (
+            ModelTask::Rerank,
+            serde_json::json!({"pooling": "mean"}),
+            "only valid for embed tasks",
+        ),
+        (
+            ModelTask::Rerank,
+            serde_json::json!({"embedding_dimensions": 384}),
+            "only valid for embed tasks",
+        ),
+    ] {
+        let err = build_with(task, options).await.unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_generate_alias() {
+    let err = build_with(ModelTask::Generate, serde_json::Value::Null)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not support"), "{err}");
+}
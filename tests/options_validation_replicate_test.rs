@@ -0,0 +1,109 @@
+#![cfg(feature = "provider-replicate")]
+
+use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
+use uni_xervo::provider::RemoteReplicateProvider;
+use uni_xervo::runtime::ModelRuntime;
+
+fn replicate_spec(model_id: &str, options: serde_json::Value) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: "test/default".to_string(),
+        task: ModelTask::Generate,
+        provider_id: "remote/replicate".to_string(),
+        model_id: model_id.to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: None,
+        options,
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_unknown_replicate_option_key() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteReplicateProvider::new())
+        .catalog(vec![replicate_spec(
+            "meta/meta-llama-3-8b-instruct",
+            serde_json::json!({"unknown": true}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Unknown option")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_valid_replicate_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteReplicateProvider::new())
+        .catalog(vec![replicate_spec(
+            "meta/meta-llama-3-8b-instruct",
+            serde_json::json!({
+                "api_key_env": "MY_REPLICATE_TOKEN",
+                "poll_interval_ms": 250,
+                "input": { "min_tokens": 1, "presence_penalty": 0.5 }
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_replicate_options() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"poll_interval_ms": 0}),
+            "must be greater than 0",
+        ),
+        (
+            serde_json::json!({"input": "min_tokens=1"}),
+            "must be a JSON object",
+        ),
+        (serde_json::json!({"api_key_env": 1}), "must be a string"),
+        (
+            serde_json::json!({"base_url": "api.replicate.com"}),
+            "must start with http:// or https://",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteReplicateProvider::new())
+            .catalog(vec![replicate_spec(
+                "meta/meta-llama-3-8b-instruct",
+                options,
+            )])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn replicate_rejects_malformed_model_id_on_load() {
+    unsafe { std::env::set_var("REPLICATE_TEST_TOKEN", "test-key") };
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteReplicateProvider::new())
+        .catalog(vec![replicate_spec(
+            "meta-llama-3-8b-instruct",
+            serde_json::json!({"api_key_env": "REPLICATE_TEST_TOKEN"}),
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let err = runtime.generator("test/default").await.err().unwrap();
+    assert!(err.to_string().contains("must be 'owner/name'"), "{err}");
+}
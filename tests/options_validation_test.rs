@@ -1,7 +1,7 @@
 #![cfg(feature = "provider-candle")]
 
 use uni_xervo::api::{ModelAliasSpec, ModelTask, WarmupPolicy};
-use uni_xervo::provider::LocalCandleProvider;
+use uni_xervo::provider::{CandleOptions, LocalCandleProvider};
 use uni_xervo::runtime::ModelRuntime;
 
 fn candle_spec(options: serde_json::Value) -> ModelAliasSpec {
@@ -15,6 +15,7 @@ fn candle_spec(options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -84,3 +85,240 @@ async fn register_rejects_unknown_candle_option_key() {
     assert!(err.is_err());
     assert!(err.unwrap_err().to_string().contains("Unknown option"));
 }
+
+#[tokio::test]
+async fn builder_accepts_valid_candle_device() {
+    for device in ["cpu", "cuda", "cuda:1", "metal"] {
+        let runtime = ModelRuntime::builder()
+            .register_provider(LocalCandleProvider::new())
+            .catalog(vec![candle_spec(serde_json::json!({"device": device}))])
+            .build()
+            .await;
+        assert!(runtime.is_ok(), "device '{}' should be accepted", device);
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_candle_device() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(serde_json::json!({"device": "cuda:x"}))])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must be one of")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_runtime_expected_dimensions_option() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"expected_dimensions": 384}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_zero_expected_dimensions() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"expected_dimensions": 0}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must be greater than 0")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_runtime_input_limit_options() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"max_input_chars": 2000, "on_overlong": "truncate"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_on_overlong() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"max_input_chars": 10, "on_overlong": "drop"}),
+            "must be one of",
+        ),
+        (
+            serde_json::json!({"on_overlong": "truncate"}),
+            "requires 'max_input_chars'",
+        ),
+        (
+            serde_json::json!({"max_input_chars": 0}),
+            "must be greater than 0",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(LocalCandleProvider::new())
+            .catalog(vec![candle_spec(options)])
+            .build()
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_dedup_documents_outside_rerank() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"dedup_documents": true}),
+            "only valid for rerank tasks",
+        ),
+        (
+            serde_json::json!({"dedup_documents": "yes"}),
+            "must be a boolean",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(LocalCandleProvider::new())
+            .catalog(vec![candle_spec(options)])
+            .build()
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_accepts_candle_hf_endpoint() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"hf_endpoint": "https://hf-mirror.com"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_candle_hf_endpoint_without_scheme() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"hf_endpoint": "hf-mirror.com"}),
+        )])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("hf_endpoint"));
+    assert!(err.contains("http://"));
+}
+
+#[tokio::test]
+async fn builder_accepts_candle_model_path() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(
+            serde_json::json!({"model_path": "/models/minilm"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_non_string_candle_model_path() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(serde_json::json!({"model_path": 42}))])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("model_path"), "{err}");
+}
+
+#[tokio::test]
+async fn builder_accepts_candle_max_batch_size() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(serde_json::json!({"max_batch_size": 32}))])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_candle_zero_max_batch_size() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(serde_json::json!({"max_batch_size": 0}))])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(err.contains("max_batch_size"));
+    assert!(err.contains("greater than 0"));
+}
+
+#[tokio::test]
+async fn builder_rejects_candle_max_batch_size_for_classify() {
+    let mut spec = candle_spec(serde_json::json!({"max_batch_size": 8}));
+    spec.task = ModelTask::Classify;
+    spec.model_id = "org/nli-model".to_string();
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![spec])
+        .build()
+        .await;
+
+    let err = runtime.err().unwrap().to_string();
+    assert!(
+        err.contains("'max_batch_size' is only valid for embed tasks"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_typed_candle_options() {
+    let options = CandleOptions::new()
+        .with_device("cpu")
+        .with_max_batch_size(16);
+    let runtime = ModelRuntime::builder()
+        .register_provider(LocalCandleProvider::new())
+        .catalog(vec![candle_spec(serde_json::to_value(&options).unwrap())])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
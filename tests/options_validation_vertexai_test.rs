@@ -15,6 +15,7 @@ fn vertex_embed_spec(options: serde_json::Value) -> ModelAliasSpec {
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -119,3 +120,67 @@ async fn builder_accepts_valid_vertex_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_rejects_multiple_vertex_auth_sources() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteVertexAIProvider::new())
+        .catalog(vec![vertex_embed_spec(serde_json::json!({
+            "api_token_env": "VERTEX_AI_TOKEN",
+            "credentials_path": "/etc/gcp/sa.json"
+        }))])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("mutually exclusive")
+    );
+}
+
+#[tokio::test]
+async fn builder_rejects_non_bool_vertex_use_adc() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteVertexAIProvider::new())
+        .catalog(vec![vertex_embed_spec(
+            serde_json::json!({"use_adc": "true"}),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_err());
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("must be a boolean")
+    );
+}
+
+#[tokio::test]
+async fn builder_accepts_vertex_safety_settings_for_generate() {
+    let mut spec = vertex_embed_spec(serde_json::json!({
+        "api_token_env": "VERTEX_AI_TOKEN",
+        "project_id": "demo-project",
+        "safety_settings": [
+            {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "threshold": "BLOCK_NONE"}
+        ],
+        "response_mime_type": "application/json"
+    }));
+    spec.alias = "generate/default".to_string();
+    spec.task = ModelTask::Generate;
+    spec.model_id = "gemini-1.5-flash".to_string();
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteVertexAIProvider::new())
+        .catalog(vec![spec])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
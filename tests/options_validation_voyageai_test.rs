@@ -15,6 +15,7 @@ fn voyageai_spec(task: ModelTask, options: serde_json::Value) -> ModelAliasSpec
         required: false,
         timeout: None,
         load_timeout: None,
+        warmup_timeout: None,
         retry: None,
         options,
     }
@@ -89,3 +90,77 @@ async fn builder_accepts_null_voyageai_options() {
 
     assert!(runtime.is_ok());
 }
+
+#[tokio::test]
+async fn builder_accepts_voyageai_embedding_knobs() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteVoyageAIProvider::new())
+        .catalog(vec![voyageai_spec(
+            ModelTask::Embed,
+            serde_json::json!({
+                "input_type": "document",
+                "truncation": false,
+                "output_dimension": 512,
+                "output_dtype": "int8"
+            }),
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_voyageai_embedding_knobs() {
+    for (options, expected) in [
+        (
+            serde_json::json!({"input_type": "search_query"}),
+            "must be one of: query, document",
+        ),
+        (
+            serde_json::json!({"truncation": "yes"}),
+            "must be one of: none, end",
+        ),
+        (
+            serde_json::json!({"truncation": "start"}),
+            "must be one of: none, end",
+        ),
+        (
+            serde_json::json!({"output_dimension": 300}),
+            "must be one of: 256, 512, 1024, 2048",
+        ),
+        (
+            serde_json::json!({"output_dtype": "float16"}),
+            "must be one of: float, int8",
+        ),
+    ] {
+        let err = ModelRuntime::builder()
+            .register_provider(RemoteVoyageAIProvider::new())
+            .catalog(vec![voyageai_spec(ModelTask::Embed, options)])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_voyageai_embed_options_for_rerank() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(RemoteVoyageAIProvider::new())
+        .catalog(vec![voyageai_spec(
+            ModelTask::Rerank,
+            serde_json::json!({"input_type": "query"}),
+        )])
+        .build()
+        .await;
+
+    assert!(
+        runtime
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("only valid for embed tasks")
+    );
+}
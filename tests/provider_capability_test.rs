@@ -3,6 +3,7 @@
 use uni_xervo::api::ModelTask;
 mod common;
 use common::mock_support::{MockProvider, make_spec};
+use uni_xervo::runtime::ModelRuntime;
 use uni_xervo::traits::{ModelProvider, ProviderHealth};
 
 #[tokio::test]
@@ -51,6 +52,55 @@ async fn test_provider_health_unhealthy() {
     }
 }
 
+#[tokio::test]
+async fn test_runtime_health_uses_probes_when_requested() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(
+            MockProvider::embed_only()
+                .with_probe_health(ProviderHealth::Unhealthy("unreachable".to_string())),
+        )
+        .register_provider(MockProvider::generate_only())
+        .build()
+        .await
+        .unwrap();
+
+    let passive = runtime.health(false).await;
+    assert_eq!(passive.len(), 2);
+    assert!(
+        passive
+            .values()
+            .all(|h| matches!(h, ProviderHealth::Healthy))
+    );
+
+    let probed = runtime.health(true).await;
+    match &probed["mock/embed"] {
+        ProviderHealth::Unhealthy(msg) => assert_eq!(msg, "unreachable"),
+        other => panic!("Expected Unhealthy status, got {other:?}"),
+    }
+    assert!(matches!(probed["mock/generate"], ProviderHealth::Healthy));
+}
+
+#[tokio::test]
+async fn test_runtime_reports_provider_capabilities() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .register_provider(MockProvider::new(
+            "mock/multi",
+            vec![ModelTask::Rerank, ModelTask::Generate],
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    let caps = runtime.provider_capabilities();
+    assert_eq!(caps.len(), 2);
+    assert_eq!(caps["mock/embed"].supported_tasks, vec![ModelTask::Embed]);
+    assert_eq!(
+        caps["mock/multi"].supported_tasks,
+        vec![ModelTask::Rerank, ModelTask::Generate]
+    );
+}
+
 #[tokio::test]
 async fn test_task_mismatch_error() {
     let provider = MockProvider::embed_only(); // Only supports Embed
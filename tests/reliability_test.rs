@@ -1,5 +1,5 @@
-use uni_xervo::api::{ModelAliasSpec, ModelTask, RetryConfig, WarmupPolicy};
-use uni_xervo::error::RuntimeError;
+use uni_xervo::api::{ModelAliasSpec, ModelTask, RetryConfig, RetryableKind, WarmupPolicy};
+use uni_xervo::error::{RuntimeError, TimeoutPhase};
 use uni_xervo::runtime::ModelRuntime;
 mod common;
 use common::mock_support::MockProvider;
@@ -20,6 +20,7 @@ async fn test_instrumented_embedding_timeout_enforced() {
             required: false,
             timeout: Some(1), // 1 second timeout
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }])
@@ -34,7 +35,10 @@ async fn test_instrumented_embedding_timeout_enforced() {
 
     assert!(res.is_err());
     match res.unwrap_err() {
-        RuntimeError::Timeout => (),
+        RuntimeError::Timeout { phase, limit } => {
+            assert_eq!(phase, TimeoutPhase::Inference);
+            assert_eq!(limit, std::time::Duration::from_secs(1));
+        }
         e => panic!("Expected Timeout error, got: {}", e),
     }
 
@@ -43,8 +47,10 @@ async fn test_instrumented_embedding_timeout_enforced() {
     assert!(elapsed.as_secs() < 2);
 }
 
+#[cfg(feature = "metrics")]
 use metrics_util::debugging::DebuggingRecorder;
 
+#[cfg(feature = "metrics")]
 #[tokio::test]
 async fn test_instrumented_embedding_metrics() {
     let recorder = DebuggingRecorder::new();
@@ -64,6 +70,7 @@ async fn test_instrumented_embedding_metrics() {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }])
@@ -107,9 +114,12 @@ async fn test_instrumented_embedding_retry_success() {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: Some(RetryConfig {
                 max_attempts: 3,
                 initial_backoff_ms: 10,
+                retry_on: None,
+                max_total_duration_ms: None,
             }),
             options: serde_json::Value::Null,
         }])
@@ -143,9 +153,12 @@ async fn test_instrumented_embedding_retry_failure() {
             required: false,
             timeout: None,
             load_timeout: None,
+            warmup_timeout: None,
             retry: Some(RetryConfig {
                 max_attempts: 3,
                 initial_backoff_ms: 10,
+                retry_on: None,
+                max_total_duration_ms: None,
             }),
             options: serde_json::Value::Null,
         }])
@@ -163,6 +176,111 @@ async fn test_instrumented_embedding_retry_failure() {
     }
 }
 
+fn retry_on_spec(alias: &str, retry_on: Vec<RetryableKind>) -> ModelAliasSpec {
+    ModelAliasSpec {
+        alias: alias.to_string(),
+        task: ModelTask::Embed,
+        provider_id: "mock/embed".to_string(),
+        model_id: "test-model".to_string(),
+        revision: None,
+        warmup: WarmupPolicy::Lazy,
+        required: false,
+        timeout: None,
+        load_timeout: None,
+        warmup_timeout: None,
+        retry: Some(RetryConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+            retry_on: Some(retry_on),
+            max_total_duration_ms: None,
+        }),
+        options: serde_json::Value::Null,
+    }
+}
+
+#[tokio::test]
+async fn test_retry_on_skips_kinds_not_listed() {
+    let provider = MockProvider::embed_only().with_model_fail_count(1);
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![retry_on_spec(
+            "embed/retry-timeouts-only",
+            vec![RetryableKind::Timeout],
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime
+        .embedding("embed/retry-timeouts-only")
+        .await
+        .unwrap();
+
+    // The mock fails once with RateLimited, which is retryable by default but
+    // not in the allow-list, so the first failure is returned.
+    match model.embed(vec!["hello"]).await {
+        Err(RuntimeError::RateLimited) => (),
+        other => panic!("Expected RateLimited error, got: {:?}", other),
+    }
+    assert!(model.embed(vec!["hello"]).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_retry_on_retries_listed_kinds() {
+    let provider = MockProvider::embed_only().with_model_fail_count(2);
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![retry_on_spec(
+            "embed/retry-rate-limits",
+            vec![RetryableKind::RateLimited],
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/retry-rate-limits").await.unwrap();
+    assert!(model.embed(vec!["hello"]).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_retry_stops_at_max_total_duration() {
+    // Every attempt fails with RateLimited after 100ms.
+    let provider = MockProvider::embed_only()
+        .with_model_delay(100)
+        .with_model_fail_count(u32::MAX);
+
+    let mut spec = retry_on_spec("embed/retry-budget", vec![RetryableKind::RateLimited]);
+    spec.retry = Some(RetryConfig {
+        max_attempts: 10,
+        initial_backoff_ms: 10,
+        retry_on: None,
+        max_total_duration_ms: Some(250),
+    });
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/retry-budget").await.unwrap();
+
+    // Ten attempts would take over 3.5s with backoff; the budget allows three.
+    let start = std::time::Instant::now();
+    match model.embed(vec!["hello"]).await {
+        Err(RuntimeError::RateLimited) => (),
+        other => panic!("Expected RateLimited error, got: {:?}", other),
+    }
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed < std::time::Duration::from_millis(1000),
+        "retries ran past the budget: {:?}",
+        elapsed
+    );
+}
+
 #[tokio::test]
 async fn test_instrumented_embedding_success_within_timeout() {
     let provider = MockProvider::embed_only().with_model_delay(500);
@@ -179,6 +297,7 @@ async fn test_instrumented_embedding_success_within_timeout() {
             required: false,
             timeout: Some(2), // 2 second timeout
             load_timeout: None,
+            warmup_timeout: None,
             retry: None,
             options: serde_json::Value::Null,
         }])
@@ -191,3 +310,25 @@ async fn test_instrumented_embedding_success_within_timeout() {
     let res = model.embed(vec!["hello"]).await;
     assert!(res.is_ok());
 }
+
+#[tokio::test]
+async fn test_instrumentation_disabled_returns_bare_model() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![common::mock_support::make_spec(
+            "embed/bare",
+            ModelTask::Embed,
+            "mock/embed",
+            "test-model",
+        )])
+        .instrumentation(false)
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/bare").await.unwrap();
+    model.embed(vec!["hello"]).await.unwrap();
+
+    // Usage is recorded by the instrumentation layer, so nothing is tracked.
+    assert!(runtime.usage_report().is_empty());
+}
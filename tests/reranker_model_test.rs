@@ -2,7 +2,7 @@
 
 mod common;
 use common::mock_support::{MockRerankerModel, runtime_with_reranker};
-use uni_xervo::traits::RerankerModel;
+use uni_xervo::traits::{RerankOptions, RerankerModel};
 
 #[tokio::test]
 async fn test_rerank_returns_scored_docs() {
@@ -112,3 +112,36 @@ async fn test_end_to_end_via_runtime() {
     assert_eq!(scored.len(), 2);
     assert!(scored[0].score > scored[1].score);
 }
+
+#[tokio::test]
+async fn test_runtime_text_follows_return_documents() {
+    // The mock echoes every document; runtime handles only keep the text
+    // when it is requested.
+    let runtime = runtime_with_reranker().await.unwrap();
+    let model = runtime.reranker("rerank/test").await.unwrap();
+    let docs = vec!["document 1", "document 2"];
+
+    let plain = model.rerank("q", &docs).await.unwrap();
+    assert!(plain.iter().all(|d| d.text.is_none()));
+
+    let without = model
+        .rerank_with_options("q", &docs, &RerankOptions::default())
+        .await
+        .unwrap();
+    assert!(without.iter().all(|d| d.text.is_none()));
+
+    let with = model
+        .rerank_with_options(
+            "q",
+            &docs,
+            &RerankOptions {
+                return_documents: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    for doc in &with {
+        assert_eq!(doc.text.as_deref(), Some(docs[doc.index]));
+    }
+}
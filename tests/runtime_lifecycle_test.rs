@@ -106,6 +106,126 @@ async fn test_runtime_register_duplicate_alias_rejected() {
     assert!(err.unwrap_err().to_string().contains("already exists"));
 }
 
+#[tokio::test]
+async fn test_reload_catalog_applies_diff_and_unloads_changed_models() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![
+            make_spec("embed/a", ModelTask::Embed, "mock/embed", "model-a"),
+            make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b"),
+            make_spec("embed/c", ModelTask::Embed, "mock/embed", "model-c"),
+        ])
+        .build()
+        .await
+        .unwrap();
+    runtime.prefetch_all().await.unwrap();
+
+    let changes = runtime
+        .reload_catalog(vec![
+            make_spec("embed/a", ModelTask::Embed, "mock/embed", "model-a"),
+            make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b2"),
+            make_spec("embed/d", ModelTask::Embed, "mock/embed", "model-d"),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(changes.added, vec!["embed/d"]);
+    assert_eq!(changes.updated, vec!["embed/b"]);
+    assert_eq!(changes.removed, vec!["embed/c"]);
+
+    // Unchanged aliases stay loaded; changed ones load their new model lazily.
+    assert!(runtime.is_loaded("embed/a").await);
+    assert!(!runtime.is_loaded("embed/b").await);
+    assert!(!runtime.contains_alias("embed/c").await);
+    assert!(runtime.embedding("embed/c").await.is_err());
+    let model = runtime.embedding("embed/b").await.unwrap();
+    assert_eq!(model.model_id(), "model-b2");
+    assert!(runtime.embedding("embed/d").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_reload_catalog_is_all_or_nothing() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![make_spec(
+            "embed/a",
+            ModelTask::Embed,
+            "mock/embed",
+            "model-a",
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let err = runtime
+        .reload_catalog(vec![
+            make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b"),
+            make_spec("embed/c", ModelTask::Embed, "mock/missing", "model-c"),
+        ])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unknown provider"));
+
+    assert!(runtime.contains_alias("embed/a").await);
+    assert!(!runtime.contains_alias("embed/b").await);
+}
+
+#[tokio::test]
+async fn test_reload_catalog_refused_while_shutting_down() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![make_spec(
+            "embed/a",
+            ModelTask::Embed,
+            "mock/embed",
+            "model-a",
+        )])
+        .build()
+        .await
+        .unwrap();
+    runtime.shutdown().await;
+
+    let err = runtime
+        .reload_catalog(vec![make_spec(
+            "embed/b",
+            ModelTask::Embed,
+            "mock/embed",
+            "model-b",
+        )])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, uni_xervo::error::RuntimeError::ShuttingDown));
+    assert!(runtime.contains_alias("embed/a").await);
+    assert!(!runtime.contains_alias("embed/b").await);
+}
+
+#[tokio::test]
+async fn test_reload_catalog_from_file() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![])
+        .build()
+        .await
+        .unwrap();
+
+    let path = std::env::temp_dir().join(format!(
+        "uni-xervo-reload-catalog-{}.json",
+        std::process::id()
+    ));
+    let specs = vec![make_spec(
+        "embed/file",
+        ModelTask::Embed,
+        "mock/embed",
+        "model-file",
+    )];
+    std::fs::write(&path, serde_json::to_string(&specs).unwrap()).unwrap();
+    let changes = runtime.reload_catalog_from_file(&path).await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(changes.unwrap().added, vec!["embed/file"]);
+    assert!(runtime.contains_alias("embed/file").await);
+}
+
 #[tokio::test]
 async fn test_runtime_register_unknown_provider_rejected() {
     let provider = MockProvider::embed_only();
@@ -146,11 +266,61 @@ async fn test_load_timeout_applies_to_provider_load() {
 
     assert!(matches!(
         result,
-        Err(uni_xervo::error::RuntimeError::Timeout)
+        Err(uni_xervo::error::RuntimeError::Timeout {
+            phase: uni_xervo::error::TimeoutPhase::Load,
+            ..
+        })
     ));
     assert!(elapsed.as_secs() < 2);
 }
 
+#[tokio::test]
+async fn test_warmup_timeout_bounds_model_warmup() {
+    let provider = MockProvider::embed_only().with_model_warmup_delay(3_000);
+    let mut spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+    spec.load_timeout = Some(10);
+    spec.warmup_timeout = Some(1);
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let result = runtime.embedding("embed/test").await;
+    let elapsed = start.elapsed();
+
+    assert!(matches!(
+        result,
+        Err(uni_xervo::error::RuntimeError::Timeout {
+            phase: uni_xervo::error::TimeoutPhase::Warmup,
+            ..
+        })
+    ));
+    assert!(elapsed.as_secs() < 3);
+}
+
+#[tokio::test]
+async fn test_warmup_does_not_count_against_load_timeout() {
+    // Load and warmup each fit in one second, but not both together.
+    let provider = MockProvider::embed_only()
+        .with_load_delay(700)
+        .with_model_warmup_delay(700);
+    let mut spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+    spec.load_timeout = Some(1);
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.embedding("embed/test").await.is_ok());
+}
+
 #[tokio::test]
 async fn test_runtime_contains_alias() {
     let provider = MockProvider::embed_only();
@@ -167,6 +337,30 @@ async fn test_runtime_contains_alias() {
     assert!(!runtime.contains_alias("embed/other").await);
 }
 
+#[tokio::test]
+async fn test_is_loaded_reflects_registry_without_loading() {
+    let provider = MockProvider::embed_only();
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(!runtime.is_loaded("embed/test").await);
+    // Asking twice must not have loaded it in between.
+    assert!(!runtime.is_loaded("embed/test").await);
+    assert!(!runtime.is_loaded("embed/other").await);
+
+    runtime.warmup("embed/test").await.unwrap();
+    assert!(runtime.is_loaded("embed/test").await);
+
+    runtime.shutdown().await;
+    assert!(!runtime.is_loaded("embed/test").await);
+}
+
 #[tokio::test]
 async fn test_resolve_embedding_model() {
     let provider = MockProvider::embed_only();
@@ -371,3 +565,837 @@ async fn test_prefetch_unknown_alias_errors() {
     assert!(err.is_err());
     assert!(err.unwrap_err().to_string().contains("not found"));
 }
+
+#[tokio::test]
+async fn test_prefetch_report_attempts_every_alias() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .register_provider(MockProvider::failing())
+        .catalog(vec![
+            make_spec("embed/a", ModelTask::Embed, "mock/embed", "model-a"),
+            make_spec("embed/broken", ModelTask::Embed, "mock/failing", "model-x"),
+            make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b"),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    let report = runtime
+        .prefetch_report(&["embed/a", "embed/broken", "embed/nonexistent", "embed/b"])
+        .await;
+
+    assert_eq!(report.len(), 4);
+    assert!(report["embed/a"].is_ok());
+    assert!(report["embed/broken"].is_err());
+    let missing = report["embed/nonexistent"].as_ref().unwrap_err();
+    assert!(missing.to_string().contains("not found"));
+    // Failures before it did not stop embed/b from loading.
+    assert!(report["embed/b"].is_ok());
+    assert!(runtime.is_loaded("embed/b").await);
+}
+
+#[tokio::test]
+async fn test_warmup_loads_one_alias_once() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let loads = Arc::new(AtomicU32::new(0));
+    let provider = MockProvider::embed_only().with_model_warmup_tracker(loads.clone());
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![
+            make_spec("embed/a", ModelTask::Embed, "mock/embed", "model-a"),
+            make_spec("embed/b", ModelTask::Embed, "mock/embed", "model-b"),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    runtime.warmup("embed/a").await.unwrap();
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+    // Already resident: no second load, and the handle is served from cache.
+    runtime.warmup("embed/a").await.unwrap();
+    assert!(runtime.embedding("embed/a").await.is_ok());
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_load_option_keys_share_instances_across_other_options() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let loads = Arc::new(AtomicU32::new(0));
+    let provider = MockProvider::embed_only()
+        .with_load_option_keys(&["device"])
+        .with_model_warmup_tracker(loads.clone());
+    let mut a = make_spec("embed/a", ModelTask::Embed, "mock/embed", "model");
+    a.options = serde_json::json!({"device": "cpu", "prefix": "query: "});
+    let mut b = make_spec("embed/b", ModelTask::Embed, "mock/embed", "model");
+    b.options = serde_json::json!({"device": "cpu", "prefix": "passage: "});
+    let mut c = make_spec("embed/c", ModelTask::Embed, "mock/embed", "model");
+    c.options = serde_json::json!({"device": "cuda:0", "prefix": "query: "});
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![a, b, c])
+        .build()
+        .await
+        .unwrap();
+
+    runtime.warmup("embed/a").await.unwrap();
+    assert!(runtime.is_loaded("embed/b").await);
+    assert!(!runtime.is_loaded("embed/c").await);
+
+    runtime.warmup("embed/b").await.unwrap();
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+    runtime.warmup("embed/c").await.unwrap();
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+/// Aliases of a built-in provider that differ only in runtime-level options
+/// share one loaded instance.
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn test_builtin_provider_shares_instance_across_runtime_options() {
+    use uni_xervo::provider::NullEmbeddingProvider;
+
+    let spec = |alias: &str, options: serde_json::Value| {
+        let mut spec = make_spec(alias, ModelTask::Embed, "test/null", "null");
+        spec.options = options;
+        spec
+    };
+    let runtime = ModelRuntime::builder()
+        .register_provider(NullEmbeddingProvider::new())
+        .catalog(vec![
+            spec("embed/a", serde_json::json!({"dimensions": 8})),
+            spec(
+                "embed/b",
+                serde_json::json!({"dimensions": 8, "max_input_chars": 100}),
+            ),
+            spec("embed/c", serde_json::json!({"dimensions": 16})),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    runtime.warmup("embed/a").await.unwrap();
+    assert!(runtime.is_loaded("embed/b").await);
+    assert!(!runtime.is_loaded("embed/c").await);
+}
+
+#[tokio::test]
+async fn test_warmup_unknown_alias_errors() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![make_spec(
+            "embed/a",
+            ModelTask::Embed,
+            "mock/embed",
+            "model-a",
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    let err = runtime.warmup("embed/missing").await.unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_download_progress_callback_reaches_provider() {
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use uni_xervo::api::ModelAliasSpec;
+    use uni_xervo::error::{Result, RuntimeError};
+    use uni_xervo::traits::{
+        DownloadProgress, DownloadProgressFn, LoadedModelHandle, ModelProvider,
+        ProviderCapabilities, ProviderHealth,
+    };
+
+    /// Reports one finished download per load through the installed callback.
+    #[derive(Default)]
+    struct Downloader(Mutex<Option<DownloadProgressFn>>);
+
+    #[async_trait]
+    impl ModelProvider for Downloader {
+        fn provider_id(&self) -> &'static str {
+            "downloader"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                supported_tasks: vec![ModelTask::Embed],
+            }
+        }
+        async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+            if let Some(callback) = self.0.lock().unwrap().as_ref() {
+                callback(&DownloadProgress {
+                    provider_id: "downloader",
+                    model_id: spec.model_id.clone(),
+                    file: "model.safetensors".to_string(),
+                    downloaded: 10,
+                    total: Some(10),
+                });
+            }
+            Err(RuntimeError::Load("nothing to load".to_string()))
+        }
+        async fn health(&self) -> ProviderHealth {
+            ProviderHealth::Healthy
+        }
+        fn set_download_progress(&self, callback: DownloadProgressFn) {
+            *self.0.lock().unwrap() = Some(callback);
+        }
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let runtime = ModelRuntime::builder()
+        .register_provider(Downloader::default())
+        .catalog(vec![make_spec(
+            "embed/test",
+            ModelTask::Embed,
+            "downloader",
+            "org/model",
+        )])
+        .on_download_progress(move |p| sink.lock().unwrap().push(p.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.embedding("embed/test").await.is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].model_id, "org/model");
+    assert_eq!(events[0].file, "model.safetensors");
+    assert_eq!(events[0].downloaded, 10);
+}
+
+#[tokio::test]
+async fn test_register_providers_accepts_a_list() {
+    use uni_xervo::traits::ModelProvider;
+
+    let providers: Vec<Box<dyn ModelProvider>> = vec![
+        Box::new(MockProvider::embed_only()),
+        Box::new(MockProvider::generate_only()),
+    ];
+    let runtime = ModelRuntime::builder()
+        .register_providers(providers)
+        .catalog(vec![
+            make_spec("embed/test", ModelTask::Embed, "mock/embed", "embed-model"),
+            make_spec(
+                "generate/test",
+                ModelTask::Generate,
+                "mock/generate",
+                "gen-model",
+            ),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.embedding("embed/test").await.is_ok());
+    assert!(runtime.generator("generate/test").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_register_providers_later_duplicate_replaces_earlier() {
+    use uni_xervo::traits::ModelProvider;
+
+    let providers: Vec<Box<dyn ModelProvider>> = vec![
+        Box::new(MockProvider::new("mock/embed", vec![ModelTask::Generate])),
+        Box::new(MockProvider::embed_only()),
+    ];
+    let runtime = ModelRuntime::builder()
+        .register_providers(providers)
+        .catalog(vec![make_spec(
+            "embed/test",
+            ModelTask::Embed,
+            "mock/embed",
+            "embed-model",
+        )])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.embedding("embed/test").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_register_provider_checked_rejects_duplicate_id() {
+    let builder = ModelRuntime::builder()
+        .register_provider_checked(MockProvider::embed_only())
+        .unwrap();
+    let Err(err) = builder.register_provider_checked(MockProvider::embed_only()) else {
+        panic!("duplicate provider registration should fail");
+    };
+
+    assert!(matches!(err, uni_xervo::error::RuntimeError::Config(_)));
+    assert!(err.to_string().contains("mock/embed"));
+}
+
+#[tokio::test]
+async fn test_register_provider_checked_accepts_distinct_ids() {
+    let runtime = ModelRuntime::builder()
+        .register_provider_checked(MockProvider::embed_only())
+        .unwrap()
+        .register_provider_checked(MockProvider::generate_only())
+        .unwrap()
+        .catalog(vec![make_spec(
+            "embed/test",
+            ModelTask::Embed,
+            "mock/embed",
+            "embed-model",
+        )])
+        .build()
+        .await;
+
+    assert!(runtime.is_ok());
+}
+
+#[tokio::test]
+async fn test_add_catalog_appends_to_base_catalog() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![make_spec(
+            "embed/base",
+            ModelTask::Embed,
+            "mock/embed",
+            "base-model",
+        )])
+        .add_catalog(vec![make_spec(
+            "embed/local",
+            ModelTask::Embed,
+            "mock/embed",
+            "local-model",
+        )])
+        .add_spec(make_spec(
+            "embed/extra",
+            ModelTask::Embed,
+            "mock/embed",
+            "extra-model",
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.contains_alias("embed/base").await);
+    assert!(runtime.contains_alias("embed/local").await);
+    assert!(runtime.contains_alias("embed/extra").await);
+}
+
+#[tokio::test]
+async fn test_add_catalog_from_str_appends() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .add_spec(make_spec(
+            "embed/base",
+            ModelTask::Embed,
+            "mock/embed",
+            "base-model",
+        ))
+        .add_catalog_from_str(
+            r#"[{"alias": "embed/local", "task": "embed", "provider_id": "mock/embed", "model_id": "local-model"}]"#,
+        )
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.contains_alias("embed/base").await);
+    assert!(runtime.contains_alias("embed/local").await);
+}
+
+#[tokio::test]
+async fn test_add_catalog_duplicate_alias_fails_build() {
+    let result = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![make_spec(
+            "embed/shared",
+            ModelTask::Embed,
+            "mock/embed",
+            "base-model",
+        )])
+        .add_spec(make_spec(
+            "embed/shared",
+            ModelTask::Embed,
+            "mock/embed",
+            "override-model",
+        ))
+        .build()
+        .await;
+
+    let err = result.err().expect("duplicate alias should fail build");
+    assert!(err.to_string().contains("Duplicate alias"));
+}
+
+fn tenant_catalog() -> Vec<uni_xervo::api::ModelAliasSpec> {
+    vec![
+        make_spec("embed/tenant", ModelTask::Embed, "mock/a", "model-a"),
+        make_spec("embed/tenant", ModelTask::Embed, "mock/b", "model-b"),
+        make_spec("embed/only-a", ModelTask::Embed, "mock/a", "model-a"),
+    ]
+}
+
+#[tokio::test]
+async fn test_provider_scoped_aliases_resolve_per_provider() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::new("mock/a", vec![ModelTask::Embed]))
+        .register_provider(MockProvider::new("mock/b", vec![ModelTask::Embed]))
+        .catalog(tenant_catalog())
+        .provider_scoped_aliases(true)
+        .build()
+        .await
+        .unwrap();
+
+    let a = runtime
+        .embedding_from("embed/tenant", "mock/a")
+        .await
+        .unwrap();
+    let b = runtime
+        .embedding_from("embed/tenant", "mock/b")
+        .await
+        .unwrap();
+    assert_eq!(a.model_id(), "model-a");
+    assert_eq!(b.model_id(), "model-b");
+
+    // The plain lookup cannot pick one.
+    let err = runtime.embedding("embed/tenant").await.err().unwrap();
+    assert!(
+        err.to_string()
+            .contains("defined for several providers (mock/a, mock/b)"),
+        "{err}"
+    );
+
+    // Aliases defined once still resolve both ways.
+    assert!(runtime.embedding("embed/only-a").await.is_ok());
+    assert!(
+        runtime
+            .embedding_from("embed/only-a", "mock/a")
+            .await
+            .is_ok()
+    );
+    let err = runtime
+        .embedding_from("embed/only-a", "mock/b")
+        .await
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string()
+            .contains("Alias 'embed/only-a' not found for provider 'mock/b'"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_aliases_across_providers_fail_by_default() {
+    let result = ModelRuntime::builder()
+        .register_provider(MockProvider::new("mock/a", vec![ModelTask::Embed]))
+        .register_provider(MockProvider::new("mock/b", vec![ModelTask::Embed]))
+        .catalog(tenant_catalog())
+        .build()
+        .await;
+
+    let err = result.err().expect("duplicate alias should fail build");
+    assert!(err.to_string().contains("Duplicate alias"));
+}
+
+#[tokio::test]
+async fn test_provider_scoped_aliases_reject_same_provider_and_non_embed() {
+    let builder = |specs| {
+        ModelRuntime::builder()
+            .register_provider(MockProvider::new(
+                "mock/a",
+                vec![ModelTask::Embed, ModelTask::Rerank],
+            ))
+            .register_provider(MockProvider::new(
+                "mock/b",
+                vec![ModelTask::Embed, ModelTask::Rerank],
+            ))
+            .catalog(specs)
+            .provider_scoped_aliases(true)
+    };
+
+    let issues = builder(vec![
+        make_spec("embed/tenant", ModelTask::Embed, "mock/a", "model-a"),
+        make_spec("embed/tenant", ModelTask::Embed, "mock/a", "model-b"),
+    ])
+    .validate()
+    .unwrap();
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].error.to_string().contains("Duplicate alias"));
+
+    let issues = builder(vec![
+        make_spec("rerank/tenant", ModelTask::Rerank, "mock/a", "model-a"),
+        make_spec("rerank/tenant", ModelTask::Rerank, "mock/b", "model-b"),
+    ])
+    .validate()
+    .unwrap();
+    assert_eq!(issues.len(), 2);
+    assert!(
+        issues[0]
+            .error
+            .to_string()
+            .contains("only supported for embed aliases")
+    );
+}
+
+#[tokio::test]
+async fn test_provider_scoped_register_adds_second_provider() {
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::new("mock/a", vec![ModelTask::Embed]))
+        .register_provider(MockProvider::new("mock/b", vec![ModelTask::Embed]))
+        .catalog(vec![make_spec(
+            "embed/tenant",
+            ModelTask::Embed,
+            "mock/a",
+            "model-a",
+        )])
+        .provider_scoped_aliases(true)
+        .build()
+        .await
+        .unwrap();
+
+    runtime
+        .register(make_spec(
+            "embed/tenant",
+            ModelTask::Embed,
+            "mock/b",
+            "model-b",
+        ))
+        .await
+        .unwrap();
+    let err = runtime
+        .register(make_spec(
+            "embed/tenant",
+            ModelTask::Embed,
+            "mock/b",
+            "model-c",
+        ))
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("already exists"), "{err}");
+
+    let b = runtime
+        .embedding_from("embed/tenant", "mock/b")
+        .await
+        .unwrap();
+    assert_eq!(b.model_id(), "model-b");
+}
+
+#[tokio::test]
+async fn test_shutdown_waits_for_inflight_calls() {
+    let provider = MockProvider::embed_only().with_model_delay(200);
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/test").await.unwrap();
+    let call = tokio::spawn(async move { model.embed(vec!["hello"]).await });
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    let start = std::time::Instant::now();
+    runtime.shutdown().await;
+    assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+    assert!(call.is_finished());
+    assert!(call.await.unwrap().is_ok());
+
+    assert!(runtime.is_shutting_down());
+    assert!(matches!(
+        runtime.embedding("embed/test").await,
+        Err(uni_xervo::error::RuntimeError::ShuttingDown)
+    ));
+}
+
+#[tokio::test]
+async fn test_shutdown_refuses_warmup_and_prefetch() {
+    use uni_xervo::error::RuntimeError;
+
+    let provider = MockProvider::embed_only();
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+    runtime.shutdown().await;
+
+    let err = runtime.warmup("embed/test").await.unwrap_err();
+    assert!(matches!(err, RuntimeError::ShuttingDown));
+    assert_eq!(err.to_string(), "Runtime is shutting down");
+    assert!(matches!(
+        runtime.prefetch(&["embed/test"]).await,
+        Err(RuntimeError::ShuttingDown)
+    ));
+    assert!(matches!(
+        runtime.prefetch_all().await,
+        Err(RuntimeError::ShuttingDown)
+    ));
+    let report = runtime.prefetch_report(&["embed/test"]).await;
+    assert!(matches!(
+        report["embed/test"],
+        Err(RuntimeError::ShuttingDown)
+    ));
+    assert!(!runtime.is_loaded("embed/test").await);
+}
+
+#[tokio::test]
+async fn test_load_finishing_during_shutdown_is_not_registered() {
+    let provider = MockProvider::embed_only().with_load_delay(200);
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    let loading = runtime.clone();
+    let load = tokio::spawn(async move { loading.embedding("embed/test").await });
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    runtime.shutdown().await;
+    assert!(matches!(
+        load.await.unwrap(),
+        Err(uni_xervo::error::RuntimeError::ShuttingDown)
+    ));
+    assert!(!runtime.is_loaded("embed/test").await);
+}
+
+#[tokio::test]
+async fn test_shutdown_grace_period_bounds_wait() {
+    let provider = MockProvider::embed_only().with_model_delay(5_000);
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(provider)
+        .catalog(vec![spec])
+        .shutdown_grace_period(std::time::Duration::from_millis(50))
+        .build()
+        .await
+        .unwrap();
+
+    let model = runtime.embedding("embed/test").await.unwrap();
+    let call = tokio::spawn(async move { model.embed(vec!["hello"]).await });
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    let start = std::time::Instant::now();
+    runtime.shutdown().await;
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    assert!(!call.is_finished());
+    call.abort();
+}
+
+#[tokio::test]
+async fn test_provider_defaults_merge_beneath_alias_options() {
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use uni_xervo::api::ModelAliasSpec;
+    use uni_xervo::error::{Result, RuntimeError};
+    use uni_xervo::traits::{
+        LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    };
+
+    /// Records the options each load receives.
+    struct Recorder(Arc<Mutex<Vec<serde_json::Value>>>);
+
+    #[async_trait]
+    impl ModelProvider for Recorder {
+        fn provider_id(&self) -> &'static str {
+            "recorder"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                supported_tasks: vec![ModelTask::Embed],
+            }
+        }
+        async fn load(&self, spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+            self.0.lock().unwrap().push(spec.options.clone());
+            Err(RuntimeError::Load("nothing to load".to_string()))
+        }
+        async fn health(&self) -> ProviderHealth {
+            ProviderHealth::Healthy
+        }
+    }
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut spec = make_spec("embed/test", ModelTask::Embed, "recorder", "org/model");
+    spec.options = serde_json::json!({ "location": "europe-west4" });
+    let runtime = ModelRuntime::builder()
+        .register_provider(Recorder(seen.clone()))
+        .provider_defaults(
+            "recorder",
+            serde_json::json!({ "project_id": "proj", "location": "us-central1" }),
+        )
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    assert!(runtime.embedding("embed/test").await.is_err());
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![serde_json::json!({ "project_id": "proj", "location": "europe-west4" })]
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limits_reported_for_loaded_alias() {
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use uni_xervo::api::ModelAliasSpec;
+    use uni_xervo::error::Result;
+    use uni_xervo::traits::{
+        EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+        RateLimitSnapshot,
+    };
+
+    fn snapshot() -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            remaining_requests: Some(59),
+            remaining_tokens: Some(1_000),
+            requests_reset_at: None,
+            tokens_reset_at: None,
+            observed_at: std::time::SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    /// Reports a fixed rate-limit snapshot.
+    struct Limited;
+
+    #[async_trait]
+    impl EmbeddingModel for Limited {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.0; 4]).collect())
+        }
+        fn dimensions(&self) -> u32 {
+            4
+        }
+        fn model_id(&self) -> &str {
+            "limited"
+        }
+        fn rate_limits(&self) -> Option<RateLimitSnapshot> {
+            Some(snapshot())
+        }
+    }
+
+    struct LimitedProvider;
+
+    #[async_trait]
+    impl ModelProvider for LimitedProvider {
+        fn provider_id(&self) -> &'static str {
+            "limited"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                supported_tasks: vec![ModelTask::Embed],
+            }
+        }
+        async fn load(&self, _spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+            let handle: Arc<dyn EmbeddingModel> = Arc::new(Limited);
+            Ok(Arc::new(handle) as LoadedModelHandle)
+        }
+        async fn health(&self) -> ProviderHealth {
+            ProviderHealth::Healthy
+        }
+    }
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(LimitedProvider)
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![
+            make_spec("embed/limited", ModelTask::Embed, "limited", "limited"),
+            make_spec("embed/mock", ModelTask::Embed, "mock/embed", "mock"),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(runtime.rate_limits("embed/limited").await, None);
+    let model = runtime.embedding("embed/limited").await.unwrap();
+    assert_eq!(model.rate_limits(), Some(snapshot()));
+    assert_eq!(runtime.rate_limits("embed/limited").await, Some(snapshot()));
+
+    runtime.embedding("embed/mock").await.unwrap();
+    assert_eq!(runtime.rate_limits("embed/mock").await, None);
+    assert_eq!(runtime.rate_limits("embed/missing").await, None);
+}
+
+#[tokio::test]
+async fn test_dimension_drift_detected_across_aliases_sharing_a_model() {
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uni_xervo::api::ModelAliasSpec;
+    use uni_xervo::error::{Result, RuntimeError};
+    use uni_xervo::traits::{
+        EmbeddingModel, LoadedModelHandle, ModelProvider, ProviderCapabilities, ProviderHealth,
+    };
+
+    /// Returns vectors of whatever length `dims` currently holds, like a
+    /// remote deployment swapped behind an unchanged model ID.
+    struct Redeployed(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl EmbeddingModel for Redeployed {
+        async fn embed(&self, texts: Vec<&str>) -> Result<Vec<Vec<f32>>> {
+            let dims = self.0.load(Ordering::SeqCst);
+            Ok(texts.iter().map(|_| vec![0.0; dims]).collect())
+        }
+        fn dimensions(&self) -> u32 {
+            self.0.load(Ordering::SeqCst) as u32
+        }
+        fn model_id(&self) -> &str {
+            "redeployed"
+        }
+    }
+
+    struct RedeployedProvider(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl ModelProvider for RedeployedProvider {
+        fn provider_id(&self) -> &'static str {
+            "redeployed"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                supported_tasks: vec![ModelTask::Embed],
+            }
+        }
+        async fn load(&self, _spec: &ModelAliasSpec) -> Result<LoadedModelHandle> {
+            let handle: Arc<dyn EmbeddingModel> = Arc::new(Redeployed(self.0.clone()));
+            Ok(Arc::new(handle) as LoadedModelHandle)
+        }
+        async fn health(&self) -> ProviderHealth {
+            ProviderHealth::Healthy
+        }
+    }
+
+    let dims = Arc::new(AtomicUsize::new(768));
+    let runtime = ModelRuntime::builder()
+        .register_provider(RedeployedProvider(dims.clone()))
+        .catalog(vec![
+            make_spec("embed/query", ModelTask::Embed, "redeployed", "org/model"),
+            make_spec("embed/docs", ModelTask::Embed, "redeployed", "org/model"),
+        ])
+        .build()
+        .await
+        .unwrap();
+
+    let query = runtime.embedding("embed/query").await.unwrap();
+    assert_eq!(query.embed_one("a").await.unwrap().len(), 768);
+
+    dims.store(1024, Ordering::SeqCst);
+    let docs = runtime.embedding("embed/docs").await.unwrap();
+    let err = docs.embed(vec!["b"]).await.unwrap_err();
+    assert!(matches!(err, RuntimeError::InferenceError(_)));
+    assert!(err.to_string().contains("returned 768"), "{err}");
+}
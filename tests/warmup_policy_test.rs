@@ -3,7 +3,7 @@
 use uni_xervo::api::{ModelTask, WarmupPolicy};
 mod common;
 use common::mock_support::{MockProvider, make_spec};
-use uni_xervo::runtime::ModelRuntime;
+use uni_xervo::runtime::{ModelRuntime, WarmupEvent};
 
 #[tokio::test]
 async fn test_eager_loads_on_build() {
@@ -123,6 +123,50 @@ async fn test_background_failure_does_not_fail_build() {
     assert!(runtime.is_ok());
 }
 
+#[tokio::test]
+async fn test_background_warmup_reports_events() {
+    let mut ok = make_spec("embed/ok", ModelTask::Embed, "mock/embed", "test-model");
+    ok.warmup = WarmupPolicy::Background;
+    let mut bad = make_spec("embed/bad", ModelTask::Embed, "mock/failing", "test-model");
+    bad.warmup = WarmupPolicy::Background;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+    let _runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .register_provider(MockProvider::failing())
+        .catalog(vec![ok, bad])
+        .warmup_events(tx)
+        .build()
+        .await
+        .unwrap();
+
+    let mut events = Vec::new();
+    while events.len() < 4 {
+        let event = tokio::time::timeout(tokio::time::Duration::from_secs(5), rx.recv())
+            .await
+            .expect("warmup event")
+            .unwrap();
+        events.push(event);
+    }
+
+    let started = |alias: &str| {
+        events
+            .iter()
+            .any(|e| matches!(e, WarmupEvent::Started { alias: a } if a == alias))
+    };
+    assert!(started("embed/ok") && started("embed/bad"));
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, WarmupEvent::Succeeded { alias, .. } if alias == "embed/ok"))
+    );
+    assert!(events.iter().any(|e| matches!(
+        e,
+        WarmupEvent::Failed { alias, error } if alias == "embed/bad"
+            && error.to_string().contains("Mock load failure")
+    )));
+}
+
 #[tokio::test]
 async fn test_lazy_failure_does_not_fail_build() {
     let provider = MockProvider::failing();
@@ -351,3 +395,109 @@ async fn test_model_warmup_called_on_eager() {
     // Model should have been warmed up exactly once
     assert_eq!(tracker.load(Ordering::SeqCst), 1);
 }
+
+#[tokio::test]
+async fn test_required_background_failure_marks_runtime_not_ready() {
+    let mut spec = make_spec("embed/bad", ModelTask::Embed, "mock/failing", "test-model");
+    spec.warmup = WarmupPolicy::Background;
+    spec.required = true;
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::failing())
+        .catalog(vec![spec])
+        .build()
+        .await
+        .unwrap();
+
+    let err = runtime.wait_for_warmup().await.unwrap_err();
+    assert!(err.to_string().contains("embed/bad"));
+    assert!(!runtime.is_ready());
+    assert_eq!(runtime.failed_warmups(), vec!["embed/bad".to_string()]);
+}
+
+#[tokio::test]
+async fn test_optional_background_failure_keeps_runtime_ready() {
+    let mut ok = make_spec("embed/ok", ModelTask::Embed, "mock/embed", "test-model");
+    ok.warmup = WarmupPolicy::Background;
+    ok.required = true;
+    let mut bad = make_spec("embed/bad", ModelTask::Embed, "mock/failing", "test-model");
+    bad.warmup = WarmupPolicy::Background;
+
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .register_provider(MockProvider::failing())
+        .catalog(vec![ok, bad])
+        .build()
+        .await
+        .unwrap();
+
+    runtime.wait_for_warmup().await.unwrap();
+    assert!(runtime.is_ready());
+    assert!(runtime.failed_warmups().is_empty());
+}
+
+#[test]
+fn test_build_lazy_needs_no_tokio_runtime() {
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+
+    // Constructed outside any tokio runtime.
+    let runtime = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![spec])
+        .build_lazy()
+        .unwrap();
+
+    // Models still load on first access.
+    let tokio = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let vectors = tokio.block_on(async {
+        let model = runtime.embedding("embed/test").await.unwrap();
+        model.embed(vec!["hello"]).await.unwrap()
+    });
+    assert_eq!(vectors.len(), 1);
+}
+
+#[test]
+fn test_build_lazy_rejects_eager_and_background_aliases() {
+    for policy in [WarmupPolicy::Eager, WarmupPolicy::Background] {
+        let mut spec = make_spec("embed/test", ModelTask::Embed, "mock/embed", "test-model");
+        spec.warmup = policy;
+
+        let err = ModelRuntime::builder()
+            .register_provider(MockProvider::embed_only())
+            .catalog(vec![spec])
+            .build_lazy()
+            .err()
+            .unwrap();
+        let message = err.to_string();
+        assert!(message.contains("alias 'embed/test'"), "{message}");
+        assert!(
+            message.contains(&format!("uses {policy} warmup")),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn test_build_lazy_rejects_eager_provider_warmup() {
+    let err = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .warmup_policy(WarmupPolicy::Eager)
+        .build_lazy()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("provider warmup policy"), "{err}");
+}
+
+#[test]
+fn test_build_lazy_validates_catalog() {
+    let spec = make_spec("embed/test", ModelTask::Embed, "mock/missing", "test-model");
+
+    let result = ModelRuntime::builder()
+        .register_provider(MockProvider::embed_only())
+        .catalog(vec![spec])
+        .build_lazy();
+    assert!(result.is_err());
+}